@@ -0,0 +1,100 @@
+//! Author mode (`mode = "author"`): a health board of the viewer's own PRs.
+//!
+//! Review requests are not fetched at all (see [`crate::model::UsageMode`]). What is left
+//! is ranked toward CI and merge readiness: a failing build stays in NEEDS YOU until it is
+//! fixed, not just when it first breaks, and so does a PR that no longer merges cleanly.
+
+use crate::model::CiState;
+use crate::refresh::{
+    SCORE_CI_FAILED_NEW, SCORE_CI_FAILED_UNCHANGED, UiPr, category_for, sort_for_display,
+};
+
+/// Enough to lift a green PR that is waiting on review into NEEDS YOU.
+pub const SCORE_MERGE_CONFLICTS: i32 = 60;
+
+/// True when GitHub reports that the PR no longer merges cleanly.
+fn has_conflicts(p: &UiPr) -> bool {
+    p.pr.mergeable
+        .as_deref()
+        .is_some_and(|s| s.eq_ignore_ascii_case("CONFLICTING"))
+}
+
+/// Re-score the viewer's PRs for merge readiness, badge conflicts, and restore display
+/// order. Drafts are left to the draft policy.
+pub fn apply_author_mode(prs: &mut [UiPr]) {
+    for p in prs.iter_mut() {
+        if !p.pr.is_viewer_author || p.pr.is_draft {
+            continue;
+        }
+        let mut boost = 0;
+        // An old failure is as much the author's problem as a new one.
+        if matches!(p.pr.ci_state, CiState::Failure) && !p.is_new_ci_failure {
+            boost += SCORE_CI_FAILED_NEW - SCORE_CI_FAILED_UNCHANGED;
+        }
+        if has_conflicts(p) {
+            boost += SCORE_MERGE_CONFLICTS;
+            p.display_status = format!("🔀 conflicts · {}", p.display_status);
+        }
+        if boost != 0 {
+            p.score += boost;
+            p.category = category_for(&p.pr, p.score);
+        }
+    }
+    sort_for_display(prs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+    use crate::refresh::Category;
+
+    fn mine(key: &str, ci: CiState, mergeable: &str, score: i32) -> UiPr {
+        let mut pr = generate_demo_prs(1_700_000_000, 0).remove(0);
+        pr.pr_key = key.to_string();
+        pr.is_viewer_author = true;
+        pr.is_draft = false;
+        pr.ci_state = ci;
+        pr.mergeable = Some(mergeable.to_string());
+        UiPr {
+            category: category_for(&pr, score),
+            pr,
+            score,
+            display_status: "status".to_string(),
+            is_new_ci_failure: false,
+            is_pinned: false,
+            sla: None,
+            draft_ranked: false,
+            bot_section: false,
+            force_pushed_since_view: false,
+            reply_to_you: false,
+            opened_since_update: false,
+            snoozed: false,
+            inaccessible_since: None,
+            author_priority: None,
+        }
+    }
+
+    #[test]
+    fn failing_ci_and_conflicts_keep_my_prs_in_needs_you() {
+        let mut prs = vec![
+            mine(
+                "a/a#1",
+                CiState::Failure,
+                "MERGEABLE",
+                SCORE_CI_FAILED_UNCHANGED,
+            ),
+            mine("a/a#2", CiState::Success, "CONFLICTING", -20),
+            mine("a/a#3", CiState::Success, "MERGEABLE", -20),
+        ];
+        apply_author_mode(&mut prs);
+
+        let by_key = |key: &str| prs.iter().find(|p| p.pr.pr_key == key).unwrap();
+        assert_eq!(by_key("a/a#1").category, Category::NeedsYou);
+        assert_eq!(by_key("a/a#1").score, SCORE_CI_FAILED_NEW);
+        let conflicting = by_key("a/a#2");
+        assert_eq!(conflicting.category, Category::NeedsYou);
+        assert_eq!(conflicting.display_status, "🔀 conflicts · status");
+        assert_eq!(by_key("a/a#3").score, -20);
+    }
+}
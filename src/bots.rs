@@ -0,0 +1,82 @@
+//! Bot PRs (`bots` in the config): dependency bumps and other PRs opened by dependabot,
+//! renovate and the like.
+//!
+//! They tend to come in batches and get merged in one sitting, so they can be listed
+//! apart in a BOTS section at the bottom, or left out. By default they are ranked like
+//! every other PR.
+
+use crate::refresh::UiPr;
+
+/// Logins GitHub's GraphQL API reports for common bots (without the `[bot]` suffix).
+const BOT_LOGINS: &[&str] = &["dependabot", "renovate", "github-actions", "mergify"];
+
+/// True for bot accounts such as `dependabot` or `renovate[bot]`.
+pub fn is_bot(login: &str) -> bool {
+    let login = login.to_ascii_lowercase();
+    login.ends_with("[bot]") || BOT_LOGINS.contains(&login.as_str())
+}
+
+/// Where bot PRs show up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BotMode {
+    /// Ranked like any other PR.
+    #[default]
+    Normal,
+    /// Listed in the BOTS section at the bottom.
+    SeparateSection,
+    /// Left out of the list.
+    Hide,
+}
+
+/// Move bot PRs to the BOTS section when `mode` says so.
+///
+/// Hidden PRs are left in place; drop them with [`retain_visible_bots`].
+pub fn apply_bot_mode(prs: &mut [UiPr], mode: BotMode) {
+    if mode != BotMode::SeparateSection {
+        return;
+    }
+    for p in prs.iter_mut().filter(|p| is_bot(&p.pr.author)) {
+        p.bot_section = true;
+    }
+}
+
+/// Drop bot PRs when `mode` hides them.
+pub fn retain_visible_bots(prs: &mut Vec<UiPr>, mode: BotMode) {
+    if mode == BotMode::Hide {
+        prs.retain(|p| !is_bot(&p.pr.author));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn bot_prs_go_to_their_section_or_away() {
+        let conn = TestDb::new("bots-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        prs[1].pr.author = "dependabot".to_string();
+        prs[3].pr.author = "Renovate[bot]".to_string();
+        let total = prs.len();
+
+        let mut normal = prs.clone();
+        apply_bot_mode(&mut normal, BotMode::Normal);
+        retain_visible_bots(&mut normal, BotMode::Normal);
+        assert_eq!(normal.len(), total);
+        assert!(normal.iter().all(|p| !p.bot_section));
+
+        let mut separate = prs.clone();
+        apply_bot_mode(&mut separate, BotMode::SeparateSection);
+        retain_visible_bots(&mut separate, BotMode::SeparateSection);
+        let in_section: Vec<usize> = (0..total).filter(|&i| separate[i].bot_section).collect();
+        assert_eq!(in_section, vec![1, 3]);
+
+        retain_visible_bots(&mut prs, BotMode::Hide);
+        assert_eq!(prs.len(), total - 2);
+        assert!(prs.iter().all(|p| !is_bot(&p.pr.author)));
+        assert!(is_bot("dependabot") && !is_bot("robot"));
+    }
+}
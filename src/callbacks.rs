@@ -0,0 +1,433 @@
+//! What the frontends call to fetch and to act on PRs, wired once per mode.
+//!
+//! The demo works on generated PRs, offline mode (no token) reads the cache and refuses
+//! everything else, and online mode goes to the code host. Scoring layers from the config
+//! wrap whichever set was built, so every frontend sees the same list.
+
+use crate::{
+    ApproveFn, CheckLogFn, CommentFn, DeclineFn, GITLAB_WRITES, LookupFn, MarkReadFn, MergeFn,
+    RefreshFn, RefreshPrFn, SetDraftFn, UpdateBranchFn, WindowFn, logged,
+};
+use needle_core::author::apply_author_mode;
+use needle_core::bots::{BotMode, apply_bot_mode, retain_visible_bots};
+use needle_core::db::{now_unix, open_db};
+use needle_core::demo::demo_check_log;
+use needle_core::drafts::{DraftPolicy, apply_draft_policy, retain_visible_drafts};
+use needle_core::github_write::{
+    approve_pr, decline_review, mark_pr_read, merge_pr, post_comment, set_draft, update_branch,
+};
+use needle_core::model::Pr;
+use needle_core::oncall::{OncallProfile, apply_oncall};
+use needle_core::provider::{LOG_TAIL_LINES, Provider, log_tail};
+use needle_core::refresh::{
+    AuthorPriority, ScopeFilters, UiPr, apply_author_priority, load_cached, lookup_demo_pr,
+    lookup_pr, refresh, refresh_demo, refresh_single, refresh_single_demo, warm_cache_for_cutoff,
+};
+use needle_core::sla::{SlaRule, apply_sla};
+use needle_core::subscriptions::{UnsubscribedMode, apply_unsubscribed, retain_subscribed};
+use octocrab::Octocrab;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Everything the frontends call back into. Only the TUI uses more than `refresh_fn`.
+pub(crate) struct Callbacks {
+    /// Refetches the attention set for the current `--days` window.
+    pub(crate) refresh_fn: RefreshFn,
+    pub(crate) refresh_pr_fn: RefreshPrFn,
+    pub(crate) window_fn: WindowFn,
+    pub(crate) lookup_fn: LookupFn,
+    pub(crate) check_log_fn: CheckLogFn,
+    pub(crate) merge_fn: MergeFn,
+    pub(crate) comment_fn: CommentFn,
+    pub(crate) update_branch_fn: UpdateBranchFn,
+    pub(crate) set_draft_fn: SetDraftFn,
+    pub(crate) decline_fn: DeclineFn,
+    pub(crate) approve_fn: ApproveFn,
+    /// Marks a PR's notifications read when it is opened (`mark_read_on_open`).
+    pub(crate) mark_read_fn: Option<MarkReadFn>,
+}
+
+impl Callbacks {
+    /// `--demo`: generated PRs in the demo cache at `path`. Write actions pretend to succeed.
+    pub(crate) fn demo(path: PathBuf, scope: &ScopeFilters, window: Arc<AtomicI64>) -> Self {
+        let path_for_refresh = path.clone();
+        let scope_for_refresh = scope.clone();
+        let window_for_refresh = window.clone();
+        let refresh_fn: RefreshFn = Arc::new(move || {
+            let c = open_db(&path_for_refresh)?;
+            refresh_demo(
+                &c,
+                window_for_refresh.load(Ordering::Relaxed),
+                &scope_for_refresh,
+            )
+        });
+        let path_for_window = path.clone();
+        let scope_for_window = scope.clone();
+        let window_fn: WindowFn = Arc::new(move |days, backfill| {
+            let c = open_db(&path_for_window)?;
+            if backfill {
+                return refresh_demo(&c, days, &scope_for_window);
+            }
+            window.store(days, Ordering::Relaxed);
+            load_cached(&c, days, &scope_for_window)
+        });
+        let refresh_pr_fn: RefreshPrFn = Arc::new(move |key| {
+            let c = open_db(&path)?;
+            refresh_single_demo(&c, key)
+        });
+
+        Callbacks {
+            refresh_fn,
+            refresh_pr_fn,
+            window_fn,
+            lookup_fn: Arc::new(lookup_demo_pr),
+            check_log_fn: Arc::new(|_, check| Ok(log_tail(&demo_check_log(check), LOG_TAIL_LINES))),
+            // Demo PRs are fake; pretend the merge went through.
+            merge_fn: Arc::new(|_, _| Ok(())),
+            comment_fn: Arc::new(|_, _| Ok(())),
+            update_branch_fn: Arc::new(|_| Ok(())),
+            set_draft_fn: Arc::new(|_, _| Ok(())),
+            decline_fn: Arc::new(|_, _| Ok(())),
+            approve_fn: Arc::new(|_| Ok(())),
+            mark_read_fn: None,
+        }
+    }
+
+    /// No token: the cache at `path` is all there is, and everything else fails with
+    /// `no_token`.
+    pub(crate) fn offline(path: PathBuf, scope: &ScopeFilters, no_token: &'static str) -> Self {
+        // Nothing can be fetched, so a wider window only shows what the cache has.
+        let scope_for_window = scope.clone();
+        let window_fn: WindowFn = Arc::new(move |days, _| {
+            let c = open_db(&path)?;
+            load_cached(&c, days, &scope_for_window)
+        });
+
+        Callbacks {
+            refresh_fn: Arc::new(move || Err(no_token.to_string())),
+            refresh_pr_fn: Arc::new(move |_| Err(no_token.to_string())),
+            window_fn,
+            lookup_fn: Arc::new(move |_| Err(no_token.to_string())),
+            check_log_fn: Arc::new(move |_, _| Err(no_token.to_string())),
+            merge_fn: Arc::new(move |_, _| Err(no_token.to_string())),
+            comment_fn: Arc::new(move |_, _| Err(no_token.to_string())),
+            update_branch_fn: Arc::new(move |_| Err(no_token.to_string())),
+            set_draft_fn: Arc::new(move |_, _| Err(no_token.to_string())),
+            decline_fn: Arc::new(move |_, _| Err(no_token.to_string())),
+            approve_fn: Arc::new(move |_| Err(no_token.to_string())),
+            mark_read_fn: None,
+        }
+    }
+
+    /// Live data from `provider`, cached at `path`. Write actions need `octo` (GitHub);
+    /// without it they fail with [`GITLAB_WRITES`].
+    pub(crate) fn online(
+        provider: Arc<dyn Provider>,
+        octo: Option<Octocrab>,
+        path: PathBuf,
+        scope: &ScopeFilters,
+        include_team_requests: bool,
+        window: Arc<AtomicI64>,
+        mark_read_on_open: bool,
+    ) -> Self {
+        let handle = tokio::runtime::Handle::current();
+        let path_for_refresh = path.clone();
+        let provider_for_refresh = provider.clone();
+        let handle_for_refresh = handle.clone();
+        let scope_for_refresh = scope.clone();
+        let window_for_refresh = window.clone();
+        let refresh_fn: RefreshFn = Arc::new(move || {
+            let c = open_db(&path_for_refresh)?;
+            let days = window_for_refresh.load(Ordering::Relaxed);
+            // Called from a non-runtime worker thread (for shimmer), so this uses handle.block_on.
+            if tokio::runtime::Handle::try_current().is_ok() {
+                tokio::task::block_in_place(|| {
+                    handle_for_refresh.block_on(refresh(
+                        &c,
+                        provider_for_refresh.as_ref(),
+                        days,
+                        &scope_for_refresh,
+                        include_team_requests,
+                    ))
+                })
+            } else {
+                handle_for_refresh.block_on(refresh(
+                    &c,
+                    provider_for_refresh.as_ref(),
+                    days,
+                    &scope_for_refresh,
+                    include_team_requests,
+                ))
+            }
+        });
+
+        // Every callback below runs on a plain worker thread; see [`Worker`].
+        let worker = Worker {
+            handle,
+            db_path: path,
+        };
+
+        let provider_for_window = provider.clone();
+        let scope_for_window = scope.clone();
+        let window_worker = worker.clone();
+        let window_fn: WindowFn = Arc::new(move |days, backfill| {
+            window_worker.with_db(async |c| {
+                if backfill {
+                    warm_cache_for_cutoff(
+                        c,
+                        provider_for_window.as_ref(),
+                        days,
+                        &scope_for_window,
+                        include_team_requests,
+                    )
+                    .await?;
+                } else {
+                    window.store(days, Ordering::Relaxed);
+                }
+                load_cached(c, days, &scope_for_window)
+            })
+        });
+
+        let provider_for_single = provider.clone();
+        let single_worker = worker.clone();
+        let refresh_pr_fn: RefreshPrFn = Arc::new(move |key| {
+            single_worker
+                .with_db(async |c| refresh_single(c, provider_for_single.as_ref(), key).await)
+        });
+
+        let provider_for_lookup = provider.clone();
+        let lookup_worker = worker.clone();
+        let lookup_fn: LookupFn = Arc::new(move |pr_ref| {
+            lookup_worker
+                .with_db(async |c| lookup_pr(c, provider_for_lookup.as_ref(), pr_ref).await)
+        });
+
+        let log_worker = worker.clone();
+        let check_log_fn: CheckLogFn = Arc::new(move |pr, check| {
+            let raw = log_worker.block_on(provider.check_log(&pr.owner, &pr.repo, check))?;
+            Ok(log_tail(&raw, LOG_TAIL_LINES))
+        });
+
+        let Some(octo) = octo else {
+            return Callbacks {
+                refresh_fn,
+                refresh_pr_fn,
+                window_fn,
+                lookup_fn,
+                check_log_fn,
+                merge_fn: Arc::new(|_, _| Err(GITLAB_WRITES.to_string())),
+                comment_fn: Arc::new(|_, _| Err(GITLAB_WRITES.to_string())),
+                update_branch_fn: Arc::new(|_| Err(GITLAB_WRITES.to_string())),
+                set_draft_fn: Arc::new(|_, _| Err(GITLAB_WRITES.to_string())),
+                decline_fn: Arc::new(|_, _| Err(GITLAB_WRITES.to_string())),
+                approve_fn: Arc::new(|_| Err(GITLAB_WRITES.to_string())),
+                mark_read_fn: None,
+            };
+        };
+
+        let mark_read_fn: Option<MarkReadFn> = mark_read_on_open.then(|| {
+            let octo = octo.clone();
+            let read_worker = worker.clone();
+            let mark_read_fn: MarkReadFn = Arc::new(move |pr| {
+                read_worker.logged(pr, "mark_read", None, mark_pr_read(&octo, pr))
+            });
+            mark_read_fn
+        });
+
+        let octo_for_merge = octo.clone();
+        let merge_worker = worker.clone();
+        let merge_fn: MergeFn = Arc::new(move |pr, method| {
+            merge_worker.logged(
+                pr,
+                "merge",
+                Some(method.label()),
+                merge_pr(&octo_for_merge, pr, method),
+            )
+        });
+
+        let octo_for_comment = octo.clone();
+        let comment_worker = worker.clone();
+        let comment_fn: CommentFn = Arc::new(move |pr, body| {
+            comment_worker.logged(
+                pr,
+                "comment",
+                None,
+                post_comment(&octo_for_comment, pr, body),
+            )
+        });
+
+        let octo_for_update = octo.clone();
+        let update_worker = worker.clone();
+        let update_branch_fn: UpdateBranchFn = Arc::new(move |pr| {
+            update_worker.logged(
+                pr,
+                "update_branch",
+                None,
+                update_branch(&octo_for_update, pr),
+            )
+        });
+
+        let octo_for_draft = octo.clone();
+        let draft_worker = worker.clone();
+        let set_draft_fn: SetDraftFn = Arc::new(move |pr, draft| {
+            let action = if draft { "draft" } else { "ready" };
+            draft_worker.logged(pr, action, None, set_draft(&octo_for_draft, pr, draft))
+        });
+
+        let octo_for_decline = octo.clone();
+        let decline_worker = worker.clone();
+        let decline_fn: DeclineFn = Arc::new(move |pr, login| {
+            decline_worker.logged(
+                pr,
+                "decline_review",
+                Some(login),
+                decline_review(&octo_for_decline, pr, login),
+            )
+        });
+
+        let approve_fn: ApproveFn =
+            Arc::new(move |pr| worker.logged(pr, "approve", None, approve_pr(&octo, pr)));
+
+        Callbacks {
+            refresh_fn,
+            refresh_pr_fn,
+            window_fn,
+            lookup_fn,
+            check_log_fn,
+            merge_fn,
+            comment_fn,
+            update_branch_fn,
+            set_draft_fn,
+            decline_fn,
+            approve_fn,
+            mark_read_fn,
+        }
+    }
+
+    /// Run every list and PR these callbacks return through `layers`.
+    pub(crate) fn with_layers(self, layers: ScoringLayers) -> Self {
+        if layers.is_empty() {
+            return self;
+        }
+        let layers = Arc::new(layers);
+        let Callbacks {
+            refresh_fn,
+            refresh_pr_fn,
+            window_fn,
+            lookup_fn,
+            ..
+        } = self;
+
+        let layers_for_refresh = layers.clone();
+        let layers_for_single = layers.clone();
+        let layers_for_window = layers.clone();
+        Callbacks {
+            refresh_fn: Arc::new(move || {
+                let mut prs = refresh_fn()?;
+                layers_for_refresh.apply_to_list(&mut prs);
+                Ok(prs)
+            }),
+            refresh_pr_fn: Arc::new(move |key| {
+                let mut pr = refresh_pr_fn(key)?;
+                layers_for_single.annotate(std::slice::from_mut(&mut pr));
+                Ok(pr)
+            }),
+            window_fn: Arc::new(move |days, backfill| {
+                let mut prs = window_fn(days, backfill)?;
+                layers_for_window.apply_to_list(&mut prs);
+                Ok(prs)
+            }),
+            lookup_fn: Arc::new(move |pr_ref| {
+                let mut pr = lookup_fn(pr_ref)?;
+                layers.annotate(std::slice::from_mut(&mut pr));
+                Ok(pr)
+            }),
+            ..self
+        }
+    }
+}
+
+/// Author mode, author priority, draft policy, unsubscribed and bot PRs, SLAs and the
+/// on-call profile, layered on top of scoring so every frontend (TUI, rpc, serve) sees them.
+pub(crate) struct ScoringLayers {
+    pub(crate) author_mode: bool,
+    pub(crate) author_priority: Vec<AuthorPriority>,
+    pub(crate) draft_policy: DraftPolicy,
+    pub(crate) unsubscribed_prs: UnsubscribedMode,
+    pub(crate) bot_mode: BotMode,
+    pub(crate) sla_rules: Vec<SlaRule>,
+    pub(crate) oncall: Option<OncallProfile>,
+}
+
+impl ScoringLayers {
+    /// Whether no layer changes anything, so the callbacks can stay unwrapped.
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.author_mode
+            && self.author_priority.is_empty()
+            && self.sla_rules.is_empty()
+            && self.oncall.is_none()
+            && self.draft_policy.is_default()
+            && self.unsubscribed_prs == UnsubscribedMode::Show
+            && self.bot_mode == BotMode::Normal
+    }
+
+    /// Drop the PRs hidden from lists, then [`annotate`](Self::annotate) the rest. A
+    /// looked-up PR is always shown, so single PRs are only annotated.
+    pub(crate) fn apply_to_list(&self, prs: &mut Vec<UiPr>) {
+        retain_visible_drafts(prs, &self.draft_policy);
+        retain_subscribed(prs, self.unsubscribed_prs);
+        retain_visible_bots(prs, self.bot_mode);
+        self.annotate(prs);
+    }
+
+    fn annotate(&self, prs: &mut [UiPr]) {
+        if self.author_mode {
+            apply_author_mode(prs);
+        }
+        apply_author_priority(prs, &self.author_priority);
+        apply_draft_policy(prs, &self.draft_policy);
+        apply_unsubscribed(prs, self.unsubscribed_prs);
+        apply_bot_mode(prs, self.bot_mode);
+        apply_sla(prs, &self.sla_rules, now_unix());
+        if let Some(profile) = &self.oncall {
+            apply_oncall(prs, profile);
+        }
+    }
+}
+
+/// What the online callbacks need to reach the cache and the network.
+///
+/// The TUI only ever calls those callbacks from plain worker threads it spawns, never from a
+/// thread owned by the runtime, so blocking on `handle` is safe there.
+#[derive(Clone)]
+struct Worker {
+    handle: tokio::runtime::Handle,
+    db_path: PathBuf,
+}
+
+impl Worker {
+    fn block_on<F: std::future::Future>(&self, fut: F) -> F::Output {
+        self.handle.block_on(fut)
+    }
+
+    /// Open the cache and drive `f` against it to completion.
+    fn with_db<T>(
+        &self,
+        f: impl AsyncFnOnce(&rusqlite::Connection) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let conn = open_db(&self.db_path)?;
+        self.block_on(f(&conn))
+    }
+
+    /// [`logged`], running `fut` as the action.
+    fn logged(
+        &self,
+        pr: &Pr,
+        action: &str,
+        detail: Option<&str>,
+        fut: impl std::future::Future<Output = Result<(), String>>,
+    ) -> Result<(), String> {
+        logged(&self.db_path, pr, action, detail, || self.block_on(fut))
+    }
+}
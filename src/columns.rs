@@ -0,0 +1,289 @@
+//! Custom list columns (`[[columns]]`): integer expressions over PR fields.
+//!
+//! An expression such as `(now - created_at) / 86400` is parsed once from the config and
+//! evaluated per PR when the list is laid out. The language is deliberately tiny: integer
+//! literals, the fields in [`FIELDS`], `+ - * /` with the usual precedence, unary minus
+//! and parentheses. Division truncates. A field the PR does not have (e.g. no review
+//! request) or a division by zero leaves the cell without a value.
+
+use crate::refresh::UiPr;
+
+/// Field names usable in expressions. Timestamps are unix seconds; flags are 0 or 1.
+pub const FIELDS: &[&str] = &[
+    "now",
+    "created_at",
+    "updated_at",
+    "review_requested_at",
+    "last_force_push_at",
+    "last_reply_at",
+    "number",
+    "score",
+    "comments",
+    "unresolved_threads",
+    "labels",
+    "reviewers",
+    "checks",
+    "failing_checks",
+    "draft",
+    "mine",
+    "assigned",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Num(i64),
+    Field(&'static str),
+    Neg(Box<Expr>),
+    Bin(Box<Expr>, Op, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Num(i64),
+    Ident(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Num(n) => format!("`{n}`"),
+            Token::Ident(name) => format!("`{name}`"),
+            Token::Op(Op::Add) => "`+`".to_string(),
+            Token::Op(Op::Sub) => "`-`".to_string(),
+            Token::Op(Op::Mul) => "`*`".to_string(),
+            Token::Op(Op::Div) => "`/`".to_string(),
+            Token::Open => "`(`".to_string(),
+            Token::Close => "`)`".to_string(),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut out = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut end = i;
+            while let Some(&(j, d)) = chars.peek() {
+                if !d.is_ascii_digit() && d != '_' {
+                    break;
+                }
+                end = j + d.len_utf8();
+                chars.next();
+            }
+            let digits = src[i..end].replace('_', "");
+            let n = digits
+                .parse()
+                .map_err(|e| format!("Bad number `{}`: {e}", &src[i..end]))?;
+            out.push(Token::Num(n));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = i;
+            while let Some(&(j, d)) = chars.peek() {
+                if !d.is_ascii_alphanumeric() && d != '_' {
+                    break;
+                }
+                end = j + d.len_utf8();
+                chars.next();
+            }
+            out.push(Token::Ident(src[i..end].to_string()));
+        } else {
+            out.push(match c {
+                '+' => Token::Op(Op::Add),
+                '-' => Token::Op(Op::Sub),
+                '*' => Token::Op(Op::Mul),
+                '/' => Token::Op(Op::Div),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => return Err(format!("Unexpected `{c}` at position {}", i + 1)),
+            });
+            chars.next();
+        }
+    }
+    Ok(out)
+}
+
+/// Recursive descent over the token list: sums of products of unary terms.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.product()?;
+        while let Some(&Token::Op(op @ (Op::Add | Op::Sub))) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Bin(Box::new(lhs), op, Box::new(self.product()?));
+        }
+        Ok(lhs)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while let Some(&Token::Op(op @ (Op::Mul | Op::Div))) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Bin(Box::new(lhs), op, Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Op(Op::Sub)) => Ok(Expr::Neg(Box::new(self.unary()?))),
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Ident(name)) => FIELDS
+                .iter()
+                .find(|f| **f == name)
+                .map(|f| Expr::Field(f))
+                .ok_or_else(|| format!("Unknown field `{name}` (known: {})", FIELDS.join(", "))),
+            Some(Token::Open) => {
+                let inner = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("Missing `)`".to_string()),
+                }
+            }
+            Some(t) => Err(format!("Unexpected {}", t.describe())),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+fn field_value(name: &str, p: &UiPr, now: i64) -> Option<i64> {
+    let count = |n: usize| Some(n as i64);
+    match name {
+        "now" => Some(now),
+        "created_at" => p.pr.created_at_unix,
+        "updated_at" => Some(p.pr.updated_at_unix),
+        "review_requested_at" => p.pr.review_requested_at_unix,
+        "last_force_push_at" => p.pr.last_force_push_at_unix,
+        "last_reply_at" => p.pr.last_reply_at_unix,
+        "number" => Some(p.pr.number),
+        "score" => Some(i64::from(p.score)),
+        "comments" => count(p.pr.comments.len()),
+        "unresolved_threads" => count(p.pr.unresolved_threads()),
+        "labels" => count(p.pr.labels.len()),
+        "reviewers" => count(p.pr.reviewers.len()),
+        "checks" => count(p.pr.ci_checks.len()),
+        "failing_checks" => count(
+            p.pr.ci_checks
+                .iter()
+                .filter(|c| c.state.is_failure())
+                .count(),
+        ),
+        "draft" => Some(i64::from(p.pr.is_draft)),
+        "mine" => Some(i64::from(p.pr.is_viewer_author)),
+        "assigned" => Some(i64::from(p.pr.is_assignee)),
+        _ => None,
+    }
+}
+
+impl Expr {
+    fn eval(&self, p: &UiPr, now: i64) -> Option<i64> {
+        match self {
+            Expr::Num(n) => Some(*n),
+            Expr::Field(name) => field_value(name, p, now),
+            Expr::Neg(e) => e.eval(p, now)?.checked_neg(),
+            Expr::Bin(l, op, r) => {
+                let (l, r) = (l.eval(p, now)?, r.eval(p, now)?);
+                match op {
+                    Op::Add => l.checked_add(r),
+                    Op::Sub => l.checked_sub(r),
+                    Op::Mul => l.checked_mul(r),
+                    Op::Div => l.checked_div(r),
+                }
+            }
+        }
+    }
+}
+
+/// One configured column: a header and the expression behind its cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomColumn {
+    pub name: String,
+    expr: Expr,
+}
+
+impl CustomColumn {
+    /// Parse `expr` for the column called `name`.
+    pub fn parse(name: &str, expr: &str) -> Result<Self, String> {
+        let parsed = tokenize(expr)
+            .and_then(|tokens| {
+                let mut parser = Parser { tokens, pos: 0 };
+                let e = parser.sum()?;
+                match parser.peek() {
+                    None => Ok(e),
+                    Some(t) => Err(format!("Unexpected {} after the expression", t.describe())),
+                }
+            })
+            .map_err(|e| format!("Column `{name}`: {e}"))?;
+        Ok(Self {
+            name: name.to_string(),
+            expr: parsed,
+        })
+    }
+
+    /// The cell for `p` as of `now`; `None` when a field is missing or the math fails.
+    pub fn value(&self, p: &UiPr, now: i64) -> Option<i64> {
+        self.expr.eval(p, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn expressions_follow_precedence_and_read_pr_fields() {
+        let conn = TestDb::new("columns-test");
+        let mut p = refresh_demo(&conn, 30, &ScopeFilters::default())
+            .unwrap()
+            .remove(0);
+        let now = 1_700_000_000;
+        p.pr.created_at_unix = Some(now - 3 * 86_400 - 60);
+
+        let value = |p: &UiPr, src: &str| CustomColumn::parse("c", src).unwrap().value(p, now);
+        assert_eq!(value(&p, "(now - created_at) / 86400"), Some(3));
+        assert_eq!(value(&p, "1 + 2 * 3 - -4"), Some(11));
+        assert_eq!(value(&p, "(1 + 2) * 3"), Some(9));
+        assert_eq!(value(&p, "number"), Some(p.pr.number));
+        assert_eq!(value(&p, "1 / (draft - draft)"), None);
+
+        p.pr.created_at_unix = None;
+        assert_eq!(value(&p, "now - created_at"), None);
+
+        let err = |src: &str| CustomColumn::parse("age", src).unwrap_err();
+        assert!(err("now - opened").starts_with("Column `age`: Unknown field `opened`"));
+        assert_eq!(err("(now - 1"), "Column `age`: Missing `)`");
+        assert_eq!(
+            err("now now"),
+            "Column `age`: Unexpected `now` after the expression"
+        );
+        assert_eq!(err("now % 2"), "Column `age`: Unexpected `%` at position 5");
+    }
+}
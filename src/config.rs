@@ -1,10 +1,35 @@
 //! Configuration file support for needle.
 //!
 //! Loads settings from `~/.config/needle/config.toml` (or platform equivalent).
-//! CLI arguments take precedence over config file values.
+//! CLI arguments take precedence over config file values, and the personal file takes
+//! precedence over an optional team-managed overlay (`team_config`).
 
+use crate::theme::ColorMode;
+use crate::tui::ListLayout;
+use needle_core::bots::BotMode;
+use needle_core::drafts::DraftMode;
+use needle_core::model::UsageMode;
+use needle_core::subscriptions::UnsubscribedMode;
+use octocrab::Octocrab;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// `cache_retention_days`, refused below one day: 0 or less would empty the cache on every
+/// start, and a team file could impose it (the shorter retention wins).
+fn at_least_one_day<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let days = Option::<i64>::deserialize(deserializer)?;
+    match days {
+        Some(n) if n < 1 => Err(serde::de::Error::custom(format!(
+            "cache_retention_days must be at least 1, got {n}"
+        ))),
+        _ => Ok(days),
+    }
+}
 
 /// Configuration loaded from TOML file.
 #[derive(Debug, Default, Deserialize)]
@@ -22,12 +47,39 @@ pub struct Config {
     /// Exclude these repos (owner/repo).
     pub exclude: Option<Vec<String>>,
 
+    /// Only show PRs in these milestones (by title).
+    pub include_milestones: Option<Vec<String>>,
+
+    /// Hide PRs in these milestones.
+    pub exclude_milestones: Option<Vec<String>>,
+
     /// Include PRs requested to teams you are in.
     pub include_team_requests: Option<bool>,
 
+    /// Extra PR scopes, e.g. `involves-me:discussion`.
+    pub scope: Option<Vec<String>>,
+
+    /// `reviewer` (review requests only) or `author` (the viewer's own PRs only).
+    pub mode: Option<UsageMode>,
+
     /// Emit a terminal bell on important new events.
     pub bell: Option<bool>,
 
+    /// Alerts that ring the bell (`needs_you`, `review_requested`, `ci_failed`,
+    /// `ready_to_merge`, `new_repo`, `reply`, `reminder`, `approved_by`). Default: all of them.
+    pub bell_events: Option<Vec<String>>,
+
+    /// Alert once more when a PR sat in NEEDS YOU this long (minutes) without being opened
+    /// or snoozed. Default: off.
+    pub remind_after_mins: Option<u64>,
+
+    /// Show the NEEDS YOU count in the terminal title, updated on every refresh.
+    pub terminal_title: Option<bool>,
+
+    /// Inside tmux, also set the global option `@needle_needs_you` to the count, for
+    /// `status-right`.
+    pub tmux_status: Option<bool>,
+
     /// Hide PR numbers column in list view.
     pub hide_pr_numbers: Option<bool>,
 
@@ -37,11 +89,304 @@ pub struct Config {
     /// Hide author column in list view.
     pub hide_author: Option<bool>,
 
+    /// List columns whose truncation keeps a trailing issue key or `[tag]` visible
+    /// (`title`, `repo`, `author`, `status`). Default: `title`.
+    pub truncate_keep_tail: Option<Vec<String>>,
+
     /// Auto-refresh interval in list view (seconds). Default: 180 (3 minutes).
     pub refresh_interval_list_secs: Option<u64>,
 
     /// Auto-refresh interval in details view (seconds). Default: 30.
     pub refresh_interval_details_secs: Option<u64>,
+
+    /// Named repo groups, usable as `group:<name>` filters and as a list grouping level.
+    pub groups: Option<BTreeMap<String, RepoGroupConfig>>,
+
+    /// Start with list sections sub-grouped by repo group.
+    pub group_list: Option<bool>,
+
+    /// List layout: `categories` (default), `split` (your PRs / needs your review), `repos`
+    /// (one block per repository) or `topics` (one block per shared label or base branch).
+    pub layout: Option<ListLayout>,
+
+    /// Terminal color depth: `auto` (default), `truecolor`, `256`, `16` or `mono`.
+    pub color_mode: Option<ColorMode>,
+
+    /// Check name patterns (`*` wildcard) collapsed into one row in details view.
+    pub check_groups: Option<Vec<String>>,
+
+    /// Comment posted when handing a PR off to a collaborator (`h` in details view);
+    /// `{login}` is replaced by their login.
+    pub handoff_message: Option<String>,
+
+    /// Review SLAs; the first matching `[[sla]]` entry applies.
+    pub sla: Option<Vec<SlaRuleConfig>>,
+
+    /// Per-repo quick links listed in details view (`[[links]]`); every matching entry applies.
+    pub links: Option<Vec<QuickLinkConfig>>,
+
+    /// Computed list columns (`[[columns]]`), shown between the title and the status.
+    pub columns: Option<Vec<CustomColumnConfig>>,
+
+    /// Team-managed config merged under this one: a file path or an http(s) URL.
+    pub team_config: Option<String>,
+
+    /// How long a fetched team config URL is cached (seconds). Default: 3600.
+    pub team_config_refresh_secs: Option<u64>,
+
+    /// Profile used while `--oncall` is active.
+    pub oncall: Option<OncallConfig>,
+
+    /// Scoring tweaks (`[scoring.drafts]`, `[scoring.authors]`).
+    pub scoring: Option<ScoringConfig>,
+
+    /// PRs unsubscribed from on GitHub: `show` (default), `demote` or `hide`.
+    pub unsubscribed_prs: Option<UnsubscribedMode>,
+
+    /// Bot PRs (dependabot, renovate): `normal` (default), `separate_section` or `hide`.
+    pub bots: Option<BotMode>,
+
+    /// Mark a PR read in the github.com inbox when it is opened from needle. Default: false.
+    pub mark_read_on_open: Option<bool>,
+
+    /// Most items on the "Today" list (`T`). Default: 10.
+    pub today_limit: Option<usize>,
+
+    /// Post alerts to a Slack channel (`[slack]`).
+    pub slack: Option<SlackConfig>,
+
+    /// Drop cached PRs not seen for this many days (checked at startup). At least 1.
+    #[serde(deserialize_with = "at_least_one_day")]
+    pub cache_retention_days: Option<i64>,
+
+    /// Keep PR titles and descriptions on disk. `false` stores SHA-256 hashes only and
+    /// no review comments. Default: true.
+    pub cache_pr_text: Option<bool>,
+
+    /// Code host: `"github"` (default) or `"gitlab"`.
+    pub provider: Option<String>,
+
+    /// GitLab instance for `provider = "gitlab"`. Default: https://gitlab.com.
+    pub gitlab_url: Option<String>,
+}
+
+impl Config {
+    /// Layer this (personal) config over `team`.
+    ///
+    /// Scalars and lists fall back to the team value when unset here, except `exclude`,
+    /// which is a mute list and is unioned. Repo groups merge by name (personal wins),
+    /// and personal SLA rules and quick links come before the team's. Data retention settings are
+    /// policy, so the stricter of the two applies. The code host, `[slack]` and
+    /// `mark_read_on_open` come from this config only.
+    pub fn merge_under(self, team: Config) -> Config {
+        let exclude = match (self.exclude, team.exclude) {
+            (Some(mut mine), Some(theirs)) => {
+                for r in theirs {
+                    if !mine.contains(&r) {
+                        mine.push(r);
+                    }
+                }
+                Some(mine)
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+        let groups = match (self.groups, team.groups) {
+            (Some(mine), Some(mut theirs)) => {
+                theirs.extend(mine);
+                Some(theirs)
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+        let sla = match (self.sla, team.sla) {
+            (Some(mut mine), Some(theirs)) => {
+                mine.extend(theirs);
+                Some(mine)
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+        let scoring = match (self.scoring, team.scoring) {
+            (Some(mine), Some(theirs)) => Some(mine.merge_under(theirs)),
+            (mine, theirs) => mine.or(theirs),
+        };
+        let links = match (self.links, team.links) {
+            (Some(mut mine), Some(theirs)) => {
+                mine.extend(theirs);
+                Some(mine)
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+        Config {
+            days: self.days.or(team.days),
+            org: self.org.or(team.org),
+            include: self.include.or(team.include),
+            exclude,
+            include_milestones: self.include_milestones.or(team.include_milestones),
+            exclude_milestones: self.exclude_milestones.or(team.exclude_milestones),
+            include_team_requests: self.include_team_requests.or(team.include_team_requests),
+            scope: self.scope.or(team.scope),
+            mode: self.mode.or(team.mode),
+            bell: self.bell.or(team.bell),
+            bell_events: self.bell_events.or(team.bell_events),
+            remind_after_mins: self.remind_after_mins.or(team.remind_after_mins),
+            terminal_title: self.terminal_title.or(team.terminal_title),
+            tmux_status: self.tmux_status.or(team.tmux_status),
+            hide_pr_numbers: self.hide_pr_numbers.or(team.hide_pr_numbers),
+            hide_repo: self.hide_repo.or(team.hide_repo),
+            hide_author: self.hide_author.or(team.hide_author),
+            truncate_keep_tail: self.truncate_keep_tail.or(team.truncate_keep_tail),
+            refresh_interval_list_secs: self
+                .refresh_interval_list_secs
+                .or(team.refresh_interval_list_secs),
+            refresh_interval_details_secs: self
+                .refresh_interval_details_secs
+                .or(team.refresh_interval_details_secs),
+            groups,
+            group_list: self.group_list.or(team.group_list),
+            layout: self.layout.or(team.layout),
+            color_mode: self.color_mode.or(team.color_mode),
+            check_groups: self.check_groups.or(team.check_groups),
+            handoff_message: self.handoff_message.or(team.handoff_message),
+            sla,
+            links,
+            columns: self.columns.or(team.columns),
+            oncall: self.oncall.or(team.oncall),
+            scoring,
+            unsubscribed_prs: self.unsubscribed_prs.or(team.unsubscribed_prs),
+            bots: self.bots.or(team.bots),
+            today_limit: self.today_limit.or(team.today_limit),
+            cache_retention_days: match (self.cache_retention_days, team.cache_retention_days) {
+                (Some(mine), Some(theirs)) => Some(mine.min(theirs)),
+                (mine, theirs) => mine.or(theirs),
+            },
+            cache_pr_text: match (self.cache_pr_text, team.cache_pr_text) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (mine, theirs) => mine.or(theirs),
+            },
+            // Where the token goes, where alerts are posted and writes on the viewer's
+            // behalf are personal: a team file, fetched from any URL, must not pick them.
+            provider: self.provider,
+            gitlab_url: self.gitlab_url,
+            slack: self.slack,
+            mark_read_on_open: self.mark_read_on_open,
+            // The overlay is a single level; a team file cannot chain another one.
+            team_config: self.team_config,
+            team_config_refresh_secs: self.team_config_refresh_secs,
+        }
+    }
+}
+
+/// One `[groups.<name>]` table.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RepoGroupConfig {
+    /// Repos in this group (`owner/repo`, or `owner/*` for a whole org).
+    pub repos: Vec<String>,
+}
+
+/// One `[[sla]]` entry.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SlaRuleConfig {
+    /// Repos this SLA covers (`owner/repo` or `owner/*`); empty means all.
+    pub repos: Vec<String>,
+    /// Labels this SLA covers (any match); empty means all.
+    pub labels: Vec<String>,
+    /// Hours allowed between the review request and the review.
+    pub hours: u32,
+    /// Count only weekdays (UTC). Default: true.
+    pub business_hours: Option<bool>,
+}
+
+/// One `[[links]]` entry.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct QuickLinkConfig {
+    /// Repos this link is shown for (`owner/repo` or `owner/*`); empty means all.
+    pub repos: Vec<String>,
+    /// Label in details view.
+    pub name: String,
+    /// Target; `{owner}`, `{repo}` and `{number}` are replaced with the PR's.
+    pub url: String,
+}
+
+/// One `[[columns]]` entry.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CustomColumnConfig {
+    /// Column header.
+    pub name: String,
+    /// Integer expression over PR fields, e.g. `(now - created_at) / 86400`.
+    pub expr: String,
+}
+
+/// The `[oncall]` table.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OncallConfig {
+    /// Labels marking incident PRs. Default: `["incident", "hotfix"]`.
+    pub labels: Option<Vec<String>>,
+    /// Head branch prefixes marking hotfixes. Default: `["hotfix/", "hotfix-"]`.
+    pub branch_prefixes: Option<Vec<String>>,
+    /// List view auto-refresh while on call (seconds). Default: 60.
+    pub refresh_interval_list_secs: Option<u64>,
+    /// Details view auto-refresh while on call (seconds). Default: 15.
+    pub refresh_interval_details_secs: Option<u64>,
+}
+
+/// The `[slack]` table.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SlackConfig {
+    /// Incoming webhook URL of the channel to post to.
+    pub webhook_url: Option<String>,
+    /// Alerts to post: `needs_you`, `review_requested`, `ci_failed`, `ready_to_merge`,
+    /// `new_repo`, `reply`, `reminder`, `approved_by`. Default: `needs_you`, `ci_failed`,
+    /// `reminder`.
+    pub events: Option<Vec<String>>,
+}
+
+/// The `[scoring]` table.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ScoringConfig {
+    pub drafts: Option<DraftScoringConfig>,
+    /// Score added to PRs by each author (negative to mute), e.g. `dependabot = -40`.
+    pub authors: Option<BTreeMap<String, i32>>,
+}
+
+impl ScoringConfig {
+    /// Field by field, so a personal `[scoring.drafts]` keeps the team's `[scoring.authors]`;
+    /// author rules are combined, with personal entries winning per login.
+    fn merge_under(self, team: ScoringConfig) -> ScoringConfig {
+        let drafts = match (self.drafts, team.drafts) {
+            (Some(mine), Some(theirs)) => Some(DraftScoringConfig {
+                mine: mine.mine.or(theirs.mine),
+                others: mine.others.or(theirs.others),
+                surface_failed_ci: mine.surface_failed_ci.or(theirs.surface_failed_ci),
+            }),
+            (mine, theirs) => mine.or(theirs),
+        };
+        let authors = match (self.authors, team.authors) {
+            (Some(mine), Some(mut theirs)) => {
+                theirs.extend(mine);
+                Some(theirs)
+            }
+            (mine, theirs) => mine.or(theirs),
+        };
+        ScoringConfig { drafts, authors }
+    }
+}
+
+/// The `[scoring.drafts]` table.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DraftScoringConfig {
+    /// Your drafts: `section` (default), `score`, or `hide`.
+    pub mine: Option<DraftMode>,
+    /// Others' drafts: `section` (default), `score`, or `hide`.
+    pub others: Option<DraftMode>,
+    /// Rank your drafts with failing CI as regular PRs (boosted). Default: false.
+    pub surface_failed_ci: Option<bool>,
 }
 
 /// Returns the path to the config file.
@@ -63,10 +408,18 @@ pub fn config_path() -> Option<PathBuf> {
 const DEFAULT_CONFIG: &str = r#"# Needle configuration file
 # All fields are optional - CLI arguments override these values
 # Uncomment and modify the options you want to customize
+# Saving while the TUI runs applies refresh intervals, hidden columns, truncate_keep_tail, bell,
+# terminal_title, tmux_status, check_groups, handoff_message and links; other settings need a
+# restart
 
 # Only include PRs updated in the last N days (default: 30)
 # days = 30
 
+# Code host: "github" (default) or "gitlab". GitLab reads NEEDLE_GITLAB_TOKEN or
+# GITLAB_TOKEN (a personal access token with the read_api scope).
+# provider = "gitlab"
+# gitlab_url = "https://gitlab.example.com"  # self-managed instance (default: gitlab.com)
+
 # Only show PRs from these orgs/users
 # org = ["my-company", "my-username"]
 
@@ -76,20 +429,173 @@ const DEFAULT_CONFIG: &str = r#"# Needle configuration file
 # Exclude these repos from the list (owner/repo)
 # exclude = ["my-company/noisy-repo", "my-company/legacy-repo"]
 
+# Only show PRs in these milestones, e.g. during a release crunch, or hide some (by title)
+# include_milestones = ["v3.2"]
+# exclude_milestones = ["Backlog"]
+
 # Include PRs where review is requested from teams you're in (default: false)
 # include_team_requests = false
 
+# Extra scopes: "involves-me:discussion" adds other people's PRs you commented on and
+# flags new replies to your comments with 💬 (default: none)
+# scope = ["involves-me:discussion"]
+
+# PRs you unsubscribed from on GitHub ("Unsubscribe" on the PR page): "show" (default),
+# "demote" (badged 🔕 and ranked last) or "hide"
+# unsubscribed_prs = "demote"
+
+# Bot PRs (dependabot, renovate, ...): "normal" (ranked like any PR, default),
+# "separate_section" (a 🤖 BOTS section at the bottom, for merging them in one go) or "hide"
+# bots = "separate_section"
+
+# Opening a PR in the browser from needle also marks it read in your github.com inbox, so
+# the web inbox does not show it again. Needs a classic token (default: false)
+# mark_read_on_open = true
+
+# How many items `T` (Today: overdue reviews, your red PRs, approved PRs to merge) lists
+# at most (default: 10)
+# today_limit = 5
+
+# "reviewer" uses needle as a review inbox only: your own PRs are not fetched (roughly
+# half the API calls), so READY TO MERGE and the split layout go away. "author" is the
+# opposite, a health board of your own PRs: review requests are not fetched, and failing
+# CI or merge conflicts keep a PR in NEEDS YOU (default: "full")
+# mode = "reviewer"
+
 # Ring terminal bell on important events (default: false)
 # bell = false
 
+# Events that ring the bell: "needs_you" (a PR entered NEEDS YOU), "review_requested",
+# "ci_failed" (or "ci_failure"), "ready_to_merge" (a PR entered READY TO MERGE), "new_repo"
+# (first PR from a repo not seen before), "reply", "reminder", "approved_by" (default: all)
+# bell_events = ["needs_you", "ci_failed"]
+
+# Alert once more (bell, `needle watch`, Slack) when a PR entered NEEDS YOU and you neither
+# opened nor snoozed it within this many minutes (default: off)
+# remind_after_mins = 30
+
+# Put the NEEDS YOU count in the terminal title ("needle (3)"), updated on every refresh, so
+# it shows on a background tab or tmux pane (default: false)
+# terminal_title = true
+
+# Inside tmux, also set the global option @needle_needs_you to the count, e.g. for
+# set -g status-right '#{@needle_needs_you} need you' (default: false)
+# tmux_status = true
+
 # Hide columns in list view
 # hide_pr_numbers = false
 # hide_repo = false
 # hide_author = false
 
+# Columns whose truncation cuts in the middle so a trailing issue key or tag stays visible
+# ("Fix login redirect… [PROJ-123]"): "title", "repo", "author", "status" (default: ["title"];
+# [] cuts every column at the end)
+# truncate_keep_tail = ["title"]
+
 # Auto-refresh intervals in seconds
 # refresh_interval_list_secs = 180    # 3 minutes for list view
 # refresh_interval_details_secs = 30  # 30 seconds for details view
+
+# Start with list sections sub-grouped by repo group (toggle with `g`; default: false)
+# group_list = false
+
+# List layout: "categories" (score-based sections, default), "split" (your PRs on top,
+# PRs needing your review below, each with its own selection; toggle with `L`), "repos"
+# (one foldable block per repository; `g` cycles to it) or "topics" (one foldable block
+# per label or non-default base branch that several PRs share; `g` again from "repos").
+# layout = "split"
+
+# Colors: "auto" (default; detected from NO_COLOR, COLORTERM and TERM), "truecolor", "256",
+# "16" or "mono". Below truecolor, colors map to the nearest the terminal has; "16" also shows
+# dimmed rows in dark gray and draws the refresh shimmer in ASCII, "mono" drops colors.
+# color_mode = "16"
+
+# Details view collapses matrix checks like `test (ubuntu, 1.75)` into one expandable row
+# per name (Enter expands). Checks matching one of these patterns (`*` = anything) also
+# collapse into a row named after the pattern.
+# check_groups = ["e2e shard *", "deploy / *"]
+
+# Comment drafted by `h` in details view to hand a PR off to a recent collaborator
+# ({login} = who you pick). You can edit it before posting.
+# handoff_message = "@{login} could you take this review over? I'm out of office."
+
+# Data retention: drop cached PRs not seen for N days (checked at startup; default: keep)
+# cache_retention_days = 30
+# Keep PR titles/descriptions and review comments in the local cache (default: true); false
+# stores SHA-256 hashes only. `needle purge --older-than 30d` clears old rows on demand.
+# cache_pr_text = false
+
+# Team config merged under this file (path or http(s) URL); your settings win,
+# `exclude` lists are combined; provider, gitlab_url, [slack] and mark_read_on_open are
+# never taken from it. URLs are cached and re-fetched once the copy is older than
+# team_config_refresh_secs: at startup, and on that timer while the TUI runs.
+# team_config = "https://raw.githubusercontent.com/my-company/needle-config/main/team.toml"
+# team_config_refresh_secs = 3600
+
+# Repo groups: filter with `group:platform`; `owner/*` matches a whole org.
+# Keep these tables at the end of the file (TOML tables absorb the keys after them).
+# [groups.platform]
+# repos = ["my-company/api", "my-company/worker"]
+# [groups.mobile]
+# repos = ["my-company/ios", "my-company/android"]
+
+# Review SLAs: requested reviews are due N hours after the request (first match wins).
+# PRs nearing the deadline get a ⏰ badge, overdue ones 🚨; filter them with `s`.
+# [[sla]]
+# labels = ["hotfix"]
+# hours = 4
+# business_hours = false
+# [[sla]]
+# repos = ["my-company/*"]
+# hours = 24              # business hours by default (weekends skipped)
+
+# Quick links in details view, opened with 1-9: dashboards, deploy pages, runbooks.
+# Every matching entry is listed; {owner}, {repo} and {number} are filled in from the PR.
+# [[links]]
+# repos = ["my-company/api"]
+# name = "Grafana"
+# url = "https://grafana.example.com/d/api-overview"
+# [[links]]
+# repos = ["my-company/*"]
+# name = "Deploys"
+# url = "https://deploy.example.com/{repo}"
+
+# Computed list columns, shown between the title and the status. Expressions use integers,
+# + - * / and parentheses over PR fields: now, created_at, updated_at, review_requested_at,
+# last_force_push_at, last_reply_at (unix seconds), number, score, comments,
+# unresolved_threads, labels, reviewers, checks, failing_checks, draft and mine (0/1).
+# [[columns]]
+# name = "age_days"
+# expr = "(now - created_at) / 86400"
+
+# On-call profile, active with `needle --oncall`: incident-labeled and hotfix-branch PRs
+# jump to the top, refresh is faster, and the bell is on.
+# [oncall]
+# labels = ["incident", "hotfix"]
+# branch_prefixes = ["hotfix/", "hotfix-"]
+# refresh_interval_list_secs = 60
+# refresh_interval_details_secs = 15
+
+# Post alerts to a Slack channel through an incoming webhook, from whichever of the TUI,
+# `needle watch`, `serve` or `rpc` is refreshing. Each alert is posted once.
+# events: any of the bell_events kinds above, e.g. "approved_by" (one of your PRs got
+# approved) (default: ["needs_you", "ci_failed", "reminder"]).
+# [slack]
+# webhook_url = "https://hooks.slack.com/services/T000/B000/XXXX"
+# events = ["needs_you", "ci_failed"]
+
+# Drafts: "section" (dimmed DRAFT section, default), "score" (ranked like other PRs), "hide".
+# surface_failed_ci pulls your drafts with failing CI into NEEDS YOU.
+# [scoring.drafts]
+# mine = "section"
+# others = "hide"
+# surface_failed_ci = true
+
+# Per-author priority: added to the score of every PR by that author, so teammates can rank
+# higher and bots lower. Logins match case-insensitively; details view shows the rule used.
+# [scoring.authors]
+# dependabot = -40
+# alice = 15
 "#;
 
 /// Create the default config file if it doesn't exist.
@@ -143,6 +649,193 @@ pub fn load_config() -> Config {
     }
 }
 
+/// Read and parse the config file at `path` without falling back to defaults, for
+/// reloading it while needle runs.
+pub fn read_config(path: &Path) -> Result<Config, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file at {}: {e}", path.display()))?;
+    toml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse config file at {}: {e}", path.display()))
+}
+
+/// Add `key = [values]` (a scope list such as `include`) to the config file at `path`,
+/// under a `# note` comment. It goes at the top, where a top-level key cannot end up inside
+/// a table. A key the file already sets is left alone.
+pub fn prepend_scope(path: &Path, key: &str, values: &[String], note: &str) -> Result<(), String> {
+    let mut block = format!("# {note}\n{key} = [\n");
+    for v in values {
+        block.push_str(&format!("  {},\n", toml::Value::String(v.clone())));
+    }
+    block.push_str("]\n");
+
+    let current = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file at {}: {e}", path.display()))?;
+    let table: toml::Table = toml::from_str(&current)
+        .map_err(|e| format!("Failed to parse config file at {}: {e}", path.display()))?;
+    if table.contains_key(key) {
+        return Err(format!(
+            "{} already sets `{key}`; merge this in by hand:\n\n{block}",
+            path.display()
+        ));
+    }
+    std::fs::write(path, format!("{block}\n{current}"))
+        .map_err(|e| format!("Failed to write config file: {e}"))
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+fn expand_home(source: &str) -> PathBuf {
+    match (source.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(source),
+    }
+}
+
+/// Cached copy of the team config at `url`, one file per URL so switching `team_config`
+/// to another URL never serves the old one.
+fn team_cache_path(url: &str) -> Option<PathBuf> {
+    let digest = ring::digest::digest(&ring::digest::SHA256, url.as_bytes());
+    let hex: String = digest.as_ref()[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    dirs::cache_dir().map(|d| d.join("needle").join(format!("team-config-{hex}.toml")))
+}
+
+fn is_fresh(path: &Path, max_age: Duration, now: SystemTime) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age < max_age)
+}
+
+/// Fetch a team config without credentials (the GitHub token never leaves for other hosts).
+async fn fetch_team_config(url: &str) -> Result<String, String> {
+    let octo = Octocrab::builder()
+        .build()
+        .map_err(|e| format!("Failed to init HTTP client: {e}"))?;
+    let res = octo
+        ._get(url)
+        .await
+        .map_err(|e| format!("Failed to fetch team config {url}: {e}"))?;
+    if !res.status().is_success() {
+        return Err(format!(
+            "Failed to fetch team config {url}: HTTP {}",
+            res.status()
+        ));
+    }
+    octo.body_to_string(res)
+        .await
+        .map_err(|e| format!("Failed to read team config {url}: {e}"))
+}
+
+/// Read the team config text, going through the on-disk cache for URLs.
+///
+/// A stale cache is still used when the fetch fails, so being offline keeps team settings;
+/// the failed fetch comes back as a warning next to the text.
+async fn read_team_config(
+    source: &str,
+    refresh_secs: u64,
+) -> Result<(String, Option<String>), String> {
+    if !is_url(source) {
+        let path = expand_home(source);
+        return std::fs::read_to_string(&path)
+            .map(|text| (text, None))
+            .map_err(|e| format!("Failed to read team config at {}: {e}", path.display()));
+    }
+    let cache = team_cache_path(source);
+    let fresh = cache
+        .as_deref()
+        .filter(|c| is_fresh(c, Duration::from_secs(refresh_secs), SystemTime::now()))
+        .and_then(|c| std::fs::read_to_string(c).ok());
+    if let Some(text) = fresh {
+        return Ok((text, None));
+    }
+    match fetch_team_config(source).await {
+        Ok(text) => {
+            if let Some(parent) = cache.as_deref().and_then(Path::parent) {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Some(cache) = cache.as_deref() {
+                let _ = std::fs::write(cache, &text);
+            }
+            Ok((text, None))
+        }
+        Err(e) => match cache.and_then(|c| std::fs::read_to_string(c).ok()) {
+            Some(text) => Ok((text, Some(format!("{e} (using cached copy)")))),
+            None => Err(e),
+        },
+    }
+}
+
+/// How often a running TUI re-checks the team config, `None` without one.
+pub fn team_config_refresh(config: &Config) -> Option<Duration> {
+    config.team_config.as_ref()?;
+    let secs = config.team_config_refresh_secs.unwrap_or(3600).max(60);
+    Some(Duration::from_secs(secs))
+}
+
+/// The team config text `config` points at (re-fetched when the cached copy is stale), or
+/// `None` without a team config or when it cannot be read.
+pub async fn team_config_text(config: &Config) -> Option<String> {
+    let source = config.team_config.as_deref()?;
+    let refresh_secs = config.team_config_refresh_secs.unwrap_or(3600);
+    let (text, _) = read_team_config(source, refresh_secs).await.ok()?;
+    Some(text)
+}
+
+/// Merge the team config named by `config.team_config` (if any) under `config`.
+///
+/// Problems are reported as warnings; the personal config is returned unchanged.
+pub async fn apply_team_config(config: Config) -> Config {
+    let (config, warning) = merge_team_config(config).await;
+    if let Some(w) = warning {
+        eprintln!("Warning: {w}");
+    }
+    config
+}
+
+/// Like [`apply_team_config`], but never fetches: a remote team config comes from the copy
+/// the last fetch cached, if any. Problems are ignored (for `needle status`, which must stay
+/// quiet and offline).
+pub fn apply_cached_team_config(config: Config) -> Config {
+    let Some(source) = config.team_config.clone() else {
+        return config;
+    };
+    let text = if is_url(&source) {
+        team_cache_path(&source).and_then(|c| std::fs::read_to_string(c).ok())
+    } else {
+        std::fs::read_to_string(expand_home(&source)).ok()
+    };
+    match text.and_then(|t| toml::from_str::<Config>(&t).ok()) {
+        Some(team) => config.merge_under(team),
+        None => config,
+    }
+}
+
+/// Like [`apply_team_config`], but hands the problem back instead of printing it (the TUI
+/// shows it when reloading the config).
+pub async fn merge_team_config(config: Config) -> (Config, Option<String>) {
+    let Some(source) = config.team_config.clone() else {
+        return (config, None);
+    };
+    let refresh_secs = config.team_config_refresh_secs.unwrap_or(3600);
+    let (team, warning) = match read_team_config(&source, refresh_secs).await {
+        Ok((text, warning)) => match toml::from_str::<Config>(&text) {
+            Ok(team) => (team, warning),
+            Err(e) => {
+                let warning = format!("Failed to parse team config {source}: {e}");
+                return (config, Some(warning));
+            }
+        },
+        Err(e) => return (config, Some(e)),
+    };
+    (config.merge_under(team), warning)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,7 +856,10 @@ org = ["my-company", "other-org"]
 include = ["my-company/important-repo"]
 exclude = ["my-company/legacy-repo"]
 include_team_requests = true
+scope = ["involves-me:discussion"]
+mode = "reviewer"
 bell = true
+bell_events = ["ci_failed", "approved_by"]
 hide_pr_numbers = false
 hide_repo = false
 hide_author = true
@@ -185,7 +881,16 @@ refresh_interval_details_secs = 15
             Some(vec!["my-company/legacy-repo".to_string()])
         );
         assert_eq!(config.include_team_requests, Some(true));
+        assert_eq!(
+            config.scope,
+            Some(vec!["involves-me:discussion".to_string()])
+        );
+        assert_eq!(config.mode, Some(UsageMode::Reviewer));
         assert_eq!(config.bell, Some(true));
+        assert_eq!(
+            config.bell_events,
+            Some(vec!["ci_failed".to_string(), "approved_by".to_string()])
+        );
         assert_eq!(config.hide_author, Some(true));
         assert_eq!(config.refresh_interval_list_secs, Some(120));
         assert_eq!(config.refresh_interval_details_secs, Some(15));
@@ -225,8 +930,419 @@ bell = true
         assert!(config.hide_pr_numbers.is_none());
         assert!(config.hide_repo.is_none());
         assert!(config.hide_author.is_none());
+        assert!(config.truncate_keep_tail.is_none());
         assert!(config.refresh_interval_list_secs.is_none());
         assert!(config.refresh_interval_details_secs.is_none());
+        assert!(config.groups.is_none());
+        assert!(config.group_list.is_none());
+        assert!(config.sla.is_none());
+        assert!(config.links.is_none());
+        assert!(config.columns.is_none());
+        assert!(config.team_config.is_none());
+        assert!(config.team_config_refresh_secs.is_none());
+        assert!(config.oncall.is_none());
+        assert!(config.scoring.is_none());
+        assert!(config.bots.is_none());
+    }
+
+    #[test]
+    fn test_parse_repo_groups() {
+        let toml_str = r#"
+group_list = true
+
+[groups.platform]
+repos = ["acme/api", "acme/worker"]
+
+[groups.mobile]
+repos = ["acme-mobile/*"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.group_list, Some(true));
+        let groups = config.groups.unwrap();
+        assert_eq!(
+            groups.keys().collect::<Vec<_>>(),
+            vec!["mobile", "platform"]
+        );
+        assert_eq!(
+            groups["platform"].repos,
+            vec!["acme/api".to_string(), "acme/worker".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_sla_rules() {
+        let toml_str = r#"
+[[sla]]
+labels = ["hotfix"]
+hours = 4
+business_hours = false
+
+[[sla]]
+repos = ["acme/*"]
+hours = 24
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let sla = config.sla.unwrap();
+        assert_eq!(
+            sla,
+            vec![
+                SlaRuleConfig {
+                    repos: vec![],
+                    labels: vec!["hotfix".to_string()],
+                    hours: 4,
+                    business_hours: Some(false),
+                },
+                SlaRuleConfig {
+                    repos: vec!["acme/*".to_string()],
+                    labels: vec![],
+                    hours: 24,
+                    business_hours: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_slack_table() {
+        let toml_str = r#"
+[slack]
+webhook_url = "https://hooks.slack.com/services/T/B/X"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let slack = config.slack.unwrap();
+        assert_eq!(
+            slack.webhook_url.as_deref(),
+            Some("https://hooks.slack.com/services/T/B/X")
+        );
+        assert!(slack.events.is_none());
+    }
+
+    #[test]
+    fn test_parse_oncall_profile() {
+        let toml_str = r#"
+[oncall]
+labels = ["sev1"]
+refresh_interval_list_secs = 30
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let oncall = config.oncall.unwrap();
+        assert_eq!(oncall.labels, Some(vec!["sev1".to_string()]));
+        assert!(oncall.branch_prefixes.is_none());
+        assert_eq!(oncall.refresh_interval_list_secs, Some(30));
+    }
+
+    #[test]
+    fn test_team_retention_policy_is_not_loosened() {
+        let mine: Config =
+            toml::from_str("cache_retention_days = 90\ncache_pr_text = true").unwrap();
+        let team: Config =
+            toml::from_str("cache_retention_days = 14\ncache_pr_text = false").unwrap();
+        let merged = mine.merge_under(team);
+        assert_eq!(merged.cache_retention_days, Some(14));
+        assert_eq!(merged.cache_pr_text, Some(false));
+
+        let mine: Config = toml::from_str("cache_retention_days = 7").unwrap();
+        let merged = mine.merge_under(Config::default());
+        assert_eq!(merged.cache_retention_days, Some(7));
+        assert_eq!(merged.cache_pr_text, None);
+
+        for days in ["0", "-1"] {
+            let parsed = toml::from_str::<Config>(&format!("cache_retention_days = {days}"));
+            assert!(parsed.is_err(), "{days}");
+        }
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        let config: Config = toml::from_str("layout = \"split\"").unwrap();
+        assert_eq!(config.layout, Some(ListLayout::Split));
+        let config: Config = toml::from_str("layout = \"repos\"").unwrap();
+        assert_eq!(config.layout, Some(ListLayout::Repos));
+        let config: Config = toml::from_str("layout = \"topics\"").unwrap();
+        assert_eq!(config.layout, Some(ListLayout::Topics));
+        assert!(toml::from_str::<Config>("layout = \"grid\"").is_err());
+    }
+
+    #[test]
+    fn test_prepend_scope_keeps_the_rest_of_the_file() {
+        let path = std::env::temp_dir().join("needle-prepend-scope-test.toml");
+        fs::write(&path, "bell = true\n\n[slack]\nevents = [\"ci_failed\"]\n").unwrap();
+        let repos = vec!["acme/api".to_string(), "acme/web".to_string()];
+        prepend_scope(&path, "include", &repos, "Repos you watch").unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(
+            written.starts_with("# Repos you watch\ninclude = [\n  \"acme/api\",\n"),
+            "{written}"
+        );
+        let config = read_config(&path).unwrap();
+        assert_eq!(config.include, Some(repos.clone()));
+        assert_eq!(config.bell, Some(true));
+        assert!(config.slack.is_some());
+
+        let err = prepend_scope(&path, "include", &repos, "again").unwrap_err();
+        assert!(err.contains("already sets `include`"), "{err}");
+        assert_eq!(fs::read_to_string(&path).unwrap(), written);
+    }
+
+    #[test]
+    fn test_parse_bots() {
+        let config: Config = toml::from_str("bots = \"separate_section\"").unwrap();
+        assert_eq!(config.bots, Some(BotMode::SeparateSection));
+        assert!(toml::from_str::<Config>("bots = \"bottom\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_unsubscribed_prs() {
+        let config: Config = toml::from_str("unsubscribed_prs = \"hide\"").unwrap();
+        assert_eq!(config.unsubscribed_prs, Some(UnsubscribedMode::Hide));
+        assert!(toml::from_str::<Config>("unsubscribed_prs = \"mute\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_color_mode() {
+        let config: Config = toml::from_str("color_mode = \"256\"").unwrap();
+        assert_eq!(config.color_mode, Some(ColorMode::Ansi256));
+        let config: Config = toml::from_str("color_mode = \"mono\"").unwrap();
+        assert_eq!(config.color_mode, Some(ColorMode::Mono));
+        assert!(toml::from_str::<Config>("color_mode = \"8\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_gitlab_provider() {
+        let config: Config =
+            toml::from_str("provider = \"gitlab\"\ngitlab_url = \"https://git.acme.io\"").unwrap();
+        assert_eq!(config.provider.as_deref(), Some("gitlab"));
+        assert_eq!(config.gitlab_url.as_deref(), Some("https://git.acme.io"));
+
+        let merged = config.merge_under(Config::default());
+        assert_eq!(merged.provider.as_deref(), Some("gitlab"));
+    }
+
+    #[test]
+    fn test_parse_draft_scoring() {
+        let toml_str = r#"
+[scoring.drafts]
+others = "hide"
+surface_failed_ci = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let drafts = config.scoring.unwrap().drafts.unwrap();
+        assert_eq!(drafts.mine, None);
+        assert_eq!(drafts.others, Some(DraftMode::Hide));
+        assert_eq!(drafts.surface_failed_ci, Some(true));
+
+        let bad = toml::from_str::<Config>("[scoring.drafts]\nmine = \"bury\"\n");
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn test_parse_author_scoring() {
+        let toml_str = r#"
+[scoring.authors]
+dependabot = -40
+"alice" = 15
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let authors = config.scoring.unwrap().authors.unwrap();
+        assert_eq!(authors["dependabot"], -40);
+        assert_eq!(authors["alice"], 15);
+
+        let bad = toml::from_str::<Config>("[scoring.authors]\nalice = \"up\"\n");
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn test_personal_config_merges_over_team_config() {
+        let personal: Config = toml::from_str(
+            r#"
+days = 7
+exclude = ["acme/noisy"]
+
+[groups.platform]
+repos = ["acme/api"]
+
+[[sla]]
+labels = ["hotfix"]
+hours = 2
+"#,
+        )
+        .unwrap();
+        let team: Config = toml::from_str(
+            r#"
+days = 30
+org = ["acme"]
+exclude = ["acme/legacy", "acme/noisy"]
+team_config = "https://example.com/chained.toml"
+
+[groups.platform]
+repos = ["acme/api", "acme/worker"]
+
+[groups.mobile]
+repos = ["acme/ios"]
+
+[[sla]]
+hours = 24
+"#,
+        )
+        .unwrap();
+
+        let merged = personal.merge_under(team);
+        assert_eq!(merged.days, Some(7));
+        assert_eq!(merged.org, Some(vec!["acme".to_string()]));
+        assert_eq!(
+            merged.exclude,
+            Some(vec!["acme/noisy".to_string(), "acme/legacy".to_string()])
+        );
+        let groups = merged.groups.unwrap();
+        assert_eq!(groups["platform"].repos, vec!["acme/api".to_string()]);
+        assert_eq!(groups["mobile"].repos, vec!["acme/ios".to_string()]);
+        let hours: Vec<u32> = merged.sla.unwrap().iter().map(|r| r.hours).collect();
+        assert_eq!(hours, vec![2, 24]);
+        assert!(merged.team_config.is_none());
+    }
+
+    #[test]
+    fn test_team_config_cannot_set_credentials_endpoints_or_writes() {
+        let team: Config = toml::from_str(
+            r#"
+provider = "gitlab"
+gitlab_url = "https://gitlab.example.com"
+mark_read_on_open = true
+
+[slack]
+webhook_url = "https://hooks.example.com/team"
+"#,
+        )
+        .unwrap();
+        let merged = Config::default().merge_under(team);
+        assert!(merged.provider.is_none());
+        assert!(merged.gitlab_url.is_none());
+        assert!(merged.slack.is_none());
+        assert!(merged.mark_read_on_open.is_none());
+    }
+
+    #[test]
+    fn test_personal_scoring_merges_over_team_scoring() {
+        let personal: Config = toml::from_str(
+            r#"
+[scoring.drafts]
+mine = "score"
+
+[scoring.authors]
+alice = 10
+"#,
+        )
+        .unwrap();
+        let team: Config = toml::from_str(
+            r#"
+[scoring.drafts]
+mine = "hide"
+others = "hide"
+
+[scoring.authors]
+alice = -5
+dependabot = -40
+"#,
+        )
+        .unwrap();
+
+        let scoring = personal.merge_under(team).scoring.unwrap();
+        let drafts = scoring.drafts.unwrap();
+        assert_eq!(drafts.mine, Some(DraftMode::Score));
+        assert_eq!(drafts.others, Some(DraftMode::Hide));
+        let authors = scoring.authors.unwrap();
+        assert_eq!(authors["alice"], 10);
+        assert_eq!(authors["dependabot"], -40);
+
+        // A personal [scoring.drafts] alone keeps the team's author rules.
+        let personal: Config = toml::from_str("[scoring.drafts]\nmine = \"score\"\n").unwrap();
+        let team: Config = toml::from_str("[scoring.authors]\nbot = -40\n").unwrap();
+        let scoring = personal.merge_under(team).scoring.unwrap();
+        assert_eq!(scoring.authors.unwrap()["bot"], -40);
+    }
+
+    #[test]
+    fn test_team_config_refresh_timer() {
+        assert_eq!(team_config_refresh(&Config::default()), None);
+        let config: Config = toml::from_str(
+            "team_config = \"https://example.com/team.toml\"\nteam_config_refresh_secs = 600\n",
+        )
+        .unwrap();
+        assert_eq!(team_config_refresh(&config), Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_team_cache_is_keyed_by_url() {
+        let a = team_cache_path("https://example.com/a.toml");
+        assert_eq!(a, team_cache_path("https://example.com/a.toml"));
+        assert_ne!(a, team_cache_path("https://example.com/b.toml"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_team_config_from_file() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("needle-test-team-config-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let team_path = temp_dir.join("team.toml");
+        fs::write(&team_path, "org = [\"acme\"]\nbell = true\n").unwrap();
+
+        let personal = Config {
+            bell: Some(false),
+            team_config: Some(team_path.display().to_string()),
+            ..Config::default()
+        };
+        let merged = apply_team_config(personal).await;
+        assert_eq!(merged.org, Some(vec!["acme".to_string()]));
+        assert_eq!(merged.bell, Some(false));
+
+        // A missing team file leaves the personal config untouched.
+        let missing = Config {
+            team_config: Some(temp_dir.join("nope.toml").display().to_string()),
+            ..Config::default()
+        };
+        assert!(apply_team_config(missing).await.org.is_none());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_read_config_reports_parse_errors() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("needle-test-read-config-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let path = temp_dir.join("config.toml");
+
+        fs::write(&path, "refresh_interval_list_secs = 60\nhide_repo = true\n").unwrap();
+        let config = read_config(&path).unwrap();
+        assert_eq!(config.refresh_interval_list_secs, Some(60));
+        assert_eq!(config.hide_repo, Some(true));
+
+        // Half-saved files are reported, not replaced by defaults.
+        fs::write(&path, "refresh_interval_list_secs = \n").unwrap();
+        let err = read_config(&path).unwrap_err();
+        assert!(err.starts_with("Failed to parse config file at"), "{err}");
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_team_cache_freshness() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("needle-test-team-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let cache = temp_dir.join("team-config.toml");
+        let hour = Duration::from_secs(3600);
+
+        assert!(!is_fresh(&cache, hour, SystemTime::now()));
+        fs::write(&cache, "").unwrap();
+        assert!(is_fresh(&cache, hour, SystemTime::now()));
+        assert!(!is_fresh(&cache, hour, SystemTime::now() + 2 * hour));
+
+        let _ = fs::remove_dir_all(&temp_dir);
     }
 
     #[test]
@@ -248,10 +1364,26 @@ bell = true
             DEFAULT_CONFIG.contains("exclude"),
             "DEFAULT_CONFIG should document 'exclude' option"
         );
+        assert!(
+            DEFAULT_CONFIG.contains("include_milestones"),
+            "DEFAULT_CONFIG should document 'include_milestones' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("exclude_milestones"),
+            "DEFAULT_CONFIG should document 'exclude_milestones' option"
+        );
         assert!(
             DEFAULT_CONFIG.contains("include_team_requests"),
             "DEFAULT_CONFIG should document 'include_team_requests' option"
         );
+        assert!(
+            DEFAULT_CONFIG.contains("involves-me:discussion"),
+            "DEFAULT_CONFIG should document 'scope' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("mode = \"reviewer\""),
+            "DEFAULT_CONFIG should document 'mode' option"
+        );
         assert!(
             DEFAULT_CONFIG.contains("bell"),
             "DEFAULT_CONFIG should document 'bell' option"
@@ -268,6 +1400,10 @@ bell = true
             DEFAULT_CONFIG.contains("hide_author"),
             "DEFAULT_CONFIG should document 'hide_author' option"
         );
+        assert!(
+            DEFAULT_CONFIG.contains("truncate_keep_tail"),
+            "DEFAULT_CONFIG should document 'truncate_keep_tail' option"
+        );
         assert!(
             DEFAULT_CONFIG.contains("refresh_interval_list_secs"),
             "DEFAULT_CONFIG should document 'refresh_interval_list_secs' option"
@@ -276,6 +1412,110 @@ bell = true
             DEFAULT_CONFIG.contains("refresh_interval_details_secs"),
             "DEFAULT_CONFIG should document 'refresh_interval_details_secs' option"
         );
+        assert!(
+            DEFAULT_CONFIG.contains("[groups."),
+            "DEFAULT_CONFIG should document 'groups' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("group_list"),
+            "DEFAULT_CONFIG should document 'group_list' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[[sla]]"),
+            "DEFAULT_CONFIG should document 'sla' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[[links]]"),
+            "DEFAULT_CONFIG should document 'links' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[[columns]]"),
+            "DEFAULT_CONFIG should document 'columns' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("team_config"),
+            "DEFAULT_CONFIG should document 'team_config' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("team_config_refresh_secs"),
+            "DEFAULT_CONFIG should document 'team_config_refresh_secs' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[oncall]"),
+            "DEFAULT_CONFIG should document 'oncall' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[scoring.drafts]"),
+            "DEFAULT_CONFIG should document 'scoring.drafts' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[scoring.authors]"),
+            "DEFAULT_CONFIG should document 'scoring.authors' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("cache_retention_days"),
+            "DEFAULT_CONFIG should document 'cache_retention_days' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("cache_pr_text"),
+            "DEFAULT_CONFIG should document 'cache_pr_text' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("layout"),
+            "DEFAULT_CONFIG should document 'layout' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("color_mode"),
+            "DEFAULT_CONFIG should document 'color_mode' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("bell_events"),
+            "DEFAULT_CONFIG should document 'bell_events' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("unsubscribed_prs"),
+            "DEFAULT_CONFIG should document 'unsubscribed_prs' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("bots ="),
+            "DEFAULT_CONFIG should document 'bots' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("mark_read_on_open"),
+            "DEFAULT_CONFIG should document 'mark_read_on_open' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("today_limit"),
+            "DEFAULT_CONFIG should document 'today_limit' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("[slack]"),
+            "DEFAULT_CONFIG should document the [slack] table"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("remind_after_mins"),
+            "DEFAULT_CONFIG should document 'remind_after_mins' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("terminal_title") && DEFAULT_CONFIG.contains("tmux_status"),
+            "DEFAULT_CONFIG should document 'terminal_title' and 'tmux_status' options"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("check_groups"),
+            "DEFAULT_CONFIG should document 'check_groups' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("handoff_message"),
+            "DEFAULT_CONFIG should document 'handoff_message' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("provider"),
+            "DEFAULT_CONFIG should document 'provider' option"
+        );
+        assert!(
+            DEFAULT_CONFIG.contains("gitlab_url"),
+            "DEFAULT_CONFIG should document 'gitlab_url' option"
+        );
     }
 
     #[test]
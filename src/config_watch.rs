@@ -0,0 +1,98 @@
+//! Live config reload for the TUI.
+//!
+//! Watches the config file's directory (editors often save by replacing the file) and, once
+//! a burst of changes settles, re-reads the config, merges the team config under it and
+//! hands the TUI the settings it can apply without a restart. A team config is also checked
+//! again every `team_config_refresh_secs`, and reloaded when its text changed.
+
+use crate::config::{
+    Config, config_path, merge_team_config, read_config, team_config_refresh, team_config_text,
+};
+use crate::tui::{ConfigReload, LiveSettings};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// Changes closer together than this count as one save.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// Send a [`ConfigReload`] after each save of the config file, built with `settings`.
+///
+/// The watcher stops when dropped. `None` when there is no config directory or it cannot
+/// be watched; the TUI then just keeps its startup settings.
+pub fn watch_config(
+    settings: impl Fn(&Config) -> LiveSettings + Send + 'static,
+) -> Option<(RecommendedWatcher, mpsc::Receiver<ConfigReload>)> {
+    let path = config_path()?;
+    let dir = path.parent()?.to_path_buf();
+    let file_name = path.file_name()?.to_os_string();
+
+    let (changed_tx, changed_rx) = mpsc::channel();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        let ours = event
+            .paths
+            .iter()
+            .any(|p| p.file_name() == Some(file_name.as_os_str()));
+        if ours && (event.kind.is_create() || event.kind.is_modify()) {
+            let _ = changed_tx.send(());
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Warning: not watching the config for changes: {e}");
+            return None;
+        }
+    };
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        eprintln!("Warning: not watching {} for changes: {e}", path.display());
+        return None;
+    }
+
+    let handle = tokio::runtime::Handle::current();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let team_of = |config: &Config| handle.block_on(team_config_text(config));
+        let mut current = read_config(&path).ok();
+        let mut team = current.as_ref().and_then(team_of);
+        // Ends once the watcher (and with it `changed_tx`) is dropped.
+        loop {
+            let saved = match current.as_ref().and_then(team_config_refresh) {
+                Some(every) => match changed_rx.recv_timeout(every) {
+                    Ok(()) => true,
+                    Err(RecvTimeoutError::Timeout) => false,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                },
+                None => match changed_rx.recv() {
+                    Ok(()) => true,
+                    Err(_) => break,
+                },
+            };
+            if saved {
+                while changed_rx.recv_timeout(SETTLE).is_ok() {}
+            }
+            let config = read_config(&path);
+            if let Ok(config) = &config {
+                let fresh_team = team_of(config);
+                // The timer only matters when the team config changed.
+                if !saved && fresh_team == team {
+                    continue;
+                }
+                team = fresh_team;
+            }
+            let reload = config.map(|config| {
+                let (config, warning) = handle.block_on(merge_team_config(config));
+                let reload = (settings(&config), warning);
+                current = Some(config);
+                reload
+            });
+            if tx.send(reload).is_err() {
+                break;
+            }
+        }
+    });
+    Some((watcher, rx))
+}
@@ -1,12 +1,20 @@
-use rusqlite::{Connection, params};
-use std::collections::HashMap;
+//! SQLite cache of the last fetched attention set (plus local-only state like pins).
+
+use crate::timeutil::{Clock, SystemClock};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Increment when cached schema/logic changes require an invalidate-and-reseed.
 pub const CACHE_VERSION: i32 = 1;
 
-#[derive(Debug, Clone)]
+/// Prefix of titles and descriptions stored as hashes (see [`set_hash_pr_text`]).
+pub const HASHED_TEXT_PREFIX: &str = "sha256:";
+
+const SETTING_HASH_PR_TEXT: &str = "hash_pr_text";
+
+/// One cached row of the `prs` table.
+#[derive(Debug, Clone, Default)]
 pub struct DbPrRow {
     pub pr_key: String,
     pub owner: String,
@@ -16,6 +24,7 @@ pub struct DbPrRow {
     pub url: String,
     pub author: Option<String>,
     pub updated_at_unix: Option<i64>,
+    pub created_at_unix: Option<i64>,
 
     pub last_commit_sha: Option<String>,
     pub last_ci_state: Option<String>,
@@ -25,24 +34,57 @@ pub struct DbPrRow {
     pub mergeable: Option<String>,
     pub merge_state_status: Option<String>,
     pub author_is_viewer: Option<i64>,
+    /// 0/1: the viewer is one of the assignees.
+    pub is_assignee: Option<i64>,
+    /// 0/1: the viewer was @-mentioned on the PR.
+    pub is_mentioned: Option<i64>,
+    pub is_unsubscribed: Option<i64>,
+    /// JSON merge blockers; NULL when nothing blocks the merge.
+    pub merge_blockers_json: Option<String>,
+    pub labels_json: Option<String>,
+    pub review_requested_at_unix: Option<i64>,
+    pub head_ref_name: Option<String>,
+    pub base_ref_name: Option<String>,
+    pub milestone: Option<String>,
+    /// Status on the PR's project boards, e.g. `Roadmap: In review`.
+    pub project_status: Option<String>,
+    /// 0/1: the head commit had no CI yet, so the CI columns are the previous commit's.
+    pub ci_previous_commit: Option<i64>,
+    /// Lines added plus lines deleted.
+    pub lines_changed: Option<i64>,
+    pub last_force_push_at_unix: Option<i64>,
+    pub last_reply_at_unix: Option<i64>,
+    pub body_excerpt: Option<String>,
+    pub reviewers_json: Option<String>,
+    pub comments_json: Option<String>,
+    pub review_threads_json: Option<String>,
 
     pub last_seen_at: Option<i64>,
     pub last_opened_at: Option<i64>,
     pub pinned: Option<i64>,
+    /// Snoozed until this unix time (see [`crate::snooze`]).
+    pub snoozed_until: Option<i64>,
+    /// Snoozed until the head moves off this commit.
+    pub snoozed_sha: Option<String>,
+    /// 0/1: hidden from every list until restored.
+    pub ignored: Option<i64>,
+    /// When the code host stopped showing this PR (repository deleted, access revoked).
+    /// Kept as a tombstone for a grace period; cleared when the PR is fetched again.
+    pub inaccessible_since: Option<i64>,
 }
 
+/// Current wall-clock time in unix seconds (see [`crate::timeutil::Clock`] for testable time).
 pub fn now_unix() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64
+    SystemClock.now_unix()
 }
 
+/// Default cache location (`<data_dir>/needle/prs.sqlite`).
 pub fn db_path() -> Result<PathBuf, String> {
     let base = dirs::data_dir().ok_or_else(|| "Failed to resolve data_dir()".to_string())?;
     Ok(base.join("needle").join("prs.sqlite"))
 }
 
+/// Open (or create) the cache at `path`, applying schema migrations.
 pub fn open_db(path: &Path) -> Result<Connection, String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create db dir: {e}"))?;
@@ -51,9 +93,64 @@ pub fn open_db(path: &Path) -> Result<Connection, String> {
     init_schema(&conn)?;
     migrate_schema(&conn)?;
     ensure_cache_version(&conn)?;
+    if hashes_pr_text(&conn)? {
+        // Overwritten plaintext must not linger in free pages.
+        conn.execute_batch("PRAGMA secure_delete = ON")
+            .map_err(|e| format!("Failed to enable secure delete: {e}"))?;
+    }
     Ok(conn)
 }
 
+/// Open the cache at `path` read-only and as is: no schema setup, migration or anything
+/// else written (for `needle status`, which runs on every shell prompt next to a TUI).
+pub fn open_db_read_only(path: &Path) -> Result<Connection, String> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Failed to open sqlite db: {e}"))
+}
+
+/// A fresh cache for one test, named after the test and this process so parallel test runs
+/// never share a file, and removed again on drop. Derefs to its connection.
+#[cfg(any(test, feature = "test-util"))]
+pub struct TestDb {
+    path: PathBuf,
+    conn: Connection,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl TestDb {
+    pub fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("needle-{name}-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let conn = open_db(&path).expect("open the test cache");
+        Self { path, conn }
+    }
+
+    /// Where the cache lives, for tests that open it a second time.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl std::ops::Deref for TestDb {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
 fn init_schema(conn: &Connection) -> Result<(), String> {
     conn.execute_batch(
         r#"
@@ -66,6 +163,7 @@ CREATE TABLE IF NOT EXISTS prs (
   url TEXT NOT NULL,
   author TEXT,
   updated_at_unix INTEGER,
+  created_at_unix INTEGER,         -- when the PR was opened
 
   last_commit_sha TEXT,
   last_ci_state TEXT,              -- success | failure | running | none
@@ -75,10 +173,135 @@ CREATE TABLE IF NOT EXISTS prs (
   mergeable TEXT,                  -- GitHub enum as string
   merge_state_status TEXT,         -- GitHub enum as string
   author_is_viewer INTEGER,        -- 0/1
+  is_assignee INTEGER,             -- 0/1
+  is_mentioned INTEGER,            -- 0/1
+  is_unsubscribed INTEGER,         -- 0/1, notifications turned off on GitHub
+  merge_blockers_json TEXT,        -- JSON merge blockers (optional)
+  labels_json TEXT,                -- JSON array of label names (optional)
+  review_requested_at_unix INTEGER, -- latest review request (for SLAs)
+  head_ref_name TEXT,              -- source branch name
+  base_ref_name TEXT,              -- target branch name
+  milestone TEXT,                  -- milestone title
+  project_status TEXT,             -- project board status, e.g. "Roadmap: In review"
+  ci_previous_commit INTEGER,      -- 0/1, CI columns are the previous commit's
+  lines_changed INTEGER,           -- additions + deletions
+  last_force_push_at_unix INTEGER, -- latest force-push reported by GitHub
+  last_reply_at_unix INTEGER,      -- latest reply by others to the viewer's comments
+  body_excerpt TEXT,               -- first lines of the description
+  reviewers_json TEXT,             -- JSON array of reviewers (optional)
+  comments_json TEXT,              -- JSON array of latest conversation comments (optional)
+  review_threads_json TEXT,        -- JSON array of latest review threads (optional)
 
   last_seen_at INTEGER,            -- unix timestamp
   last_opened_at INTEGER           -- unix timestamp
 );
+
+-- PRs the user asked to follow (`needle add`), fetched on every refresh even when
+-- outside the attention set. Kept separate from `prs` so cache resets don't drop them.
+CREATE TABLE IF NOT EXISTS tracked_prs (
+  pr_key TEXT PRIMARY KEY,        -- "{owner}/{repo}#{number}"
+  added_at INTEGER NOT NULL       -- unix timestamp
+);
+
+-- Head SHA changes observed between refreshes.
+CREATE TABLE IF NOT EXISTS pr_events (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  pr_key TEXT NOT NULL,
+  kind TEXT NOT NULL,              -- push | force_push
+  old_sha TEXT,
+  new_sha TEXT,
+  at INTEGER NOT NULL              -- unix timestamp (when needle noticed)
+);
+CREATE INDEX IF NOT EXISTS pr_events_by_key ON pr_events (pr_key, kind, at);
+
+-- Oldest `updated_at` the cache fully covers, per fetch scope (the high-water mark).
+-- Lets a larger `--days` fetch only the older window it is missing.
+CREATE TABLE IF NOT EXISTS fetch_marks (
+  scope_key TEXT PRIMARY KEY,
+  covered_since INTEGER NOT NULL,  -- unix timestamp
+  updated_at INTEGER NOT NULL      -- unix timestamp
+);
+
+-- Digest of what the last full refresh fetched, per fetch scope. An equal digest means
+-- nothing changed and the refresh can skip its writes.
+CREATE TABLE IF NOT EXISTS fetch_digests (
+  scope_key TEXT PRIMARY KEY,
+  digest TEXT NOT NULL
+);
+
+-- Where the TUI was left (filter, toggles, layout, selected PR), restored on the next start.
+CREATE TABLE IF NOT EXISTS ui_state (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
+
+-- PRs dealt with in the "Today" view, per local day (`done` or `skipped`).
+CREATE TABLE IF NOT EXISTS today_marks (
+  day TEXT NOT NULL,               -- YYYY-MM-DD, local time
+  pr_key TEXT NOT NULL,
+  mark TEXT NOT NULL,
+  PRIMARY KEY (day, pr_key)
+);
+
+-- Requested-changes threads the author checked off as addressed (details view `x`).
+CREATE TABLE IF NOT EXISTS addressed_threads (
+  pr_key TEXT NOT NULL,
+  thread_key TEXT NOT NULL,        -- ReviewThread::key()
+  addressed_at INTEGER NOT NULL,   -- unix timestamp
+  PRIMARY KEY (pr_key, thread_key)
+);
+
+-- Failures of each check on a PR's head commit, as noticed across refreshes. A rerun that
+-- fails again counts once more; rows for older commits are dropped when the head moves.
+CREATE TABLE IF NOT EXISTS check_failures (
+  pr_key TEXT NOT NULL,
+  sha TEXT NOT NULL,
+  check_name TEXT NOT NULL,
+  failures INTEGER NOT NULL,
+  first_failed_at INTEGER NOT NULL, -- unix timestamp (when needle first saw it fail)
+  failing INTEGER NOT NULL,         -- 0/1 as of the last refresh
+  PRIMARY KEY (pr_key, sha, check_name)
+);
+
+-- CI state of the default branch of repositories the viewer reviews into. Checked lazily
+-- during refreshes and re-checked once older than REPO_CI_TTL_SECS.
+CREATE TABLE IF NOT EXISTS repo_ci (
+  repo TEXT PRIMARY KEY,           -- owner/repo
+  ci_state TEXT NOT NULL,          -- success | failure | running | none
+  checked_at INTEGER NOT NULL      -- unix timestamp
+);
+
+-- Alerts already posted to the Slack webhook, so each is sent once.
+CREATE TABLE IF NOT EXISTS slack_posts (
+  pr_key TEXT NOT NULL,
+  event_key TEXT NOT NULL,         -- alert kind plus what identifies this occurrence
+  posted_at INTEGER NOT NULL,      -- unix timestamp
+  PRIMARY KEY (pr_key, event_key)
+);
+
+-- Alerts already announced by the bell or `needle watch`, so restarting either (or running
+-- both) does not repeat them.
+CREATE TABLE IF NOT EXISTS announced_alerts (
+  pr_key TEXT NOT NULL,
+  event_key TEXT NOT NULL,         -- same keys as slack_posts
+  announced_at INTEGER NOT NULL,   -- unix timestamp
+  PRIMARY KEY (pr_key, event_key)
+);
+
+-- Remote actions needle took on the user's behalf (`needle log`), append-only.
+CREATE TABLE IF NOT EXISTS actions_log (
+  at INTEGER NOT NULL,             -- unix timestamp
+  pr_key TEXT NOT NULL,
+  action TEXT NOT NULL,            -- merge | comment | update_branch | draft | ready | ...
+  detail TEXT,                     -- e.g. the merge method
+  error TEXT                       -- why it failed; NULL when it went through
+);
+
+-- Cache-wide options that every writer must honor (e.g. hash_pr_text = 1).
+CREATE TABLE IF NOT EXISTS settings (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
 "#,
     )
     .map_err(|e| format!("Failed to init schema: {e}"))?;
@@ -120,6 +343,29 @@ fn migrate_schema(conn: &Connection) -> Result<(), String> {
     add_if_missing(conn, &existing, "merge_state_status", "TEXT")?;
     add_if_missing(conn, &existing, "author_is_viewer", "INTEGER")?;
     add_if_missing(conn, &existing, "pinned", "INTEGER")?;
+    add_if_missing(conn, &existing, "labels_json", "TEXT")?;
+    add_if_missing(conn, &existing, "review_requested_at_unix", "INTEGER")?;
+    add_if_missing(conn, &existing, "head_ref_name", "TEXT")?;
+    add_if_missing(conn, &existing, "last_force_push_at_unix", "INTEGER")?;
+    add_if_missing(conn, &existing, "last_reply_at_unix", "INTEGER")?;
+    add_if_missing(conn, &existing, "body_excerpt", "TEXT")?;
+    add_if_missing(conn, &existing, "reviewers_json", "TEXT")?;
+    add_if_missing(conn, &existing, "snoozed_until", "INTEGER")?;
+    add_if_missing(conn, &existing, "snoozed_sha", "TEXT")?;
+    add_if_missing(conn, &existing, "ignored", "INTEGER")?;
+    add_if_missing(conn, &existing, "comments_json", "TEXT")?;
+    add_if_missing(conn, &existing, "review_threads_json", "TEXT")?;
+    add_if_missing(conn, &existing, "inaccessible_since", "INTEGER")?;
+    add_if_missing(conn, &existing, "created_at_unix", "INTEGER")?;
+    add_if_missing(conn, &existing, "is_assignee", "INTEGER")?;
+    add_if_missing(conn, &existing, "is_mentioned", "INTEGER")?;
+    add_if_missing(conn, &existing, "merge_blockers_json", "TEXT")?;
+    add_if_missing(conn, &existing, "lines_changed", "INTEGER")?;
+    add_if_missing(conn, &existing, "is_unsubscribed", "INTEGER")?;
+    add_if_missing(conn, &existing, "base_ref_name", "TEXT")?;
+    add_if_missing(conn, &existing, "milestone", "TEXT")?;
+    add_if_missing(conn, &existing, "project_status", "TEXT")?;
+    add_if_missing(conn, &existing, "ci_previous_commit", "INTEGER")?;
 
     Ok(())
 }
@@ -140,6 +386,7 @@ fn ensure_cache_version(conn: &Connection) -> Result<(), String> {
     Ok(())
 }
 
+/// Load every cached PR row keyed by `pr_key`.
 pub fn load_all_prs(conn: &Connection) -> Result<HashMap<String, DbPrRow>, String> {
     let mut stmt = conn
         .prepare(
@@ -148,7 +395,11 @@ SELECT
   pr_key, owner, repo, number, title, url, author, updated_at_unix,
   last_commit_sha, last_ci_state, last_review_state,
   ci_checks_json, is_draft, mergeable, merge_state_status, author_is_viewer,
-  last_seen_at, last_opened_at, pinned
+  last_seen_at, last_opened_at, pinned, labels_json, review_requested_at_unix,
+  head_ref_name, last_force_push_at_unix, last_reply_at_unix, body_excerpt, reviewers_json,
+  snoozed_until, snoozed_sha, ignored, comments_json, review_threads_json,
+  inaccessible_since, created_at_unix, is_assignee, is_mentioned, merge_blockers_json,
+  lines_changed, is_unsubscribed, base_ref_name, milestone, project_status, ci_previous_commit
 FROM prs
 "#,
         )
@@ -172,6 +423,16 @@ FROM prs
             url: row.get(5).map_err(|e| format!("Row decode: {e}"))?,
             author: row.get(6).map_err(|e| format!("Row decode: {e}"))?,
             updated_at_unix: row.get(7).map_err(|e| format!("Row decode: {e}"))?,
+            created_at_unix: row.get(32).map_err(|e| format!("Row decode: {e}"))?,
+            is_assignee: row.get(33).map_err(|e| format!("Row decode: {e}"))?,
+            is_mentioned: row.get(34).map_err(|e| format!("Row decode: {e}"))?,
+            merge_blockers_json: row.get(35).map_err(|e| format!("Row decode: {e}"))?,
+            lines_changed: row.get(36).map_err(|e| format!("Row decode: {e}"))?,
+            is_unsubscribed: row.get(37).map_err(|e| format!("Row decode: {e}"))?,
+            base_ref_name: row.get(38).map_err(|e| format!("Row decode: {e}"))?,
+            milestone: row.get(39).map_err(|e| format!("Row decode: {e}"))?,
+            project_status: row.get(40).map_err(|e| format!("Row decode: {e}"))?,
+            ci_previous_commit: row.get(41).map_err(|e| format!("Row decode: {e}"))?,
             last_commit_sha: row.get(8).map_err(|e| format!("Row decode: {e}"))?,
             last_ci_state: row.get(9).map_err(|e| format!("Row decode: {e}"))?,
             last_review_state: row.get(10).map_err(|e| format!("Row decode: {e}"))?,
@@ -183,27 +444,59 @@ FROM prs
             last_seen_at: row.get(16).map_err(|e| format!("Row decode: {e}"))?,
             last_opened_at: row.get(17).map_err(|e| format!("Row decode: {e}"))?,
             pinned: row.get(18).map_err(|e| format!("Row decode: {e}"))?,
+            labels_json: row.get(19).map_err(|e| format!("Row decode: {e}"))?,
+            review_requested_at_unix: row.get(20).map_err(|e| format!("Row decode: {e}"))?,
+            head_ref_name: row.get(21).map_err(|e| format!("Row decode: {e}"))?,
+            last_force_push_at_unix: row.get(22).map_err(|e| format!("Row decode: {e}"))?,
+            last_reply_at_unix: row.get(23).map_err(|e| format!("Row decode: {e}"))?,
+            body_excerpt: row.get(24).map_err(|e| format!("Row decode: {e}"))?,
+            reviewers_json: row.get(25).map_err(|e| format!("Row decode: {e}"))?,
+            snoozed_until: row.get(26).map_err(|e| format!("Row decode: {e}"))?,
+            snoozed_sha: row.get(27).map_err(|e| format!("Row decode: {e}"))?,
+            ignored: row.get(28).map_err(|e| format!("Row decode: {e}"))?,
+            comments_json: row.get(29).map_err(|e| format!("Row decode: {e}"))?,
+            review_threads_json: row.get(30).map_err(|e| format!("Row decode: {e}"))?,
+            inaccessible_since: row.get(31).map_err(|e| format!("Row decode: {e}"))?,
         };
         out.insert(pr.pr_key.clone(), pr);
     }
     Ok(out)
 }
 
+/// Insert or update a cached PR row, stamping `last_seen_at`.
 pub fn upsert_pr(conn: &Connection, pr: &DbPrRow, last_seen_at: i64) -> Result<(), String> {
-    // Note: pinned is intentionally NOT updated here to preserve user's pin state.
-    // Use toggle_pin() to change the pinned state.
+    // Note: pinned, the snooze and ignored are intentionally NOT updated here to preserve
+    // the user's choices. Use toggle_pin() / snooze_pr() / set_ignored() to change them.
+    // A fetched PR is accessible again, so any tombstone (see mark_inaccessible) is cleared.
+    let redacted;
+    let pr = if hashes_pr_text(conn)? {
+        redacted = redact_pr_text(pr);
+        &redacted
+    } else {
+        pr
+    };
     conn.execute(
         r#"
 INSERT INTO prs (
   pr_key, owner, repo, number, title, url, author, updated_at_unix,
   last_commit_sha, last_ci_state, last_review_state,
   ci_checks_json, is_draft, mergeable, merge_state_status,
-  author_is_viewer, last_seen_at, last_opened_at, pinned
+  author_is_viewer, last_seen_at, last_opened_at, pinned,
+  labels_json, review_requested_at_unix, head_ref_name, last_force_push_at_unix,
+  last_reply_at_unix, body_excerpt, reviewers_json, snoozed_until, snoozed_sha,
+  ignored, comments_json, review_threads_json, created_at_unix, is_assignee, is_mentioned,
+  merge_blockers_json, lines_changed, is_unsubscribed, base_ref_name, milestone,
+  project_status, ci_previous_commit
 ) VALUES (
   ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
   ?9, ?10, ?11,
   ?12, ?13, ?14, ?15,
-  ?16, ?17, ?18, ?19
+  ?16, ?17, ?18, ?19,
+  ?20, ?21, ?22, ?23,
+  ?24, ?25, ?26, ?27, ?28,
+  ?29, ?30, ?31, ?32, ?33, ?34,
+  ?35, ?36, ?37, ?38, ?39,
+  ?40, ?41
 )
 ON CONFLICT(pr_key) DO UPDATE SET
   owner = excluded.owner,
@@ -213,6 +506,7 @@ ON CONFLICT(pr_key) DO UPDATE SET
   url = excluded.url,
   author = excluded.author,
   updated_at_unix = excluded.updated_at_unix,
+  created_at_unix = excluded.created_at_unix,
   last_commit_sha = excluded.last_commit_sha,
   last_ci_state = excluded.last_ci_state,
   last_review_state = excluded.last_review_state,
@@ -221,7 +515,26 @@ ON CONFLICT(pr_key) DO UPDATE SET
   mergeable = excluded.mergeable,
   merge_state_status = excluded.merge_state_status,
   author_is_viewer = excluded.author_is_viewer,
-  last_seen_at = excluded.last_seen_at
+  is_assignee = excluded.is_assignee,
+  is_mentioned = excluded.is_mentioned,
+  merge_blockers_json = excluded.merge_blockers_json,
+  lines_changed = excluded.lines_changed,
+  is_unsubscribed = excluded.is_unsubscribed,
+  base_ref_name = excluded.base_ref_name,
+  milestone = excluded.milestone,
+  project_status = excluded.project_status,
+  ci_previous_commit = excluded.ci_previous_commit,
+  labels_json = excluded.labels_json,
+  review_requested_at_unix = excluded.review_requested_at_unix,
+  head_ref_name = excluded.head_ref_name,
+  last_force_push_at_unix = excluded.last_force_push_at_unix,
+  last_reply_at_unix = excluded.last_reply_at_unix,
+  body_excerpt = excluded.body_excerpt,
+  reviewers_json = excluded.reviewers_json,
+  comments_json = excluded.comments_json,
+  review_threads_json = excluded.review_threads_json,
+  last_seen_at = excluded.last_seen_at,
+  inaccessible_since = NULL
 "#,
         params![
             pr.pr_key,
@@ -242,13 +555,153 @@ ON CONFLICT(pr_key) DO UPDATE SET
             pr.author_is_viewer,
             last_seen_at,
             pr.last_opened_at,
-            pr.pinned
+            pr.pinned,
+            pr.labels_json,
+            pr.review_requested_at_unix,
+            pr.head_ref_name,
+            pr.last_force_push_at_unix,
+            pr.last_reply_at_unix,
+            pr.body_excerpt,
+            pr.reviewers_json,
+            pr.snoozed_until,
+            pr.snoozed_sha,
+            pr.ignored,
+            pr.comments_json,
+            pr.review_threads_json,
+            pr.created_at_unix,
+            pr.is_assignee,
+            pr.is_mentioned,
+            pr.merge_blockers_json,
+            pr.lines_changed,
+            pr.is_unsubscribed,
+            pr.base_ref_name,
+            pr.milestone,
+            pr.project_status,
+            pr.ci_previous_commit
         ],
     )
     .map_err(|e| format!("Failed to upsert pr: {e}"))?;
     Ok(())
 }
 
+fn hash_text(text: &str) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, text.as_bytes());
+    let hex: String = digest.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+    format!("{HASHED_TEXT_PREFIX}{hex}")
+}
+
+/// True for a title or description stored as a hash.
+pub fn is_hashed_text(text: &str) -> bool {
+    text.starts_with(HASHED_TEXT_PREFIX)
+}
+
+/// `row` as stored with `hash_pr_text` on: title and description hashed, conversation
+/// dropped.
+fn redact_pr_text(row: &DbPrRow) -> DbPrRow {
+    let hash = |s: &str| {
+        if is_hashed_text(s) {
+            s.to_string()
+        } else {
+            hash_text(s)
+        }
+    };
+    DbPrRow {
+        title: hash(&row.title),
+        body_excerpt: row.body_excerpt.as_deref().map(hash),
+        comments_json: None,
+        review_threads_json: None,
+        ..row.clone()
+    }
+}
+
+fn hashes_pr_text(conn: &Connection) -> Result<bool, String> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![SETTING_HASH_PR_TEXT],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read cache settings: {e}"))?;
+    Ok(value.as_deref() == Some("1"))
+}
+
+/// Store PR titles and descriptions as SHA-256 hashes only, and review comments not at
+/// all (`cache_pr_text = false`). Turning it on also scrubs the rows already cached.
+pub fn set_hash_pr_text(conn: &Connection, on: bool) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![SETTING_HASH_PR_TEXT, if on { "1" } else { "0" }],
+    )
+    .map_err(|e| format!("Failed to save cache settings: {e}"))?;
+    if !on {
+        return Ok(());
+    }
+    conn.execute_batch("PRAGMA secure_delete = ON")
+        .map_err(|e| format!("Failed to enable secure delete: {e}"))?;
+    for row in load_all_prs(conn)?.into_values() {
+        let scrubbed = redact_pr_text(&row);
+        conn.execute(
+            "UPDATE prs SET title = ?1, body_excerpt = ?2, comments_json = NULL,
+             review_threads_json = NULL WHERE pr_key = ?3",
+            params![scrubbed.title, scrubbed.body_excerpt, row.pr_key],
+        )
+        .map_err(|e| format!("Failed to scrub cached text: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Delete cached PRs needle has not seen since `cutoff` (unix seconds), with their
+/// events. Returns how many PRs were removed.
+///
+/// Pins, snoozes and ignores on those PRs go with them: retention wins over local state.
+pub fn purge_prs_older_than(conn: &Connection, cutoff: i64) -> Result<usize, String> {
+    conn.execute_batch("PRAGMA secure_delete = ON")
+        .map_err(|e| format!("Failed to enable secure delete: {e}"))?;
+    let purged = conn
+        .execute(
+            "DELETE FROM prs WHERE COALESCE(last_seen_at, updated_at_unix, 0) < ?1",
+            params![cutoff],
+        )
+        .map_err(|e| format!("Failed to purge prs: {e}"))?;
+    conn.execute(
+        "DELETE FROM pr_events WHERE at < ?1 OR pr_key NOT IN (SELECT pr_key FROM prs)",
+        params![cutoff],
+    )
+    .map_err(|e| format!("Failed to purge pr events: {e}"))?;
+    conn.execute(
+        "DELETE FROM addressed_threads WHERE pr_key NOT IN (SELECT pr_key FROM prs)",
+        [],
+    )
+    .map_err(|e| format!("Failed to purge addressed threads: {e}"))?;
+    conn.execute(
+        "DELETE FROM slack_posts WHERE pr_key NOT IN (SELECT pr_key FROM prs)",
+        [],
+    )
+    .map_err(|e| format!("Failed to purge Slack posts: {e}"))?;
+    conn.execute(
+        "DELETE FROM announced_alerts WHERE pr_key NOT IN (SELECT pr_key FROM prs)",
+        [],
+    )
+    .map_err(|e| format!("Failed to purge announced alerts: {e}"))?;
+    conn.execute(
+        "DELETE FROM check_failures WHERE pr_key NOT IN (SELECT pr_key FROM prs)",
+        [],
+    )
+    .map_err(|e| format!("Failed to purge check failures: {e}"))?;
+    conn.execute("DELETE FROM repo_ci WHERE checked_at < ?1", params![cutoff])
+        .map_err(|e| format!("Failed to purge repository CI states: {e}"))?;
+    if purged > 0 {
+        // The marks vouch for rows that may be gone now; the next refresh re-fetches.
+        conn.execute("DELETE FROM fetch_marks", [])
+            .map_err(|e| format!("Failed to delete fetch marks: {e}"))?;
+        conn.execute("DELETE FROM fetch_digests", [])
+            .map_err(|e| format!("Failed to delete fetch digests: {e}"))?;
+    }
+    Ok(purged)
+}
+
 /// Toggle the pinned state of a PR. Returns the new pinned state.
 pub fn toggle_pin(conn: &Connection, pr_key: &str) -> Result<bool, String> {
     // Get current state
@@ -271,11 +724,543 @@ pub fn toggle_pin(conn: &Connection, pr_key: &str) -> Result<bool, String> {
     Ok(new_state == 1)
 }
 
+/// Snooze a PR until `until` (unix seconds) or, with `sha`, until its head moves off
+/// that commit. Replaces any previous snooze.
+pub fn snooze_pr(
+    conn: &Connection,
+    pr_key: &str,
+    until: Option<i64>,
+    sha: Option<&str>,
+) -> Result<(), String> {
+    conn.execute(
+        "UPDATE prs SET snoozed_until = ?1, snoozed_sha = ?2 WHERE pr_key = ?3",
+        params![until, sha, pr_key],
+    )
+    .map_err(|e| format!("Failed to snooze pr: {e}"))?;
+    Ok(())
+}
+
+/// Wake a snoozed PR.
+pub fn unsnooze_pr(conn: &Connection, pr_key: &str) -> Result<(), String> {
+    snooze_pr(conn, pr_key, None, None)
+}
+
+/// Ignore a PR (hide it from every list) or restore it.
+pub fn set_ignored(conn: &Connection, pr_key: &str, ignored: bool) -> Result<(), String> {
+    conn.execute(
+        "UPDATE prs SET ignored = ?1 WHERE pr_key = ?2",
+        params![i64::from(ignored), pr_key],
+    )
+    .map_err(|e| format!("Failed to update ignored state: {e}"))?;
+    Ok(())
+}
+
+/// Tombstone a PR the code host no longer shows (repository deleted, access revoked)
+/// instead of dropping it from the cache right away.
+pub fn mark_inaccessible(conn: &Connection, pr_key: &str, since: i64) -> Result<(), String> {
+    conn.execute(
+        "UPDATE prs SET inaccessible_since = ?1 WHERE pr_key = ?2",
+        params![since, pr_key],
+    )
+    .map_err(|e| format!("Failed to mark pr inaccessible: {e}"))?;
+    Ok(())
+}
+
+/// Ignored PRs still in the cache, sorted by key.
+pub fn load_ignored_prs(conn: &Connection) -> Result<Vec<DbPrRow>, String> {
+    let mut out: Vec<DbPrRow> = load_all_prs(conn)?
+        .into_values()
+        .filter(|r| r.ignored.unwrap_or(0) != 0)
+        .collect();
+    out.sort_by(|a, b| a.pr_key.cmp(&b.pr_key));
+    Ok(out)
+}
+
+/// Remember that the user looked at a PR (details view or browser).
+pub fn mark_opened(conn: &Connection, pr_key: &str, now: i64) -> Result<(), String> {
+    conn.execute(
+        "UPDATE prs SET last_opened_at = ?1 WHERE pr_key = ?2",
+        params![now, pr_key],
+    )
+    .map_err(|e| format!("Failed to mark pr opened: {e}"))?;
+    Ok(())
+}
 
+/// When the user last looked at a PR, if ever (see [`mark_opened`]).
+pub fn opened_at(conn: &Connection, pr_key: &str) -> Result<Option<i64>, String> {
+    conn.query_row(
+        "SELECT last_opened_at FROM prs WHERE pr_key = ?1",
+        params![pr_key],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(Option::flatten)
+    .map_err(|e| format!("Failed to query pr opened: {e}"))
+}
+
+/// Check a review thread off as addressed, or uncheck it.
+pub fn set_thread_addressed(
+    conn: &Connection,
+    pr_key: &str,
+    thread_key: &str,
+    addressed: bool,
+    now: i64,
+) -> Result<(), String> {
+    let res = if addressed {
+        conn.execute(
+            "INSERT OR IGNORE INTO addressed_threads (pr_key, thread_key, addressed_at)
+             VALUES (?1, ?2, ?3)",
+            params![pr_key, thread_key, now],
+        )
+    } else {
+        conn.execute(
+            "DELETE FROM addressed_threads WHERE pr_key = ?1 AND thread_key = ?2",
+            params![pr_key, thread_key],
+        )
+    };
+    res.map_err(|e| format!("Failed to update addressed thread: {e}"))?;
+    Ok(())
+}
+
+/// Thread keys checked off as addressed, per PR.
+pub fn load_addressed_threads(
+    conn: &Connection,
+) -> Result<HashMap<String, HashSet<String>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT pr_key, thread_key FROM addressed_threads")
+        .map_err(|e| format!("Failed to prepare addressed threads query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| format!("Failed to query addressed threads: {e}"))?;
+    let mut out: HashMap<String, HashSet<String>> = HashMap::new();
+    for row in rows {
+        let (pr_key, thread_key) =
+            row.map_err(|e| format!("Failed to decode addressed thread: {e}"))?;
+        out.entry(pr_key).or_default().insert(thread_key);
+    }
+    Ok(out)
+}
+
+/// How often one check failed on a PR's current head commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckFailure {
+    pub failures: i64,
+    pub first_failed_at: i64,
+}
+
+/// Record the state of `checks` (`(name, failing)`) on head `sha`. A check counts one more
+/// failure when it fails after not failing at the previous refresh (e.g. a rerun).
+pub fn record_check_states<'a>(
+    conn: &Connection,
+    pr_key: &str,
+    sha: &str,
+    checks: impl IntoIterator<Item = (&'a str, bool)>,
+    now: i64,
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM check_failures WHERE pr_key = ?1 AND sha != ?2",
+        params![pr_key, sha],
+    )
+    .map_err(|e| format!("Failed to drop old check failures: {e}"))?;
+    for (name, failing) in checks {
+        let res = if failing {
+            conn.execute(
+                "INSERT INTO check_failures
+                   (pr_key, sha, check_name, failures, first_failed_at, failing)
+                 VALUES (?1, ?2, ?3, 1, ?4, 1)
+                 ON CONFLICT(pr_key, sha, check_name) DO UPDATE SET
+                   failures = failures + 1 - failing,
+                   failing = 1",
+                params![pr_key, sha, name, now],
+            )
+        } else {
+            conn.execute(
+                "UPDATE check_failures SET failing = 0
+                 WHERE pr_key = ?1 AND sha = ?2 AND check_name = ?3",
+                params![pr_key, sha, name],
+            )
+        };
+        res.map_err(|e| format!("Failed to record check state: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Check failures on each PR's current head commit, by PR and check name.
+pub fn load_check_failures(
+    conn: &Connection,
+) -> Result<HashMap<String, HashMap<String, CheckFailure>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT pr_key, check_name, failures, first_failed_at FROM check_failures")
+        .map_err(|e| format!("Failed to prepare check failures query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                CheckFailure {
+                    failures: row.get(2)?,
+                    first_failed_at: row.get(3)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Failed to query check failures: {e}"))?;
+    let mut out: HashMap<String, HashMap<String, CheckFailure>> = HashMap::new();
+    for row in rows {
+        let (pr_key, name, failure) =
+            row.map_err(|e| format!("Failed to decode check failure: {e}"))?;
+        out.entry(pr_key).or_default().insert(name, failure);
+    }
+    Ok(out)
+}
+
+/// Default-branch CI state of a repository, as of `checked_at`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoCi {
+    pub ci_state: String,
+    pub checked_at: i64,
+}
+
+/// Store the default-branch CI state of `repo` (`owner/repo`).
+pub fn save_repo_ci(conn: &Connection, repo: &str, ci_state: &str, now: i64) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO repo_ci (repo, ci_state, checked_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(repo) DO UPDATE SET ci_state = ?2, checked_at = ?3",
+        params![repo, ci_state, now],
+    )
+    .map_err(|e| format!("Failed to save repository CI state: {e}"))?;
+    Ok(())
+}
+
+/// Default-branch CI states by `owner/repo`.
+pub fn load_repo_ci(conn: &Connection) -> Result<HashMap<String, RepoCi>, String> {
+    let mut stmt = conn
+        .prepare("SELECT repo, ci_state, checked_at FROM repo_ci")
+        .map_err(|e| format!("Failed to prepare repository CI query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                RepoCi {
+                    ci_state: row.get(1)?,
+                    checked_at: row.get(2)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Failed to query repository CI states: {e}"))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to decode repository CI state: {e}"))
+}
+
+/// Whether the alert `event_key` for `pr_key` was already posted to Slack.
+pub fn slack_posted(conn: &Connection, pr_key: &str, event_key: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT 1 FROM slack_posts WHERE pr_key = ?1 AND event_key = ?2",
+        params![pr_key, event_key],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .map_err(|e| format!("Failed to query Slack posts: {e}"))
+}
+
+/// Remember that the alert `event_key` for `pr_key` was posted to Slack.
+pub fn mark_slack_posted(
+    conn: &Connection,
+    pr_key: &str,
+    event_key: &str,
+    now: i64,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR IGNORE INTO slack_posts (pr_key, event_key, posted_at) VALUES (?1, ?2, ?3)",
+        params![pr_key, event_key, now],
+    )
+    .map_err(|e| format!("Failed to record Slack post: {e}"))?;
+    Ok(())
+}
+
+/// Remember that the alert `event_key` for `pr_key` was announced; false when it already
+/// was, by this process or another one sharing the cache.
+pub fn mark_alert_announced(
+    conn: &Connection,
+    pr_key: &str,
+    event_key: &str,
+    now: i64,
+) -> Result<bool, String> {
+    conn.execute(
+        "INSERT OR IGNORE INTO announced_alerts (pr_key, event_key, announced_at)
+         VALUES (?1, ?2, ?3)",
+        params![pr_key, event_key, now],
+    )
+    .map(|inserted| inserted > 0)
+    .map_err(|e| format!("Failed to record announced alert: {e}"))
+}
+
+/// Append a head SHA change to `pr_events`.
+pub fn record_pr_event(
+    conn: &Connection,
+    pr_key: &str,
+    kind: &str,
+    old_sha: Option<&str>,
+    new_sha: Option<&str>,
+    at: i64,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO pr_events (pr_key, kind, old_sha, new_sha, at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![pr_key, kind, old_sha, new_sha, at],
+    )
+    .map_err(|e| format!("Failed to record pr event: {e}"))?;
+    Ok(())
+}
+
+/// Time of the latest event of `kind` per PR.
+pub fn load_last_event_times(
+    conn: &Connection,
+    kind: &str,
+) -> Result<HashMap<String, i64>, String> {
+    let mut stmt = conn
+        .prepare("SELECT pr_key, MAX(at) FROM pr_events WHERE kind = ?1 GROUP BY pr_key")
+        .map_err(|e| format!("Failed to prepare events query: {e}"))?;
+    let rows = stmt
+        .query_map(params![kind], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .map_err(|e| format!("Failed to query pr events: {e}"))?;
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Failed to decode pr event: {e}"))
+}
+
+/// Start tracking a PR. Returns false if it was already tracked.
+pub fn track_pr(conn: &Connection, pr_key: &str, now: i64) -> Result<bool, String> {
+    let n = conn
+        .execute(
+            "INSERT OR IGNORE INTO tracked_prs (pr_key, added_at) VALUES (?1, ?2)",
+            params![pr_key, now],
+        )
+        .map_err(|e| format!("Failed to track pr: {e}"))?;
+    Ok(n > 0)
+}
+
+/// Stop tracking a PR. Returns false if it was not tracked.
+pub fn untrack_pr(conn: &Connection, pr_key: &str) -> Result<bool, String> {
+    let n = conn
+        .execute("DELETE FROM tracked_prs WHERE pr_key = ?1", params![pr_key])
+        .map_err(|e| format!("Failed to untrack pr: {e}"))?;
+    Ok(n > 0)
+}
+
+/// Keys of all tracked PRs, oldest first.
+pub fn load_tracked_prs(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT pr_key FROM tracked_prs ORDER BY added_at, pr_key")
+        .map_err(|e| format!("Failed to prepare tracked query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query tracked prs: {e}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to decode tracked pr: {e}"))
+}
+
+/// Digest of the last full refresh for `scope_key`.
+pub fn load_fetch_digest(conn: &Connection, scope_key: &str) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT digest FROM fetch_digests WHERE scope_key = ?1",
+        params![scope_key],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to read fetch digest: {e}"))
+}
+
+/// Remember the digest of what a full refresh for `scope_key` fetched.
+pub fn save_fetch_digest(conn: &Connection, scope_key: &str, digest: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO fetch_digests (scope_key, digest) VALUES (?1, ?2)
+         ON CONFLICT(scope_key) DO UPDATE SET digest = ?2",
+        params![scope_key, digest],
+    )
+    .map_err(|e| format!("Failed to save fetch digest: {e}"))?;
+    Ok(())
+}
+
+/// Mark the cached rows of `pr_keys` as seen at `now` (keeps them clear of
+/// `cache_retention_days`).
+pub fn touch_prs(conn: &Connection, pr_keys: &[String], now: i64) -> Result<(), String> {
+    if pr_keys.is_empty() {
+        return Ok(());
+    }
+    let placeholders = (0..pr_keys.len())
+        .map(|_| "?")
+        .collect::<Vec<_>>()
+        .join(",");
+    let sql = format!("UPDATE prs SET last_seen_at = {now} WHERE pr_key IN ({placeholders})");
+    conn.execute(&sql, rusqlite::params_from_iter(pr_keys))
+        .map_err(|e| format!("Failed to update last seen times: {e}"))?;
+    Ok(())
+}
+
+/// PRs marked in the "Today" view on `day`, by key.
+pub fn load_today_marks(conn: &Connection, day: &str) -> Result<HashMap<String, String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT pr_key, mark FROM today_marks WHERE day = ?1")
+        .map_err(|e| format!("Failed to prepare today marks query: {e}"))?;
+    let rows = stmt
+        .query_map(params![day], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query today marks: {e}"))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to decode today marks: {e}"))
+}
+
+/// Mark a PR `done` or `skipped` for `day`. Marks of earlier days are dropped.
+pub fn set_today_mark(
+    conn: &Connection,
+    day: &str,
+    pr_key: &str,
+    mark: &str,
+) -> Result<(), String> {
+    conn.execute("DELETE FROM today_marks WHERE day <> ?1", params![day])
+        .map_err(|e| format!("Failed to drop old today marks: {e}"))?;
+    conn.execute(
+        "INSERT INTO today_marks (day, pr_key, mark) VALUES (?1, ?2, ?3)
+         ON CONFLICT(day, pr_key) DO UPDATE SET mark = excluded.mark",
+        params![day, pr_key, mark],
+    )
+    .map_err(|e| format!("Failed to save today mark: {e}"))?;
+    Ok(())
+}
+
+/// One remote action needle took on the user's behalf, from [`load_actions_log`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedAction {
+    pub at: i64,
+    pub pr_key: String,
+    /// `merge`, `comment`, `update_branch`, ...
+    pub action: String,
+    /// What the action was given, e.g. the merge method.
+    pub detail: Option<String>,
+    /// Why the host refused it; `None` when it went through.
+    pub error: Option<String>,
+}
+
+/// Append a remote action to the audit log (`needle log`). Rows are never updated or
+/// deleted by needle.
+pub fn log_action(
+    conn: &Connection,
+    at: i64,
+    pr_key: &str,
+    action: &str,
+    detail: Option<&str>,
+    error: Option<&str>,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO actions_log (at, pr_key, action, detail, error) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![at, pr_key, action, detail, error],
+    )
+    .map_err(|e| format!("Failed to log action: {e}"))?;
+    Ok(())
+}
+
+/// The `limit` most recent logged actions, oldest first.
+pub fn load_actions_log(conn: &Connection, limit: usize) -> Result<Vec<LoggedAction>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT at, pr_key, action, detail, error FROM
+               (SELECT rowid, * FROM actions_log ORDER BY rowid DESC LIMIT ?1)
+             ORDER BY rowid",
+        )
+        .map_err(|e| format!("Failed to prepare actions log query: {e}"))?;
+    let rows = stmt
+        .query_map(params![limit as i64], |row| {
+            Ok(LoggedAction {
+                at: row.get(0)?,
+                pr_key: row.get(1)?,
+                action: row.get(2)?,
+                detail: row.get(3)?,
+                error: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query actions log: {e}"))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to decode logged action: {e}"))
+}
+
+/// The TUI state saved by [`save_ui_state`], by key.
+pub fn load_ui_state(conn: &Connection) -> Result<HashMap<String, String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM ui_state")
+        .map_err(|e| format!("Failed to prepare ui state query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query ui state: {e}"))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to decode ui state: {e}"))
+}
+
+/// Replace the saved TUI state with `entries`.
+pub fn save_ui_state(conn: &Connection, entries: &[(&str, String)]) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to begin ui state write: {e}"))?;
+    tx.execute("DELETE FROM ui_state", [])
+        .map_err(|e| format!("Failed to clear ui state: {e}"))?;
+    for (key, value) in entries {
+        tx.execute(
+            "INSERT INTO ui_state (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )
+        .map_err(|e| format!("Failed to save ui state: {e}"))?;
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit ui state: {e}"))
+}
+
+/// Oldest update time the cache covers for `scope_key`, if it was ever fully fetched.
+pub fn load_fetch_mark(conn: &Connection, scope_key: &str) -> Result<Option<i64>, String> {
+    conn.query_row(
+        "SELECT covered_since FROM fetch_marks WHERE scope_key = ?1",
+        params![scope_key],
+        |row| row.get::<_, i64>(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to read fetch mark: {e}"))
+}
+
+/// Record that the cache now covers PRs updated since `covered_since` for `scope_key`.
+pub fn save_fetch_mark(
+    conn: &Connection,
+    scope_key: &str,
+    covered_since: i64,
+    now: i64,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO fetch_marks (scope_key, covered_since, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(scope_key) DO UPDATE SET covered_since = ?2, updated_at = ?3",
+        params![scope_key, covered_since, now],
+    )
+    .map_err(|e| format!("Failed to save fetch mark: {e}"))?;
+    Ok(())
+}
+
+/// Delete cached rows whose key is not in `keep_pr_keys` (all rows when empty).
 pub fn delete_prs_not_in(conn: &Connection, keep_pr_keys: &[String]) -> Result<(), String> {
+    // Ignored PRs are kept so they stay ignored if they re-enter the attention set.
     if keep_pr_keys.is_empty() {
-        conn.execute("DELETE FROM prs", [])
+        // Also how `--no-cache` wipes the cache, so it forgets what each scope covered too.
+        // Fetch marks and digests are per scope, not per PR: a refresh rewrites both right
+        // after pruning, so a non-empty prune has nothing of theirs to drop.
+        conn.execute("DELETE FROM prs WHERE COALESCE(ignored, 0) = 0", [])
             .map_err(|e| format!("Failed to delete prs: {e}"))?;
+        conn.execute("DELETE FROM pr_events", [])
+            .map_err(|e| format!("Failed to delete pr events: {e}"))?;
+        conn.execute("DELETE FROM addressed_threads", [])
+            .map_err(|e| format!("Failed to delete addressed threads: {e}"))?;
+        conn.execute("DELETE FROM check_failures", [])
+            .map_err(|e| format!("Failed to delete check failures: {e}"))?;
+        conn.execute("DELETE FROM fetch_marks", [])
+            .map_err(|e| format!("Failed to delete fetch marks: {e}"))?;
+        conn.execute("DELETE FROM fetch_digests", [])
+            .map_err(|e| format!("Failed to delete fetch digests: {e}"))?;
         return Ok(());
     }
 
@@ -283,13 +1268,19 @@ pub fn delete_prs_not_in(conn: &Connection, keep_pr_keys: &[String]) -> Result<(
         .map(|_| "?")
         .collect::<Vec<_>>()
         .join(",");
-    let sql = format!("DELETE FROM prs WHERE pr_key NOT IN ({placeholders})");
-
-    let mut stmt = conn
-        .prepare(&sql)
-        .map_err(|e| format!("Failed to prepare delete query: {e}"))?;
     let refs: Vec<&str> = keep_pr_keys.iter().map(|s| s.as_str()).collect();
-    stmt.execute(rusqlite::params_from_iter(refs))
-        .map_err(|e| format!("Failed to delete old prs: {e}"))?;
+    for (table, extra) in [
+        ("prs", " AND COALESCE(ignored, 0) = 0"),
+        ("pr_events", ""),
+        ("addressed_threads", ""),
+        ("check_failures", ""),
+    ] {
+        let sql = format!("DELETE FROM {table} WHERE pr_key NOT IN ({placeholders}){extra}");
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare delete query: {e}"))?;
+        stmt.execute(rusqlite::params_from_iter(&refs))
+            .map_err(|e| format!("Failed to delete old {table}: {e}"))?;
+    }
     Ok(())
 }
@@ -1,8 +1,19 @@
-use crate::model::{CiCheck, CiCheckState, CiState, MergeBlockers, Pr, ReviewState};
+//! Deterministic fake PRs for `--demo` mode and tests.
+
+use crate::model::{
+    CiCheck, CiCheckState, CiState, Comment, MergeBlockers, Pr, ReviewState, ReviewThread, Reviewer,
+};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Login the demo data treats as the viewer.
+pub const DEMO_VIEWER_LOGIN: &str = "you";
+
+/// Demo repository whose default branch is failing CI.
+pub const DEMO_RED_BASE_REPO: &str = "acme-inc/monorepo";
+
 static DEMO_TICK: AtomicU64 = AtomicU64::new(0);
 
+/// Advance the global demo tick (each refresh makes the data drift a little).
 pub fn next_demo_tick() -> u64 {
     DEMO_TICK.fetch_add(1, Ordering::Relaxed).wrapping_add(1)
 }
@@ -33,6 +44,153 @@ struct DemoPrSpec {
     blockers: Option<MergeBlockers>,
 }
 
+/// A couple of PRs look like incident work, so `--oncall` has something to surface.
+fn demo_labels(s: &DemoPrSpec) -> Vec<String> {
+    match (s.repo, s.number) {
+        ("payments-worker", 219) => vec!["incident".to_string()],
+        ("api", 1104) | ("backend", 611) | ("sdk-rust", 98) => vec!["reliability".to_string()],
+        ("product", 12) | ("web", 1940) => vec!["onboarding".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Both mobile PRs go into the release branch being stabilized; the rest target `main`.
+fn demo_base_branch(s: &DemoPrSpec) -> String {
+    match s.repo {
+        "mobile" => "release/3.2".to_string(),
+        _ => "main".to_string(),
+    }
+}
+
+/// The release being stabilized: the mobile PRs into its branch, plus the API retry work.
+fn demo_milestone(s: &DemoPrSpec) -> Option<String> {
+    matches!((s.repo, s.number), ("mobile", _) | ("api", 1104)).then(|| "v3.2".to_string())
+}
+
+/// The reliability PRs are tracked on a project board.
+fn demo_project_status(s: &DemoPrSpec) -> Option<String> {
+    match (s.repo, s.number) {
+        ("api", 1104) => Some("Reliability: In review".to_string()),
+        ("backend", 611) => Some("Reliability: In progress".to_string()),
+        _ => None,
+    }
+}
+
+/// Drafts come without a description; everything else gets a short one.
+fn demo_body(s: &DemoPrSpec) -> Option<String> {
+    (!s.is_draft).then(|| {
+        format!(
+            "{}.\n\n- Scoped to `{}/{}`\n- Covered by the existing test suite",
+            s.title, s.owner, s.repo
+        )
+    })
+}
+
+/// The idempotency fix is a one-liner with a test; the rest are mid-sized.
+fn demo_lines_changed(s: &DemoPrSpec) -> i64 {
+    match (s.repo, s.number) {
+        ("billing-api", 842) => 24,
+        _ => (s.number % 13 + 2) * 40,
+    }
+}
+
+fn demo_reviewers(s: &DemoPrSpec) -> Vec<Reviewer> {
+    let r = |login: &str, state: &str| Reviewer {
+        login: login.to_string(),
+        state: state.to_string(),
+    };
+    match s.review {
+        ReviewState::Requested => vec![r("mira", "COMMENTED"), r("you", "REQUESTED")],
+        ReviewState::TeamRequested => vec![r("platform", "REQUESTED")],
+        ReviewState::Approved => vec![r("mira", "APPROVED")],
+        ReviewState::ChangesRequested => vec![r("mira", "CHANGES_REQUESTED")],
+        ReviewState::None if s.is_viewer_author => vec![r("kai", "REQUESTED")],
+        ReviewState::None => Vec::new(),
+    }
+}
+
+/// PRs awaiting review carry one settled and one open review thread; the flaky-test fix
+/// has the exchange behind its reply badge.
+fn demo_conversation(s: &DemoPrSpec, updated_at: i64) -> (Vec<Comment>, Vec<ReviewThread>) {
+    let c = |author: &str, body: &str, ago: i64| Comment {
+        author: author.to_string(),
+        body: body.to_string(),
+        created_at_unix: updated_at - ago,
+    };
+    let comments = if s.repo == "web" && s.number == 1940 {
+        vec![
+            c(
+                "you",
+                "Does this also flake on main, or only on this branch?",
+                3600,
+            ),
+            c(
+                s.author,
+                "Main too. Pinning the clock in the fixture fixes both.",
+                0,
+            ),
+        ]
+    } else {
+        Vec::new()
+    };
+    let threads = if s.repo == "web" && s.number == 1940 {
+        // A thread you opened; the author answered and is waiting on you.
+        vec![ReviewThread {
+            path: Some("tests/fixtures/clock.ts".to_string()),
+            is_resolved: false,
+            comments: vec![
+                c(
+                    "you",
+                    "Can the sleep go now that the clock is pinned?",
+                    5400,
+                ),
+                c(s.author, "Removed it. Good to resolve?", 1800),
+            ],
+            started_by_viewer: true,
+        }]
+    } else if matches!(s.review, ReviewState::Requested) {
+        vec![
+            ReviewThread {
+                path: Some("src/lib.rs".to_string()),
+                is_resolved: true,
+                comments: vec![
+                    c("mira", "Could this be named `retry_budget`?", 7200),
+                    c(s.author, "Renamed, thanks.", 5400),
+                ],
+                started_by_viewer: false,
+            },
+            ReviewThread {
+                path: Some("src/client.rs".to_string()),
+                is_resolved: false,
+                comments: vec![c(
+                    "mira",
+                    "This swallows the error. Should it bubble up to the caller instead?",
+                    1800,
+                )],
+                started_by_viewer: false,
+            },
+        ]
+    } else {
+        Vec::new()
+    };
+    (comments, threads)
+}
+
+fn demo_branch(s: &DemoPrSpec) -> String {
+    let slug: String = s
+        .title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .take(4)
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    match (s.repo, s.number) {
+        ("mobile", 523) => format!("hotfix/{slug}"),
+        _ => format!("{}/{slug}", s.author),
+    }
+}
+
 fn fnv1a_64(s: &str) -> u64 {
     let mut h: u64 = 0xcbf29ce484222325;
     for b in s.as_bytes() {
@@ -59,6 +217,36 @@ fn actions_url(owner: &str, repo: &str, run_id: u64) -> String {
     format!("https://github.com/{owner}/{repo}/actions/runs/{run_id}")
 }
 
+/// A made-up job log for `check`: setup noise, then a failing test for failed checks.
+pub fn demo_check_log(check: &CiCheck) -> String {
+    let mut log = String::from(
+        "2024-03-01T10:00:00.0000000Z ##[group]Run actions/checkout@v4\n\
+         2024-03-01T10:00:02.0000000Z ##[endgroup]\n",
+    );
+    log.push_str(&format!(
+        "2024-03-01T10:00:03.0000000Z ##[group]Run {}\n",
+        check.name
+    ));
+    for i in 0..40 {
+        log.push_str(&format!(
+            "2024-03-01T10:01:{:02}.0000000Z test suite::case_{i:02} ... \u{1b}[32mok\u{1b}[0m\n",
+            i % 60
+        ));
+    }
+    if check.state.is_failure() {
+        log.push_str(
+            "2024-03-01T10:02:00.0000000Z test suite::retries_after_timeout ... \u{1b}[31mFAILED\u{1b}[0m\n\
+             2024-03-01T10:02:00.0000000Z \n\
+             2024-03-01T10:02:00.0000000Z ---- suite::retries_after_timeout stdout ----\n\
+             2024-03-01T10:02:00.0000000Z assertion failed: `(left == right)`\n\
+             2024-03-01T10:02:00.0000000Z   left: `2`,\n\
+             2024-03-01T10:02:00.0000000Z  right: `3`: expected a third attempt after the timeout\n\
+             2024-03-01T10:02:01.0000000Z ##[error]Process completed with exit code 101.\n",
+        );
+    }
+    log
+}
+
 fn pr_url(owner: &str, repo: &str, number: i64) -> String {
     format!("https://github.com/{owner}/{repo}/pull/{number}")
 }
@@ -136,6 +324,7 @@ fn checks_for(
     }
 }
 
+/// Build the demo PR set for a given time and tick.
 pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
     let specs: &[DemoPrSpec] = &[
         DemoPrSpec {
@@ -155,7 +344,7 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
             owner: "you-inc",
             repo: "product",
             number: 12,
-            author: "you",
+            author: DEMO_VIEWER_LOGIN,
             title: "Ready: merge cleanup for onboarding flow",
             updated_age_secs: 45 * 60,
             review: ReviewState::Approved,
@@ -164,6 +353,19 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
             is_viewer_author: true,
             blockers: None,
         },
+        DemoPrSpec {
+            owner: "you-inc",
+            repo: "product",
+            number: 15,
+            author: DEMO_VIEWER_LOGIN,
+            title: "Settings: remember table column widths per user",
+            updated_age_secs: 3 * 3600,
+            review: ReviewState::ChangesRequested,
+            ci: CiProfile::Green,
+            is_draft: false,
+            is_viewer_author: true,
+            blockers: None,
+        },
         DemoPrSpec {
             owner: "orbit",
             repo: "web",
@@ -356,7 +558,7 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
             author: "jules",
             title: "Terraform: split prod/staging state and add drift detection",
             updated_age_secs: 18 * 86400,
-            review: ReviewState::None,
+            review: ReviewState::TeamRequested,
             ci: CiProfile::Green,
             is_draft: false,
             is_viewer_author: false,
@@ -408,6 +610,7 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
                 0
             };
             let updated_at_unix = now.saturating_sub(s.updated_age_secs.saturating_sub(wobble));
+            let (comments, review_threads) = demo_conversation(s, updated_at_unix);
 
             Pr {
                 pr_key: key,
@@ -418,6 +621,10 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
                 title: s.title.to_string(),
                 url,
                 updated_at_unix,
+                // Opened a few days before the latest activity.
+                created_at_unix: Some(
+                    now.saturating_sub(s.updated_age_secs + (s.number % 7 + 1) * 86_400),
+                ),
                 last_commit_sha: Some(sha),
                 ci_state,
                 ci_checks,
@@ -426,7 +633,32 @@ pub fn generate_demo_prs(now: i64, tick: u64) -> Vec<Pr> {
                 mergeable: Some("MERGEABLE".to_string()),
                 merge_state_status: Some("CLEAN".to_string()),
                 is_viewer_author: s.is_viewer_author,
+                // Someone handed the design-system review over by assigning it.
+                is_assignee: s.repo == "design-system",
+                // Noah asked you about the OAuth scopes in the docs PR.
+                is_mentioned: s.repo == "docs",
+                // Notifications for someone else's collector PR were turned off on GitHub.
+                is_unsubscribed: s.repo == "otel-collector",
                 merge_blockers: s.blockers.clone(),
+                labels: demo_labels(s),
+                // Demo review requests arrive with the PR's latest update.
+                review_requested_at_unix: matches!(s.review, ReviewState::Requested)
+                    .then_some(updated_at_unix),
+                head_ref_name: Some(demo_branch(s)),
+                base_ref_name: Some(demo_base_branch(s)),
+                milestone: demo_milestone(s),
+                project_status: demo_project_status(s),
+                ci_from_previous_commit: false,
+                lines_changed: Some(demo_lines_changed(s)),
+                // The monorepo branch gets rebased on every refresh.
+                last_force_push_at_unix: (s.repo == "monorepo").then_some(now),
+                // The author answered a question the viewer left on the flaky-test fix.
+                last_reply_at_unix: (s.repo == "web" && s.number == 1940)
+                    .then_some(updated_at_unix),
+                body_excerpt: demo_body(s),
+                reviewers: demo_reviewers(s),
+                comments,
+                review_threads,
             }
         })
         .collect()
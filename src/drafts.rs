@@ -0,0 +1,172 @@
+//! Draft PR policy (`[scoring.drafts]`): where drafts show up and how they score.
+//!
+//! By default every draft is dimmed in its own DRAFT section regardless of score. The
+//! policy can rank drafts like regular PRs, hide them, or pull the viewer's own drafts
+//! with failing CI out of the DRAFT section so they get fixed.
+
+use crate::model::{CiState, Pr};
+use crate::refresh::{UiPr, category_for, sort_for_display};
+
+/// Boost for the viewer's drafts whose CI failed (with `surface_failed_ci`).
+pub const SCORE_DRAFT_CI_FAILED: i32 = 40;
+
+/// How a group of drafts is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftMode {
+    /// Dimmed, in the DRAFT section at the bottom.
+    #[default]
+    Section,
+    /// Dimmed, but ranked in the regular sections by score.
+    Score,
+    /// Left out of the list entirely.
+    Hide,
+}
+
+/// Draft handling, split by authorship.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DraftPolicy {
+    pub mine: DraftMode,
+    pub others: DraftMode,
+    /// Rank the viewer's drafts with failing CI as regular PRs, with a boost.
+    pub surface_failed_ci: bool,
+}
+
+impl DraftPolicy {
+    fn mode_for(&self, pr: &Pr) -> DraftMode {
+        if pr.is_viewer_author {
+            self.mine
+        } else {
+            self.others
+        }
+    }
+
+    fn surfaces(&self, pr: &Pr) -> bool {
+        self.surface_failed_ci && pr.is_viewer_author && matches!(pr.ci_state, CiState::Failure)
+    }
+
+    /// True when `pr` should be dropped from the list.
+    pub fn hides(&self, pr: &Pr) -> bool {
+        pr.is_draft && self.mode_for(pr) == DraftMode::Hide && !self.surfaces(pr)
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == DraftPolicy::default()
+    }
+}
+
+/// Rank, boost, and badge drafts according to `policy`, then restore display order.
+///
+/// Hidden drafts are left in place; drop them with [`retain_visible_drafts`].
+pub fn apply_draft_policy(prs: &mut [UiPr], policy: &DraftPolicy) {
+    if policy.is_default() {
+        return;
+    }
+    for p in prs.iter_mut() {
+        if !p.pr.is_draft {
+            continue;
+        }
+        if policy.surfaces(&p.pr) {
+            p.score += SCORE_DRAFT_CI_FAILED;
+            p.category = category_for(&p.pr, p.score);
+            p.display_status = format!("📝 draft · {}", p.display_status);
+            p.draft_ranked = true;
+        } else if policy.mode_for(&p.pr) == DraftMode::Score {
+            p.display_status = format!("📝 draft · {}", p.display_status);
+            p.draft_ranked = true;
+        }
+    }
+    sort_for_display(prs);
+}
+
+/// Drop drafts the policy hides.
+pub fn retain_visible_drafts(prs: &mut Vec<UiPr>, policy: &DraftPolicy) {
+    prs.retain(|p| !policy.hides(&p.pr));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+    use crate::refresh::Category;
+
+    fn drafts() -> Vec<UiPr> {
+        let base = generate_demo_prs(1_700_000_000, 0).remove(0);
+        let mk = |key: &str, mine: bool, ci: CiState, score: i32| {
+            let mut pr = base.clone();
+            pr.pr_key = key.to_string();
+            pr.is_draft = true;
+            pr.is_viewer_author = mine;
+            pr.ci_state = ci;
+            UiPr {
+                pr,
+                score,
+                category: Category::Waiting,
+                display_status: "status".to_string(),
+                is_new_ci_failure: false,
+                is_pinned: false,
+                sla: None,
+                draft_ranked: false,
+                bot_section: false,
+                force_pushed_since_view: false,
+                reply_to_you: false,
+                snoozed: false,
+                opened_since_update: false,
+                inaccessible_since: None,
+                author_priority: None,
+            }
+        };
+        vec![
+            mk("a/a#1", true, CiState::Failure, -30),
+            mk("a/a#2", true, CiState::Success, 0),
+            mk("a/a#3", false, CiState::Failure, 10),
+        ]
+    }
+
+    #[test]
+    fn default_policy_changes_nothing() {
+        let mut prs = drafts();
+        apply_draft_policy(&mut prs, &DraftPolicy::default());
+        retain_visible_drafts(&mut prs, &DraftPolicy::default());
+        assert_eq!(prs.len(), 3);
+        assert!(prs.iter().all(|p| !p.draft_ranked));
+    }
+
+    #[test]
+    fn surfaces_my_failing_drafts_and_hides_others() {
+        let policy = DraftPolicy {
+            mine: DraftMode::Section,
+            others: DraftMode::Hide,
+            surface_failed_ci: true,
+        };
+        let mut prs = drafts();
+        apply_draft_policy(&mut prs, &policy);
+        retain_visible_drafts(&mut prs, &policy);
+
+        let keys: Vec<&str> = prs.iter().map(|p| p.pr.pr_key.as_str()).collect();
+        assert_eq!(keys, vec!["a/a#1", "a/a#2"]);
+        let failing = &prs[0];
+        assert!(failing.draft_ranked);
+        assert_eq!(failing.score, -30 + SCORE_DRAFT_CI_FAILED);
+        assert_eq!(failing.display_status, "📝 draft · status");
+        assert!(!prs[1].draft_ranked);
+    }
+
+    #[test]
+    fn score_mode_ranks_drafts_without_boost() {
+        let policy = DraftPolicy {
+            others: DraftMode::Score,
+            ..DraftPolicy::default()
+        };
+        let mut prs = drafts();
+        apply_draft_policy(&mut prs, &policy);
+        let other = prs.iter().find(|p| p.pr.pr_key == "a/a#3").unwrap();
+        assert!(other.draft_ranked);
+        assert_eq!(other.score, 10);
+        assert!(
+            prs.iter()
+                .filter(|p| p.pr.is_viewer_author)
+                .all(|p| !p.draft_ranked)
+        );
+    }
+}
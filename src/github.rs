@@ -1,7 +1,15 @@
-use crate::model::{CiCheck, CiCheckState, CiState, MergeBlockers, Pr, ReviewState};
+//! GitHub GraphQL fetching of the viewer's attention set.
+
+use crate::model::{
+    CiCheck, CiCheckState, CiState, Comment, MergeBlockers, Pr, ReviewState, ReviewThread,
+    Reviewer, UsageMode,
+};
+use crate::provider::BoxFuture;
 use crate::timeutil::{parse_github_datetime_to_unix, unix_to_ymd};
 use octocrab::Octocrab;
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Debug, serde::Serialize)]
 struct PaginationVars {
@@ -15,6 +23,80 @@ struct GraphQlPayload<V> {
     variables: V,
 }
 
+/// Sends GitHub GraphQL requests: [`Octocrab`] against the live API, canned responses in
+/// tests. Everything that reads the attention set goes through it.
+pub trait GraphQlClient: Send + Sync {
+    /// POST `payload` (`query` and `variables`) and return the response body, or why the
+    /// request failed (network, HTTP status, secondary rate limits).
+    fn graphql(
+        &self,
+        payload: serde_json::Value,
+    ) -> BoxFuture<'_, Result<serde_json::Value, String>>;
+}
+
+impl GraphQlClient for Octocrab {
+    fn graphql(
+        &self,
+        payload: serde_json::Value,
+    ) -> BoxFuture<'_, Result<serde_json::Value, String>> {
+        Box::pin(async move {
+            Octocrab::graphql(self, &payload)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+}
+
+/// Send `payload` through `client` and decode the response body.
+async fn post_graphql<T: serde::de::DeserializeOwned>(
+    client: &dyn GraphQlClient,
+    payload: &impl serde::Serialize,
+) -> Result<T, String> {
+    let mut payload = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+    let asks_projects = payload["query"]
+        .as_str()
+        .is_some_and(|q| q.contains(PROJECT_ITEMS_FIELD));
+    if asks_projects && PROJECTS_REFUSED.load(Ordering::Relaxed) {
+        drop_project_items(&mut payload);
+    }
+    let mut body = client.graphql(payload.clone()).await?;
+    if asks_projects && !PROJECTS_REFUSED.load(Ordering::Relaxed) && refused_projects(&body) {
+        PROJECTS_REFUSED.store(true, Ordering::Relaxed);
+        drop_project_items(&mut payload);
+        body = client.graphql(payload).await?;
+    }
+    serde_json::from_value(body).map_err(|e| e.to_string())
+}
+
+/// Start of the line PR queries use to ask for project board status.
+const PROJECT_ITEMS_FIELD: &str = "projectItems(";
+
+/// Set once GitHub refused project items: they need the `read:project` scope, which
+/// tokens from `gh auth login` usually lack. Later queries go without them.
+static PROJECTS_REFUSED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the response is GitHub refusing the `projectItems` of a query.
+fn refused_projects(body: &serde_json::Value) -> bool {
+    body["errors"].as_array().is_some_and(|errors| {
+        errors.iter().any(|e| {
+            let e = e.to_string();
+            e.contains("read:project") || e.contains("projectItems")
+        })
+    })
+}
+
+/// Take the `projectItems` line out of the query of `payload`.
+fn drop_project_items(payload: &mut serde_json::Value) {
+    let Some(query) = payload["query"].as_str() else {
+        return;
+    };
+    let query: Vec<&str> = query
+        .lines()
+        .filter(|l| !l.trim_start().starts_with(PROJECT_ITEMS_FIELD))
+        .collect();
+    payload["query"] = serde_json::Value::String(query.join("\n"));
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct PageInfo {
     #[serde(rename = "hasNextPage")]
@@ -55,6 +137,7 @@ struct RequestedReviewer {
     #[serde(rename = "__typename")]
     typename: Option<String>,
     login: Option<String>, // User
+    slug: Option<String>,  // Team
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -84,6 +167,8 @@ struct StatusContextNode {
     state: Option<String>,
     #[serde(rename = "targetUrl")]
     target_url: Option<String>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -108,6 +193,17 @@ struct ReviewsConnection {
     total_count: Option<i32>,
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct LatestReviewConnection {
+    nodes: Option<Vec<LatestReviewNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LatestReviewNode {
+    author: Option<Author>,
+    state: Option<String>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct BranchProtectionRule {
     #[serde(rename = "requiredApprovingReviewCount")]
@@ -122,6 +218,81 @@ struct BaseRef {
     branch_protection_rule: Option<BranchProtectionRule>,
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct LabelConnection {
+    nodes: Option<Vec<LabelNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LabelNode {
+    name: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct MilestoneNode {
+    title: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProjectItemConnection {
+    #[serde(default, deserialize_with = "lenient_nodes")]
+    nodes: Option<Vec<ProjectItemNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProjectItemNode {
+    project: Option<ProjectNode>,
+    /// The item's `Status` field; `null` when the project has none.
+    status: Option<ProjectStatusValue>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProjectNode {
+    title: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProjectStatusValue {
+    name: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TimelineConnection {
+    nodes: Option<Vec<TimelineNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TimelineNode {
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CommentConnection {
+    nodes: Option<Vec<CommentNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CommentNode {
+    author: Option<Author>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    body: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ReviewThreadConnection {
+    nodes: Option<Vec<ReviewThreadNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ReviewThreadNode {
+    #[serde(rename = "isResolved")]
+    is_resolved: Option<bool>,
+    path: Option<String>,
+    comments: Option<CommentConnection>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct PullRequestNode {
     number: i64,
@@ -129,12 +300,22 @@ struct PullRequestNode {
     url: String,
     #[serde(rename = "updatedAt")]
     updated_at: String,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
     repository: Repository,
     author: Option<Author>,
     #[serde(rename = "reviewRequests")]
     review_requests: Option<ReviewRequestConnection>,
     #[serde(rename = "headRefOid")]
     head_ref_oid: Option<String>,
+    #[serde(rename = "headRefName")]
+    head_ref_name: Option<String>,
+    #[serde(rename = "baseRefName")]
+    base_ref_name: Option<String>,
+    additions: Option<i64>,
+    deletions: Option<i64>,
+    #[serde(rename = "viewerSubscription")]
+    viewer_subscription: Option<String>,
     #[serde(rename = "reviewDecision")]
     review_decision: Option<String>,
     #[serde(rename = "isDraft")]
@@ -146,12 +327,27 @@ struct PullRequestNode {
     reviews: Option<ReviewsConnection>,
     #[serde(rename = "baseRef")]
     base_ref: Option<BaseRef>,
+    labels: Option<LabelConnection>,
+    milestone: Option<MilestoneNode>,
+    #[serde(rename = "projectItems")]
+    project_items: Option<ProjectItemConnection>,
+    #[serde(rename = "timelineItems")]
+    timeline_items: Option<TimelineConnection>,
+    #[serde(rename = "forcePushes")]
+    force_pushes: Option<TimelineConnection>,
+    comments: Option<CommentConnection>,
+    #[serde(rename = "reviewThreads")]
+    review_threads: Option<ReviewThreadConnection>,
+    body: Option<String>,
+    #[serde(rename = "latestReviews")]
+    latest_reviews: Option<LatestReviewConnection>,
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct ViewerPullRequests {
     #[serde(rename = "pageInfo")]
     page_info: PageInfo,
+    #[serde(default, deserialize_with = "lenient_nodes")]
     nodes: Option<Vec<PullRequestNode>>,
 }
 
@@ -165,17 +361,150 @@ struct Viewer {
 #[derive(Debug, serde::Deserialize)]
 struct AuthoredData {
     viewer: Viewer,
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<RateLimitNode>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RateLimitNode {
+    limit: i64,
+    remaining: i64,
+    #[serde(rename = "resetAt")]
+    reset_at: String,
+}
+
+/// GraphQL API budget as reported by the most recent query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: i64,
+    pub remaining: i64,
+    pub reset_at_unix: i64,
+}
+
+impl RateLimit {
+    /// Less than a quarter of the budget is left.
+    pub fn is_low(&self) -> bool {
+        self.remaining * 4 < self.limit
+    }
+
+    /// Auto-refresh interval given this budget: `base_secs` while plenty is left, stretched
+    /// 2x under 25% and 4x under 10% (never past the reset), and no refresh at all until the
+    /// reset once it is used up.
+    pub fn refresh_interval_secs(&self, base_secs: u64, now: i64) -> u64 {
+        let until_reset = u64::try_from(self.reset_at_unix.saturating_sub(now)).unwrap_or(0);
+        if self.remaining <= 0 {
+            return base_secs.max(until_reset + 1);
+        }
+        let factor = if self.remaining * 10 < self.limit {
+            4
+        } else if self.is_low() {
+            2
+        } else {
+            1
+        };
+        (base_secs * factor).min(until_reset.max(base_secs))
+    }
+}
+
+static LAST_RATE_LIMIT: Mutex<Option<RateLimit>> = Mutex::new(None);
+
+/// Rate limit seen on the last GraphQL response in this process, if any.
+pub fn last_rate_limit() -> Option<RateLimit> {
+    LAST_RATE_LIMIT.lock().ok().and_then(|g| *g)
+}
+
+fn record_rate_limit(node: Option<&RateLimitNode>) {
+    let Some(node) = node else { return };
+    let Some(reset_at_unix) = parse_github_datetime_to_unix(&node.reset_at) else {
+        return;
+    };
+    if let Ok(mut g) = LAST_RATE_LIMIT.lock() {
+        *g = Some(RateLimit {
+            limit: node.limit,
+            remaining: node.remaining,
+            reset_at_unix,
+        });
+    }
+}
+
+/// Problems GitHub reported next to the data of recent queries, newest last.
+static PARTIAL_ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Drain what was skipped because of per-node errors (e.g. `FORBIDDEN` for one repository)
+/// since the last call, so the frontend can log it.
+pub fn take_partial_errors() -> Vec<String> {
+    PARTIAL_ERRORS
+        .lock()
+        .map(|mut g| std::mem::take(&mut *g))
+        .unwrap_or_default()
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct GraphQlResponse<T> {
-    data: T,
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GraphQlError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+impl<T> GraphQlResponse<T> {
+    /// The data of a possibly partial response. GitHub nulls out the nodes an error is
+    /// about and still returns the rest, so errors next to data are recorded for
+    /// [`take_partial_errors`] instead of failing the whole query.
+    fn into_data(self, what: &str) -> Result<T, String> {
+        let messages: Vec<String> = self
+            .errors
+            .iter()
+            .map(|e| match &e.kind {
+                Some(kind) => format!("{kind}: {}", e.message),
+                None => e.message.clone(),
+            })
+            .collect();
+        let Some(data) = self.data else {
+            return Err(format!(
+                "GitHub GraphQL {what} query failed: {}",
+                messages.join("; ")
+            ));
+        };
+        if !messages.is_empty() {
+            let mut g = PARTIAL_ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+            g.extend(
+                messages
+                    .into_iter()
+                    .map(|m| format!("{what} query skipped nodes: {m}")),
+            );
+        }
+        Ok(data)
+    }
+}
+
+/// A node list that drops the nodes GitHub could not resolve: they come back `null` (or
+/// missing required fields) next to an entry in `errors`.
+fn lenient_nodes<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let raw: Option<Vec<serde_json::Value>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(raw.map(|nodes| {
+        nodes
+            .into_iter()
+            .filter_map(|n| serde_json::from_value(n).ok())
+            .collect()
+    }))
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct SearchResult {
     #[serde(rename = "pageInfo")]
     page_info: PageInfo,
+    #[serde(default, deserialize_with = "lenient_nodes")]
     nodes: Option<Vec<SearchNode>>,
 }
 
@@ -188,12 +517,22 @@ struct SearchNode {
     url: Option<String>,
     #[serde(rename = "updatedAt")]
     updated_at: Option<String>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
     repository: Option<Repository>,
     author: Option<Author>,
     #[serde(rename = "reviewRequests")]
     review_requests: Option<ReviewRequestConnection>,
     #[serde(rename = "headRefOid")]
     head_ref_oid: Option<String>,
+    #[serde(rename = "headRefName")]
+    head_ref_name: Option<String>,
+    #[serde(rename = "baseRefName")]
+    base_ref_name: Option<String>,
+    additions: Option<i64>,
+    deletions: Option<i64>,
+    #[serde(rename = "viewerSubscription")]
+    viewer_subscription: Option<String>,
     #[serde(rename = "reviewDecision")]
     review_decision: Option<String>,
     #[serde(rename = "isDraft")]
@@ -205,6 +544,20 @@ struct SearchNode {
     reviews: Option<ReviewsConnection>,
     #[serde(rename = "baseRef")]
     base_ref: Option<BaseRef>,
+    labels: Option<LabelConnection>,
+    milestone: Option<MilestoneNode>,
+    #[serde(rename = "projectItems")]
+    project_items: Option<ProjectItemConnection>,
+    #[serde(rename = "timelineItems")]
+    timeline_items: Option<TimelineConnection>,
+    #[serde(rename = "forcePushes")]
+    force_pushes: Option<TimelineConnection>,
+    comments: Option<CommentConnection>,
+    #[serde(rename = "reviewThreads")]
+    review_threads: Option<ReviewThreadConnection>,
+    body: Option<String>,
+    #[serde(rename = "latestReviews")]
+    latest_reviews: Option<LatestReviewConnection>,
 }
 
 impl SearchNode {
@@ -217,10 +570,16 @@ impl SearchNode {
             title: self.title?,
             url: self.url?,
             updated_at: self.updated_at?,
+            created_at: self.created_at,
             repository: self.repository?,
             author: self.author,
             review_requests: self.review_requests,
             head_ref_oid: self.head_ref_oid,
+            head_ref_name: self.head_ref_name,
+            base_ref_name: self.base_ref_name,
+            additions: self.additions,
+            deletions: self.deletions,
+            viewer_subscription: self.viewer_subscription,
             review_decision: self.review_decision,
             is_draft: self.is_draft,
             mergeable: self.mergeable,
@@ -228,6 +587,15 @@ impl SearchNode {
             commits: self.commits,
             reviews: self.reviews,
             base_ref: self.base_ref,
+            labels: self.labels,
+            milestone: self.milestone,
+            project_items: self.project_items,
+            timeline_items: self.timeline_items,
+            force_pushes: self.force_pushes,
+            comments: self.comments,
+            review_threads: self.review_threads,
+            body: self.body,
+            latest_reviews: self.latest_reviews,
         })
     }
 }
@@ -235,65 +603,104 @@ impl SearchNode {
 #[derive(Debug, serde::Deserialize)]
 struct SearchData {
     search: SearchResult,
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<RateLimitNode>,
 }
 
-const AUTHORED_QUERY: &str = r#"
-query($page_size: Int!, $cursor: String) {
-  viewer {
-    login
-    pullRequests(first: $page_size, after: $cursor, states: OPEN, orderBy: {field: UPDATED_AT, direction: DESC}) {
-      pageInfo { hasNextPage endCursor }
-      nodes {
-        number
-        author { login }
-        title
-        url
-        updatedAt
-        headRefOid
-        reviewDecision
-        isDraft
-        mergeable
-        mergeStateStatus
-        repository { name owner { login } }
-        reviewRequests(first: 50) {
-          nodes {
-            requestedReviewer {
+#[derive(Debug, serde::Deserialize)]
+struct ViewerLogin {
+    login: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SinglePrRepository {
+    #[serde(rename = "pullRequest")]
+    pull_request: Option<PullRequestNode>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SinglePrData {
+    viewer: ViewerLogin,
+    repository: Option<SinglePrRepository>,
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<RateLimitNode>,
+}
+
+/// Every pull request field needle reads, shared by the queries that fetch pull requests
+/// (`...PrFields`). A macro so `concat!` can append it to each query at compile time.
+macro_rules! pr_fields_fragment {
+    () => {
+        r#"
+fragment PrFields on PullRequest {
+  number
+  author { login }
+  title
+  url
+  updatedAt
+  createdAt
+  headRefOid
+  headRefName
+  baseRefName
+  additions
+  deletions
+  viewerSubscription
+  reviewDecision
+  isDraft
+  mergeable
+  mergeStateStatus
+  body
+  labels(first: 20) { nodes { name } }
+  milestone { title }
+  projectItems(first: 5, includeArchived: false) { nodes { project { title } status: fieldValueByName(name: "Status") { ... on ProjectV2ItemFieldSingleSelectValue { name } } } }
+  latestReviews(first: 50) { nodes { author { login } state } }
+  timelineItems(itemTypes: [REVIEW_REQUESTED_EVENT], last: 1) {
+    nodes { ... on ReviewRequestedEvent { createdAt } }
+  }
+  forcePushes: timelineItems(itemTypes: [HEAD_REF_FORCE_PUSHED_EVENT], last: 1) {
+    nodes { ... on HeadRefForcePushedEvent { createdAt } }
+  }
+  comments(last: 30) { nodes { author { login } createdAt body } }
+  reviewThreads(last: 20) {
+    nodes { isResolved path comments(last: 10) { nodes { author { login } createdAt body } } }
+  }
+  repository { name owner { login } }
+  reviewRequests(first: 50) {
+    nodes {
+      requestedReviewer {
+        __typename
+        ... on User { login }
+        ... on Team { slug }
+      }
+    }
+  }
+  reviews(states: [APPROVED], first: 50) {
+    totalCount
+  }
+  baseRef {
+    branchProtectionRule {
+      requiredApprovingReviewCount
+      requiredStatusCheckContexts
+    }
+  }
+  commits(last: 2) {
+    nodes {
+      commit {
+        statusCheckRollup {
+          state
+          contexts(first: 50) {
+            nodes {
               __typename
-              ... on User { login }
-              ... on Team { slug }
-            }
-          }
-        }
-        reviews(states: [APPROVED], first: 50) {
-          totalCount
-        }
-        baseRef {
-          branchProtectionRule {
-            requiredApprovingReviewCount
-            requiredStatusCheckContexts
-          }
-        }
-        commits(last: 1) {
-          nodes {
-            commit {
-              statusCheckRollup {
+              ... on CheckRun {
+                name
+                conclusion
+                detailsUrl
+                startedAt
+              }
+              ... on StatusContext {
+                context
                 state
-                contexts(first: 50) {
-                  nodes {
-                    __typename
-                    ... on CheckRun {
-                      name
-                      conclusion
-                      detailsUrl
-                      startedAt
-                    }
-                    ... on StatusContext {
-                      context
-                      state
-                      targetUrl
-                    }
-                  }
-                }
+                targetUrl
+                createdAt
               }
             }
           }
@@ -302,89 +709,98 @@ query($page_size: Int!, $cursor: String) {
     }
   }
 }
-"#;
+"#
+    };
+}
 
-const REVIEW_REQUESTED_QUERY: &str = r#"
+const AUTHORED_QUERY: &str = concat!(
+    r#"
+query($page_size: Int!, $cursor: String) {
+  rateLimit { limit remaining resetAt }
+  viewer {
+    login
+    pullRequests(first: $page_size, after: $cursor, states: OPEN, orderBy: {field: UPDATED_AT, direction: DESC}) {
+      pageInfo { hasNextPage endCursor }
+      nodes { ...PrFields }
+    }
+  }
+}
+"#,
+    pr_fields_fragment!()
+);
+
+const SEARCH_QUERY: &str = concat!(
+    r#"
 query($page_size: Int!, $cursor: String, $search_query: String!) {
+  rateLimit { limit remaining resetAt }
   search(query: $search_query, type: ISSUE, first: $page_size, after: $cursor) {
     pageInfo { hasNextPage endCursor }
     nodes {
       __typename
-      ... on PullRequest {
-        number
-        author { login }
-        title
-        url
-        updatedAt
-        headRefOid
-        reviewDecision
-        isDraft
-        mergeable
-        mergeStateStatus
-        repository { name owner { login } }
-        reviewRequests(first: 50) {
-          nodes {
-            requestedReviewer {
-              __typename
-              ... on User { login }
-              ... on Team { slug }
-            }
-          }
-        }
-        reviews(states: [APPROVED], first: 50) {
-          totalCount
-        }
-        baseRef {
-          branchProtectionRule {
-            requiredApprovingReviewCount
-            requiredStatusCheckContexts
-          }
-        }
-        commits(last: 1) {
-          nodes {
-            commit {
-              statusCheckRollup {
-                state
-                contexts(first: 50) {
-                  nodes {
-                    __typename
-                    ... on CheckRun {
-                      name
-                      conclusion
-                      detailsUrl
-                      startedAt
-                    }
-                    ... on StatusContext {
-                      context
-                      state
-                      targetUrl
-                    }
-                  }
-                }
-              }
-            }
-          }
-        }
-      }
+      ...PrFields
     }
   }
 }
+"#,
+    pr_fields_fragment!()
+);
+
+const PR_EXISTS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  rateLimit { limit remaining resetAt }
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { number }
+  }
+}
 "#;
 
-fn map_ci_checks(node: &PullRequestNode) -> Vec<CiCheck> {
-    let Some(commits) = &node.commits else {
-        return Vec::new();
-    };
-    let Some(nodes) = &commits.nodes else {
-        return Vec::new();
-    };
-    let Some(first) = nodes.first() else {
-        return Vec::new();
-    };
-    let Some(commit) = &first.commit else {
-        return Vec::new();
-    };
-    let Some(rollup) = &commit.status_check_rollup else {
+const DEFAULT_BRANCH_CI_QUERY: &str = r#"
+query($owner: String!, $name: String!) {
+  rateLimit { limit remaining resetAt }
+  repository(owner: $owner, name: $name) {
+    defaultBranchRef {
+      target { ... on Commit { statusCheckRollup { state } } }
+    }
+  }
+}
+"#;
+
+const PR_QUERY: &str = concat!(
+    r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  rateLimit { limit remaining resetAt }
+  viewer { login }
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { ...PrFields }
+  }
+}
+"#,
+    pr_fields_fragment!()
+);
+
+/// The status rollup CI is read from: the head commit's or, right after a push while the
+/// head has none yet, the previous commit's (then `true`), so CI does not blink to "none".
+fn ci_rollup(node: &PullRequestNode) -> (Option<&StatusCheckRollup>, bool) {
+    let commits = node
+        .commits
+        .as_ref()
+        .and_then(|c| c.nodes.as_deref())
+        .unwrap_or_default();
+    fn rollup(c: &CommitNode) -> Option<&StatusCheckRollup> {
+        c.commit.as_ref()?.status_check_rollup.as_ref()
+    }
+    match commits {
+        [.., previous, head] => match rollup(head) {
+            Some(r) => (Some(r), false),
+            None => (rollup(previous), rollup(previous).is_some()),
+        },
+        [head] => (rollup(head), false),
+        [] => (None, false),
+    }
+}
+
+fn map_ci_checks(rollup: Option<&StatusCheckRollup>) -> Vec<CiCheck> {
+    let Some(rollup) = rollup else {
         return Vec::new();
     };
     let Some(ctxs) = &rollup.contexts else {
@@ -393,7 +809,11 @@ fn map_ci_checks(node: &PullRequestNode) -> Vec<CiCheck> {
     let Some(nodes) = &ctxs.nodes else {
         return Vec::new();
     };
+    map_ci_contexts(nodes)
+}
 
+/// Check runs and commit statuses from a status rollup, failed first.
+fn map_ci_contexts(nodes: &[StatusContextNode]) -> Vec<CiCheck> {
     let mut out = Vec::new();
     for n in nodes {
         match n.typename.as_deref() {
@@ -432,11 +852,17 @@ fn map_ci_checks(node: &PullRequestNode) -> Vec<CiCheck> {
                     Some("PENDING") | Some("EXPECTED") => CiCheckState::Running,
                     _ => CiCheckState::None,
                 };
+                // Statuses have no start time; the latest status for the context was posted
+                // when the job went pending, which is when it started as far as we can tell.
+                let started_at_unix = n
+                    .created_at
+                    .as_deref()
+                    .and_then(parse_github_datetime_to_unix);
                 out.push(CiCheck {
                     name,
                     state,
                     url: n.target_url.clone(),
-                    started_at_unix: None,
+                    started_at_unix,
                 });
             }
             _ => {}
@@ -490,21 +916,13 @@ fn derive_ci_state(rollup_state: Option<&str>, checks: &[CiCheck]) -> CiState {
     }
 }
 
-fn rollup_state(node: &PullRequestNode) -> Option<&str> {
-    let commits = node.commits.as_ref()?;
-    let nodes = commits.nodes.as_ref()?;
-    let first = nodes.first()?;
-    let commit = first.commit.as_ref()?;
-    let rollup = commit.status_check_rollup.as_ref()?;
-    rollup.state.as_deref()
-}
-
 fn map_review_state(node: &PullRequestNode, is_requested: bool) -> ReviewState {
     if is_requested {
         return ReviewState::Requested;
     }
     match node.review_decision.as_deref() {
         Some("APPROVED") => ReviewState::Approved,
+        Some("CHANGES_REQUESTED") => ReviewState::ChangesRequested,
         _ => ReviewState::None,
     }
 }
@@ -580,9 +998,187 @@ fn compute_merge_blockers(node: &PullRequestNode, ci_checks: &[CiCheck]) -> Merg
     }
 }
 
+/// `Project: Status` for each project board the PR is on with a status, comma-separated.
+fn project_status(items: Option<&ProjectItemConnection>) -> Option<String> {
+    let statuses: Vec<String> = items
+        .and_then(|i| i.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let project = item.project.as_ref()?;
+            let status = item.status.as_ref()?.name.as_ref()?;
+            Some(format!("{}: {status}", project.title))
+        })
+        .collect();
+    (!statuses.is_empty()).then(|| statuses.join(", "))
+}
+
+fn latest_event_at(items: Option<&TimelineConnection>) -> Option<i64> {
+    items
+        .and_then(|t| t.nodes.as_ref())
+        .and_then(|nodes| nodes.last())
+        .and_then(|n| n.created_at.as_deref())
+        .and_then(parse_github_datetime_to_unix)
+}
+
+/// Latest comment by someone else after the viewer's last comment in one thread.
+fn reply_after_viewer(thread: &CommentConnection, viewer_login: &str) -> Option<i64> {
+    let nodes = thread.nodes.as_ref()?;
+    let by_viewer = |c: &CommentNode| c.author.as_ref().is_some_and(|a| a.login == viewer_login);
+    let last_mine = nodes.iter().rposition(by_viewer)?;
+    nodes[last_mine + 1..]
+        .iter()
+        .filter(|c| !by_viewer(c))
+        .filter_map(|c| c.created_at.as_deref())
+        .filter_map(parse_github_datetime_to_unix)
+        .max()
+}
+
+/// Latest reply to the viewer across the conversation and every review thread.
+fn last_reply_to_viewer(node: &PullRequestNode, viewer_login: &str) -> Option<i64> {
+    let threads = node
+        .review_threads
+        .as_ref()
+        .and_then(|t| t.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.comments.as_ref());
+    node.comments
+        .iter()
+        .chain(threads)
+        .filter_map(|c| reply_after_viewer(c, viewer_login))
+        .max()
+}
+
+/// Conversation comments kept per PR for the details view.
+pub(crate) const CONVERSATION_COMMENTS: usize = 10;
+/// Longer comment bodies are cut; the full text is a click away on GitHub.
+pub(crate) const COMMENT_BODY_CHARS: usize = 2000;
+
+fn to_comment(c: &CommentNode) -> Option<Comment> {
+    Some(Comment {
+        author: c
+            .author
+            .as_ref()
+            .map_or_else(|| "ghost".to_string(), |a| a.login.clone()),
+        body: c
+            .body
+            .as_deref()
+            .unwrap_or("")
+            .trim()
+            .chars()
+            .take(COMMENT_BODY_CHARS)
+            .collect(),
+        created_at_unix: parse_github_datetime_to_unix(c.created_at.as_deref()?)?,
+    })
+}
+
+fn comments_of(conn: Option<&CommentConnection>) -> Vec<Comment> {
+    conn.and_then(|c| c.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(to_comment)
+        .collect()
+}
+
+/// Latest conversation comments, oldest first.
+fn conversation(node: &PullRequestNode) -> Vec<Comment> {
+    let mut all = comments_of(node.comments.as_ref());
+    let skip = all.len().saturating_sub(CONVERSATION_COMMENTS);
+    all.drain(..skip);
+    all
+}
+
+/// Review threads that still have comments, oldest first.
+fn review_threads(
+    threads: Option<&ReviewThreadConnection>,
+    viewer_login: &str,
+) -> Vec<ReviewThread> {
+    threads
+        .and_then(|t| t.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|t| {
+            let comments = comments_of(t.comments.as_ref());
+            ReviewThread {
+                path: t.path.clone(),
+                is_resolved: t.is_resolved.unwrap_or(false),
+                started_by_viewer: comments.first().is_some_and(|c| c.author == viewer_login),
+                comments,
+            }
+        })
+        .filter(|t| !t.comments.is_empty())
+        .collect()
+}
+
+const BODY_EXCERPT_LINES: usize = 8;
+const BODY_EXCERPT_CHARS: usize = 600;
+
+/// First non-blank lines of a PR description, without HTML comments (template hints).
+pub(crate) fn body_excerpt(body: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("<!--") {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("-->")
+            .map(|end| &rest[start + end + 3..])
+            .unwrap_or("");
+    }
+    text.push_str(rest);
+
+    let excerpt: String = text
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|l| l.trim().is_empty())
+        .take(BODY_EXCERPT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .chars()
+        .take(BODY_EXCERPT_CHARS)
+        .collect();
+    (!excerpt.is_empty()).then_some(excerpt)
+}
+
+/// Latest review per reviewer, then pending user/team requests.
+fn reviewers(node: &PullRequestNode) -> Vec<Reviewer> {
+    let mut out: Vec<Reviewer> = node
+        .latest_reviews
+        .as_ref()
+        .and_then(|r| r.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|r| {
+            Some(Reviewer {
+                login: r.author.as_ref()?.login.clone(),
+                state: r.state.clone()?,
+            })
+        })
+        .collect();
+    let requested = node
+        .review_requests
+        .as_ref()
+        .and_then(|r| r.nodes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|n| n.requested_reviewer.as_ref())
+        .filter_map(|r| r.login.clone().or_else(|| r.slug.clone()));
+    for login in requested {
+        // A re-requested reviewer shows as pending, not with their old verdict.
+        out.retain(|r| r.login != login);
+        out.push(Reviewer {
+            login,
+            state: "REQUESTED".to_string(),
+        });
+    }
+    out
+}
+
 fn to_pr(node: PullRequestNode, is_requested: bool, viewer_login: &str) -> Option<Pr> {
-    let ci_checks = map_ci_checks(&node);
-    let ci_state = derive_ci_state(rollup_state(&node), &ci_checks);
+    let (rollup, ci_from_previous_commit) = ci_rollup(&node);
+    let ci_checks = map_ci_checks(rollup);
+    let ci_state = derive_ci_state(rollup.and_then(|r| r.state.as_deref()), &ci_checks);
     let last_commit_sha = node.head_ref_oid.clone();
     let review_state = map_review_state(&node, is_requested);
     let owner = node.repository.owner.login.clone();
@@ -593,6 +1189,10 @@ fn to_pr(node: PullRequestNode, is_requested: bool, viewer_login: &str) -> Optio
         .map(|a| a.login.clone())
         .unwrap_or_else(|| "unknown".to_string());
     let updated_at_unix = parse_github_datetime_to_unix(&node.updated_at)?;
+    let created_at_unix = node
+        .created_at
+        .as_deref()
+        .and_then(parse_github_datetime_to_unix);
     let pr_key = format!("{owner}/{repo}#{}", node.number);
 
     let is_viewer_author = node
@@ -601,6 +1201,21 @@ fn to_pr(node: PullRequestNode, is_requested: bool, viewer_login: &str) -> Optio
         .map(|a| a.login.as_str() == viewer_login)
         .unwrap_or(false);
 
+    let labels = node
+        .labels
+        .as_ref()
+        .and_then(|l| l.nodes.as_ref())
+        .map(|nodes| nodes.iter().map(|n| n.name.clone()).collect())
+        .unwrap_or_default();
+    let project_status = project_status(node.project_items.as_ref());
+    let review_requested_at_unix = latest_event_at(node.timeline_items.as_ref());
+    let last_force_push_at_unix = latest_event_at(node.force_pushes.as_ref());
+    let last_reply_at_unix = last_reply_to_viewer(&node, viewer_login);
+    let body_excerpt = node.body.as_deref().and_then(body_excerpt);
+    let reviewers = reviewers(&node);
+    let comments = conversation(&node);
+    let review_threads = review_threads(node.review_threads.as_ref(), viewer_login);
+
     let merge_blockers = compute_merge_blockers(&node, &ci_checks);
     let merge_blockers = if merge_blockers.is_clear() {
         None
@@ -617,6 +1232,7 @@ fn to_pr(node: PullRequestNode, is_requested: bool, viewer_login: &str) -> Optio
         title: node.title,
         url: node.url,
         updated_at_unix,
+        created_at_unix,
         last_commit_sha,
         ci_state,
         ci_checks,
@@ -625,7 +1241,28 @@ fn to_pr(node: PullRequestNode, is_requested: bool, viewer_login: &str) -> Optio
         mergeable: node.mergeable.clone(),
         merge_state_status: node.merge_state_status.clone(),
         is_viewer_author,
+        is_assignee: false,
+        is_mentioned: false,
+        // SUBSCRIBED, or IGNORED / UNSUBSCRIBED once turned off on github.com.
+        is_unsubscribed: matches!(
+            node.viewer_subscription.as_deref(),
+            Some("UNSUBSCRIBED" | "IGNORED")
+        ),
         merge_blockers,
+        labels,
+        review_requested_at_unix,
+        head_ref_name: node.head_ref_name.clone(),
+        base_ref_name: node.base_ref_name.clone(),
+        milestone: node.milestone.as_ref().map(|m| m.title.clone()),
+        project_status,
+        ci_from_previous_commit,
+        lines_changed: node.additions.zip(node.deletions).map(|(a, d)| a + d),
+        last_force_push_at_unix,
+        last_reply_at_unix,
+        body_excerpt,
+        reviewers,
+        comments,
+        review_threads,
     })
 }
 
@@ -638,10 +1275,323 @@ fn merge_into(map: &mut HashMap<String, Pr>, mut pr: Pr) {
     map.insert(pr.pr_key.clone(), pr);
 }
 
+/// Parse a PR reference: a GitHub PR URL or `owner/repo#123`.
+///
+/// Returns `(owner, repo, number)`. Surrounding whitespace, `<>` and trailing
+/// path segments (`/files`, `#discussion...`) are ignored.
+pub fn parse_pr_ref(s: &str) -> Option<(String, String, i64)> {
+    let s = s.trim().trim_matches(|c| c == '<' || c == '>');
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    // Short form: owner/repo#123
+    if let Some((repo_path, number)) = s.split_once('#') {
+        let short = repo_path
+            .split_once('/')
+            .zip(number.parse::<i64>().ok())
+            .filter(|((owner, repo), n)| *n > 0 && valid(owner) && valid(repo));
+        if let Some(((owner, repo), n)) = short {
+            return Some((owner.to_string(), repo.to_string(), n));
+        }
+    }
+
+    let rest = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .unwrap_or(s);
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let rest = rest.strip_prefix("github.com/")?;
+    let mut parts = rest.split(['/', '#', '?']);
+    let owner = parts.next()?;
+    let repo = parts.next()?;
+    if parts.next()? != "pull" {
+        return None;
+    }
+    let number = parts.next()?.parse::<i64>().ok().filter(|n| *n > 0)?;
+    if valid(owner) && valid(repo) {
+        Some((owner.to_string(), repo.to_string(), number))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct PrVars<'a> {
+    owner: &'a str,
+    name: &'a str,
+    number: i64,
+}
+
+/// Fetch a single PR by number, regardless of whether it is in the attention set.
+pub async fn fetch_pr(
+    client: &dyn GraphQlClient,
+    owner: &str,
+    repo: &str,
+    number: i64,
+) -> Result<Pr, String> {
+    let payload = GraphQlPayload {
+        query: PR_QUERY,
+        variables: PrVars {
+            owner,
+            name: repo,
+            number,
+        },
+    };
+    let resp: GraphQlResponse<SinglePrData> = post_graphql(client, &payload)
+        .await
+        .map_err(|e| format!("GitHub GraphQL PR query failed: {e}"))?;
+    let data = resp.into_data("PR")?;
+    record_rate_limit(data.rate_limit.as_ref());
+
+    let viewer_login = data.viewer.login;
+    let node = data
+        .repository
+        .and_then(|r| r.pull_request)
+        .ok_or_else(|| format!("PR {owner}/{repo}#{number} not found"))?;
+    let requested = is_review_requested_by_user(&node, &viewer_login);
+    to_pr(node, requested, &viewer_login)
+        .ok_or_else(|| format!("PR {owner}/{repo}#{number} has an unreadable timestamp"))
+}
+
+/// Whether the viewer can still see a PR. GitHub answers a deleted repository or revoked
+/// access with a `null` node (and a `NOT_FOUND` or `FORBIDDEN` error), so that is `false`.
+pub async fn pr_exists(
+    client: &dyn GraphQlClient,
+    owner: &str,
+    repo: &str,
+    number: i64,
+) -> Result<bool, String> {
+    #[derive(Debug, serde::Deserialize)]
+    struct Repository {
+        #[serde(rename = "pullRequest")]
+        pull_request: Option<serde::de::IgnoredAny>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct Data {
+        repository: Option<Repository>,
+        #[serde(rename = "rateLimit")]
+        rate_limit: Option<RateLimitNode>,
+    }
+
+    let payload = GraphQlPayload {
+        query: PR_EXISTS_QUERY,
+        variables: PrVars {
+            owner,
+            name: repo,
+            number,
+        },
+    };
+    let resp: GraphQlResponse<Data> = post_graphql(client, &payload)
+        .await
+        .map_err(|e| format!("GitHub GraphQL PR check failed: {e}"))?;
+    let data = resp.into_data("PR check")?;
+    record_rate_limit(data.rate_limit.as_ref());
+    Ok(data.repository.and_then(|r| r.pull_request).is_some())
+}
+
+/// CI state of the head commit of a repository's default branch (`None` without checks).
+pub async fn default_branch_ci(
+    client: &dyn GraphQlClient,
+    owner: &str,
+    repo: &str,
+) -> Result<CiState, String> {
+    #[derive(Debug, serde::Deserialize)]
+    struct Target {
+        #[serde(rename = "statusCheckRollup")]
+        status_check_rollup: Option<StatusCheckRollup>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct BranchRef {
+        target: Option<Target>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct Repository {
+        #[serde(rename = "defaultBranchRef")]
+        default_branch_ref: Option<BranchRef>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct Data {
+        repository: Option<Repository>,
+        #[serde(rename = "rateLimit")]
+        rate_limit: Option<RateLimitNode>,
+    }
+    #[derive(Debug, serde::Serialize)]
+    struct RepoVars<'a> {
+        owner: &'a str,
+        name: &'a str,
+    }
+
+    let payload = GraphQlPayload {
+        query: DEFAULT_BRANCH_CI_QUERY,
+        variables: RepoVars { owner, name: repo },
+    };
+    let resp: GraphQlResponse<Data> = post_graphql(client, &payload)
+        .await
+        .map_err(|e| format!("GitHub GraphQL default branch check failed: {e}"))?;
+    let data = resp.into_data("default branch check")?;
+    record_rate_limit(data.rate_limit.as_ref());
+    let state = data
+        .repository
+        .and_then(|r| r.default_branch_ref)
+        .and_then(|b| b.target)
+        .and_then(|t| t.status_check_rollup)
+        .and_then(|r| r.state);
+    Ok(derive_ci_state(state.as_deref(), &[]))
+}
+
+/// The GitHub Actions job behind a check URL: `.../actions/runs/1/job/2` or the check run
+/// page `.../runs/2` (a check run's ID is its job's ID).
+pub fn actions_job_id(url: &str) -> Option<u64> {
+    let path = url.split(['?', '#']).next()?.trim_end_matches('/');
+    let segs: Vec<&str> = path.split('/').collect();
+    match segs.as_slice() {
+        [.., "job", id] => id.parse().ok(),
+        [.., prev, "runs", id] if *prev != "actions" => id.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Full log of the GitHub Actions job behind `check`.
+pub async fn actions_job_log(
+    octo: &Octocrab,
+    owner: &str,
+    repo: &str,
+    check: &CiCheck,
+) -> Result<String, String> {
+    let job_id = check
+        .url
+        .as_deref()
+        .and_then(actions_job_id)
+        .ok_or_else(|| format!("{} is not a GitHub Actions job", check.name))?;
+    let fail = |e: octocrab::Error| format!("Fetching the log of {} failed: {e}", check.name);
+    let route = format!("/repos/{owner}/{repo}/actions/jobs/{job_id}/logs");
+    // The API answers with a redirect to a short-lived download URL.
+    let resp = octo._get(route).await.map_err(fail)?;
+    let resp = octo.follow_location_to_data(resp).await.map_err(fail)?;
+    let resp = octocrab::map_github_error(resp).await.map_err(fail)?;
+    octo.body_to_string(resp).await.map_err(fail)
+}
+
+/// Most repositories [`watched_repos`] and [`starred_repos`] return (10 pages).
+pub const VIEWER_REPOS_MAX: usize = 1_000;
+
+/// Repositories the viewer watches, as `owner/repo`.
+pub async fn watched_repos(octo: &Octocrab) -> Result<Vec<String>, String> {
+    viewer_repo_list(octo, "/user/subscriptions", "watched").await
+}
+
+/// Repositories the viewer starred, as `owner/repo`.
+pub async fn starred_repos(octo: &Octocrab) -> Result<Vec<String>, String> {
+    viewer_repo_list(octo, "/user/starred", "starred").await
+}
+
+async fn viewer_repo_list(octo: &Octocrab, route: &str, what: &str) -> Result<Vec<String>, String> {
+    #[derive(Debug, serde::Deserialize)]
+    struct RepoNode {
+        full_name: String,
+    }
+    const PER_PAGE: usize = 100;
+
+    let mut out = Vec::new();
+    for page in 1..=VIEWER_REPOS_MAX / PER_PAGE {
+        let repos: Vec<RepoNode> = octo
+            .get(
+                route,
+                Some(&serde_json::json!({ "per_page": PER_PAGE, "page": page })),
+            )
+            .await
+            .map_err(|e| format!("Listing your {what} repositories failed: {e}"))?;
+        let last = repos.len() < PER_PAGE;
+        out.extend(repos.into_iter().map(|r| r.full_name));
+        if last {
+            break;
+        }
+    }
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn pr_queries_share_one_field_selection() {
+        for query in [AUTHORED_QUERY, SEARCH_QUERY, PR_QUERY] {
+            assert_eq!(query.matches("fragment PrFields on PullRequest").count(), 1);
+            assert_eq!(query.matches("...PrFields").count(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn milestones_and_project_status_are_read_and_projects_dropped_when_refused() {
+        use crate::github_mock::{MockGitHub, pr_node, projects_refused, single_pr};
+        let mut node = pr_node("api", 7, "2024-03-09T12:00:00Z", "SUCCESS");
+        node["milestone"] = serde_json::json!({ "title": "v3.2" });
+        node["projectItems"] = serde_json::json!({ "nodes": [
+            { "project": { "title": "Roadmap" }, "status": { "name": "In review" } },
+            { "project": { "title": "Triage" }, "status": null }
+        ] });
+        let mock = MockGitHub::default().respond("projectItems", single_pr(node.clone()));
+        let pr = fetch_pr(&mock, "acme", "api", 7).await.unwrap();
+        assert_eq!(pr.milestone.as_deref(), Some("v3.2"));
+        assert_eq!(pr.project_status.as_deref(), Some("Roadmap: In review"));
+
+        // A token without `read:project`: asked again without projects, and from then on.
+        node.as_object_mut().unwrap().remove("projectItems");
+        let mock = MockGitHub::default()
+            .respond("projectItems", projects_refused())
+            .respond("pullRequest(number", single_pr(node.clone()))
+            .respond("pullRequest(number", single_pr(node));
+        let pr = fetch_pr(&mock, "acme", "api", 7).await.unwrap();
+        assert_eq!(pr.milestone.as_deref(), Some("v3.2"));
+        assert_eq!(pr.project_status, None);
+        fetch_pr(&mock, "acme", "api", 7).await.unwrap();
+        assert_eq!(mock.requests_with("projectItems"), 1);
+        assert_eq!(mock.unused(), 0);
+    }
+
+    #[tokio::test]
+    async fn ci_falls_back_to_the_previous_commit_until_the_head_has_a_rollup() {
+        use crate::github_mock::{MockGitHub, pr_node, single_pr};
+        let mut node = pr_node("api", 8, "2024-03-09T12:00:00Z", "FAILURE");
+        let previous = node["commits"]["nodes"][0].clone();
+        node["commits"]["nodes"] =
+            serde_json::json!([previous, { "commit": { "statusCheckRollup": null } }]);
+        let mock = MockGitHub::default().respond("pullRequest(number", single_pr(node.clone()));
+        let pr = fetch_pr(&mock, "acme", "api", 8).await.unwrap();
+        assert!(matches!(pr.ci_state, CiState::Failure));
+        assert!(pr.ci_from_previous_commit);
+
+        // Once the head reports, its own rollup wins.
+        node["commits"]["nodes"][1] =
+            serde_json::json!({ "commit": { "statusCheckRollup": { "state": "PENDING" } } });
+        let mock = MockGitHub::default().respond("pullRequest(number", single_pr(node));
+        let pr = fetch_pr(&mock, "acme", "api", 8).await.unwrap();
+        assert!(matches!(pr.ci_state, CiState::Running));
+        assert!(!pr.ci_from_previous_commit);
+    }
+
+    #[test]
+    fn token_errors_point_at_the_fix() {
+        assert!(describe_token_error(401, "Bad credentials").contains("expired or revoked"));
+        assert!(
+            describe_token_error(
+                403,
+                "Resource protected by organization SAML enforcement. You must grant your Personal Access token access to this organization."
+            )
+            .contains("Configure SSO")
+        );
+        assert!(describe_token_error(403, "Forbidden").contains("`repo` scope"));
+        assert_eq!(
+            describe_token_error(502, "Bad gateway"),
+            "GitHub token check failed (502): Bad gateway"
+        );
+    }
+
     fn mk_check(state: CiCheckState) -> CiCheck {
         CiCheck {
             name: "x".to_string(),
@@ -651,6 +1601,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_pr_ref_accepts_urls_and_short_refs() {
+        let want = Some(("acme".to_string(), "api".to_string(), 42));
+        assert_eq!(parse_pr_ref("acme/api#42"), want);
+        assert_eq!(parse_pr_ref("https://github.com/acme/api/pull/42"), want);
+        assert_eq!(
+            parse_pr_ref("  https://github.com/acme/api/pull/42/files\n"),
+            want
+        );
+        assert_eq!(
+            parse_pr_ref("<https://www.github.com/acme/api/pull/42#discussion_r1>"),
+            want
+        );
+        assert_eq!(parse_pr_ref("github.com/acme/api/pull/42?w=1"), want);
+    }
+
+    #[test]
+    fn parse_pr_ref_rejects_non_pr_links() {
+        assert_eq!(parse_pr_ref("https://github.com/acme/api/issues/42"), None);
+        assert_eq!(parse_pr_ref("https://gitlab.com/acme/api/pull/42"), None);
+        assert_eq!(parse_pr_ref("acme/api#0"), None);
+        assert_eq!(parse_pr_ref("acme#42"), None);
+        assert_eq!(parse_pr_ref("hello world"), None);
+    }
+
     #[test]
     fn derive_ci_state_prefers_running_checks_over_failure_rollup() {
         let checks = vec![
@@ -678,6 +1653,144 @@ mod tests {
         assert!(matches!(s, CiState::Running));
     }
 
+    #[test]
+    fn body_excerpt_skips_template_comments_and_caps_lines() {
+        let body =
+            "<!-- Describe your change -->\n\nAdds retries.\n<!-- multi\nline -->\n- a\n- b\n";
+        assert_eq!(
+            body_excerpt(body).as_deref(),
+            Some("Adds retries.\n\n- a\n- b")
+        );
+        assert_eq!(body_excerpt("<!-- only a template -->\n  \n"), None);
+
+        let long: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        assert_eq!(
+            body_excerpt(&long).unwrap().lines().count(),
+            BODY_EXCERPT_LINES
+        );
+    }
+
+    #[test]
+    fn refresh_interval_stretches_as_the_rate_limit_budget_runs_out() {
+        let now = 1_000_000;
+        let rl = |remaining: i64, reset_in: i64| RateLimit {
+            limit: 5000,
+            remaining,
+            reset_at_unix: now + reset_in,
+        };
+        assert_eq!(rl(4000, 3600).refresh_interval_secs(180, now), 180);
+        assert_eq!(rl(1000, 3600).refresh_interval_secs(180, now), 360);
+        assert_eq!(rl(400, 3600).refresh_interval_secs(180, now), 720);
+        // Never wait past the reset, but never refresh faster than configured either.
+        assert_eq!(rl(400, 300).refresh_interval_secs(180, now), 300);
+        assert_eq!(rl(400, 60).refresh_interval_secs(180, now), 180);
+        // Used up: wait for the reset.
+        assert_eq!(rl(0, 1800).refresh_interval_secs(180, now), 1801);
+        assert!(rl(1000, 0).is_low() && !rl(1250, 0).is_low());
+    }
+
+    #[test]
+    fn pending_statuses_start_when_they_were_posted() {
+        let nodes: Vec<StatusContextNode> = serde_json::from_value(serde_json::json!([
+            { "__typename": "StatusContext", "context": "ci/jenkins", "state": "PENDING",
+              "targetUrl": "https://jenkins/1", "createdAt": "2024-03-01T10:00:00Z" },
+            { "__typename": "CheckRun", "name": "lint", "conclusion": null,
+              "startedAt": "2024-03-01T10:05:00Z" }
+        ]))
+        .unwrap();
+        let checks = map_ci_contexts(&nodes);
+        let started = |name: &str| {
+            checks
+                .iter()
+                .find(|c| c.name == name)
+                .and_then(|c| c.started_at_unix)
+        };
+        assert_eq!(
+            started("ci/jenkins"),
+            parse_github_datetime_to_unix("2024-03-01T10:00:00Z")
+        );
+        assert_eq!(
+            started("lint"),
+            parse_github_datetime_to_unix("2024-03-01T10:05:00Z")
+        );
+    }
+
+    #[test]
+    fn replies_count_only_after_the_viewers_last_comment() {
+        let thread: CommentConnection = serde_json::from_value(serde_json::json!({
+            "nodes": [
+                { "author": { "login": "ana" }, "createdAt": "2024-03-01T10:00:00Z" },
+                { "author": { "login": "me" }, "createdAt": "2024-03-01T11:00:00Z" },
+                { "author": { "login": "ana" }, "createdAt": "2024-03-01T12:00:00Z" },
+                { "author": { "login": "bo" }, "createdAt": "2024-03-01T13:00:00Z" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            reply_after_viewer(&thread, "me"),
+            parse_github_datetime_to_unix("2024-03-01T13:00:00Z")
+        );
+        // Nothing to reply to when the viewer never commented, or had the last word.
+        assert_eq!(reply_after_viewer(&thread, "cy"), None);
+        assert_eq!(reply_after_viewer(&thread, "bo"), None);
+    }
+
+    #[test]
+    fn review_threads_keep_resolution_and_drop_empty_threads() {
+        let threads: ReviewThreadConnection = serde_json::from_value(serde_json::json!({
+            "nodes": [
+                { "isResolved": true, "path": "src/lib.rs", "comments": { "nodes": [
+                    { "author": { "login": "ana" }, "createdAt": "2024-03-01T10:00:00Z", "body": " nit \n" }
+                ] } },
+                { "isResolved": false, "path": "src/main.rs", "comments": { "nodes": [] } },
+                { "isResolved": false, "path": null, "comments": { "nodes": [
+                    { "author": null, "createdAt": "2024-03-01T11:00:00Z", "body": "why?" }
+                ] } }
+            ]
+        }))
+        .unwrap();
+        let mapped = review_threads(Some(&threads), "ana");
+        assert_eq!(mapped.len(), 2);
+        assert!(mapped[0].is_resolved);
+        assert!(mapped[0].started_by_viewer);
+        assert!(!mapped[1].started_by_viewer);
+        assert_eq!(mapped[0].comments[0].body, "nit");
+        assert_eq!(mapped[1].comments[0].author, "ghost");
+        assert!(!mapped[1].is_resolved);
+    }
+
+    #[test]
+    fn partial_responses_keep_the_resolved_nodes_and_record_the_errors() {
+        let json = r#"{
+            "data": {
+                "search": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": null},
+                    "nodes": [null, {"__typename": "PullRequest", "number": 7}]
+                }
+            },
+            "errors": [{"type": "FORBIDDEN", "message": "Resource not accessible by integration"}]
+        }"#;
+        let resp: GraphQlResponse<SearchData> = serde_json::from_str(json).unwrap();
+        let data = resp.into_data("search").unwrap();
+        let nodes = data.search.nodes.unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].number, Some(7));
+        assert!(
+            take_partial_errors().contains(
+                &"search query skipped nodes: FORBIDDEN: Resource not accessible by integration"
+                    .to_string()
+            )
+        );
+
+        let failed: GraphQlResponse<SearchData> =
+            serde_json::from_str(r#"{"data": null, "errors": [{"message": "Bad query"}]}"#)
+                .unwrap();
+        assert_eq!(
+            failed.into_data("search").unwrap_err(),
+            "GitHub GraphQL search query failed: Bad query"
+        );
+    }
+
     #[test]
     fn merge_preserves_viewer_authorship_when_requested_pr_overwrites() {
         let mut map: HashMap<String, Pr> = HashMap::new();
@@ -690,6 +1803,7 @@ mod tests {
             title: "A".into(),
             url: "url".into(),
             updated_at_unix: 0,
+            created_at_unix: None,
             last_commit_sha: None,
             ci_state: CiState::Success,
             ci_checks: Vec::new(),
@@ -698,7 +1812,24 @@ mod tests {
             mergeable: None,
             merge_state_status: None,
             is_viewer_author: true,
+            is_assignee: false,
+            is_mentioned: false,
+            is_unsubscribed: false,
             merge_blockers: None,
+            labels: Vec::new(),
+            review_requested_at_unix: None,
+            head_ref_name: None,
+            base_ref_name: None,
+            milestone: None,
+            project_status: None,
+            ci_from_previous_commit: false,
+            lines_changed: None,
+            last_force_push_at_unix: None,
+            last_reply_at_unix: None,
+            body_excerpt: None,
+            reviewers: Vec::new(),
+            comments: Vec::new(),
+            review_threads: Vec::new(),
         };
         let mut requested = authored.clone();
         requested.is_viewer_author = false;
@@ -711,53 +1842,92 @@ mod tests {
         assert!(pr.is_viewer_author, "viewer author flag should persist");
         assert!(matches!(pr.review_state, ReviewState::Requested));
     }
+
+    #[test]
+    fn assigned_and_mentioned_prs_are_added_or_flagged_once() {
+        let node = |number: i64| -> PullRequestNode {
+            serde_json::from_value(serde_json::json!({
+                "number": number,
+                "title": "T",
+                "url": "url",
+                "updatedAt": "2024-01-01T00:00:00Z",
+                "repository": {"name": "repo", "owner": {"login": "acme"}},
+                "author": {"login": "alice"}
+            }))
+            .unwrap()
+        };
+        let prs = merge_attention_nodes(
+            Vec::new(),
+            vec![node(1)],
+            vec![node(1), node(2)],
+            vec![node(2)],
+            vec![node(3)],
+            "me",
+        );
+        let by_key = |key: &str| prs.iter().find(|p| p.pr_key == key).unwrap();
+        assert_eq!(prs.len(), 3);
+        assert!(by_key("acme/repo#1").is_assignee);
+        assert!(matches!(
+            by_key("acme/repo#1").review_state,
+            ReviewState::TeamRequested
+        ));
+        assert!(by_key("acme/repo#2").is_assignee);
+        assert!(!by_key("acme/repo#3").is_assignee);
+        assert!(by_key("acme/repo#2").is_mentioned);
+        assert!(!by_key("acme/repo#1").is_mentioned);
+    }
 }
 
-pub async fn fetch_attention_prs(
-    octo: &Octocrab,
+/// Run a PR search (newest first) and collect results updated since `cutoff_ts`.
+async fn search_pull_requests(
+    client: &dyn GraphQlClient,
+    search_query: &str,
     cutoff_ts: i64,
-    include_team_requests: bool,
-) -> Result<Vec<Pr>, String> {
-    // Fetch authored PRs
-    let mut authored: Vec<PullRequestNode> = Vec::new();
+    what: &str,
+) -> Result<Vec<PullRequestNode>, String> {
+    #[derive(Debug, serde::Serialize)]
+    struct SearchVars<'a> {
+        page_size: i32,
+        cursor: Option<String>,
+        search_query: &'a str,
+    }
+
+    let mut out: Vec<PullRequestNode> = Vec::new();
     let mut cursor: Option<String> = None;
-    let mut viewer_login: Option<String> = None;
     loop {
-        let vars = PaginationVars {
+        let vars = SearchVars {
             page_size: 50,
             cursor: cursor.clone(),
+            search_query,
         };
         let payload = GraphQlPayload {
-            query: AUTHORED_QUERY,
+            query: SEARCH_QUERY,
             variables: vars,
         };
-        let resp: GraphQlResponse<AuthoredData> = octo
-            .graphql(&payload)
+        let resp: GraphQlResponse<SearchData> = post_graphql(client, &payload)
             .await
-            .map_err(|e| format!("GitHub GraphQL authored query failed: {e}"))?;
+            .map_err(|e| format!("GitHub GraphQL {what} query failed: {e}"))?;
+        let data = resp.into_data(what)?;
+        record_rate_limit(data.rate_limit.as_ref());
 
-        if viewer_login.is_none() {
-            viewer_login = Some(resp.data.viewer.login.clone());
-        }
-
-        if let Some(nodes) = resp.data.viewer.pull_requests.nodes {
-            // Order is updatedAt DESC, so we can stop paginating once this page crosses cutoff.
-            let mut keep = Vec::new();
+        if let Some(nodes) = data.search.nodes {
             let mut min_updated: Option<i64> = None;
             for n in nodes {
-                if let Some(u) = parse_github_datetime_to_unix(&n.updated_at) {
-                    min_updated = Some(min_updated.map(|m| m.min(u)).unwrap_or(u));
-                    if u >= cutoff_ts {
-                        keep.push(n);
+                if let Some(pr) = n.into_pull_request() {
+                    if let Some(u) = parse_github_datetime_to_unix(&pr.updated_at) {
+                        min_updated = Some(min_updated.map(|m| m.min(u)).unwrap_or(u));
+                        if u < cutoff_ts {
+                            continue;
+                        }
                     }
+                    out.push(pr);
                 }
             }
-            authored.extend(keep);
             if min_updated.is_some_and(|m| m < cutoff_ts) {
                 break;
             }
         }
-        let pi = resp.data.viewer.pull_requests.page_info;
+        let pi = data.search.page_info;
         if !pi.has_next_page {
             break;
         }
@@ -766,79 +1936,153 @@ pub async fn fetch_attention_prs(
             break;
         }
     }
+    Ok(out)
+}
 
-    let viewer_login = viewer_login.unwrap_or_else(|| "unknown".to_string());
-
-    // Fetch review-requested PRs
-    let cutoff_date = unix_to_ymd(cutoff_ts)
-        .map(|(y, m, d)| format!("{y:04}-{m:02}-{d:02}"))
-        .unwrap_or_else(|| "1970-01-01".to_string());
-    let search_query = format!(
-        "is:pr is:open review-requested:@me sort:updated-desc updated:>={}",
-        cutoff_date
-    );
-
-    let mut requested_nodes: Vec<PullRequestNode> = Vec::new();
-    let mut cursor: Option<String> = None;
-    loop {
-        #[derive(Debug, serde::Serialize)]
-        struct SearchVars {
-            page_size: i32,
-            cursor: Option<String>,
-            search_query: String,
-        }
-
-        let vars = SearchVars {
-            page_size: 50,
-            cursor: cursor.clone(),
-            search_query: search_query.clone(),
-        };
-        let payload = GraphQlPayload {
-            query: REVIEW_REQUESTED_QUERY,
-            variables: vars,
-        };
-        let resp: GraphQlResponse<SearchData> = octo
-            .graphql(&payload)
-            .await
-            .map_err(|e| format!("GitHub GraphQL review-requested query failed: {e}"))?;
+/// Fetch open PRs authored by the viewer or requesting their review, updated since `cutoff_ts`.
+///
+/// Team review requests are only kept when `include_team_requests` is set. With
+/// `include_discussions` (the `involves-me:discussion` scope), PRs the viewer commented on
+/// are included too. `mode` skips the viewer's own PRs (`reviewer`) or the review requests
+/// and discussions (`author`) entirely.
+pub async fn fetch_attention_prs(
+    client: &dyn GraphQlClient,
+    cutoff_ts: i64,
+    include_team_requests: bool,
+    include_discussions: bool,
+    mode: UsageMode,
+) -> Result<Vec<Pr>, String> {
+    // Fetch authored PRs (skipped in reviewer mode)
+    let mut authored: Vec<PullRequestNode> = Vec::new();
+    let mut viewer_login: Option<String> = None;
+    if mode.includes_authored() {
+        let mut cursor: Option<String> = None;
+        loop {
+            let vars = PaginationVars {
+                page_size: 50,
+                cursor: cursor.clone(),
+            };
+            let payload = GraphQlPayload {
+                query: AUTHORED_QUERY,
+                variables: vars,
+            };
+            let resp: GraphQlResponse<AuthoredData> = post_graphql(client, &payload)
+                .await
+                .map_err(|e| format!("GitHub GraphQL authored query failed: {e}"))?;
+            let data = resp.into_data("authored")?;
+            record_rate_limit(data.rate_limit.as_ref());
+
+            if viewer_login.is_none() {
+                viewer_login = Some(data.viewer.login.clone());
+            }
 
-        if let Some(nodes) = resp.data.search.nodes {
-            let mut min_updated: Option<i64> = None;
-            for n in nodes {
-                if let Some(pr) = n.into_pull_request() {
-                    if let Some(u) = parse_github_datetime_to_unix(&pr.updated_at) {
+            if let Some(nodes) = data.viewer.pull_requests.nodes {
+                // Order is updatedAt DESC, so we can stop paginating once this page crosses cutoff.
+                let mut keep = Vec::new();
+                let mut min_updated: Option<i64> = None;
+                for n in nodes {
+                    if let Some(u) = parse_github_datetime_to_unix(&n.updated_at) {
                         min_updated = Some(min_updated.map(|m| m.min(u)).unwrap_or(u));
-                        if u < cutoff_ts {
-                            continue;
+                        if u >= cutoff_ts {
+                            keep.push(n);
                         }
                     }
-                    // Only keep PRs where the viewer is explicitly requested as a User reviewer
-                    // (ignore team review requests).
-                    if include_team_requests || is_review_requested_by_user(&pr, &viewer_login) {
-                        requested_nodes.push(pr);
-                    }
+                }
+                authored.extend(keep);
+                if min_updated.is_some_and(|m| m < cutoff_ts) {
+                    break;
                 }
             }
-            if min_updated.is_some_and(|m| m < cutoff_ts) {
+            let pi = data.viewer.pull_requests.page_info;
+            if !pi.has_next_page {
+                break;
+            }
+            cursor = pi.end_cursor;
+            if cursor.is_none() {
                 break;
             }
-        }
-        let pi = resp.data.search.page_info;
-        if !pi.has_next_page {
-            break;
-        }
-        cursor = pi.end_cursor;
-        if cursor.is_none() {
-            break;
         }
     }
 
-    // Merge & dedupe into Pr list, applying requested-review state when applicable.
+    let viewer_login = match viewer_login {
+        Some(login) => login,
+        None if mode.includes_authored() => "unknown".to_string(),
+        None => fetch_viewer_login(client).await?,
+    };
+
+    // Fetch review-requested PRs
+    let cutoff_date = search_date(cutoff_ts);
+    let search_query = format!(
+        "is:pr is:open review-requested:@me sort:updated-desc updated:>={}",
+        cutoff_date
+    );
+    let requested_nodes: Vec<PullRequestNode> = if mode.includes_requested() {
+        search_pull_requests(client, &search_query, cutoff_ts, "review-requested")
+            .await?
+            .into_iter()
+            // Only keep PRs where the viewer is explicitly requested as a User reviewer
+            // (ignore team review requests).
+            .filter(|pr| include_team_requests || is_review_requested_by_user(pr, &viewer_login))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Other people's PRs the viewer is assigned to, even without a review request.
+    let assigned_nodes: Vec<PullRequestNode> = if mode.includes_requested() {
+        let search_query = format!(
+            "is:pr is:open assignee:@me -author:@me sort:updated-desc updated:>={cutoff_date}"
+        );
+        search_pull_requests(client, &search_query, cutoff_ts, "assigned").await?
+    } else {
+        Vec::new()
+    };
+
+    // PRs where someone @-mentioned the viewer.
+    let mentioned_nodes: Vec<PullRequestNode> = if mode.includes_requested() {
+        let search_query =
+            format!("is:pr is:open mentions:@me sort:updated-desc updated:>={cutoff_date}");
+        search_pull_requests(client, &search_query, cutoff_ts, "mentioned").await?
+    } else {
+        Vec::new()
+    };
+
+    // `involves-me:discussion`: other people's PRs the viewer commented on.
+    let discussion_nodes: Vec<PullRequestNode> = if include_discussions && mode.includes_requested()
+    {
+        let search_query = format!(
+            "is:pr is:open commenter:@me -author:@me sort:updated-desc updated:>={cutoff_date}"
+        );
+        search_pull_requests(client, &search_query, cutoff_ts, "discussion").await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(merge_attention_nodes(
+        authored,
+        requested_nodes,
+        assigned_nodes,
+        mentioned_nodes,
+        discussion_nodes,
+        &viewer_login,
+    ))
+}
+
+/// Merge and dedupe the authored, review-requested, assigned, mentioned and discussion
+/// results into PRs, applying requested-review state when applicable.
+fn merge_attention_nodes(
+    authored: Vec<PullRequestNode>,
+    requested_nodes: Vec<PullRequestNode>,
+    assigned_nodes: Vec<PullRequestNode>,
+    mentioned_nodes: Vec<PullRequestNode>,
+    discussion_nodes: Vec<PullRequestNode>,
+    viewer_login: &str,
+) -> Vec<Pr> {
     let mut by_key: HashMap<String, Pr> = HashMap::new();
 
     for node in authored {
-        let requested_user = is_review_requested_by_user(&node, &viewer_login);
-        if let Some(mut pr) = to_pr(node, requested_user, &viewer_login) {
+        let requested_user = is_review_requested_by_user(&node, viewer_login);
+        if let Some(mut pr) = to_pr(node, requested_user, viewer_login) {
             // Authored query is always "my PRs", so force the flag on even if GitHub omitted author.
             pr.is_viewer_author = true;
             merge_into(&mut by_key, pr);
@@ -846,10 +2090,207 @@ pub async fn fetch_attention_prs(
     }
 
     for node in requested_nodes {
-        if let Some(pr) = to_pr(node, true, &viewer_login) {
+        // The search also matches requests to the viewer's teams (`include_team_requests`).
+        let requested_user = is_review_requested_by_user(&node, viewer_login);
+        if let Some(mut pr) = to_pr(node, true, viewer_login) {
+            if !requested_user {
+                pr.review_state = ReviewState::TeamRequested;
+            }
             merge_into(&mut by_key, pr);
         }
     }
 
-    Ok(by_key.into_values().collect())
+    // Assigned PRs are added, or just flagged when another query already found them.
+    for node in assigned_nodes {
+        let requested_user = is_review_requested_by_user(&node, viewer_login);
+        if let Some(pr) = to_pr(node, requested_user, viewer_login) {
+            by_key.entry(pr.pr_key.clone()).or_insert(pr).is_assignee = true;
+        }
+    }
+
+    // Same for mentions.
+    for node in mentioned_nodes {
+        let requested_user = is_review_requested_by_user(&node, viewer_login);
+        if let Some(pr) = to_pr(node, requested_user, viewer_login) {
+            by_key.entry(pr.pr_key.clone()).or_insert(pr).is_mentioned = true;
+        }
+    }
+
+    // Discussion-only PRs never override what the authored/requested queries found.
+    for node in discussion_nodes {
+        let requested_user = is_review_requested_by_user(&node, viewer_login);
+        if let Some(pr) = to_pr(node, requested_user, viewer_login) {
+            by_key.entry(pr.pr_key.clone()).or_insert(pr);
+        }
+    }
+
+    by_key.into_values().collect()
+}
+
+async fn query_viewer_login(octo: &Octocrab) -> Result<String, octocrab::Error> {
+    #[derive(Debug, serde::Deserialize)]
+    struct ViewerData {
+        viewer: ViewerLogin,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct ViewerResponse {
+        data: ViewerData,
+    }
+    let resp: ViewerResponse = octo
+        .graphql(&serde_json::json!({ "query": "query { viewer { login } }" }))
+        .await?;
+    Ok(resp.data.viewer.login)
+}
+
+/// The viewer's login, for queries that do not return it alongside the PRs.
+async fn fetch_viewer_login(client: &dyn GraphQlClient) -> Result<String, String> {
+    #[derive(Debug, serde::Deserialize)]
+    struct ViewerData {
+        viewer: ViewerLogin,
+    }
+    let payload = serde_json::json!({ "query": "query { viewer { login } }" });
+    let resp: GraphQlResponse<ViewerData> = post_graphql(client, &payload)
+        .await
+        .map_err(|e| format!("GitHub GraphQL viewer query failed: {e}"))?;
+    Ok(resp.into_data("viewer")?.viewer.login)
+}
+
+/// Why the startup token check failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// GitHub answered and refused the token; refreshing cannot work.
+    Rejected(String),
+    /// GitHub could not be asked (offline, DNS, timeouts); the token may well be fine.
+    Unreachable(String),
+}
+
+/// Check the token with the cheapest authenticated query. Returns the viewer's login.
+pub async fn validate_token(octo: &Octocrab) -> Result<String, TokenError> {
+    match query_viewer_login(octo).await {
+        Ok(login) => Ok(login),
+        Err(octocrab::Error::GitHub { source, .. }) => Err(TokenError::Rejected(
+            describe_token_error(source.status_code.as_u16(), &source.message),
+        )),
+        Err(e) => Err(TokenError::Unreachable(format!(
+            "Could not reach GitHub: {e}"
+        ))),
+    }
+}
+
+/// Turn a rejected token check into an actionable one-line message.
+fn describe_token_error(status: u16, message: &str) -> String {
+    let lower = message.to_lowercase();
+    if lower.contains("saml") || lower.contains("sso") {
+        format!(
+            "GitHub token is not authorized for your organization's SSO ({message}); authorize it under Settings > Developer settings > Tokens > Configure SSO"
+        )
+    } else if status == 401 {
+        format!(
+            "GitHub rejected the token ({message}); check NEEDLE_GITHUB_TOKEN / GITHUB_TOKEN, it may be mistyped, expired or revoked"
+        )
+    } else if status == 403 {
+        format!("GitHub token lacks access ({message}); it needs the `repo` scope")
+    } else {
+        format!("GitHub token check failed ({status}): {message}")
+    }
+}
+
+fn search_date(ts: i64) -> String {
+    unix_to_ymd(ts)
+        .map(|(y, m, d)| format!("{y:04}-{m:02}-{d:02}"))
+        .unwrap_or_else(|| "1970-01-01".to_string())
+}
+
+/// Like [`fetch_attention_prs`], but only PRs last updated in `[from_ts, to_ts)`.
+///
+/// Everything goes through search (`updated:FROM..TO`), so an older window costs a few
+/// small queries instead of paging through every newer PR first.
+pub async fn fetch_attention_prs_between(
+    client: &dyn GraphQlClient,
+    from_ts: i64,
+    to_ts: i64,
+    include_team_requests: bool,
+    include_discussions: bool,
+    mode: UsageMode,
+) -> Result<Vec<Pr>, String> {
+    let viewer_login = fetch_viewer_login(client).await?;
+
+    let range = format!("updated:{}..{}", search_date(from_ts), search_date(to_ts));
+    let in_window = |nodes: Vec<PullRequestNode>| -> Vec<PullRequestNode> {
+        nodes
+            .into_iter()
+            .filter(|n| parse_github_datetime_to_unix(&n.updated_at).is_some_and(|u| u < to_ts))
+            .collect()
+    };
+    let search = |q: String, what: &'static str| async move {
+        search_pull_requests(client, &q, from_ts, what).await
+    };
+
+    let authored = if mode.includes_authored() {
+        in_window(
+            search(
+                format!("is:pr is:open author:@me sort:updated-desc {range}"),
+                "authored",
+            )
+            .await?,
+        )
+    } else {
+        Vec::new()
+    };
+    let requested = if mode.includes_requested() {
+        in_window(
+            search(
+                format!("is:pr is:open review-requested:@me sort:updated-desc {range}"),
+                "review-requested",
+            )
+            .await?,
+        )
+        .into_iter()
+        .filter(|pr| include_team_requests || is_review_requested_by_user(pr, &viewer_login))
+        .collect()
+    } else {
+        Vec::new()
+    };
+    let assigned = if mode.includes_requested() {
+        in_window(
+            search(
+                format!("is:pr is:open assignee:@me -author:@me sort:updated-desc {range}"),
+                "assigned",
+            )
+            .await?,
+        )
+    } else {
+        Vec::new()
+    };
+    let mentioned = if mode.includes_requested() {
+        in_window(
+            search(
+                format!("is:pr is:open mentions:@me sort:updated-desc {range}"),
+                "mentioned",
+            )
+            .await?,
+        )
+    } else {
+        Vec::new()
+    };
+    let discussion = if include_discussions && mode.includes_requested() {
+        in_window(
+            search(
+                format!("is:pr is:open commenter:@me -author:@me sort:updated-desc {range}"),
+                "discussion",
+            )
+            .await?,
+        )
+    } else {
+        Vec::new()
+    };
+
+    Ok(merge_attention_nodes(
+        authored,
+        requested,
+        assigned,
+        mentioned,
+        discussion,
+        &viewer_login,
+    ))
 }
@@ -0,0 +1,176 @@
+//! Canned GitHub GraphQL responses for tests, so the refresh pipeline (fetch → score →
+//! persist) runs end to end without network access.
+//!
+//! [`MockGitHub`] stands in for [`octocrab::Octocrab`] behind
+//! [`GitHubProvider::with_graphql`](crate::provider::GitHubProvider::with_graphql); the
+//! builders below produce response bodies shaped like GitHub's.
+
+use crate::github::GraphQlClient;
+use crate::provider::BoxFuture;
+use serde_json::{Value, json};
+use std::sync::Mutex;
+
+/// A [`GraphQlClient`] answering from a queue of canned responses.
+///
+/// Each request takes the first queued response whose marker occurs in the request (its
+/// query text or variables, e.g. `"review-requested:@me"` or a pagination cursor). A
+/// request nothing matches fails, so tests notice queries they did not expect.
+#[derive(Default)]
+pub(crate) struct MockGitHub {
+    queue: Mutex<Vec<(String, Result<Value, String>)>>,
+    /// Every request received, oldest first.
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockGitHub {
+    /// Answer the next request containing `marker` with `body`.
+    pub(crate) fn respond(self, marker: &str, body: Value) -> Self {
+        self.push(marker, Ok(body))
+    }
+
+    /// Fail the next request containing `marker` the way a transport error would (e.g.
+    /// an HTTP 403 for a secondary rate limit).
+    pub(crate) fn fail(self, marker: &str, error: &str) -> Self {
+        self.push(marker, Err(error.to_string()))
+    }
+
+    fn push(self, marker: &str, response: Result<Value, String>) -> Self {
+        self.queue
+            .lock()
+            .unwrap()
+            .push((marker.to_string(), response));
+        self
+    }
+
+    /// How many requests contained `marker`.
+    pub(crate) fn requests_with(&self, marker: &str) -> usize {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().filter(|r| r.contains(marker)).count()
+    }
+
+    /// Canned responses nobody asked for.
+    pub(crate) fn unused(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+}
+
+impl GraphQlClient for MockGitHub {
+    fn graphql(&self, payload: Value) -> BoxFuture<'_, Result<Value, String>> {
+        let request = payload.to_string();
+        let response = {
+            let mut queue = self.queue.lock().unwrap();
+            match queue
+                .iter()
+                .position(|(marker, _)| request.contains(marker))
+            {
+                Some(i) => queue.remove(i).1,
+                None => Err(format!("no canned response for {request}")),
+            }
+        };
+        self.requests.lock().unwrap().push(request);
+        Box::pin(async move { response })
+    }
+}
+
+/// Plenty of API budget left.
+fn rate_limit() -> Value {
+    json!({ "limit": 5000, "remaining": 4990, "resetAt": "2030-01-01T00:00:00Z" })
+}
+
+fn page_info(next_cursor: Option<&str>) -> Value {
+    json!({ "hasNextPage": next_cursor.is_some(), "endCursor": next_cursor })
+}
+
+/// An open PR in `acme/{repo}` by alice, with CI in `ci_state` (`SUCCESS`, `FAILURE`, ...).
+pub(crate) fn pr_node(repo: &str, number: i64, updated_at: &str, ci_state: &str) -> Value {
+    json!({
+        "number": number,
+        "title": format!("Change {number}"),
+        "url": format!("https://github.com/acme/{repo}/pull/{number}"),
+        "updatedAt": updated_at,
+        "createdAt": updated_at,
+        "repository": { "name": repo, "owner": { "login": "acme" } },
+        "author": { "login": "alice" },
+        "headRefOid": format!("sha{number}"),
+        "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "state": ci_state } } }] }
+    })
+}
+
+/// `node` with a review requested from `login`.
+pub(crate) fn requesting(mut node: Value, login: &str) -> Value {
+    node["reviewRequests"] = json!({
+        "nodes": [{ "requestedReviewer": { "__typename": "User", "login": login } }]
+    });
+    node
+}
+
+/// One page of the viewer's own PRs (the authored query), viewer `me`.
+pub(crate) fn authored_page(nodes: Vec<Value>, next_cursor: Option<&str>) -> Value {
+    json!({
+        "data": {
+            "rateLimit": rate_limit(),
+            "viewer": {
+                "login": "me",
+                "pullRequests": { "pageInfo": page_info(next_cursor), "nodes": nodes }
+            }
+        }
+    })
+}
+
+/// One page of a PR search (review requests, assignments, mentions).
+pub(crate) fn search_page(nodes: Vec<Value>, next_cursor: Option<&str>) -> Value {
+    let nodes: Vec<Value> = nodes
+        .into_iter()
+        .map(|mut n| {
+            n["__typename"] = json!("PullRequest");
+            n
+        })
+        .collect();
+    json!({
+        "data": {
+            "rateLimit": rate_limit(),
+            "search": { "pageInfo": page_info(next_cursor), "nodes": nodes }
+        }
+    })
+}
+
+/// The single PR asked about (the PR query behind `needle add` and per-PR refreshes), viewer `me`.
+pub(crate) fn single_pr(node: Value) -> Value {
+    json!({
+        "data": {
+            "rateLimit": rate_limit(),
+            "viewer": { "login": "me" },
+            "repository": { "pullRequest": node }
+        }
+    })
+}
+
+/// The default branch of the repository asked about, with CI in `ci_state`.
+pub(crate) fn default_branch(ci_state: &str) -> Value {
+    json!({
+        "data": {
+            "rateLimit": rate_limit(),
+            "repository": {
+                "defaultBranchRef": { "target": { "statusCheckRollup": { "state": ci_state } } }
+            }
+        }
+    })
+}
+
+/// What GitHub answers when the token lacks the `read:project` scope a query needs.
+pub(crate) fn projects_refused() -> Value {
+    json!({
+        "errors": [{
+            "type": "INSUFFICIENT_SCOPES",
+            "message": "Your token has not been granted the required scopes to execute this query. The 'title' field requires one of the following scopes: ['read:project'], but your token has only been granted the: ['repo'] scopes."
+        }]
+    })
+}
+
+/// What GitHub answers once the GraphQL budget is spent: no data, one error.
+pub(crate) fn rate_limited() -> Value {
+    json!({
+        "data": null,
+        "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded for user ID 1." }]
+    })
+}
@@ -0,0 +1,475 @@
+//! GitHub write actions. Everything else in needle only reads.
+//!
+//! Comments are posted to the PR conversation (not as review comments on a line).
+//! Merging goes through the REST API and pins the head SHA needle last saw, so a push
+//! that lands while the merge picker is open is never merged unseen. Updating a branch
+//! (merging its base into it) pins the head SHA the same way. Drafts are marked ready
+//! for review, and back, through the GraphQL mutations. Declining a review request
+//! removes the viewer from the PR's requested reviewers. Approving submits an approving
+//! review for the head SHA needle last saw. Marking a PR read clears its unread thread in
+//! the viewer's github.com inbox.
+
+use crate::model::{CiState, Pr};
+use octocrab::Octocrab;
+
+/// How to merge a PR (the three options GitHub offers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub const ALL: [MergeMethod; 3] =
+        [MergeMethod::Merge, MergeMethod::Squash, MergeMethod::Rebase];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        }
+    }
+}
+
+impl From<MergeMethod> for octocrab::params::pulls::MergeMethod {
+    fn from(m: MergeMethod) -> Self {
+        match m {
+            MergeMethod::Merge => octocrab::params::pulls::MergeMethod::Merge,
+            MergeMethod::Squash => octocrab::params::pulls::MergeMethod::Squash,
+            MergeMethod::Rebase => octocrab::params::pulls::MergeMethod::Rebase,
+        }
+    }
+}
+
+/// `Ok` when needle offers to merge the PR: not a draft, mergeable, and CI green.
+///
+/// Branch protection (required reviews, checks needle cannot see) is still enforced by
+/// GitHub and reported by [`merge_pr`].
+pub fn merge_readiness(pr: &Pr) -> Result<(), String> {
+    if pr.is_draft {
+        return Err(format!("{} is a draft", pr.pr_key));
+    }
+    if pr.mergeable.as_deref() != Some("MERGEABLE") {
+        let state = pr.mergeable.as_deref().unwrap_or("UNKNOWN").to_lowercase();
+        return Err(format!("{} is not mergeable ({state})", pr.pr_key));
+    }
+    if !matches!(pr.ci_state, CiState::Success) {
+        return Err(format!("{} needs green CI to merge", pr.pr_key));
+    }
+    Ok(())
+}
+
+/// Merge `pr` with `method`, requiring the head to still be at the last seen commit.
+pub async fn merge_pr(octo: &Octocrab, pr: &Pr, method: MergeMethod) -> Result<(), String> {
+    let number = u64::try_from(pr.number).map_err(|_| format!("Bad PR number: {}", pr.number))?;
+    let pulls = octo.pulls(pr.owner.as_str(), pr.repo.as_str());
+    let mut req = pulls.merge(number).method(method);
+    if let Some(sha) = &pr.last_commit_sha {
+        req = req.sha(sha.as_str());
+    }
+    match req.send().await {
+        Ok(_) => Ok(()),
+        Err(octocrab::Error::GitHub { source, .. }) => Err(describe_merge_error(
+            &pr.pr_key,
+            source.status_code.as_u16(),
+            &source.message,
+        )),
+        Err(e) => Err(format!("Merging {} failed: {e}", pr.pr_key)),
+    }
+}
+
+/// `Ok` when needle offers to update the PR branch: GitHub reports it behind its base.
+pub fn update_branch_readiness(pr: &Pr) -> Result<(), String> {
+    let behind = pr
+        .merge_state_status
+        .as_deref()
+        .is_some_and(|s| s.eq_ignore_ascii_case("BEHIND"));
+    if !behind {
+        return Err(format!("{} is not behind its base branch", pr.pr_key));
+    }
+    Ok(())
+}
+
+#[derive(Debug, serde::Serialize)]
+struct GraphQlPayload<V> {
+    query: &'static str,
+    variables: V,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+impl<T> GraphQlResponse<T> {
+    /// The data, or every error message when GitHub reported any.
+    fn into_result(self) -> Result<Option<T>, String> {
+        if self.errors.is_empty() {
+            return Ok(self.data);
+        }
+        let messages: Vec<&str> = self.errors.iter().map(|e| e.message.as_str()).collect();
+        Err(messages.join("; "))
+    }
+}
+
+const PR_ID_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) { id }
+  }
+}
+"#;
+
+const READY_FOR_REVIEW_MUTATION: &str = r#"
+mutation($id: ID!) {
+  markPullRequestReadyForReview(input: { pullRequestId: $id }) {
+    pullRequest { isDraft }
+  }
+}
+"#;
+
+const CONVERT_TO_DRAFT_MUTATION: &str = r#"
+mutation($id: ID!) {
+  convertPullRequestToDraft(input: { pullRequestId: $id }) {
+    pullRequest { isDraft }
+  }
+}
+"#;
+
+const UPDATE_BRANCH_MUTATION: &str = r#"
+mutation($id: ID!, $sha: GitObjectID) {
+  updatePullRequestBranch(input: { pullRequestId: $id, expectedHeadOid: $sha }) {
+    pullRequest { number }
+  }
+}
+"#;
+
+/// The GraphQL node ID of `pr`, which the mutations take. `fail` words transport errors.
+async fn pull_request_id(
+    octo: &Octocrab,
+    pr: &Pr,
+    fail: impl Fn(String) -> String,
+) -> Result<String, String> {
+    #[derive(Debug, serde::Deserialize)]
+    struct PullRequest {
+        id: String,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct Repository {
+        #[serde(rename = "pullRequest")]
+        pull_request: Option<PullRequest>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct IdData {
+        repository: Option<Repository>,
+    }
+
+    let payload = GraphQlPayload {
+        query: PR_ID_QUERY,
+        variables: serde_json::json!({
+            "owner": pr.owner,
+            "name": pr.repo,
+            "number": pr.number,
+        }),
+    };
+    let resp: GraphQlResponse<IdData> = octo
+        .graphql(&payload)
+        .await
+        .map_err(|e| fail(e.to_string()))?;
+    resp.into_result()
+        .map_err(&fail)?
+        .and_then(|d| d.repository)
+        .and_then(|r| r.pull_request)
+        .map(|p| p.id)
+        .ok_or_else(|| format!("PR {} not found", pr.pr_key))
+}
+
+/// The draft state toggling would give `pr`: `Ok(true)` to convert it to a draft,
+/// `Ok(false)` to mark it ready for review. Offered for the viewer's own PRs only.
+pub fn draft_toggle(pr: &Pr) -> Result<bool, String> {
+    if !pr.is_viewer_author {
+        return Err(format!(
+            "{} is not yours; only its author marks it ready or draft",
+            pr.pr_key
+        ));
+    }
+    Ok(!pr.is_draft)
+}
+
+/// Convert `pr` to a draft (`draft = true`) or mark it ready for review.
+pub async fn set_draft(octo: &Octocrab, pr: &Pr, draft: bool) -> Result<(), String> {
+    let (query, action) = if draft {
+        (
+            CONVERT_TO_DRAFT_MUTATION,
+            format!("Converting {} to a draft", pr.pr_key),
+        )
+    } else {
+        (
+            READY_FOR_REVIEW_MUTATION,
+            format!("Marking {} ready for review", pr.pr_key),
+        )
+    };
+    let fail = |e: String| format!("{action} failed: {e}");
+    let id = pull_request_id(octo, pr, fail).await?;
+    let payload = GraphQlPayload {
+        query,
+        variables: serde_json::json!({ "id": id }),
+    };
+    let resp: GraphQlResponse<serde::de::IgnoredAny> = octo
+        .graphql(&payload)
+        .await
+        .map_err(|e| fail(e.to_string()))?;
+    resp.into_result().map(|_| ()).map_err(fail)
+}
+
+/// Remove `login` from the requested reviewers of `pr` (declining the review request).
+pub async fn decline_review(octo: &Octocrab, pr: &Pr, login: &str) -> Result<(), String> {
+    let route = format!(
+        "/repos/{}/{}/pulls/{}/requested_reviewers",
+        pr.owner, pr.repo, pr.number
+    );
+    let body = serde_json::json!({ "reviewers": [login] });
+    octo.delete::<serde::de::IgnoredAny, _, _>(route, Some(&body))
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Declining the review of {} failed: {e}", pr.pr_key))
+}
+
+/// `Ok` when needle offers to approve the PR: GitHub refuses approvals of your own PRs.
+pub fn approve_readiness(pr: &Pr) -> Result<(), String> {
+    if pr.is_viewer_author {
+        return Err(format!("{} is your own PR", pr.pr_key));
+    }
+    Ok(())
+}
+
+/// Approve `pr`. The review is pinned to the last seen commit, so a push that landed since
+/// is not approved unseen.
+pub async fn approve_pr(octo: &Octocrab, pr: &Pr) -> Result<(), String> {
+    let route = format!(
+        "/repos/{}/{}/pulls/{}/reviews",
+        pr.owner, pr.repo, pr.number
+    );
+    let mut body = serde_json::json!({ "event": "APPROVE" });
+    if let Some(sha) = &pr.last_commit_sha {
+        body["commit_id"] = serde_json::json!(sha);
+    }
+    octo.post::<_, serde::de::IgnoredAny>(route, Some(&body))
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Approving {} failed: {e}", pr.pr_key))
+}
+
+/// An unread thread in the viewer's GitHub inbox.
+#[derive(Debug, serde::Deserialize)]
+struct InboxThread {
+    id: String,
+    subject: InboxSubject,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct InboxSubject {
+    #[serde(rename = "type")]
+    kind: String,
+    url: Option<String>,
+}
+
+/// The id of the unread thread about PR `number`, if any. Threads point at the PR's
+/// REST URL (`…/repos/acme/api/pulls/42`).
+fn pr_thread_id(threads: &[InboxThread], number: i64) -> Option<u64> {
+    let suffix = format!("/pulls/{number}");
+    threads
+        .iter()
+        .find(|t| {
+            t.subject.kind == "PullRequest"
+                && t.subject
+                    .url
+                    .as_deref()
+                    .is_some_and(|u| u.ends_with(&suffix))
+        })
+        .and_then(|t| t.id.parse().ok())
+}
+
+/// Mark the viewer's unread inbox thread about `pr` as read. Nothing to do when the PR
+/// has no unread thread.
+///
+/// GitHub only offers this to classic tokens (with the `notifications` or `repo` scope).
+pub async fn mark_pr_read(octo: &Octocrab, pr: &Pr) -> Result<(), String> {
+    let fail = |e: String| format!("Marking {} read on GitHub failed: {e}", pr.pr_key);
+    let route = format!("/repos/{}/{}/notifications", pr.owner, pr.repo);
+    let threads: Vec<InboxThread> = octo
+        .get(route, Some(&serde_json::json!({ "per_page": 50 })))
+        .await
+        .map_err(|e| fail(e.to_string()))?;
+    let Some(id) = pr_thread_id(&threads, pr.number) else {
+        return Ok(());
+    };
+    octo.activity()
+        .notifications()
+        .mark_as_read(id.into())
+        .await
+        .map_err(|e| fail(e.to_string()))
+}
+
+/// Merge the base branch into the head branch of `pr` (GitHub's "Update branch"),
+/// requiring the head to still be at the last seen commit.
+pub async fn update_branch(octo: &Octocrab, pr: &Pr) -> Result<(), String> {
+    let fail = |e: String| format!("Updating the branch of {} failed: {e}", pr.pr_key);
+    let id = pull_request_id(octo, pr, fail).await?;
+    let payload = GraphQlPayload {
+        query: UPDATE_BRANCH_MUTATION,
+        variables: serde_json::json!({ "id": id, "sha": pr.last_commit_sha }),
+    };
+    let resp: GraphQlResponse<serde::de::IgnoredAny> = octo
+        .graphql(&payload)
+        .await
+        .map_err(|e| fail(e.to_string()))?;
+    resp.into_result()
+        .map(|_| ())
+        .map_err(|e| describe_update_error(&pr.pr_key, &e))
+}
+
+/// Turn an update-branch error into a one-line message for the footer.
+fn describe_update_error(pr_key: &str, message: &str) -> String {
+    let lower = message.to_lowercase();
+    if lower.contains("expected head") || lower.contains("head sha") {
+        format!("{pr_key} has new commits since the last refresh; refresh and retry")
+    } else if lower.contains("conflict") {
+        format!("{pr_key} conflicts with its base branch; update it locally")
+    } else {
+        format!("Updating the branch of {pr_key} failed: {message}")
+    }
+}
+
+/// Post `body` as a comment on the PR conversation.
+pub async fn post_comment(octo: &Octocrab, pr: &Pr, body: &str) -> Result<(), String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err("Comment is empty".to_string());
+    }
+    let number = u64::try_from(pr.number).map_err(|_| format!("Bad PR number: {}", pr.number))?;
+    octo.issues(pr.owner.as_str(), pr.repo.as_str())
+        .create_comment(number, body)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Commenting on {} failed: {e}", pr.pr_key))
+}
+
+/// Turn a merge API error into a one-line message for the footer.
+fn describe_merge_error(pr_key: &str, status: u16, message: &str) -> String {
+    match status {
+        // Branch protection: required reviews/checks, merge queue, disallowed method...
+        405 => format!("{pr_key} is blocked by branch protection: {message}"),
+        409 => format!("{pr_key} has new commits since the last refresh; refresh and retry"),
+        403 | 404 => format!("No permission to merge {pr_key}: {message}"),
+        _ => format!("Merging {pr_key} failed ({status}): {message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+
+    #[test]
+    fn readiness_requires_mergeable_green_non_draft() {
+        let mut pr = generate_demo_prs(1_700_000_000, 0).remove(0);
+        pr.is_draft = false;
+        pr.mergeable = Some("MERGEABLE".to_string());
+        pr.ci_state = CiState::Success;
+        assert_eq!(merge_readiness(&pr), Ok(()));
+
+        let mut running = pr.clone();
+        running.ci_state = CiState::Running;
+        assert!(merge_readiness(&running).unwrap_err().contains("green CI"));
+
+        let mut conflicting = pr.clone();
+        conflicting.mergeable = Some("CONFLICTING".to_string());
+        assert!(
+            merge_readiness(&conflicting)
+                .unwrap_err()
+                .contains("not mergeable (conflicting)")
+        );
+
+        let mut draft = pr.clone();
+        draft.is_draft = true;
+        assert!(merge_readiness(&draft).is_err());
+    }
+
+    #[test]
+    fn branch_updates_are_offered_when_behind_and_errors_spelled_out() {
+        let mut pr = generate_demo_prs(1_700_000_000, 0).remove(0);
+        pr.merge_state_status = Some("BEHIND".to_string());
+        assert_eq!(update_branch_readiness(&pr), Ok(()));
+        pr.merge_state_status = Some("CLEAN".to_string());
+        assert!(
+            update_branch_readiness(&pr)
+                .unwrap_err()
+                .contains("not behind its base")
+        );
+
+        assert!(
+            describe_update_error(
+                "acme/api#7",
+                "expected head sha didn't match current head ref."
+            )
+            .contains("refresh and retry")
+        );
+        assert!(describe_update_error("acme/api#7", "Merge conflict").contains("locally"));
+        assert_eq!(
+            describe_update_error("acme/api#7", "Resource not accessible by integration"),
+            "Updating the branch of acme/api#7 failed: Resource not accessible by integration"
+        );
+    }
+
+    #[test]
+    fn only_my_prs_toggle_between_draft_and_ready() {
+        let mut pr = generate_demo_prs(1_700_000_000, 0).remove(0);
+        pr.is_viewer_author = true;
+        pr.is_draft = true;
+        assert_eq!(draft_toggle(&pr), Ok(false));
+        pr.is_draft = false;
+        assert_eq!(draft_toggle(&pr), Ok(true));
+
+        pr.is_viewer_author = false;
+        assert!(draft_toggle(&pr).unwrap_err().contains("is not yours"));
+    }
+
+    #[test]
+    fn inbox_threads_are_matched_to_the_pr() {
+        let threads: Vec<InboxThread> = serde_json::from_value(serde_json::json!([
+            { "id": "11", "subject": { "type": "Issue", "url": "https://api.github.com/repos/acme/api/issues/42" } },
+            { "id": "12", "subject": { "type": "PullRequest", "url": "https://api.github.com/repos/acme/api/pulls/420" } },
+            { "id": "13", "subject": { "type": "PullRequest", "url": "https://api.github.com/repos/acme/api/pulls/42" } },
+            { "id": "14", "subject": { "type": "Release", "url": null } }
+        ]))
+        .unwrap();
+        assert_eq!(pr_thread_id(&threads, 42), Some(13));
+        assert_eq!(pr_thread_id(&threads, 7), None);
+    }
+
+    #[test]
+    fn branch_protection_errors_are_spelled_out() {
+        let msg = describe_merge_error(
+            "acme/api#7",
+            405,
+            "At least 1 approving review is required by reviewers with write access.",
+        );
+        assert_eq!(
+            msg,
+            "acme/api#7 is blocked by branch protection: At least 1 approving review is required by reviewers with write access."
+        );
+        assert!(
+            describe_merge_error("acme/api#7", 409, "Head branch was modified").contains("refresh")
+        );
+    }
+}
@@ -0,0 +1,949 @@
+//! GitLab GraphQL fetching of the viewer's merge requests (`provider = "gitlab"`).
+//!
+//! Merge requests are mapped onto the same [`Pr`] model as GitHub pull requests:
+//! `owner` is the project's namespace (`group/subgroup`), `repo` the project path and
+//! `number` the merge request IID, so keys read `group/project#12`.
+
+use crate::github::{COMMENT_BODY_CHARS, CONVERSATION_COMMENTS, body_excerpt};
+use crate::model::{
+    CiCheck, CiCheckState, CiState, Comment, MergeBlockers, Pr, ReviewState, ReviewThread,
+    Reviewer, UsageMode,
+};
+use crate::provider::{BoxFuture, Provider};
+use crate::timeutil::parse_github_datetime_to_unix;
+use chrono::DateTime;
+use octocrab::Octocrab;
+use std::collections::HashMap;
+
+/// Instance used when `gitlab_url` is not set.
+pub const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
+
+const PAGE_SIZE: i32 = 20;
+/// Safety cap on pages per list; the cutoff normally ends paging much earlier.
+const MAX_PAGES: usize = 10;
+
+/// GitLab through its GraphQL API (gitlab.com or a self-managed instance).
+#[derive(Clone)]
+pub struct GitLabProvider {
+    client: Octocrab,
+    base_url: String,
+}
+
+impl GitLabProvider {
+    /// A client for `base_url` (e.g. `https://gitlab.example.com`) using a personal access
+    /// token with the `read_api` scope.
+    pub fn new(base_url: &str, token: String) -> Result<Self, String> {
+        let client = Octocrab::builder()
+            .personal_token(token)
+            .build()
+            .map_err(|e| format!("Failed to init GitLab client: {e}"))?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn graphql<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, String> {
+        let url = format!("{}/api/graphql", self.base_url);
+        let payload = serde_json::json!({ "query": query, "variables": variables });
+        let resp: GraphQlResponse<T> = self
+            .client
+            .post(&url, Some(&payload))
+            .await
+            .map_err(|e| format!("GitLab GraphQL request to {url} failed: {e}"))?;
+        if let Some(errors) = resp.errors.filter(|e| !e.is_empty()) {
+            let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            return Err(format!("GitLab GraphQL error: {}", messages.join("; ")));
+        }
+        resp.data
+            .ok_or_else(|| "GitLab GraphQL response had no data".to_string())
+    }
+
+    async fn current_username(&self) -> Result<String, String> {
+        #[derive(serde::Deserialize)]
+        struct Data {
+            #[serde(rename = "currentUser")]
+            current_user: Option<UserNode>,
+        }
+        let data: Data = self
+            .graphql("query { currentUser { username } }", serde_json::json!({}))
+            .await?;
+        data.current_user
+            .map(|u| u.username)
+            .ok_or_else(|| "GitLab token was rejected (no current user); check that it is valid and has the `read_api` scope".to_string())
+    }
+
+    /// One list (`authoredMergeRequests`, `reviewRequestedMergeRequests` or
+    /// `assignedMergeRequests`), newest first.
+    async fn list(&self, field: &str, cutoff_ts: i64) -> Result<Vec<MergeRequestNode>, String> {
+        #[derive(serde::Deserialize)]
+        struct Data {
+            #[serde(rename = "currentUser")]
+            current_user: Option<HashMap<String, MergeRequestConnection>>,
+        }
+        let query = list_query(field);
+        let since = DateTime::from_timestamp(cutoff_ts.max(0), 0)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_default();
+        let mut out = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let data: Data = self
+                .graphql(
+                    &query,
+                    serde_json::json!({ "since": since, "first": PAGE_SIZE, "after": cursor }),
+                )
+                .await?;
+            let Some(conn) = data.current_user.and_then(|mut u| u.remove(field)) else {
+                break;
+            };
+            out.extend(conn.nodes.into_iter().flatten());
+            match conn.page_info {
+                Some(PageInfo {
+                    has_next_page: true,
+                    end_cursor: Some(next),
+                }) => cursor = Some(next),
+                _ => break,
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Provider for GitLabProvider {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    /// GitLab has no team review requests, "commented on" or mentions search, so
+    /// `include_team_requests` and `include_discussions` have no effect here.
+    fn fetch_attention_prs(
+        &self,
+        cutoff_ts: i64,
+        _include_team_requests: bool,
+        _include_discussions: bool,
+        mode: UsageMode,
+    ) -> BoxFuture<'_, Result<Vec<Pr>, String>> {
+        Box::pin(async move {
+            let viewer = self.current_username().await?;
+            let authored = if mode.includes_authored() {
+                self.list("authoredMergeRequests", cutoff_ts).await?
+            } else {
+                Vec::new()
+            };
+            let requested = if mode.includes_requested() {
+                self.list("reviewRequestedMergeRequests", cutoff_ts).await?
+            } else {
+                Vec::new()
+            };
+            let assigned = if mode.includes_requested() {
+                self.list("assignedMergeRequests", cutoff_ts).await?
+            } else {
+                Vec::new()
+            };
+
+            let mut map: HashMap<String, Pr> = HashMap::new();
+            for node in authored.into_iter().chain(requested) {
+                let Some(pr) = to_pr(node, &viewer, &self.base_url) else {
+                    continue;
+                };
+                if pr.updated_at_unix >= cutoff_ts {
+                    map.insert(pr.pr_key.clone(), pr);
+                }
+            }
+            // Assigned MRs are added, or just flagged when another list already found them.
+            // The viewer's own MRs are often self-assigned, so those are left alone.
+            for node in assigned {
+                let Some(pr) = to_pr(node, &viewer, &self.base_url) else {
+                    continue;
+                };
+                if pr.updated_at_unix >= cutoff_ts && !pr.is_viewer_author {
+                    map.entry(pr.pr_key.clone()).or_insert(pr).is_assignee = true;
+                }
+            }
+            let mut prs: Vec<Pr> = map.into_values().collect();
+            prs.sort_by_key(|p| std::cmp::Reverse(p.updated_at_unix));
+            Ok(prs)
+        })
+    }
+
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<Pr, String>> {
+        Box::pin(async move {
+            #[derive(serde::Deserialize)]
+            struct ProjectNode {
+                #[serde(rename = "mergeRequest")]
+                merge_request: Option<MergeRequestNode>,
+            }
+            #[derive(serde::Deserialize)]
+            struct Data {
+                project: Option<ProjectNode>,
+            }
+            let viewer = self.current_username().await?;
+            let data: Data = self
+                .graphql(
+                    &single_query(),
+                    serde_json::json!({
+                        "fullPath": format!("{owner}/{repo}"),
+                        "iid": number.to_string(),
+                    }),
+                )
+                .await?;
+            data.project
+                .and_then(|p| p.merge_request)
+                .and_then(|node| to_pr(node, &viewer, &self.base_url))
+                .ok_or_else(|| format!("Merge request {owner}/{repo}!{number} not found"))
+        })
+    }
+
+    /// A deleted project or revoked access comes back as a `null` project.
+    fn pr_exists<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<bool, String>> {
+        Box::pin(async move {
+            #[derive(serde::Deserialize)]
+            struct ProjectNode {
+                #[serde(rename = "mergeRequest")]
+                merge_request: Option<serde::de::IgnoredAny>,
+            }
+            #[derive(serde::Deserialize)]
+            struct Data {
+                project: Option<ProjectNode>,
+            }
+            let data: Data = self
+                .graphql(
+                    "query($fullPath: ID!, $iid: String!) { project(fullPath: $fullPath) { mergeRequest(iid: $iid) { iid } } }",
+                    serde_json::json!({
+                        "fullPath": format!("{owner}/{repo}"),
+                        "iid": number.to_string(),
+                    }),
+                )
+                .await?;
+            Ok(data.project.and_then(|p| p.merge_request).is_some())
+        })
+    }
+
+    /// The latest pipeline on the project's default branch.
+    fn default_branch_ci<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+    ) -> BoxFuture<'a, Result<CiState, String>> {
+        Box::pin(async move {
+            #[derive(serde::Deserialize)]
+            struct Repository {
+                #[serde(rename = "rootRef")]
+                root_ref: Option<String>,
+            }
+            #[derive(serde::Deserialize)]
+            struct RefProject {
+                repository: Option<Repository>,
+            }
+            #[derive(serde::Deserialize)]
+            struct RefData {
+                project: Option<RefProject>,
+            }
+            #[derive(serde::Deserialize)]
+            struct PipelineProject {
+                pipelines: Option<Nodes<PipelineNode>>,
+            }
+            #[derive(serde::Deserialize)]
+            struct PipelineData {
+                project: Option<PipelineProject>,
+            }
+            let full_path = format!("{owner}/{repo}");
+            let data: RefData = self
+                .graphql(
+                    "query($fullPath: ID!) { project(fullPath: $fullPath) { repository { rootRef } } }",
+                    serde_json::json!({ "fullPath": full_path }),
+                )
+                .await?;
+            let Some(root_ref) = data
+                .project
+                .and_then(|p| p.repository)
+                .and_then(|r| r.root_ref)
+            else {
+                return Ok(CiState::None);
+            };
+            let data: PipelineData = self
+                .graphql(
+                    "query($fullPath: ID!, $ref: String!) { project(fullPath: $fullPath) { pipelines(ref: $ref, first: 1) { nodes { status } } } }",
+                    serde_json::json!({ "fullPath": full_path, "ref": root_ref }),
+                )
+                .await?;
+            let status = data
+                .project
+                .and_then(|p| p.pipelines)
+                .and_then(|c| c.nodes)
+                .and_then(|nodes| nodes.into_iter().next())
+                .and_then(|p| p.status);
+            Ok(match job_state(status.as_deref()) {
+                CiCheckState::Success => CiState::Success,
+                CiCheckState::Failure => CiState::Failure,
+                CiCheckState::Running => CiState::Running,
+                CiCheckState::Neutral | CiCheckState::None => CiState::None,
+            })
+        })
+    }
+
+    /// The job trace through the REST API (GraphQL has no trace field).
+    fn check_log<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        check: &'a CiCheck,
+    ) -> BoxFuture<'a, Result<String, String>> {
+        Box::pin(async move {
+            let job_id = check
+                .url
+                .as_deref()
+                .and_then(job_id)
+                .ok_or_else(|| format!("{} is not a GitLab CI job", check.name))?;
+            let fail =
+                |e: octocrab::Error| format!("Fetching the log of {} failed: {e}", check.name);
+            let project = format!("{owner}/{repo}").replace('/', "%2F");
+            let url = format!(
+                "{}/api/v4/projects/{project}/jobs/{job_id}/trace",
+                self.base_url
+            );
+            let resp = self.client._get(url).await.map_err(fail)?;
+            let resp = octocrab::map_github_error(resp).await.map_err(fail)?;
+            self.client.body_to_string(resp).await.map_err(fail)
+        })
+    }
+
+    fn viewer_login(&self) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(self.current_username())
+    }
+}
+
+/// The job ID in a job page URL (`.../group/project/-/jobs/123`).
+fn job_id(url: &str) -> Option<u64> {
+    let (_, rest) = url.split_once("/-/jobs/")?;
+    rest.split(['/', '?', '#']).next()?.parse().ok()
+}
+
+/// Parse a merge request reference: a GitLab MR URL (any host, nested groups) or
+/// `group/project#12` / `group/project!12`.
+///
+/// Returns `(namespace, project, iid)`.
+pub fn parse_mr_ref(s: &str) -> Option<(String, String, i64)> {
+    let s = s.trim().trim_matches(|c| c == '<' || c == '>');
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part.split('/').all(|seg| {
+                !seg.is_empty()
+                    && seg
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            })
+    };
+    let split = |path: &str, iid: &str| {
+        let iid = iid.parse::<i64>().ok().filter(|n| *n > 0)?;
+        let (owner, repo) = path.rsplit_once('/')?;
+        (valid(owner) && valid(repo)).then(|| (owner.to_string(), repo.to_string(), iid))
+    };
+
+    if let Some((_, rest)) = s.split_once("://") {
+        let (_host, path) = rest.split_once('/')?;
+        let (project, tail) = path.split_once("/-/merge_requests/")?;
+        let iid = tail.split(['/', '#', '?']).next()?;
+        return split(project, iid);
+    }
+    let (project, iid) = s.rsplit_once(['#', '!'])?;
+    split(project, iid)
+}
+
+const MR_FIELDS: &str = r#"
+fragment mr on MergeRequest {
+  iid
+  title
+  webUrl
+  draft
+  updatedAt
+  createdAt
+  description
+  sourceBranch
+  targetBranch
+  diffHeadSha
+  diffStatsSummary { additions deletions }
+  conflicts
+  mergeStatusEnum
+  detailedMergeStatus
+  approved
+  approvalsRequired
+  author { username }
+  project { fullPath }
+  labels(first: 20) { nodes { title } }
+  milestone { title }
+  approvedBy(first: 50) { nodes { username } }
+  reviewers(first: 50) { nodes { username mergeRequestInteraction { reviewState } } }
+  headPipeline {
+    status
+    jobs(first: 50) { nodes { name status webPath startedAt } }
+  }
+  discussions(last: 20) {
+    nodes {
+      resolvable
+      resolved
+      notes(first: 20) {
+        nodes { body system createdAt author { username } position { filePath } }
+      }
+    }
+  }
+}
+"#;
+
+fn list_query(field: &str) -> String {
+    format!(
+        r#"query($since: Time, $first: Int, $after: String) {{
+  currentUser {{
+    {field}(state: opened, updatedAfter: $since, sort: UPDATED_DESC, first: $first, after: $after) {{
+      pageInfo {{ hasNextPage endCursor }}
+      nodes {{ ...mr }}
+    }}
+  }}
+}}
+{MR_FIELDS}"#
+    )
+}
+
+fn single_query() -> String {
+    format!(
+        r#"query($fullPath: ID!, $iid: String!) {{
+  project(fullPath: $fullPath) {{
+    mergeRequest(iid: $iid) {{ ...mr }}
+  }}
+}}
+{MR_FIELDS}"#
+    )
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct MergeRequestConnection {
+    #[serde(rename = "pageInfo")]
+    page_info: Option<PageInfo>,
+    nodes: Option<Vec<MergeRequestNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Nodes<T> {
+    nodes: Option<Vec<T>>,
+}
+
+impl<T> Nodes<T> {
+    fn iter(conn: Option<&Nodes<T>>) -> impl Iterator<Item = &T> {
+        conn.and_then(|c| c.nodes.as_ref()).into_iter().flatten()
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct UserNode {
+    username: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProjectRef {
+    #[serde(rename = "fullPath")]
+    full_path: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DiffStats {
+    additions: i64,
+    deletions: i64,
+}
+
+/// A label or milestone; only the title is read.
+#[derive(Debug, serde::Deserialize)]
+struct LabelNode {
+    title: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ReviewerNode {
+    username: String,
+    #[serde(rename = "mergeRequestInteraction")]
+    interaction: Option<ReviewerInteraction>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ReviewerInteraction {
+    #[serde(rename = "reviewState")]
+    review_state: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct PipelineNode {
+    status: Option<String>,
+    jobs: Option<Nodes<JobNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct JobNode {
+    name: Option<String>,
+    status: Option<String>,
+    #[serde(rename = "webPath")]
+    web_path: Option<String>,
+    #[serde(rename = "startedAt")]
+    started_at: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DiscussionNode {
+    resolvable: Option<bool>,
+    resolved: Option<bool>,
+    notes: Option<Nodes<NoteNode>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct NoteNode {
+    body: Option<String>,
+    system: Option<bool>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    author: Option<UserNode>,
+    position: Option<NotePosition>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct NotePosition {
+    #[serde(rename = "filePath")]
+    file_path: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct MergeRequestNode {
+    iid: String,
+    title: String,
+    #[serde(rename = "webUrl")]
+    web_url: Option<String>,
+    draft: Option<bool>,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    description: Option<String>,
+    #[serde(rename = "sourceBranch")]
+    source_branch: Option<String>,
+    #[serde(rename = "targetBranch")]
+    target_branch: Option<String>,
+    #[serde(rename = "diffHeadSha")]
+    diff_head_sha: Option<String>,
+    #[serde(rename = "diffStatsSummary")]
+    diff_stats: Option<DiffStats>,
+    conflicts: Option<bool>,
+    #[serde(rename = "mergeStatusEnum")]
+    merge_status: Option<String>,
+    #[serde(rename = "detailedMergeStatus")]
+    detailed_merge_status: Option<String>,
+    approved: Option<bool>,
+    #[serde(rename = "approvalsRequired")]
+    approvals_required: Option<i64>,
+    author: Option<UserNode>,
+    project: ProjectRef,
+    labels: Option<Nodes<LabelNode>>,
+    milestone: Option<LabelNode>,
+    #[serde(rename = "approvedBy")]
+    approved_by: Option<Nodes<UserNode>>,
+    reviewers: Option<Nodes<ReviewerNode>>,
+    #[serde(rename = "headPipeline")]
+    head_pipeline: Option<PipelineNode>,
+    discussions: Option<Nodes<DiscussionNode>>,
+}
+
+fn job_state(status: Option<&str>) -> CiCheckState {
+    match status.unwrap_or("").to_ascii_uppercase().as_str() {
+        "SUCCESS" => CiCheckState::Success,
+        "FAILED" => CiCheckState::Failure,
+        "CREATED" | "WAITING_FOR_RESOURCE" | "PREPARING" | "PENDING" | "RUNNING" | "SCHEDULED" => {
+            CiCheckState::Running
+        }
+        "CANCELED" | "SKIPPED" | "MANUAL" => CiCheckState::Neutral,
+        _ => CiCheckState::None,
+    }
+}
+
+fn ci_checks(node: &MergeRequestNode, base_url: &str) -> Vec<CiCheck> {
+    let jobs = node.head_pipeline.as_ref().and_then(|p| p.jobs.as_ref());
+    let mut out: Vec<CiCheck> = Nodes::iter(jobs)
+        .map(|j| CiCheck {
+            name: j.name.clone().unwrap_or_else(|| "job".to_string()),
+            state: job_state(j.status.as_deref()),
+            url: j.web_path.as_ref().map(|p| format!("{base_url}{p}")),
+            started_at_unix: j
+                .started_at
+                .as_deref()
+                .and_then(parse_github_datetime_to_unix),
+        })
+        .collect();
+    // Same ordering as GitHub checks: failed, running, passed, then by name.
+    out.sort_by(|a, b| {
+        let rank = |s: &CiCheckState| match s {
+            CiCheckState::Failure => 0,
+            CiCheckState::Running => 1,
+            CiCheckState::Success => 2,
+            CiCheckState::Neutral => 3,
+            CiCheckState::None => 4,
+        };
+        rank(&a.state)
+            .cmp(&rank(&b.state))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    out
+}
+
+fn ci_state(node: &MergeRequestNode) -> CiState {
+    let status = node
+        .head_pipeline
+        .as_ref()
+        .and_then(|p| p.status.as_deref());
+    match job_state(status) {
+        CiCheckState::Success => CiState::Success,
+        CiCheckState::Failure => CiState::Failure,
+        CiCheckState::Running => CiState::Running,
+        CiCheckState::Neutral | CiCheckState::None => CiState::None,
+    }
+}
+
+/// Reviewer verdict in the same vocabulary as GitHub's latest reviews.
+fn reviewer_state(r: &ReviewerNode) -> &'static str {
+    match r
+        .interaction
+        .as_ref()
+        .and_then(|i| i.review_state.as_deref())
+    {
+        Some("APPROVED") => "APPROVED",
+        Some("REQUESTED_CHANGES") => "CHANGES_REQUESTED",
+        Some("REVIEWED") => "COMMENTED",
+        _ => "REQUESTED",
+    }
+}
+
+fn note_to_comment(n: &NoteNode) -> Option<Comment> {
+    Some(Comment {
+        author: n
+            .author
+            .as_ref()
+            .map_or_else(|| "ghost".to_string(), |a| a.username.clone()),
+        body: n
+            .body
+            .as_deref()
+            .unwrap_or("")
+            .trim()
+            .chars()
+            .take(COMMENT_BODY_CHARS)
+            .collect(),
+        created_at_unix: parse_github_datetime_to_unix(n.created_at.as_deref()?)?,
+    })
+}
+
+fn user_notes(d: &DiscussionNode) -> impl Iterator<Item = &NoteNode> {
+    Nodes::iter(d.notes.as_ref()).filter(|n| !n.system.unwrap_or(false))
+}
+
+/// Latest note by someone else after the viewer's last note in the same discussion.
+fn last_reply_to_viewer(node: &MergeRequestNode, viewer: &str) -> Option<i64> {
+    Nodes::iter(node.discussions.as_ref())
+        .filter_map(|d| {
+            let notes: Vec<Comment> = user_notes(d).filter_map(note_to_comment).collect();
+            let mine = notes.iter().rposition(|c| c.author == viewer)?;
+            notes[mine + 1..]
+                .iter()
+                .filter(|c| c.author != viewer)
+                .map(|c| c.created_at_unix)
+                .max()
+        })
+        .max()
+}
+
+/// Resolvable discussions become review threads; plain notes become the conversation.
+fn discussions(node: &MergeRequestNode, viewer: &str) -> (Vec<Comment>, Vec<ReviewThread>) {
+    let mut comments = Vec::new();
+    let mut threads = Vec::new();
+    for d in Nodes::iter(node.discussions.as_ref()) {
+        let notes: Vec<Comment> = user_notes(d).filter_map(note_to_comment).collect();
+        if notes.is_empty() {
+            continue;
+        }
+        if d.resolvable.unwrap_or(false) {
+            threads.push(ReviewThread {
+                path: user_notes(d).find_map(|n| n.position.as_ref()?.file_path.clone()),
+                is_resolved: d.resolved.unwrap_or(false),
+                started_by_viewer: notes[0].author == viewer,
+                comments: notes,
+            });
+        } else {
+            comments.extend(notes);
+        }
+    }
+    comments.sort_by_key(|c| c.created_at_unix);
+    let skip = comments.len().saturating_sub(CONVERSATION_COMMENTS);
+    comments.drain(..skip);
+    (comments, threads)
+}
+
+fn to_pr(node: MergeRequestNode, viewer: &str, base_url: &str) -> Option<Pr> {
+    let number = node.iid.parse::<i64>().ok()?;
+    let (owner, repo) = node.project.full_path.rsplit_once('/')?;
+    let (owner, repo) = (owner.to_string(), repo.to_string());
+    let updated_at_unix = parse_github_datetime_to_unix(&node.updated_at)?;
+    let author = node
+        .author
+        .as_ref()
+        .map_or_else(|| "unknown".to_string(), |a| a.username.clone());
+
+    let ci_checks = ci_checks(&node, base_url);
+    let ci_state = ci_state(&node);
+    let mut reviewers: Vec<Reviewer> = Nodes::iter(node.reviewers.as_ref())
+        .map(|r| Reviewer {
+            login: r.username.clone(),
+            state: reviewer_state(r).to_string(),
+        })
+        .collect();
+    // Anyone can approve, not only the assigned reviewers.
+    for approver in Nodes::iter(node.approved_by.as_ref()) {
+        if !reviewers.iter().any(|r| r.login == approver.username) {
+            reviewers.push(Reviewer {
+                login: approver.username.clone(),
+                state: "APPROVED".to_string(),
+            });
+        }
+    }
+    let is_requested = reviewers
+        .iter()
+        .any(|r| r.login == viewer && r.state == "REQUESTED");
+    let review_state = if is_requested {
+        ReviewState::Requested
+    } else if node.approved.unwrap_or(false) {
+        ReviewState::Approved
+    } else {
+        ReviewState::None
+    };
+
+    let has_conflicts = node.conflicts.unwrap_or(false);
+    let detailed = node.detailed_merge_status.as_deref().unwrap_or("");
+    let mergeable = if has_conflicts {
+        "CONFLICTING"
+    } else if node.merge_status.as_deref() == Some("CAN_BE_MERGED") {
+        "MERGEABLE"
+    } else {
+        "UNKNOWN"
+    };
+    let merge_state_status = match detailed {
+        "MERGEABLE" => "CLEAN",
+        "NEED_REBASE" => "BEHIND",
+        "CONFLICT" => "DIRTY",
+        "CI_STILL_RUNNING" | "CHECKING" | "UNCHECKED" | "PREPARING" => "UNKNOWN",
+        _ => "BLOCKED",
+    };
+    let current_approvals = Nodes::iter(node.approved_by.as_ref()).count() as u32;
+    let blockers = MergeBlockers {
+        has_conflicts,
+        required_approvals: node.approvals_required.filter(|n| *n > 0).map(|n| n as u32),
+        current_approvals,
+        required_checks: Vec::new(),
+        failing_required_checks: Vec::new(),
+        is_behind_base: detailed == "NEED_REBASE",
+    };
+    let (comments, review_threads) = discussions(&node, viewer);
+    let last_reply_at_unix = last_reply_to_viewer(&node, viewer);
+
+    Some(Pr {
+        pr_key: format!("{owner}/{repo}#{number}"),
+        owner,
+        repo,
+        number,
+        is_viewer_author: author == viewer,
+        is_assignee: false,
+        is_mentioned: false,
+        is_unsubscribed: false,
+        author,
+        title: node.title.clone(),
+        url: node.web_url.clone().unwrap_or_default(),
+        updated_at_unix,
+        created_at_unix: node
+            .created_at
+            .as_deref()
+            .and_then(parse_github_datetime_to_unix),
+        last_commit_sha: node.diff_head_sha.clone(),
+        ci_state,
+        ci_checks,
+        review_state,
+        is_draft: node.draft.unwrap_or(false),
+        mergeable: Some(mergeable.to_string()),
+        merge_state_status: Some(merge_state_status.to_string()),
+        merge_blockers: (!blockers.is_clear()).then_some(blockers),
+        labels: Nodes::iter(node.labels.as_ref())
+            .map(|l| l.title.clone())
+            .collect(),
+        review_requested_at_unix: None,
+        head_ref_name: node.source_branch.clone(),
+        base_ref_name: node.target_branch.clone(),
+        milestone: node.milestone.as_ref().map(|m| m.title.clone()),
+        project_status: None,
+        ci_from_previous_commit: false,
+        lines_changed: node.diff_stats.as_ref().map(|d| d.additions + d.deletions),
+        last_force_push_at_unix: None,
+        last_reply_at_unix,
+        body_excerpt: node.description.as_deref().and_then(body_excerpt),
+        reviewers,
+        comments,
+        review_threads,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mr_ref_accepts_urls_nested_groups_and_short_refs() {
+        let want = Some(("acme/platform".to_string(), "api".to_string(), 12));
+        assert_eq!(
+            parse_mr_ref("https://gitlab.com/acme/platform/api/-/merge_requests/12"),
+            want
+        );
+        assert_eq!(
+            parse_mr_ref(
+                "<https://git.acme.io/acme/platform/api/-/merge_requests/12/diffs#note_3>"
+            ),
+            want
+        );
+        assert_eq!(parse_mr_ref("acme/platform/api!12"), want);
+        assert_eq!(parse_mr_ref("acme/platform/api#12"), want);
+        assert_eq!(
+            parse_mr_ref("https://gitlab.com/acme/api/-/issues/12"),
+            None
+        );
+        assert_eq!(parse_mr_ref("api#12"), None);
+        assert_eq!(parse_mr_ref("acme/api!0"), None);
+        assert_eq!(job_id("https://gitlab.com/acme/api/-/jobs/981"), Some(981));
+    }
+
+    #[test]
+    fn merge_requests_map_onto_the_pr_model() {
+        let node: MergeRequestNode = serde_json::from_value(serde_json::json!({
+            "iid": "7",
+            "title": "Add rate limiting",
+            "webUrl": "https://gitlab.com/acme/platform/api/-/merge_requests/7",
+            "draft": false,
+            "updatedAt": "2024-05-01T10:00:00Z",
+            "description": "<!-- template -->\nLimits requests per token.",
+            "sourceBranch": "feat/rate-limit",
+            "diffHeadSha": "abc123",
+            "diffStatsSummary": { "additions": 30, "deletions": 12 },
+            "conflicts": false,
+            "mergeStatusEnum": "CAN_BE_MERGED",
+            "detailedMergeStatus": "NOT_APPROVED",
+            "approved": false,
+            "approvalsRequired": 3,
+            "author": { "username": "alice" },
+            "project": { "fullPath": "acme/platform/api" },
+            "labels": { "nodes": [{ "title": "backend" }] },
+            "approvedBy": { "nodes": [{ "username": "bob" }, { "username": "carol" }] },
+            "reviewers": { "nodes": [
+                { "username": "bob", "mergeRequestInteraction": { "reviewState": "APPROVED" } },
+                { "username": "me", "mergeRequestInteraction": { "reviewState": "UNREVIEWED" } }
+            ] },
+            "headPipeline": {
+                "status": "FAILED",
+                "jobs": { "nodes": [
+                    { "name": "test", "status": "FAILED", "webPath": "/acme/platform/api/-/jobs/1", "startedAt": null },
+                    { "name": "lint", "status": "SUCCESS", "webPath": null, "startedAt": null }
+                ] }
+            },
+            "discussions": { "nodes": [
+                { "resolvable": false, "resolved": false, "notes": { "nodes": [
+                    { "body": "added 1 commit", "system": true, "createdAt": "2024-05-01T08:00:00Z", "author": { "username": "alice" }, "position": null },
+                    { "body": "Can you add a test?", "system": false, "createdAt": "2024-05-01T09:00:00Z", "author": { "username": "me" }, "position": null }
+                ] } },
+                { "resolvable": true, "resolved": false, "notes": { "nodes": [
+                    { "body": "Off by one?", "system": false, "createdAt": "2024-05-01T09:30:00Z", "author": { "username": "me" }, "position": { "filePath": "src/limit.rs" } },
+                    { "body": "Fixed", "system": false, "createdAt": "2024-05-01T09:45:00Z", "author": { "username": "alice" }, "position": { "filePath": "src/limit.rs" } }
+                ] } }
+            ] }
+        }))
+        .unwrap();
+
+        let pr = to_pr(node, "me", "https://gitlab.com").unwrap();
+        assert_eq!(pr.pr_key, "acme/platform/api#7");
+        assert_eq!(
+            (pr.owner.as_str(), pr.repo.as_str()),
+            ("acme/platform", "api")
+        );
+        assert!(!pr.is_viewer_author);
+        assert!(matches!(pr.review_state, ReviewState::Requested));
+        assert!(matches!(pr.ci_state, CiState::Failure));
+        assert_eq!(pr.ci_checks[0].name, "test");
+        assert_eq!(
+            pr.ci_checks[0].url.as_deref(),
+            Some("https://gitlab.com/acme/platform/api/-/jobs/1")
+        );
+        assert_eq!(pr.mergeable.as_deref(), Some("MERGEABLE"));
+        assert_eq!(pr.merge_state_status.as_deref(), Some("BLOCKED"));
+        let blockers = pr.merge_blockers.as_ref().unwrap();
+        assert_eq!(
+            (blockers.required_approvals, blockers.current_approvals),
+            (Some(3), 2)
+        );
+        assert_eq!(pr.labels, vec!["backend".to_string()]);
+        assert_eq!(pr.head_ref_name.as_deref(), Some("feat/rate-limit"));
+        assert_eq!(pr.lines_changed, Some(42));
+        assert_eq!(
+            pr.body_excerpt.as_deref(),
+            Some("Limits requests per token.")
+        );
+        let reviewers: Vec<(&str, &str)> = pr
+            .reviewers
+            .iter()
+            .map(|r| (r.login.as_str(), r.state.as_str()))
+            .collect();
+        assert_eq!(
+            reviewers,
+            vec![
+                ("bob", "APPROVED"),
+                ("me", "REQUESTED"),
+                ("carol", "APPROVED")
+            ]
+        );
+        assert_eq!(pr.comments.len(), 1, "system notes are skipped");
+        assert_eq!(pr.review_threads.len(), 1);
+        assert_eq!(pr.review_threads[0].path.as_deref(), Some("src/limit.rs"));
+        assert_eq!(pr.unresolved_threads(), 1);
+        assert_eq!(
+            pr.last_reply_at_unix,
+            parse_github_datetime_to_unix("2024-05-01T09:45:00Z")
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! needle-core: the attention-PR engine behind the `needle` TUI.
+//!
+//! This library fetches open pull requests that involve the signed-in GitHub user,
+//! scores them by how urgently they need attention, and persists a snapshot in SQLite
+//! so the next run can render instantly from cache. The `needle` binary is a thin
+//! terminal UI on top of it; editors, bots, and dashboards can use the same API.
+//!
+//! Modules:
+//! - [`github`]: GraphQL fetching of authored and review-requested PRs.
+//! - [`github_write`]: write actions (merging and commenting from the TUI).
+//! - [`gitlab`]: GraphQL fetching of merge requests when `provider = "gitlab"`.
+//! - [`provider`]: the [`provider::Provider`] trait both hosts implement.
+//! - [`refresh`]: scoring, categorization, and the refresh pipeline ([`refresh::refresh`],
+//!   [`refresh::load_cached`]).
+//! - [`db`]: SQLite cache (schema, migrations, upserts, pins).
+//! - [`model`]: plain data types shared by all layers.
+//! - [`demo`]: deterministic fake data for demos and tests.
+//! - [`drafts`]: the `[scoring.drafts]` policy (rank, surface, or hide drafts).
+//! - [`oncall`]: the `--oncall` profile (incident/hotfix PRs first).
+//! - [`columns`]: custom list columns (`[[columns]]` expressions over PR fields).
+//! - [`author`]: `mode = "author"` (the viewer's own PRs ranked by CI and merge health).
+//! - [`bots`]: bot PRs (dependabot, renovate) listed apart or hidden.
+//! - [`shed`]: review requests that are safe to decline or hand off.
+//! - [`snooze`]: snoozing PRs for a while or until the next push.
+//! - [`subscriptions`]: demoting or hiding PRs unsubscribed from on GitHub.
+//! - [`sla`]: per-repo/label review SLAs (due dates, badges, score boosts).
+//! - [`topics`]: PR topics (shared labels or base branches) for the topic layout.
+//! - [`timeutil`]: GitHub timestamp parsing helpers.
+//! - [`today`]: the "Today" to-do list (overdue reviews, red PRs, PRs ready to merge).
+//!
+//! ```no_run
+//! use needle_core::db::{db_path, open_db};
+//! use needle_core::refresh::{ScopeFilters, refresh};
+//!
+//! # async fn run(octo: octocrab::Octocrab) -> Result<(), String> {
+//! let github = needle_core::provider::GitHubProvider::new(octo);
+//! let conn = open_db(&db_path()?)?;
+//! let prs = refresh(&conn, &github, 30, &ScopeFilters::default(), false).await?;
+//! for p in prs.iter().filter(|p| p.category == needle_core::refresh::Category::NeedsYou) {
+//!     println!("{} {}", p.pr.pr_key, p.display_status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod author;
+pub mod bots;
+pub mod columns;
+pub mod db;
+pub mod demo;
+pub mod drafts;
+pub mod github;
+#[cfg(test)]
+pub(crate) mod github_mock;
+pub mod github_write;
+pub mod gitlab;
+pub mod model;
+pub mod oncall;
+pub mod provider;
+pub mod refresh;
+pub mod shed;
+pub mod sla;
+pub mod snooze;
+pub mod subscriptions;
+pub mod timeutil;
+pub mod today;
+pub mod topics;
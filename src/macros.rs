@@ -0,0 +1,196 @@
+//! Keyboard macros: `Q` and a register letter records keys until the next `Q`, `@` and
+//! the letter replays them, and `@@` replays the last macro again.
+//!
+//! Replayed keys go back through the event loop as if they were typed, so a macro can do
+//! whatever the keyboard can (open details, open the failing check, snooze, move on).
+//! Registers last for the session.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::{HashMap, VecDeque};
+
+/// Keys one replay may feed in total, so a macro that replays itself comes to a stop.
+const MAX_REPLAYED_KEYS: usize = 1_000;
+
+/// A `Q` or `@` waiting for its register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pending {
+    Record,
+    Replay,
+}
+
+/// What the event loop should do with a key the macro layer has seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MacroKey {
+    /// Not a macro key: handle it as usual.
+    Pass,
+    /// Taken by the macro layer, with an optional footer message.
+    Consumed(Option<String>),
+}
+
+/// Recorded registers, the recording in progress and the keys still to replay.
+#[derive(Debug, Default)]
+pub(crate) struct Macros {
+    registers: HashMap<char, Vec<KeyEvent>>,
+    recording: Option<(char, Vec<KeyEvent>)>,
+    pending: Option<Pending>,
+    last: Option<char>,
+    queue: VecDeque<KeyEvent>,
+    budget: usize,
+}
+
+impl Macros {
+    /// The register being recorded into, if any.
+    pub(crate) fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(reg, _)| *reg)
+    }
+
+    /// The next key of a running replay.
+    pub(crate) fn next_replayed(&mut self) -> Option<KeyEvent> {
+        self.queue.pop_front()
+    }
+
+    /// Look at a key press before the loop handles it. While `typing` (a text field has
+    /// focus) `Q` and `@` are plain text. `replayed` keys are not recorded a second time.
+    pub(crate) fn feed(&mut self, k: &KeyEvent, typing: bool, replayed: bool) -> MacroKey {
+        if let Some(pending) = self.pending.take() {
+            if !replayed {
+                self.record(k);
+            }
+            let KeyCode::Char(c) = k.code else {
+                return MacroKey::Consumed(None);
+            };
+            return match pending {
+                Pending::Record if c.is_ascii_lowercase() => {
+                    self.recording = Some((c, Vec::new()));
+                    MacroKey::Consumed(Some(format!("Recording macro @{c} (Q stops)")))
+                }
+                Pending::Record => {
+                    MacroKey::Consumed(Some("Macro registers are a to z".to_string()))
+                }
+                Pending::Replay => {
+                    let reg = if c == '@' { self.last } else { Some(c) };
+                    self.replay(reg, replayed)
+                }
+            };
+        }
+        if !typing {
+            match k.code {
+                KeyCode::Char('Q') => {
+                    return MacroKey::Consumed(match self.recording.take() {
+                        Some((reg, keys)) => {
+                            let msg = format!("Recorded macro @{reg} ({} keys)", keys.len());
+                            self.registers.insert(reg, keys);
+                            Some(msg)
+                        }
+                        None => {
+                            self.pending = Some(Pending::Record);
+                            None
+                        }
+                    });
+                }
+                KeyCode::Char('@') => {
+                    if !replayed {
+                        self.record(k);
+                    }
+                    self.pending = Some(Pending::Replay);
+                    return MacroKey::Consumed(None);
+                }
+                _ => {}
+            }
+        }
+        if !replayed {
+            self.record(k);
+        }
+        MacroKey::Pass
+    }
+
+    fn record(&mut self, k: &KeyEvent) {
+        if let Some((_, keys)) = self.recording.as_mut() {
+            keys.push(*k);
+        }
+    }
+
+    /// Queue the keys of `reg`. A `nested` replay (from inside a macro) runs before the
+    /// rest of the outer macro and shares its key budget.
+    fn replay(&mut self, reg: Option<char>, nested: bool) -> MacroKey {
+        let Some(reg) = reg else {
+            return MacroKey::Consumed(Some("No macro replayed yet".to_string()));
+        };
+        let Some(keys) = self.registers.get(&reg).filter(|keys| !keys.is_empty()) else {
+            return MacroKey::Consumed(Some(format!("Macro @{reg} is empty")));
+        };
+        if !nested {
+            self.queue.clear();
+            self.budget = MAX_REPLAYED_KEYS;
+        }
+        if keys.len() > self.budget {
+            self.queue.clear();
+            return MacroKey::Consumed(Some(format!(
+                "Macro @{reg} stopped after {MAX_REPLAYED_KEYS} keys"
+            )));
+        }
+        self.budget -= keys.len();
+        for k in keys.iter().rev() {
+            self.queue.push_front(*k);
+        }
+        self.last = Some(reg);
+        MacroKey::Consumed(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    /// Feed typed `keys`, then drain the replay queue like the event loop does. Returns
+    /// the keys that reached the loop.
+    fn run(m: &mut Macros, keys: &str) -> String {
+        let mut seen = String::new();
+        for c in keys.chars() {
+            let mut next = Some((key(c), false));
+            while let Some((k, replayed)) = next {
+                if let (MacroKey::Pass, KeyCode::Char(c)) = (m.feed(&k, false, replayed), k.code) {
+                    seen.push(c);
+                }
+                next = m.next_replayed().map(|k| (k, true));
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn recorded_keys_replay_through_the_loop() {
+        let mut m = Macros::default();
+        assert_eq!(run(&mut m, "Qajzj"), "jzj");
+        assert_eq!(m.recording(), Some('a'));
+        assert_eq!(
+            m.feed(&key('Q'), false, false),
+            MacroKey::Consumed(Some("Recorded macro @a (3 keys)".to_string()))
+        );
+        assert_eq!(m.recording(), None);
+
+        assert_eq!(run(&mut m, "@a"), "jzj");
+        assert_eq!(run(&mut m, "k@@"), "kjzj");
+        // A macro can replay another one.
+        assert_eq!(run(&mut m, "Qbx@aQ"), "xjzj");
+        assert_eq!(run(&mut m, "@b"), "xjzj");
+
+        // While typing, `Q` and `@` are text.
+        assert_eq!(m.feed(&key('@'), true, false), MacroKey::Pass);
+        assert_eq!(run(&mut m, "@c"), "", "an empty register replays nothing");
+    }
+
+    #[test]
+    fn a_macro_that_replays_itself_stops() {
+        let mut m = Macros::default();
+        run(&mut m, "Qrj@rQ");
+        let seen = run(&mut m, "@r");
+        assert_eq!(seen.len(), MAX_REPLAYED_KEYS / 3);
+        assert_eq!(m.next_replayed(), None);
+    }
+}
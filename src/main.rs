@@ -1,19 +1,164 @@
+mod callbacks;
 mod config;
-mod db;
-mod demo;
-mod github;
-mod model;
-mod refresh;
-mod timeutil;
+mod config_watch;
+mod macros;
+mod report;
+mod rpc;
+mod script;
+mod serve;
+mod slack;
+mod status;
+mod theme;
 mod tui;
+mod watch;
 
-use crate::config::load_config;
-use crate::db::{db_path, delete_prs_not_in, open_db};
-use crate::refresh::{ScopeFilters, load_cached, refresh, refresh_demo};
-use crate::tui::{AppState, RefreshIntervals, UiPrefs, run_tui};
-use clap::{ArgAction, Parser};
+use crate::callbacks::{Callbacks, ScoringLayers};
+use crate::config::{
+    Config, apply_cached_team_config, apply_team_config, config_path, load_config, prepend_scope,
+};
+use crate::report::ReportFormat;
+use crate::tui::{
+    AppState, CheckGroups, ListLayout, LiveSettings, QuickLink, QuickLinks, RefreshIntervals,
+    RepoGroups, TailColumns, TitleStatus, UiPrefs, run_tui,
+};
+use crate::watch::{ALERT_ALIASES, ALERT_KINDS, Reminders};
+use clap::{ArgAction, Parser, Subcommand};
+use needle_core::columns::CustomColumn;
+use needle_core::db::{
+    LoggedAction, db_path, delete_prs_not_in, load_actions_log, log_action, now_unix, open_db,
+    open_db_read_only, purge_prs_older_than, set_hash_pr_text, track_pr, untrack_pr,
+};
+use needle_core::demo::DEMO_VIEWER_LOGIN;
+use needle_core::drafts::DraftPolicy;
+use needle_core::github::{TokenError, starred_repos, validate_token, watched_repos};
+use needle_core::github_write::MergeMethod;
+use needle_core::gitlab::{DEFAULT_GITLAB_URL, GitLabProvider};
+use needle_core::model::UsageMode;
+use needle_core::model::{CiCheck, Pr};
+use needle_core::oncall::OncallProfile;
+use needle_core::provider::{GitHubProvider, Provider, ProviderKind, parse_ref};
+use needle_core::refresh::{
+    AuthorPriority, SCOPE_DISCUSSION, ScopeFilters, UiPr, load_cached, refresh_demo,
+    warm_cache_for_cutoff,
+};
+use needle_core::sla::SlaRule;
+use needle_core::today::DEFAULT_TODAY_LIMIT;
 use octocrab::Octocrab;
 use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+
+const DEMO_DB_PATH: &str = "target/needle-demo/prs.sqlite";
+
+type RefreshFn = Arc<dyn Fn() -> Result<Vec<UiPr>, String> + Send + Sync>;
+type LookupFn = Arc<dyn Fn(&str) -> Result<UiPr, String> + Send + Sync>;
+type RefreshPrFn = Arc<dyn Fn(&str) -> Result<UiPr, String> + Send + Sync>;
+type CheckLogFn = Arc<dyn Fn(&Pr, &CiCheck) -> Result<Vec<String>, String> + Send + Sync>;
+type MergeFn = Arc<dyn Fn(&Pr, MergeMethod) -> Result<(), String> + Send + Sync>;
+type CommentFn = Arc<dyn Fn(&Pr, &str) -> Result<(), String> + Send + Sync>;
+type UpdateBranchFn = Arc<dyn Fn(&Pr) -> Result<(), String> + Send + Sync>;
+type SetDraftFn = Arc<dyn Fn(&Pr, bool) -> Result<(), String> + Send + Sync>;
+type DeclineFn = Arc<dyn Fn(&Pr, &str) -> Result<(), String> + Send + Sync>;
+type ApproveFn = Arc<dyn Fn(&Pr) -> Result<(), String> + Send + Sync>;
+type MarkReadFn = Arc<dyn Fn(&Pr) -> Result<(), String> + Send + Sync>;
+/// Switches to another `--days` window and lists the cache for it (`false`), or fetches the
+/// older part of that window the cache is missing and lists it again (`true`).
+type WindowFn = Arc<dyn Fn(i64, bool) -> Result<Vec<UiPr>, String> + Send + Sync>;
+
+const GITLAB_WRITES: &str = "Write actions (merge, comment, update branch, draft, decline, approve) are not supported for GitLab yet";
+
+/// Message shown when the configured host has no token.
+fn no_token(kind: ProviderKind) -> &'static str {
+    match kind {
+        ProviderKind::GitHub => "Missing NEEDLE_GITHUB_TOKEN or GITHUB_TOKEN env var",
+        ProviderKind::GitLab => "Missing NEEDLE_GITLAB_TOKEN or GITLAB_TOKEN env var",
+    }
+}
+
+/// Token for the configured host; the `NEEDLE_` variable takes priority.
+fn host_token(kind: ProviderKind) -> Option<String> {
+    kind.token_env_vars()
+        .iter()
+        .find_map(|v| std::env::var(v).ok())
+}
+
+/// Data retention settings from the config (`cache_retention_days`, `cache_pr_text`).
+struct CachePolicy {
+    retention_days: Option<i64>,
+    hash_pr_text: bool,
+}
+
+/// Open the real cache database, honoring `--purge-cache`, `--no-cache` and the
+/// retention policy.
+fn open_cache_db(
+    args: &CliArgs,
+    policy: &CachePolicy,
+) -> (std::path::PathBuf, rusqlite::Connection) {
+    let path = db_path().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    if args.purge_cache {
+        let _ = std::fs::remove_file(&path);
+    }
+    let conn = open_db(&path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+
+    if args.no_cache {
+        let _ = delete_prs_not_in(&conn, &[]);
+    }
+    // No cutoff either when the retention reaches back past the epoch: nothing is that old.
+    let cutoff = policy
+        .retention_days
+        .and_then(|days| days.checked_mul(86_400))
+        .and_then(|secs| now_unix().checked_sub(secs));
+    let applied = set_hash_pr_text(&conn, policy.hash_pr_text).and_then(|()| match cutoff {
+        Some(cutoff) => purge_prs_older_than(&conn, cutoff).map(|_| ()),
+        None => Ok(()),
+    });
+    if let Err(e) = applied {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    (path, conn)
+}
+
+/// Open the real cache for `needle status`: read-only, leaving retention, scrubbing and the
+/// cache flags to the frontends that refresh. Without a readable cache the counts are zero.
+fn open_status_db() -> (std::path::PathBuf, rusqlite::Connection) {
+    let path = db_path().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let conn = open_db_read_only(&path)
+        .or_else(|_| rusqlite::Connection::open_in_memory().map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+    (path, conn)
+}
+
+/// Parse a `purge --older-than` age (`30d`, `12h`, `2w`; bare numbers are days) into
+/// seconds.
+fn parse_age(s: &str) -> Result<i64, String> {
+    let s = s.trim();
+    let (num, unit_secs) = match s.char_indices().last() {
+        Some((i, 'h')) => (&s[..i], 3600),
+        Some((i, 'd')) => (&s[..i], 86_400),
+        Some((i, 'w')) => (&s[..i], 7 * 86_400),
+        _ => (s, 86_400),
+    };
+    let n: i64 = num
+        .parse()
+        .map_err(|_| format!("Invalid age `{s}` (expected e.g. 30d, 12h or 2w)"))?;
+    if n < 0 {
+        return Err(format!("Invalid age `{s}`: must not be negative"));
+    }
+    n.checked_mul(unit_secs)
+        .ok_or_else(|| format!("Invalid age `{s}`: too large"))
+}
 
 #[derive(Parser, Debug, Clone)]
 #[command(
@@ -28,39 +173,43 @@ struct CliArgs {
     version: (),
 
     /// Skip loading cached PRs on startup (start empty, rely on refresh).
-    #[arg(long = "no-cache")]
+    #[arg(long = "no-cache", global = true)]
     no_cache: bool,
 
     /// Delete the cache database before starting (also applies to --demo path).
-    #[arg(long = "purge-cache")]
+    #[arg(long = "purge-cache", global = true)]
     purge_cache: bool,
 
     /// Only include PRs updated in the last N days.
-    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(i64).range(0..))]
+    #[arg(long, global = true, default_value_t = 30, value_parser = clap::value_parser!(i64).range(0..))]
     days: i64,
 
     /// Start with diverse fake data (no GitHub token required).
-    #[arg(long)]
+    #[arg(long, global = true)]
     demo: bool,
 
     /// Only show PRs from these orgs/users (repeatable or comma-delimited).
-    #[arg(long, value_delimiter = ',', num_args = 0..)]
+    #[arg(long, global = true, value_delimiter = ',', num_args = 0..)]
     org: Vec<String>,
 
     /// Only show these repos (owner/repo) (repeatable or comma-delimited).
-    #[arg(long, value_delimiter = ',', num_args = 0..)]
+    #[arg(long, global = true, value_delimiter = ',', num_args = 0..)]
     include: Vec<String>,
 
     /// Exclude these repos (owner/repo) (repeatable or comma-delimited).
-    #[arg(long, value_delimiter = ',', num_args = 0..)]
+    #[arg(long, global = true, value_delimiter = ',', num_args = 0..)]
     exclude: Vec<String>,
 
     /// Include PRs requested to teams you are in (default: only explicit user requests).
-    #[arg(long)]
+    #[arg(long, global = true)]
     include_team_requests: bool,
 
+    /// Extra scopes: involves-me:discussion adds PRs you commented on (repeatable).
+    #[arg(long, global = true, value_delimiter = ',', num_args = 0.., value_parser = [SCOPE_DISCUSSION])]
+    scope: Vec<String>,
+
     /// Emit a terminal bell on important new events.
-    #[arg(long)]
+    #[arg(long, global = true)]
     bell: bool,
 
     /// Hide PR numbers column in list view.
@@ -74,6 +223,103 @@ struct CliArgs {
     /// Hide author column in list view.
     #[arg(long)]
     hide_author: bool,
+
+    /// List layout: score-based categories, split into your PRs and PRs to review,
+    /// grouped by repository, or grouped by topic.
+    #[arg(long, value_enum)]
+    layout: Option<ListLayout>,
+
+    /// On-call mode: incident/hotfix PRs first, faster refresh, bell on (see [oncall] in config).
+    #[arg(long, global = true)]
+    oncall: bool,
+
+    /// Replay a key script against the TUI off-screen and record it (see README).
+    #[arg(long, value_name = "FILE")]
+    script: Option<std::path::PathBuf>,
+
+    /// Where to write the --script recording (default: the script path with .cast).
+    #[arg(long, value_name = "FILE", requires = "script")]
+    cast: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Track PRs outside your attention set (URL or owner/repo#123); they refresh with the rest.
+    Add {
+        #[arg(required = true, value_name = "PR")]
+        prs: Vec<String>,
+    },
+    /// Stop tracking PRs added with `needle add`.
+    Remove {
+        #[arg(required = true, value_name = "PR")]
+        prs: Vec<String>,
+    },
+    /// Delete cached PRs needle has not seen for a while, e.g. `needle purge --older-than 30d`.
+    Purge {
+        /// Age cutoff: days (`30d`), hours (`12h`) or weeks (`2w`).
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        older_than: i64,
+    },
+    /// List what needle did on GitHub on your behalf (merges, comments, ...), newest last.
+    Log {
+        /// How many actions to show.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Seed `include` in the config from the repositories you watch (or starred), e.g.
+    /// `needle import-scope --from starred`.
+    ImportScope {
+        /// Which repositories to import.
+        #[arg(long, value_enum, default_value_t)]
+        from: RepoSource,
+        /// Write the repositories' owners to `org` instead.
+        #[arg(long)]
+        orgs: bool,
+    },
+    /// Serve PR data as line-delimited JSON-RPC 2.0 on stdin/stdout (for editor plugins).
+    Rpc,
+    /// Print the categorized PR list as a report for standup notes, e.g. `needle report --format md`.
+    Report {
+        /// Output format.
+        #[arg(long, value_enum, default_value_t)]
+        format: ReportFormat,
+    },
+    /// Serve a read-only HTML dashboard that auto-refreshes (for wall displays).
+    Serve {
+        /// Port to listen on.
+        #[arg(long, default_value_t = 8800)]
+        port: u16,
+        /// Address to bind (use 0.0.0.0 to expose on the network).
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Also serve only `/metrics` on this address for Prometheus, e.g. `:9878` (on
+        /// `--host`) or `0.0.0.0:9878`.
+        #[arg(long, value_name = "ADDR")]
+        metrics: Option<String>,
+    },
+    /// Print what needs you from the cache, without touching the network, e.g.
+    /// `needle status --short` (`🔥3 ❌1`) for a shell prompt or status bar.
+    Status {
+        /// Compact output: NEEDS YOU and your red PRs, empty when both are zero.
+        #[arg(long)]
+        short: bool,
+    },
+    /// Keep refreshing without the TUI (e.g. under launchd/systemd), printing a line when a
+    /// PR needs you, CI fails or someone replies.
+    Watch,
+}
+
+/// Repositories `needle import-scope` reads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum RepoSource {
+    /// Repositories you watch.
+    #[default]
+    Watched,
+    /// Repositories you starred.
+    Starred,
 }
 
 #[tokio::main(flavor = "multi_thread")]
@@ -81,6 +327,96 @@ async fn main() {
     let args = CliArgs::parse();
     let config = load_config();
 
+    let track = match &args.command {
+        Some(Command::Add { prs }) => Some((prs, true)),
+        Some(Command::Remove { prs }) => Some((prs, false)),
+        _ => None,
+    };
+    if let Some((prs, add)) = track {
+        if let Err(e) = run_track(args.demo, prs, add) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(Command::Purge { older_than }) = &args.command {
+        if let Err(e) = run_purge(args.demo, *older_than) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Command::Log { limit }) = &args.command {
+        if let Err(e) = run_log(args.demo, *limit) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Command::ImportScope { from, orgs }) = &args.command {
+        let result = match config.provider.as_deref().map(ProviderKind::parse) {
+            Some(Some(ProviderKind::GitLab)) => {
+                Err("`needle import-scope` only reads GitHub repositories".to_string())
+            }
+            _ => run_import_scope(*from, *orgs).await,
+        };
+        if let Err(e) = result {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `needle status` runs on every prompt: no network, so only a cached team config.
+    let config = if matches!(args.command, Some(Command::Status { .. })) {
+        apply_cached_team_config(config)
+    } else {
+        apply_team_config(config).await
+    };
+    if let Some(Err(e)) = config.truncate_keep_tail.as_deref().map(TailColumns::parse) {
+        eprintln!("Invalid truncate_keep_tail in config: {e}");
+        std::process::exit(1);
+    }
+    let LiveSettings {
+        ui,
+        refresh_intervals,
+        bell_events,
+        check_groups,
+        handoff_message,
+        quick_links,
+        title_status,
+    } = live_settings(&args, &config);
+    warn_unknown_alert_kinds("bell event", config.bell_events.iter().flatten());
+    let cache_policy = CachePolicy {
+        retention_days: config.cache_retention_days,
+        hash_pr_text: config.cache_pr_text == Some(false),
+    };
+
+    let provider_kind = match config.provider.as_deref() {
+        None => ProviderKind::default(),
+        Some(p) => ProviderKind::parse(p).unwrap_or_else(|| {
+            eprintln!("Unknown provider `{p}` in config (known: github, gitlab)");
+            std::process::exit(1);
+        }),
+    };
+    let custom_columns = config
+        .columns
+        .iter()
+        .flatten()
+        .map(|c| CustomColumn::parse(&c.name, &c.expr))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid [[columns]] entry in config: {e}");
+            std::process::exit(1);
+        });
+    let gitlab_url = config
+        .gitlab_url
+        .clone()
+        .unwrap_or_else(|| DEFAULT_GITLAB_URL.to_string());
+    let no_token = no_token(provider_kind);
+
     // Merge config with CLI args (CLI takes precedence).
     // For days, only use config if CLI is at default (30).
     let days = if args.days != 30 {
@@ -106,30 +442,97 @@ async fn main() {
         config.exclude.unwrap_or_default()
     };
 
+    let scopes = if !args.scope.is_empty() {
+        args.scope.clone()
+    } else {
+        config.scope.unwrap_or_default()
+    };
+    for s in scopes.iter().filter(|s| s.as_str() != SCOPE_DISCUSSION) {
+        eprintln!("Warning: ignoring unknown scope `{s}` (known: {SCOPE_DISCUSSION})");
+    }
+
     let scope = ScopeFilters {
         orgs,
         include_repos,
         exclude_repos,
+        include_milestones: config.include_milestones.unwrap_or_default(),
+        exclude_milestones: config.exclude_milestones.unwrap_or_default(),
+        discussion: scopes.iter().any(|s| s == SCOPE_DISCUSSION),
+        mode: config.mode.unwrap_or_default(),
     };
 
     // For boolean flags, CLI true overrides config; otherwise use config value.
     let include_team_requests =
         args.include_team_requests || config.include_team_requests.unwrap_or(false);
-    let bell_enabled = args.bell || config.bell.unwrap_or(false);
+    let reminder_secs = config.remind_after_mins.map(|m| m as i64 * 60);
 
-    let ui = UiPrefs {
-        hide_pr_numbers: args.hide_pr_numbers || config.hide_pr_numbers.unwrap_or(false),
-        hide_repo: args.hide_repo || config.hide_repo.unwrap_or(false),
-        hide_author: args.hide_author || config.hide_author.unwrap_or(false),
-    };
+    let groups = RepoGroups::new(
+        config
+            .groups
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, g)| (name, g.repos)),
+    );
+    let group_list = config.group_list.unwrap_or(false);
+    let layout = args.layout.or(config.layout).unwrap_or_default();
+    let color_mode = config.color_mode.unwrap_or_default();
 
-    let refresh_intervals = RefreshIntervals {
-        list_secs: config.refresh_interval_list_secs.unwrap_or(180),
-        details_secs: config.refresh_interval_details_secs.unwrap_or(30),
+    let sla_rules: Vec<SlaRule> = config
+        .sla
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| SlaRule {
+            repos: r.repos,
+            labels: r.labels,
+            hours: r.hours,
+            business_hours: r.business_hours.unwrap_or(true),
+        })
+        .collect();
+
+    let scoring = config.scoring.unwrap_or_default();
+    let author_priority: Vec<AuthorPriority> = scoring
+        .authors
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(author, delta)| AuthorPriority { author, delta })
+        .collect();
+    let draft_config = scoring.drafts.unwrap_or_default();
+    let draft_policy = DraftPolicy {
+        mine: draft_config.mine.unwrap_or_default(),
+        others: draft_config.others.unwrap_or_default(),
+        surface_failed_ci: draft_config.surface_failed_ci.unwrap_or(false),
     };
+    let unsubscribed_prs = config.unsubscribed_prs.unwrap_or_default();
+    let bot_mode = config.bots.unwrap_or_default();
+    let mark_read_on_open = config.mark_read_on_open.unwrap_or(false);
+    let today_limit = config.today_limit.unwrap_or(DEFAULT_TODAY_LIMIT);
+
+    let oncall_config = config.oncall.unwrap_or_default();
+    let oncall = args.oncall.then(|| {
+        let defaults = OncallProfile::default();
+        OncallProfile {
+            labels: oncall_config.labels.clone().unwrap_or(defaults.labels),
+            branch_prefixes: oncall_config
+                .branch_prefixes
+                .clone()
+                .unwrap_or(defaults.branch_prefixes),
+        }
+    });
+
+    // Without a token the TUI and `needle report` still work from the cached snapshot
+    // (read-only); the other frontends need live data and refuse to start.
+    // `needle status` always reads the cache only.
+    let offline = !args.demo
+        && (matches!(args.command, Some(Command::Status { .. }))
+            || matches!(args.command, None | Some(Command::Report { .. }))
+                && host_token(provider_kind).is_none());
+    // Shown in the header once the token checked out.
+    let mut viewer_login: Option<String> = args.demo.then(|| DEMO_VIEWER_LOGIN.to_string());
+    // The TUI can switch the window at runtime (`w`); refreshes follow it.
+    let window = Arc::new(AtomicI64::new(days));
 
-    if args.demo {
-        let demo_path = std::path::PathBuf::from("target/needle-demo/prs.sqlite");
+    let (conn, mut initial, callbacks) = if args.demo {
+        let demo_path = std::path::PathBuf::from(DEMO_DB_PATH);
         if args.purge_cache {
             let _ = std::fs::remove_file(&demo_path);
         }
@@ -145,110 +548,431 @@ async fn main() {
         // Seed once, then run again so some CI failures look "unchanged" on first render.
         let _ = refresh_demo(&conn, days, &scope);
         let demo_prs = refresh_demo(&conn, days, &scope).unwrap_or_else(|_e| Vec::new());
-        let state = AppState::new(demo_prs, ui);
+        let callbacks = Callbacks::demo(demo_path, &scope, window);
+        (conn, demo_prs, callbacks)
+    } else if offline {
+        let (path, conn) = if matches!(args.command, Some(Command::Status { .. })) {
+            open_status_db()
+        } else {
+            open_cache_db(&args, &cache_policy)
+        };
+        let cached = if args.no_cache {
+            Vec::new()
+        } else {
+            load_cached(&conn, days, &scope).unwrap_or_else(|_e| Vec::new())
+        };
+        (conn, cached, Callbacks::offline(path, &scope, no_token))
+    } else {
+        let token = host_token(provider_kind).unwrap_or_else(|| {
+            eprintln!("{no_token}");
+            std::process::exit(1);
+        });
 
-        let demo_path_for_refresh = demo_path.clone();
-        let scope_for_refresh = scope.clone();
-        let refresh_fn: Arc<dyn Fn() -> Result<Vec<crate::refresh::UiPr>, String> + Send + Sync> =
-            Arc::new(move || {
-                let c = open_db(&demo_path_for_refresh)?;
-                refresh_demo(&c, days, &scope_for_refresh)
-            });
+        // Merging and commenting still go straight to GitHub's API, so keep the client.
+        let (provider, octo): (Arc<dyn Provider>, Option<Octocrab>) = match provider_kind {
+            ProviderKind::GitHub => {
+                let octo = Octocrab::builder()
+                    .personal_token(token)
+                    .build()
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to init octocrab: {e}");
+                        std::process::exit(1);
+                    });
 
-        if let Err(e) = run_tui(
-            &conn,
-            state,
-            refresh_fn,
-            false,
-            bell_enabled,
-            refresh_intervals,
-        ) {
-            eprintln!("{e}");
-            std::process::exit(1);
-        }
-        return;
-    }
+                // A bad token would otherwise only show up as empty refreshes. When GitHub
+                // cannot be reached at all, carry on with the cache.
+                match validate_token(&octo).await {
+                    Ok(login) => viewer_login = Some(login),
+                    Err(TokenError::Rejected(e)) => {
+                        eprintln!("{e}");
+                        std::process::exit(1);
+                    }
+                    Err(TokenError::Unreachable(e)) => eprintln!("Warning: {e}"),
+                }
+                (Arc::new(GitHubProvider::new(octo.clone())), Some(octo))
+            }
+            ProviderKind::GitLab => {
+                let gitlab = GitLabProvider::new(&gitlab_url, token).unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    std::process::exit(1);
+                });
+                match gitlab.viewer_login().await {
+                    Ok(login) => viewer_login = Some(login),
+                    Err(e) => eprintln!("Warning: {e}"),
+                }
+                (Arc::new(gitlab), None)
+            }
+        };
 
-    let token = std::env::var("NEEDLE_GITHUB_TOKEN")
-        .or_else(|_| std::env::var("GITHUB_TOKEN"))
-        .unwrap_or_else(|_| {
-            eprintln!("Missing NEEDLE_GITHUB_TOKEN or GITHUB_TOKEN env var");
-            std::process::exit(1);
-        });
+        let (path, conn) = open_cache_db(&args, &cache_policy);
 
-    let octo = Octocrab::builder()
-        .personal_token(token)
-        .build()
-        .unwrap_or_else(|e| {
-            eprintln!("Failed to init octocrab: {e}");
-            std::process::exit(1);
-        });
+        // Fast startup: render cached SQLite snapshot immediately, then refresh in background.
+        // A wider `--days` than last time first fetches just the missing older window.
+        let cached = if args.no_cache {
+            Vec::new()
+        } else {
+            let _ = warm_cache_for_cutoff(
+                &conn,
+                provider.as_ref(),
+                days,
+                &scope,
+                include_team_requests,
+            )
+            .await;
+            load_cached(&conn, days, &scope).unwrap_or_else(|_e| Vec::new())
+        };
 
-    let path = db_path().unwrap_or_else(|e| {
-        eprintln!("{e}");
-        std::process::exit(1);
-    });
-    if args.purge_cache {
-        let _ = std::fs::remove_file(&path);
+        let callbacks = Callbacks::online(
+            provider,
+            octo,
+            path,
+            &scope,
+            include_team_requests,
+            window,
+            mark_read_on_open,
+        );
+        (conn, cached, callbacks)
+    };
+    // Only live data is worth refreshing right away.
+    let start_refresh_immediately = !args.demo && !offline;
+
+    let layers = ScoringLayers {
+        author_mode: scope.mode == UsageMode::Author,
+        author_priority,
+        draft_policy,
+        unsubscribed_prs,
+        bot_mode,
+        sla_rules,
+        oncall,
+    };
+    if !layers.is_empty() {
+        layers.apply_to_list(&mut initial);
     }
-    let conn = open_db(&path).unwrap_or_else(|e| {
+    let mut callbacks = callbacks.with_layers(layers);
+
+    // Slack gets the same alerts as the bell, from whichever frontend is refreshing.
+    let slack = config
+        .slack
+        .and_then(|s| Some((s.webhook_url?, s.events)))
+        .filter(|_| !args.demo && !offline);
+    if let Some((webhook_url, events)) = slack {
+        warn_unknown_alert_kinds("Slack event", events.iter().flatten());
+        callbacks.refresh_fn = slack::with_slack(
+            callbacks.refresh_fn,
+            slack::SlackHook::new(webhook_url, events),
+            initial.clone(),
+            Reminders::new(reminder_secs),
+            tokio::runtime::Handle::current(),
+        );
+    }
+
+    let result = match args.command {
+        Some(
+            Command::Add { .. }
+            | Command::Remove { .. }
+            | Command::Purge { .. }
+            | Command::Log { .. }
+            | Command::ImportScope { .. },
+        ) => unreachable!("handled above"),
+        Some(Command::Report { format }) => {
+            report::run_report(initial, callbacks.refresh_fn, format)
+        }
+        Some(Command::Rpc) => rpc::run_rpc(&conn, initial, callbacks.refresh_fn),
+        Some(Command::Status { short }) => status::run_status(&initial, short),
+        Some(Command::Watch) => watch::run_watch(
+            &conn,
+            initial,
+            callbacks.refresh_fn,
+            refresh_intervals.list_secs,
+            bell_events,
+            Reminders::new(reminder_secs),
+        ),
+        Some(Command::Serve {
+            port,
+            host,
+            metrics,
+        }) => serve::run_serve(
+            initial,
+            callbacks.refresh_fn,
+            start_refresh_immediately,
+            refresh_intervals.list_secs,
+            &host,
+            port,
+            metrics.as_deref(),
+        ),
+        None => match args.script {
+            Some(script_path) => {
+                let cast_path = args
+                    .cast
+                    .clone()
+                    .unwrap_or_else(|| script_path.with_extension("cast"));
+                script::run_script(
+                    &conn,
+                    AppState::new(initial, ui)
+                        .with_groups(groups, group_list)
+                        .with_layout(layout)
+                        .with_check_groups(check_groups)
+                        .with_handoff_message(handoff_message)
+                        .with_quick_links(quick_links)
+                        .with_custom_columns(custom_columns)
+                        .with_reminders(reminder_secs)
+                        .with_today_limit(today_limit)
+                        .with_oncall(args.oncall)
+                        .with_offline(offline)
+                        .with_viewer_login(viewer_login)
+                        .with_scope(&scope, days),
+                    callbacks.refresh_fn,
+                    start_refresh_immediately,
+                    refresh_intervals,
+                    &script_path,
+                    &cast_path,
+                )
+            }
+            None => {
+                // The watcher stops when dropped, so it lives as long as the TUI.
+                let reload_args = args.clone();
+                let (_watcher, config_reload) =
+                    config_watch::watch_config(move |config| live_settings(&reload_args, config))
+                        .unzip();
+                run_tui(
+                    &conn,
+                    AppState::new(initial, ui)
+                        .with_groups(groups, group_list)
+                        .with_layout(layout)
+                        .with_check_groups(check_groups)
+                        .with_handoff_message(handoff_message)
+                        .with_quick_links(quick_links)
+                        .with_custom_columns(custom_columns)
+                        .with_reminders(reminder_secs)
+                        .with_today_limit(today_limit)
+                        .with_oncall(args.oncall)
+                        .with_title_status(title_status)
+                        .with_offline(offline)
+                        .with_viewer_login(viewer_login)
+                        // An explicit --layout beats the one saved on quit.
+                        .with_saved_ui_state(&conn, args.layout.is_some())
+                        .with_scope(&scope, days)
+                        .with_color_mode(color_mode),
+                    callbacks,
+                    start_refresh_immediately,
+                    bell_events,
+                    refresh_intervals,
+                    config_reload,
+                )
+            }
+        },
+    };
+    if let Err(e) = result {
         eprintln!("{e}");
         std::process::exit(1);
-    });
+    }
+}
 
-    if args.no_cache {
-        let _ = delete_prs_not_in(&conn, &[]);
+/// Warn about `kinds` that are not alert kinds (typos in `bell_events` or `[slack] events`).
+fn warn_unknown_alert_kinds<'a>(what: &str, kinds: impl IntoIterator<Item = &'a String>) {
+    for kind in kinds {
+        let known = ALERT_KINDS.contains(&kind.as_str())
+            || ALERT_ALIASES.iter().any(|&(alias, _)| alias == kind);
+        if !known {
+            eprintln!(
+                "Warning: ignoring unknown {what} `{kind}` (known: {})",
+                ALERT_KINDS.join(", ")
+            );
+        }
     }
+}
 
-    // Fast startup: render cached SQLite snapshot immediately, then refresh in background.
-    let cached = if args.no_cache {
-        Vec::new()
+/// Settings the TUI can pick up again when the config changes. CLI flags still win over
+/// the file, and `--oncall` uses the `[oncall]` intervals.
+fn live_settings(args: &CliArgs, config: &Config) -> LiveSettings {
+    let oncall = config.oncall.as_ref();
+    let refresh_intervals = if args.oncall {
+        RefreshIntervals {
+            list_secs: oncall
+                .and_then(|o| o.refresh_interval_list_secs)
+                .unwrap_or(60),
+            details_secs: oncall
+                .and_then(|o| o.refresh_interval_details_secs)
+                .unwrap_or(15),
+        }
     } else {
-        load_cached(&conn, days, &scope).unwrap_or_else(|_e| Vec::new())
+        RefreshIntervals {
+            list_secs: config.refresh_interval_list_secs.unwrap_or(180),
+            details_secs: config.refresh_interval_details_secs.unwrap_or(30),
+        }
     };
-    let state = AppState::new(cached, ui);
-
-    let handle = tokio::runtime::Handle::current();
-    let db_path_for_refresh = path.clone();
-    let octo_for_refresh = octo.clone();
-    let handle_for_refresh = handle.clone();
-    let scope_for_refresh = scope.clone();
-    let refresh_fn: Arc<dyn Fn() -> Result<Vec<crate::refresh::UiPr>, String> + Send + Sync> =
-        Arc::new(move || {
-            let c = open_db(&db_path_for_refresh)?;
-            // Called from a non-runtime worker thread (for shimmer), so this uses handle.block_on.
-            if tokio::runtime::Handle::try_current().is_ok() {
-                tokio::task::block_in_place(|| {
-                    handle_for_refresh.block_on(refresh(
-                        &c,
-                        &octo_for_refresh,
-                        days,
-                        &scope_for_refresh,
-                        include_team_requests,
-                    ))
+    LiveSettings {
+        ui: UiPrefs {
+            hide_pr_numbers: args.hide_pr_numbers || config.hide_pr_numbers.unwrap_or(false),
+            hide_repo: args.hide_repo || config.hide_repo.unwrap_or(false),
+            hide_author: args.hide_author || config.hide_author.unwrap_or(false),
+            // Checked at startup; a reload with a typo keeps the default.
+            keep_tail: config
+                .truncate_keep_tail
+                .as_deref()
+                .map_or(Ok(TailColumns::default()), TailColumns::parse)
+                .unwrap_or_default(),
+        },
+        refresh_intervals,
+        bell_events: if args.oncall || args.bell || config.bell.unwrap_or(false) {
+            config
+                .bell_events
+                .clone()
+                .unwrap_or_else(|| ALERT_KINDS.map(String::from).to_vec())
+        } else {
+            Vec::new()
+        },
+        check_groups: CheckGroups::new(config.check_groups.clone().unwrap_or_default()),
+        handoff_message: config.handoff_message.clone(),
+        quick_links: QuickLinks::new(
+            config
+                .links
+                .iter()
+                .flatten()
+                .map(|l| QuickLink {
+                    repos: l.repos.clone(),
+                    name: l.name.clone(),
+                    url: l.url.clone(),
                 })
+                .collect(),
+        ),
+        title_status: TitleStatus {
+            terminal: config.terminal_title.unwrap_or(false),
+            tmux: config.tmux_status.unwrap_or(false),
+        },
+    }
+}
+
+/// The cache `needle add/remove/purge` work on.
+fn open_command_db(demo: bool) -> Result<rusqlite::Connection, String> {
+    let path = if demo {
+        std::path::PathBuf::from(DEMO_DB_PATH)
+    } else {
+        db_path()?
+    };
+    open_db(&path)
+}
+
+/// Run the remote action `action` on `pr` and append it, with how it went, to the actions
+/// log in the cache at `path` (`needle log`). Failing to log never fails the action.
+fn logged(
+    path: &std::path::Path,
+    pr: &Pr,
+    action: &str,
+    detail: Option<&str>,
+    run: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    let result = run();
+    if let Ok(conn) = open_db(path) {
+        let error = result.as_ref().err().map(String::as_str);
+        let _ = log_action(&conn, now_unix(), &pr.pr_key, action, detail, error);
+    }
+    result
+}
+
+/// One `needle log` line: local time, PR, action (with its detail) and how it went.
+fn format_logged_action(entry: &LoggedAction) -> String {
+    use chrono::TimeZone;
+    let at = chrono::Local
+        .timestamp_opt(entry.at, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let action = match &entry.detail {
+        Some(detail) => format!("{} ({detail})", entry.action),
+        None => entry.action.clone(),
+    };
+    let result = match &entry.error {
+        Some(e) => format!("failed: {e}"),
+        None => "ok".to_string(),
+    };
+    format!("{at}  {}  {action}  {result}", entry.pr_key)
+}
+
+/// `needle log`: the last `limit` remote actions, oldest first.
+fn run_log(demo: bool, limit: usize) -> Result<(), String> {
+    let conn = open_command_db(demo)?;
+    let entries = load_actions_log(&conn, limit)?;
+    if entries.is_empty() {
+        println!("needle has not acted on any PR yet");
+    }
+    for entry in &entries {
+        println!("{}", format_logged_action(entry));
+    }
+    Ok(())
+}
+
+/// `needle purge`: drop cached PRs not seen in `older_than` seconds, then compact the
+/// file so the deleted rows do not linger on disk.
+fn run_purge(demo: bool, older_than: i64) -> Result<(), String> {
+    let conn = open_command_db(demo)?;
+    let cutoff = now_unix()
+        .checked_sub(older_than)
+        .ok_or_else(|| "Invalid age: too large".to_string())?;
+    let purged = purge_prs_older_than(&conn, cutoff)?;
+    conn.execute_batch("VACUUM")
+        .map_err(|e| format!("Failed to compact the cache: {e}"))?;
+    println!("Purged {purged} cached PR(s)");
+    Ok(())
+}
+
+/// `needle import-scope`: add the repositories the viewer watches or starred (or their
+/// owners) to the config, so a new setup does not start from hand-written lists.
+async fn run_import_scope(from: RepoSource, orgs: bool) -> Result<(), String> {
+    let token = host_token(ProviderKind::GitHub)
+        .ok_or_else(|| no_token(ProviderKind::GitHub).to_string())?;
+    let octo = Octocrab::builder()
+        .personal_token(token)
+        .build()
+        .map_err(|e| format!("Failed to init octocrab: {e}"))?;
+    let (mut repos, verb) = match from {
+        RepoSource::Watched => (watched_repos(&octo).await?, "watch"),
+        RepoSource::Starred => (starred_repos(&octo).await?, "starred"),
+    };
+    if repos.is_empty() {
+        return Err(format!("There are no repositories you {verb} to import"));
+    }
+    repos.sort();
+    let (key, values, note) = if orgs {
+        let mut owners: Vec<String> = repos
+            .iter()
+            .filter_map(|r| r.split_once('/'))
+            .map(|(owner, _)| owner.to_string())
+            .collect();
+        owners.sort();
+        owners.dedup();
+        let note = format!("Owners of the repositories you {verb} (`needle import-scope`)");
+        ("org", owners, note)
+    } else {
+        let note = format!("Repositories you {verb} (`needle import-scope`)");
+        ("include", repos, note)
+    };
+    let path = config_path().ok_or("Could not find the config directory")?;
+    prepend_scope(&path, key, &values, &note)?;
+    println!("Added {} {key} entries to {}", values.len(), path.display());
+    Ok(())
+}
+
+/// `needle add` / `needle remove`: edit the tracked-PR list without touching the network.
+fn run_track(demo: bool, refs: &[String], add: bool) -> Result<(), String> {
+    let conn = open_command_db(demo)?;
+    for r in refs {
+        let (owner, repo, number) =
+            parse_ref(r).ok_or_else(|| format!("Not a PR reference: {r}"))?;
+        let key = format!("{owner}/{repo}#{number}");
+        if add {
+            if track_pr(&conn, &key, now_unix())? {
+                println!("Tracking {key}");
             } else {
-                handle_for_refresh.block_on(refresh(
-                    &c,
-                    &octo_for_refresh,
-                    days,
-                    &scope_for_refresh,
-                    include_team_requests,
-                ))
+                println!("Already tracking {key}");
             }
-        });
-
-    if let Err(e) = run_tui(
-        &conn,
-        state,
-        refresh_fn,
-        true,
-        bell_enabled,
-        refresh_intervals,
-    ) {
-        eprintln!("{e}");
-        std::process::exit(1);
+        } else if untrack_pr(&conn, &key)? {
+            println!("Stopped tracking {key}");
+        } else {
+            println!("Not tracked: {key}");
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -265,4 +989,45 @@ mod tests {
         assert!(!help.contains("--no-notifications"));
         assert!(!help.to_lowercase().contains("notification"));
     }
+
+    #[test]
+    fn purge_ages_accept_days_hours_and_weeks() {
+        assert_eq!(parse_age("30d"), Ok(30 * 86_400));
+        assert_eq!(parse_age("12h"), Ok(12 * 3600));
+        assert_eq!(parse_age("2w"), Ok(14 * 86_400));
+        assert_eq!(parse_age("7"), Ok(7 * 86_400));
+        assert!(parse_age("soon").is_err());
+        assert!(parse_age("-3d").is_err());
+        assert!(parse_age("99999999999999w").is_err());
+    }
+
+    #[test]
+    fn remote_actions_are_logged_with_their_outcome() {
+        let conn = needle_core::db::TestDb::new("actions-log-test");
+        let pr = needle_core::demo::generate_demo_prs(1_710_028_800, 0).remove(0);
+
+        assert_eq!(
+            logged(conn.path(), &pr, "merge", Some("squash"), || Ok(())),
+            Ok(())
+        );
+        let refused = logged(conn.path(), &pr, "comment", None, || {
+            Err("HTTP 403".to_string())
+        });
+        assert_eq!(refused, Err("HTTP 403".to_string()));
+
+        let entries = load_actions_log(&conn, 50).unwrap();
+        let lines: Vec<String> = entries.iter().map(format_logged_action).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(
+            lines[0].ends_with(&format!("  {}  merge (squash)  ok", pr.pr_key)),
+            "{}",
+            lines[0]
+        );
+        assert!(
+            lines[1].ends_with("  comment  failed: HTTP 403"),
+            "{}",
+            lines[1]
+        );
+        assert_eq!(load_actions_log(&conn, 1).unwrap(), entries[1..]);
+    }
 }
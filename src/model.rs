@@ -1,4 +1,8 @@
-#[derive(Debug, Clone)]
+//! Plain data types shared by fetching, scoring, persistence, and rendering.
+
+/// Aggregate CI state of a PR's head commit.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CiState {
     Success,
     Failure,
@@ -17,10 +21,50 @@ impl CiState {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Which PRs needle is used for (`mode` in the config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageMode {
+    /// The viewer's own PRs and their review requests.
+    #[default]
+    Full,
+    /// A review inbox: the viewer's own PRs are not fetched.
+    Reviewer,
+    /// A health board of the viewer's own PRs: review requests are not fetched.
+    Author,
+}
+
+impl UsageMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageMode::Full => "full",
+            UsageMode::Reviewer => "reviewer",
+            UsageMode::Author => "author",
+        }
+    }
+
+    /// Whether the viewer's own PRs are fetched.
+    pub fn includes_authored(&self) -> bool {
+        *self != UsageMode::Reviewer
+    }
+
+    /// Whether review requests (and discussions) are fetched.
+    pub fn includes_requested(&self) -> bool {
+        *self != UsageMode::Author
+    }
+}
+
+/// Review state from the viewer's point of view.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ReviewState {
     Requested,
+    /// Review requested from one of the viewer's teams, not from them directly
+    /// (`include_team_requests`).
+    TeamRequested,
     Approved,
+    /// A reviewer requested changes (GitHub's `reviewDecision`).
+    ChangesRequested,
     None,
 }
 
@@ -28,12 +72,15 @@ impl ReviewState {
     pub fn as_str(&self) -> &'static str {
         match self {
             ReviewState::Requested => "requested",
+            ReviewState::TeamRequested => "team_requested",
             ReviewState::Approved => "approved",
+            ReviewState::ChangesRequested => "changes_requested",
             ReviewState::None => "none",
         }
     }
 }
 
+/// State of a single check run or status context.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum CiCheckState {
     Success,
@@ -49,6 +96,7 @@ impl CiCheckState {
     }
 }
 
+/// A single CI check as shown in details view.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CiCheck {
     pub name: String,
@@ -57,6 +105,43 @@ pub struct CiCheck {
     pub started_at_unix: Option<i64>,
 }
 
+/// A reviewer of a PR: someone who reviewed, or a pending user/team request.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Reviewer {
+    pub login: String, // user login or team slug
+    pub state: String, // "REQUESTED" | "APPROVED" | "CHANGES_REQUESTED" | "COMMENTED" | ...
+}
+
+/// A comment on the PR conversation or in a review thread.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Comment {
+    pub author: String,
+    pub body: String,
+    pub created_at_unix: i64,
+}
+
+/// A review thread on the diff, oldest comment first.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReviewThread {
+    pub path: Option<String>,
+    pub is_resolved: bool,
+    pub comments: Vec<Comment>,
+    /// The signed-in user opened the thread.
+    #[serde(default)]
+    pub started_by_viewer: bool,
+}
+
+impl ReviewThread {
+    /// Identity that survives refreshes: file, opening author and opening time.
+    pub fn key(&self) -> String {
+        let path = self.path.as_deref().unwrap_or("");
+        match self.comments.first() {
+            Some(c) => format!("{path}|{}|{}", c.author, c.created_at_unix),
+            None => path.to_string(),
+        }
+    }
+}
+
 /// Detailed information about why a PR cannot be merged.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct MergeBlockers {
@@ -111,7 +196,8 @@ impl MergeBlockers {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A pull request in the attention set.
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Pr {
     pub pr_key: String, // "{owner}/{repo}#{number}"
     pub owner: String,
@@ -122,6 +208,7 @@ pub struct Pr {
     pub url: String,
 
     pub updated_at_unix: i64,
+    pub created_at_unix: Option<i64>, // when the PR was opened
     pub last_commit_sha: Option<String>,
     pub ci_state: CiState,
     pub ci_checks: Vec<CiCheck>,
@@ -132,5 +219,73 @@ pub struct Pr {
     pub mergeable: Option<String>, // e.g. "MERGEABLE" | "CONFLICTING" | "UNKNOWN"
     pub merge_state_status: Option<String>, // e.g. "CLEAN" | "BLOCKED" | ...
     pub is_viewer_author: bool,    // true when this PR is authored by the signed-in user
+    pub is_assignee: bool,         // the signed-in user is one of the assignees
+    pub is_mentioned: bool,        // the signed-in user was @-mentioned on the PR
+    pub is_unsubscribed: bool,     // the signed-in user unsubscribed from its notifications
     pub merge_blockers: Option<MergeBlockers>,
+    pub labels: Vec<String>,
+    pub review_requested_at_unix: Option<i64>, // latest review request event (drives SLAs)
+    pub head_ref_name: Option<String>,         // source branch, e.g. "hotfix/login"
+    pub base_ref_name: Option<String>,         // target branch, e.g. "main"
+    pub milestone: Option<String>,             // milestone title, e.g. "v3.2"
+    pub project_status: Option<String>,        // project board status, e.g. "Roadmap: In review"
+    pub ci_from_previous_commit: bool, // head commit has no CI yet; the CI shown is its parent's
+    pub lines_changed: Option<i64>,    // additions + deletions
+    pub last_force_push_at_unix: Option<i64>, // latest force-push to the head branch
+    pub last_reply_at_unix: Option<i64>, // latest reply by others to the viewer's comments
+    pub body_excerpt: Option<String>,  // first lines of the description (list preview)
+    pub reviewers: Vec<Reviewer>,
+    pub comments: Vec<Comment>, // latest conversation comments, oldest first
+    pub review_threads: Vec<ReviewThread>, // latest review threads, oldest first
+}
+
+impl Pr {
+    /// Review threads nobody has resolved yet.
+    pub fn unresolved_threads(&self) -> usize {
+        self.review_threads
+            .iter()
+            .filter(|t| !t.is_resolved)
+            .count()
+    }
+
+    /// Unresolved review threads the viewer opened on someone else's PR.
+    pub fn my_unresolved_threads(&self) -> Vec<&ReviewThread> {
+        if self.is_viewer_author {
+            return Vec::new();
+        }
+        self.review_threads
+            .iter()
+            .filter(|t| t.started_by_viewer && !t.is_resolved)
+            .collect()
+    }
+
+    /// Of [`Pr::my_unresolved_threads`], those where someone else wrote last: the author
+    /// answered and the ball is with the viewer.
+    pub fn my_threads_awaiting_reply(&self) -> usize {
+        self.my_unresolved_threads()
+            .iter()
+            .filter(|t| match (t.comments.first(), t.comments.last()) {
+                (Some(first), Some(last)) => last.author != first.author,
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Review threads opened by reviewers whose latest review requests changes.
+    pub fn change_request_threads(&self) -> Vec<&ReviewThread> {
+        let requesters: Vec<&str> = self
+            .reviewers
+            .iter()
+            .filter(|r| r.state == "CHANGES_REQUESTED")
+            .map(|r| r.login.as_str())
+            .collect();
+        self.review_threads
+            .iter()
+            .filter(|t| {
+                t.comments
+                    .first()
+                    .is_some_and(|c| requesters.contains(&c.author.as_str()))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,122 @@
+//! On-call profile: pull incident and hotfix work to the top of the list.
+//!
+//! Enabled with `needle --oncall`. PRs carrying an incident label or coming from a hotfix
+//! branch get a large score boost and a badge, whatever their other signals say.
+
+use crate::model::Pr;
+use crate::refresh::{UiPr, category_for, sort_for_display};
+
+/// Enough to outrank any combination of regular signals.
+pub const SCORE_ONCALL_INCIDENT: i32 = 100;
+
+/// Which PRs count as incident work while on call.
+#[derive(Debug, Clone)]
+pub struct OncallProfile {
+    pub labels: Vec<String>,          // case-insensitive label names
+    pub branch_prefixes: Vec<String>, // e.g. "hotfix/"
+}
+
+impl Default for OncallProfile {
+    fn default() -> Self {
+        Self {
+            labels: vec!["incident".to_string(), "hotfix".to_string()],
+            branch_prefixes: vec!["hotfix/".to_string(), "hotfix-".to_string()],
+        }
+    }
+}
+
+impl OncallProfile {
+    /// True when the PR carries an incident label or comes from a hotfix branch.
+    pub fn is_incident(&self, pr: &Pr) -> bool {
+        let labeled = pr
+            .labels
+            .iter()
+            .any(|l| self.labels.iter().any(|want| want.eq_ignore_ascii_case(l)));
+        let hotfix_branch = pr.head_ref_name.as_deref().is_some_and(|branch| {
+            let branch = branch.to_ascii_lowercase();
+            self.branch_prefixes
+                .iter()
+                .any(|p| branch.starts_with(&p.to_ascii_lowercase()))
+        });
+        labeled || hotfix_branch
+    }
+}
+
+/// Boost and badge incident PRs, recompute categories, and restore display order.
+pub fn apply_oncall(prs: &mut [UiPr], profile: &OncallProfile) {
+    for p in prs.iter_mut() {
+        if !profile.is_incident(&p.pr) {
+            continue;
+        }
+        p.score += SCORE_ONCALL_INCIDENT;
+        p.category = category_for(&p.pr, p.score);
+        p.display_status = format!("🚒 incident · {}", p.display_status);
+    }
+    sort_for_display(prs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+    use crate::refresh::Category;
+
+    fn ui(pr: Pr, score: i32) -> UiPr {
+        UiPr {
+            pr,
+            score,
+            category: Category::Waiting,
+            display_status: "✅ green".to_string(),
+            is_new_ci_failure: false,
+            is_pinned: false,
+            sla: None,
+            draft_ranked: false,
+            bot_section: false,
+            force_pushed_since_view: false,
+            reply_to_you: false,
+            snoozed: false,
+            opened_since_update: false,
+            inaccessible_since: None,
+            author_priority: None,
+        }
+    }
+
+    #[test]
+    fn labels_and_hotfix_branches_count_as_incidents() {
+        let profile = OncallProfile::default();
+        let mut pr = generate_demo_prs(1_700_000_000, 0).remove(0);
+        pr.labels = Vec::new();
+        pr.head_ref_name = Some("alice/cleanup".to_string());
+        assert!(!profile.is_incident(&pr));
+
+        pr.labels = vec!["Incident".to_string()];
+        assert!(profile.is_incident(&pr));
+
+        pr.labels = Vec::new();
+        pr.head_ref_name = Some("Hotfix/login-loop".to_string());
+        assert!(profile.is_incident(&pr));
+    }
+
+    #[test]
+    fn incidents_jump_to_the_top() {
+        let profile = OncallProfile::default();
+        let mut prs: Vec<UiPr> = generate_demo_prs(1_700_000_000, 0)
+            .into_iter()
+            .map(|mut pr| {
+                pr.labels = Vec::new();
+                pr.head_ref_name = None;
+                ui(pr, 60)
+            })
+            .collect();
+        let last = prs.len() - 1;
+        prs[last].score = -30;
+        prs[last].pr.head_ref_name = Some("hotfix/db-failover".to_string());
+        let key = prs[last].pr.pr_key.clone();
+
+        apply_oncall(&mut prs, &profile);
+
+        assert_eq!(prs[0].pr.pr_key, key);
+        assert_eq!(prs[0].score, -30 + SCORE_ONCALL_INCIDENT);
+        assert_eq!(prs[0].display_status, "🚒 incident · ✅ green");
+    }
+}
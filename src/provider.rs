@@ -0,0 +1,330 @@
+//! Code hosts needle can triage: GitHub (default) and GitLab (`provider = "gitlab"`).
+//!
+//! A provider turns the host's API into [`Pr`]s; scoring, the cache and every frontend
+//! only ever see the model, so they work the same for pull and merge requests.
+
+use crate::github::{
+    GraphQlClient, actions_job_log, default_branch_ci, fetch_attention_prs,
+    fetch_attention_prs_between, fetch_pr, parse_pr_ref, pr_exists, validate_token,
+};
+use crate::gitlab::parse_mr_ref;
+use crate::model::{CiCheck, CiState, Pr, UsageMode};
+use octocrab::Octocrab;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Boxed future returned by [`Provider`] methods (keeps the trait object-safe).
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Which code host to talk to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProviderKind {
+    #[default]
+    GitHub,
+    GitLab,
+}
+
+impl ProviderKind {
+    /// Parse the `provider` config value (case-insensitive).
+    pub fn parse(s: &str) -> Option<ProviderKind> {
+        match s.to_ascii_lowercase().as_str() {
+            "github" => Some(ProviderKind::GitHub),
+            "gitlab" => Some(ProviderKind::GitLab),
+            _ => None,
+        }
+    }
+
+    /// Environment variables holding the token, in lookup order.
+    pub fn token_env_vars(&self) -> [&'static str; 2] {
+        match self {
+            ProviderKind::GitHub => ["NEEDLE_GITHUB_TOKEN", "GITHUB_TOKEN"],
+            ProviderKind::GitLab => ["NEEDLE_GITLAB_TOKEN", "GITLAB_TOKEN"],
+        }
+    }
+}
+
+/// A code host that can list the viewer's attention set.
+pub trait Provider: Send + Sync {
+    /// Host name for messages ("GitHub", "GitLab").
+    fn name(&self) -> &'static str;
+
+    /// Open PRs authored by the viewer or awaiting their review, updated since `cutoff_ts`.
+    /// `mode` leaves out the viewer's own PRs or the review requests.
+    fn fetch_attention_prs(
+        &self,
+        cutoff_ts: i64,
+        include_team_requests: bool,
+        include_discussions: bool,
+        mode: UsageMode,
+    ) -> BoxFuture<'_, Result<Vec<Pr>, String>>;
+
+    /// Like [`Provider::fetch_attention_prs`], but only PRs last updated in
+    /// `[from_ts, to_ts)`.
+    fn fetch_attention_prs_between(
+        &self,
+        from_ts: i64,
+        to_ts: i64,
+        include_team_requests: bool,
+        include_discussions: bool,
+        mode: UsageMode,
+    ) -> BoxFuture<'_, Result<Vec<Pr>, String>> {
+        Box::pin(async move {
+            let prs = self
+                .fetch_attention_prs(from_ts, include_team_requests, include_discussions, mode)
+                .await?;
+            Ok(prs
+                .into_iter()
+                .filter(|p| p.updated_at_unix < to_ts)
+                .collect())
+        })
+    }
+
+    /// One PR by `owner/repo` and number, whether or not it is in the attention set.
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<Pr, String>>;
+
+    /// Whether the viewer can still see a PR: `Ok(false)` once its repository is deleted
+    /// or access to it was revoked. Errors only mean the host could not be asked.
+    fn pr_exists<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<bool, String>>;
+
+    /// CI state of the latest commit on the repository's default branch.
+    fn default_branch_ci<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+    ) -> BoxFuture<'a, Result<CiState, String>>;
+
+    /// Full log of the CI job behind `check` on a PR in `owner/repo`.
+    fn check_log<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        check: &'a CiCheck,
+    ) -> BoxFuture<'a, Result<String, String>>;
+
+    /// Login the token authenticates as.
+    fn viewer_login(&self) -> BoxFuture<'_, Result<String, String>>;
+}
+
+/// How many log lines the details view keeps for a failing check (`l`).
+pub const LOG_TAIL_LINES: usize = 200;
+
+/// The last `lines` lines of a CI job log, without the timestamps GitHub Actions puts in
+/// front of every line or terminal color codes.
+pub fn log_tail(raw: &str, lines: usize) -> Vec<String> {
+    let all: Vec<&str> = raw.lines().collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| strip_ansi(strip_timestamp(line.trim_end_matches('\r'))))
+        .collect()
+}
+
+/// `2024-03-01T10:00:00.1234567Z rest` -> `rest`.
+fn strip_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((stamp, rest))
+            if stamp.len() >= 20
+                && stamp.ends_with('Z')
+                && stamp.as_bytes()[..4].iter().all(u8::is_ascii_digit)
+                && stamp.as_bytes()[10] == b'T' =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// Drop `ESC [ ... letter` sequences (colors, cursor moves).
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Parse a PR reference for any provider: a GitHub PR URL, a GitLab merge request URL,
+/// or `owner/repo#123` (`group/subgroup/project#12` for nested GitLab groups).
+pub fn parse_ref(s: &str) -> Option<(String, String, i64)> {
+    parse_pr_ref(s).or_else(|| parse_mr_ref(s))
+}
+
+/// GitHub through its GraphQL API.
+#[derive(Clone)]
+pub struct GitHubProvider {
+    octo: Octocrab,
+    /// Where attention-set queries go; `octo` unless replaced with [`Self::with_graphql`].
+    graphql: Arc<dyn GraphQlClient>,
+}
+
+impl GitHubProvider {
+    pub fn new(octo: Octocrab) -> Self {
+        Self {
+            graphql: Arc::new(octo.clone()),
+            octo,
+        }
+    }
+
+    /// Send the GraphQL queries behind refreshes to `client` instead (e.g. canned
+    /// responses in tests). Job logs and the token check still use the REST client.
+    pub fn with_graphql(mut self, client: Arc<dyn GraphQlClient>) -> Self {
+        self.graphql = client;
+        self
+    }
+}
+
+impl Provider for GitHubProvider {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn fetch_attention_prs(
+        &self,
+        cutoff_ts: i64,
+        include_team_requests: bool,
+        include_discussions: bool,
+        mode: UsageMode,
+    ) -> BoxFuture<'_, Result<Vec<Pr>, String>> {
+        Box::pin(fetch_attention_prs(
+            &*self.graphql,
+            cutoff_ts,
+            include_team_requests,
+            include_discussions,
+            mode,
+        ))
+    }
+
+    fn fetch_attention_prs_between(
+        &self,
+        from_ts: i64,
+        to_ts: i64,
+        include_team_requests: bool,
+        include_discussions: bool,
+        mode: UsageMode,
+    ) -> BoxFuture<'_, Result<Vec<Pr>, String>> {
+        Box::pin(fetch_attention_prs_between(
+            &*self.graphql,
+            from_ts,
+            to_ts,
+            include_team_requests,
+            include_discussions,
+            mode,
+        ))
+    }
+
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<Pr, String>> {
+        Box::pin(fetch_pr(&*self.graphql, owner, repo, number))
+    }
+
+    fn pr_exists<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        number: i64,
+    ) -> BoxFuture<'a, Result<bool, String>> {
+        Box::pin(pr_exists(&*self.graphql, owner, repo, number))
+    }
+
+    fn default_branch_ci<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+    ) -> BoxFuture<'a, Result<CiState, String>> {
+        Box::pin(default_branch_ci(&*self.graphql, owner, repo))
+    }
+
+    fn check_log<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+        check: &'a CiCheck,
+    ) -> BoxFuture<'a, Result<String, String>> {
+        Box::pin(actions_job_log(&self.octo, owner, repo, check))
+    }
+
+    fn viewer_login(&self) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(async move {
+            validate_token(&self.octo).await.map_err(|e| match e {
+                crate::github::TokenError::Rejected(m)
+                | crate::github::TokenError::Unreachable(m) => m,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_resolve_for_both_hosts() {
+        assert_eq!(
+            parse_ref("https://github.com/acme/api/pull/7"),
+            Some(("acme".to_string(), "api".to_string(), 7))
+        );
+        assert_eq!(
+            parse_ref("https://gitlab.com/acme/platform/api/-/merge_requests/12/diffs"),
+            Some(("acme/platform".to_string(), "api".to_string(), 12))
+        );
+        assert_eq!(
+            parse_ref("acme/platform/api#12"),
+            Some(("acme/platform".to_string(), "api".to_string(), 12))
+        );
+        assert_eq!(ProviderKind::parse("GitLab"), Some(ProviderKind::GitLab));
+        assert_eq!(ProviderKind::parse("bitbucket"), None);
+    }
+
+    #[test]
+    fn log_tails_drop_timestamps_and_colors() {
+        let raw = "2024-03-01T10:00:00.1234567Z first\r\n\
+                   2024-03-01T10:00:01.0000000Z \u{1b}[31merror\u{1b}[0m: test failed\n\
+                   plain line\n";
+        assert_eq!(
+            log_tail(raw, 2),
+            vec!["error: test failed".to_string(), "plain line".to_string()]
+        );
+        assert_eq!(log_tail(raw, 10)[0], "first");
+        assert_eq!(log_tail("", 10), Vec::<String>::new());
+
+        use crate::github::actions_job_id;
+        assert_eq!(
+            actions_job_id("https://github.com/acme/api/actions/runs/11/job/22"),
+            Some(22)
+        );
+        assert_eq!(
+            actions_job_id("https://github.com/acme/api/runs/33?check_suite_focus=true"),
+            Some(33)
+        );
+        assert_eq!(
+            actions_job_id("https://github.com/acme/api/actions/runs/11"),
+            None
+        );
+        assert_eq!(actions_job_id("https://ci.example.com/build/1"), None);
+    }
+}
@@ -1,18 +1,35 @@
-use crate::db::{DbPrRow, delete_prs_not_in, load_all_prs, now_unix, upsert_pr};
-use crate::demo::{generate_demo_prs, next_demo_tick};
-use crate::github::fetch_attention_prs;
-use crate::model::{CiCheck, CiState, Pr, ReviewState};
-use octocrab::Octocrab;
+//! Scoring, categorization, and the refresh pipeline (fetch → score → persist).
+
+use crate::db::{
+    DbPrRow, RepoCi, delete_prs_not_in, is_hashed_text, load_all_prs, load_fetch_digest,
+    load_fetch_mark, load_last_event_times, load_repo_ci, load_tracked_prs, mark_inaccessible,
+    record_check_states, record_pr_event, save_fetch_digest, save_fetch_mark, save_repo_ci,
+    touch_prs, upsert_pr,
+};
+use crate::demo::{DEMO_RED_BASE_REPO, generate_demo_prs, next_demo_tick};
+use crate::github::parse_pr_ref;
+use crate::model::{
+    CiCheck, CiCheckState, CiState, MergeBlockers, Pr, ReviewState, Reviewer, UsageMode,
+};
+use crate::provider::{Provider, parse_ref};
+use crate::sla::SlaStatus;
+use crate::snooze::is_snoozed;
+use crate::timeutil::{Clock, SystemClock};
 use rusqlite::Connection;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 // Scoring constants (single source of truth, also used by TUI help).
 pub const SCORE_REVIEW_REQUESTED: i32 = 50;
+pub const SCORE_TEAM_REVIEW_REQUESTED: i32 = 20;
 pub const SCORE_CI_FAILED_NEW: i32 = 40;
 pub const SCORE_CI_RUNNING_LONG: i32 = 20;
 pub const SCORE_APPROVED_UNMERGED_OLD: i32 = 15;
 pub const SCORE_WAITING_ON_OTHERS_GREEN: i32 = -20;
 pub const SCORE_CI_FAILED_UNCHANGED: i32 = -30;
+pub const SCORE_REPLY_TO_YOU: i32 = 40;
+pub const SCORE_ASSIGNED: i32 = 10;
+pub const SCORE_THREAD_AWAITING_YOU: i32 = 20;
+pub const SCORE_CHANGES_REQUESTED: i32 = 45;
 
 pub const CATEGORY_NEEDS_YOU_MIN: i32 = 40;
 pub const CATEGORY_NO_ACTION_MIN: i32 = 0;
@@ -20,11 +37,51 @@ pub const CATEGORY_NO_ACTION_MIN: i32 = 0;
 pub const CI_RUNNING_LONG_SECS: i64 = 10 * 60;
 pub const APPROVED_UNMERGED_OLD_SECS: i64 = 24 * 3600;
 
+/// How long a PR the code host stopped showing (repository deleted, access revoked) stays
+/// listed, greyed out, before it is dropped from the cache.
+pub const INACCESSIBLE_GRACE_SECS: i64 = 3 * 86_400;
+
+/// Status prefix for PRs with an unread reply to one of the viewer's comments.
+pub const REPLY_BADGE: &str = "💬 reply to you · ";
+
+/// Status prefix for PRs where the viewer was @-mentioned (unless a reply badge is shown).
+pub const MENTION_BADGE: &str = "💬 mentioned · ";
+
+/// Status prefix for review requests into a repository whose default branch is failing CI.
+pub const BASE_RED_BADGE: &str = "🔴 base red · ";
+
+/// How long a repository's default-branch CI state is trusted before it is checked again.
+pub const REPO_CI_TTL_SECS: i64 = 15 * 60;
+
+/// Title shown for cached PRs whose title is stored as a hash (`cache_pr_text = false`).
+pub const HIDDEN_TITLE: &str = "🔒 title not stored";
+
+/// Title of a cached row, or [`HIDDEN_TITLE`] when only its hash is on disk.
+pub fn cached_title(row: &DbPrRow) -> String {
+    if is_hashed_text(&row.title) {
+        HIDDEN_TITLE.to_string()
+    } else {
+        row.title.clone()
+    }
+}
+
+/// Scope name that adds PRs the viewer commented on (`--scope`, `scope = [...]`).
+pub const SCOPE_DISCUSSION: &str = "involves-me:discussion";
+
+/// Org/repo scope applied to every fetched or cached PR.
 #[derive(Debug, Clone, Default)]
 pub struct ScopeFilters {
     pub orgs: Vec<String>,
     pub include_repos: Vec<String>, // owner/repo
     pub exclude_repos: Vec<String>, // owner/repo
+    /// Only PRs in one of these milestones (by title, ignoring case).
+    pub include_milestones: Vec<String>,
+    /// Leave out PRs in these milestones.
+    pub exclude_milestones: Vec<String>,
+    /// `involves-me:discussion`: also fetch PRs the viewer commented on.
+    pub discussion: bool,
+    /// `mode`: leave out the viewer's own PRs or other people's (they are not even fetched).
+    pub mode: UsageMode,
 }
 
 impl ScopeFilters {
@@ -39,11 +96,65 @@ impl ScopeFilters {
         if self.exclude_repos.iter().any(|r| r == &full) {
             return false;
         }
-        true
+        let in_milestone = |milestones: &[String]| {
+            let milestone = pr.milestone.as_deref().unwrap_or_default();
+            milestones.iter().any(|m| m.eq_ignore_ascii_case(milestone))
+        };
+        if !self.include_milestones.is_empty() && !in_milestone(&self.include_milestones) {
+            return false;
+        }
+        if pr.milestone.is_some() && in_milestone(&self.exclude_milestones) {
+            return false;
+        }
+        match self.mode {
+            UsageMode::Full => true,
+            UsageMode::Reviewer => !pr.is_viewer_author,
+            UsageMode::Author => pr.is_viewer_author,
+        }
+    }
+
+    /// Short labels for the active filters (`org:acme`, `-repo:acme/old`, ...).
+    pub fn summary(&self) -> Vec<String> {
+        let mut out: Vec<String> = self.orgs.iter().map(|o| format!("org:{o}")).collect();
+        out.extend(self.include_repos.iter().map(|r| format!("repo:{r}")));
+        out.extend(self.exclude_repos.iter().map(|r| format!("-repo:{r}")));
+        out.extend(
+            self.include_milestones
+                .iter()
+                .map(|m| format!("milestone:{m}")),
+        );
+        out.extend(
+            self.exclude_milestones
+                .iter()
+                .map(|m| format!("-milestone:{m}")),
+        );
+        if self.discussion {
+            out.push(SCOPE_DISCUSSION.to_string());
+        }
+        if self.mode != UsageMode::Full {
+            out.push(format!("mode:{}", self.mode.as_str()));
+        }
+        out
+    }
+
+    /// Identifies what a refresh with these filters fetches (keys the fetch marks).
+    fn fetch_key(&self, include_team_requests: bool) -> String {
+        format!(
+            "orgs={};include={};exclude={};milestones={};-milestones={};discussion={};teams={include_team_requests};mode={}",
+            self.orgs.join(","),
+            self.include_repos.join(","),
+            self.exclude_repos.join(","),
+            self.include_milestones.join(","),
+            self.exclude_milestones.join(","),
+            self.discussion,
+            self.mode.as_str(),
+        )
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// List section a PR is rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Category {
     NeedsYou,
     ReadyToMerge,
@@ -51,7 +162,8 @@ pub enum Category {
     Stale,
 }
 
-#[derive(Debug, Clone)]
+/// A scored PR, ready to render.
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct UiPr {
     pub pr: Pr,
     pub score: i32,
@@ -59,6 +171,70 @@ pub struct UiPr {
     pub display_status: String,
     pub is_new_ci_failure: bool,
     pub is_pinned: bool,
+    /// Review SLA standing, set by [`crate::sla::apply_sla`] when a rule matches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sla: Option<SlaStatus>,
+    /// A draft ranked in the regular sections instead of the DRAFT section
+    /// (set by [`crate::drafts::apply_draft_policy`]).
+    pub draft_ranked: bool,
+    /// A bot PR listed in the BOTS section (set by [`crate::bots::apply_bot_mode`]).
+    pub bot_section: bool,
+    /// The head branch was force-pushed since the user last opened this PR in needle.
+    pub force_pushed_since_view: bool,
+    /// Someone replied to the viewer's comments since the user last opened this PR.
+    pub reply_to_you: bool,
+    /// The user opened this PR in needle (Enter or Tab) since it was last updated.
+    pub opened_since_update: bool,
+    /// Snoozed by the user (see [`crate::snooze`]); listed apart and kept off the bell.
+    pub snoozed: bool,
+    /// When the code host stopped showing this PR; it is a cached tombstone until
+    /// [`INACCESSIBLE_GRACE_SECS`] later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inaccessible_since: Option<i64>,
+    /// Score change from the author's `[scoring.authors]` rule (set by
+    /// [`apply_author_priority`]), so details view can explain the ranking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_priority: Option<i32>,
+}
+
+/// Display order: pinned first, then by score desc, then by updated_at desc.
+pub fn sort_for_display(prs: &mut [UiPr]) {
+    prs.sort_by(|a, b| {
+        b.is_pinned
+            .cmp(&a.is_pinned)
+            .then_with(|| b.score.cmp(&a.score))
+            .then_with(|| b.pr.updated_at_unix.cmp(&a.pr.updated_at_unix))
+    });
+}
+
+/// One `[scoring.authors]` entry: PRs by `author` get `delta` added to their score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorPriority {
+    pub author: String,
+    pub delta: i32,
+}
+
+/// Bot logins show up both with and without the `[bot]` suffix.
+fn same_author(rule: &str, author: &str) -> bool {
+    let bare = |s: &str| s.trim_end_matches("[bot]").to_lowercase();
+    bare(rule) == bare(author)
+}
+
+/// Boost or mute PRs by author, e.g. teammates up and dependabot down. Each PR gets the
+/// delta of its author's rule (logins compare case-insensitively) and is re-categorized.
+pub fn apply_author_priority(prs: &mut [UiPr], rules: &[AuthorPriority]) {
+    if rules.is_empty() {
+        return;
+    }
+    for p in prs.iter_mut() {
+        let Some(rule) = rules.iter().find(|r| same_author(&r.author, &p.pr.author)) else {
+            continue;
+        };
+        p.score += rule.delta;
+        p.category = category_for(&p.pr, p.score);
+        p.author_priority = Some(rule.delta);
+    }
+    sort_for_display(prs);
 }
 
 fn parse_ci_state(s: Option<&str>) -> CiState {
@@ -74,7 +250,9 @@ fn parse_ci_state(s: Option<&str>) -> CiState {
 fn parse_review_state(s: Option<&str>) -> ReviewState {
     match s {
         Some("requested") => ReviewState::Requested,
+        Some("team_requested") => ReviewState::TeamRequested,
         Some("approved") => ReviewState::Approved,
+        Some("changes_requested") => ReviewState::ChangesRequested,
         Some("none") | None => ReviewState::None,
         _ => ReviewState::None,
     }
@@ -90,66 +268,133 @@ pub fn load_cached(
     conn: &Connection,
     cutoff_days: i64,
     scope: &ScopeFilters,
+) -> Result<Vec<UiPr>, String> {
+    load_cached_with_clock(conn, cutoff_days, scope, &SystemClock)
+}
+
+/// [`load_cached`] scored against an explicit clock.
+pub fn load_cached_with_clock(
+    conn: &Connection,
+    cutoff_days: i64,
+    scope: &ScopeFilters,
+    clock: &dyn Clock,
 ) -> Result<Vec<UiPr>, String> {
     let existing: HashMap<String, DbPrRow> = load_all_prs(conn)?;
-    let now = now_unix();
+    let tracked: HashSet<String> = load_tracked_prs(conn)?.into_iter().collect();
+    let force_pushes = load_last_event_times(conn, "force_push")?;
+    let now = clock.now_unix();
     let cutoff_ts = now.saturating_sub(cutoff_days.saturating_mul(86_400));
 
-    let mut out: Vec<UiPr> = Vec::new();
-    for (_k, row) in existing {
-        let updated_at_unix = row.updated_at_unix.or(row.last_seen_at).unwrap_or(now);
-        if updated_at_unix < cutoff_ts {
-            continue;
+    let rows: Vec<DbPrRow> = existing.into_values().collect();
+    let mut out: Vec<UiPr> = par_map(rows, |row| {
+        if is_ignored(&row) || is_expired_tombstone(&row, now) {
+            return None;
         }
-        let pr = Pr {
-            pr_key: row.pr_key.clone(),
-            owner: row.owner.clone(),
-            repo: row.repo.clone(),
-            number: row.number,
-            author: row.author.clone().unwrap_or_else(|| "unknown".to_string()),
-            title: row.title.clone(),
-            url: row.url.clone(),
-            updated_at_unix,
-            last_commit_sha: row.last_commit_sha.clone(),
-            ci_state: parse_ci_state(row.last_ci_state.as_deref()),
-            ci_checks: parse_ci_checks_json(row.ci_checks_json.as_deref()),
-            review_state: parse_review_state(row.last_review_state.as_deref()),
-            is_draft: row.is_draft.unwrap_or(0) != 0,
-            mergeable: row.mergeable.clone(),
-            merge_state_status: row.merge_state_status.clone(),
-            is_viewer_author: db_int_to_bool(row.author_is_viewer),
-            // Merge blockers are computed fresh from GraphQL, not cached
-            merge_blockers: None,
-        };
-        if !scope.matches(&pr) {
-            continue;
+        let ui = from_cache(&row, force_pushes.get(&row.pr_key).copied(), now);
+        let is_tracked = tracked.contains(&row.pr_key);
+        if (ui.pr.updated_at_unix < cutoff_ts || !scope.matches(&ui.pr)) && !is_tracked {
+            return None;
         }
+        Some(ui)
+    })
+    .into_iter()
+    .flatten()
+    .collect();
+
+    mark_red_bases(&mut out, &load_repo_ci(conn)?);
+    sort_for_display(&mut out);
+    Ok(out)
+}
 
-        let is_new_review = false;
-        let is_new_ci_failure = false;
-        let is_pinned = row.pinned.unwrap_or(0) != 0;
-        let score = score_pr(&pr, None, now, is_new_ci_failure);
-        let category = category_for(&pr, score);
-        let display_status = status_text(&pr, now, is_new_ci_failure, is_new_review);
-
-        out.push(UiPr {
-            pr,
-            score,
-            category,
-            display_status,
-            is_new_ci_failure,
-            is_pinned,
-        });
+/// Rebuild a scored PR from its cached row, without touching the network.
+fn from_cache(row: &DbPrRow, last_force_push: Option<i64>, now: i64) -> UiPr {
+    let updated_at_unix = row.updated_at_unix.or(row.last_seen_at).unwrap_or(now);
+    let pr = Pr {
+        pr_key: row.pr_key.clone(),
+        owner: row.owner.clone(),
+        repo: row.repo.clone(),
+        number: row.number,
+        author: row.author.clone().unwrap_or_else(|| "unknown".to_string()),
+        title: cached_title(row),
+        url: row.url.clone(),
+        updated_at_unix,
+        created_at_unix: row.created_at_unix,
+        last_commit_sha: row.last_commit_sha.clone(),
+        ci_state: parse_ci_state(row.last_ci_state.as_deref()),
+        ci_checks: parse_ci_checks_json(row.ci_checks_json.as_deref()),
+        review_state: parse_review_state(row.last_review_state.as_deref()),
+        is_draft: row.is_draft.unwrap_or(0) != 0,
+        mergeable: row.mergeable.clone(),
+        merge_state_status: row.merge_state_status.clone(),
+        is_viewer_author: db_int_to_bool(row.author_is_viewer),
+        is_assignee: db_int_to_bool(row.is_assignee),
+        is_mentioned: db_int_to_bool(row.is_mentioned),
+        is_unsubscribed: db_int_to_bool(row.is_unsubscribed),
+        merge_blockers: row
+            .merge_blockers_json
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok()),
+        labels: parse_labels_json(row.labels_json.as_deref()),
+        review_requested_at_unix: row.review_requested_at_unix,
+        head_ref_name: row.head_ref_name.clone(),
+        base_ref_name: row.base_ref_name.clone(),
+        milestone: row.milestone.clone(),
+        project_status: row.project_status.clone(),
+        ci_from_previous_commit: db_int_to_bool(row.ci_previous_commit),
+        lines_changed: row.lines_changed,
+        last_force_push_at_unix: row.last_force_push_at_unix,
+        last_reply_at_unix: row.last_reply_at_unix,
+        body_excerpt: row.body_excerpt.clone().filter(|b| !is_hashed_text(b)),
+        reviewers: parse_reviewers_json(row.reviewers_json.as_deref()),
+        comments: parse_list_json(row.comments_json.as_deref()),
+        review_threads: parse_list_json(row.review_threads_json.as_deref()),
+    };
+
+    let is_new_review = false;
+    let is_new_ci_failure = false;
+    let is_pinned = row.pinned.unwrap_or(0) != 0;
+    let snoozed = is_snoozed(&pr, Some(row), now);
+    let reply_to_you = has_unread_reply(&pr, Some(row));
+    let opened_since_update = was_opened_since_update(&pr, Some(row));
+    let score = score_pr(&pr, Some(row), now, is_new_ci_failure);
+    let category = category_for(&pr, score);
+    let display_status = match row.inaccessible_since {
+        Some(since) => format!("🚫 no access · {}", human_age(now, since)),
+        None => with_conversation_badge(
+            status_text(&pr, now, is_new_ci_failure, is_new_review),
+            reply_to_you,
+            pr.is_mentioned,
+        ),
+    };
+
+    UiPr {
+        pr,
+        score,
+        category,
+        display_status,
+        is_new_ci_failure,
+        is_pinned,
+        sla: None,
+        draft_ranked: false,
+        bot_section: false,
+        force_pushed_since_view: force_pushed_since_view(Some(row), last_force_push),
+        reply_to_you,
+        opened_since_update,
+        snoozed,
+        inaccessible_since: row.inaccessible_since,
+        author_priority: None,
     }
+}
 
-    // Sort: pinned first, then by score desc, then by updated_at desc
-    out.sort_by(|a, b| {
-        b.is_pinned
-            .cmp(&a.is_pinned)
-            .then_with(|| b.score.cmp(&a.score))
-            .then_with(|| b.pr.updated_at_unix.cmp(&a.pr.updated_at_unix))
-    });
-    Ok(out)
+/// A tombstoned PR (see [`INACCESSIBLE_GRACE_SECS`]) past its grace period.
+fn is_expired_tombstone(row: &DbPrRow, now: i64) -> bool {
+    row.inaccessible_since
+        .is_some_and(|since| now - since >= INACCESSIBLE_GRACE_SECS)
+}
+
+/// The user hid this PR with `i` in the TUI.
+fn is_ignored(row: &DbPrRow) -> bool {
+    row.ignored.unwrap_or(0) != 0
 }
 
 fn ci_to_db(ci: &CiState) -> &'static str {
@@ -172,6 +417,43 @@ fn db_int_to_bool(v: Option<i64>) -> bool {
     v.unwrap_or(0) != 0
 }
 
+fn labels_to_db_json(labels: &[String]) -> Option<String> {
+    if labels.is_empty() {
+        return None;
+    }
+    serde_json::to_string(labels).ok()
+}
+
+fn parse_labels_json(s: Option<&str>) -> Vec<String> {
+    s.and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default()
+}
+
+fn reviewers_to_db_json(reviewers: &[Reviewer]) -> Option<String> {
+    if reviewers.is_empty() {
+        return None;
+    }
+    serde_json::to_string(reviewers).ok()
+}
+
+fn parse_reviewers_json(s: Option<&str>) -> Vec<Reviewer> {
+    s.and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default()
+}
+
+/// JSON column for a list of comments or review threads (`NULL` when empty).
+fn list_to_db_json<T: serde::Serialize>(items: &[T]) -> Option<String> {
+    if items.is_empty() {
+        return None;
+    }
+    serde_json::to_string(items).ok()
+}
+
+fn parse_list_json<T: serde::de::DeserializeOwned>(s: Option<&str>) -> Vec<T> {
+    s.and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default()
+}
+
 fn ci_checks_to_db_json(checks: &[CiCheck]) -> Option<String> {
     if checks.is_empty() {
         return None;
@@ -187,6 +469,11 @@ fn score_pr(pr: &Pr, old: Option<&DbPrRow>, now: i64, is_new_ci_failure: bool) -
         score += SCORE_REVIEW_REQUESTED;
     }
 
+    // +20  review requested from one of the user's teams
+    if matches!(pr.review_state, ReviewState::TeamRequested) {
+        score += SCORE_TEAM_REVIEW_REQUESTED;
+    }
+
     // CI failure scoring
     if matches!(pr.ci_state, CiState::Failure) {
         if is_new_ci_failure {
@@ -205,6 +492,11 @@ fn score_pr(pr: &Pr, old: Option<&DbPrRow>, now: i64, is_new_ci_failure: bool) -
         }
     }
 
+    // +45 a reviewer requested changes on the viewer's PR: the next move is theirs
+    if matches!(pr.review_state, ReviewState::ChangesRequested) && pr.is_viewer_author {
+        score += SCORE_CHANGES_REQUESTED;
+    }
+
     // +15 approved but unmerged for >24h
     if matches!(pr.review_state, ReviewState::Approved) {
         if now.saturating_sub(pr.updated_at_unix) > APPROVED_UNMERGED_OLD_SECS {
@@ -218,39 +510,69 @@ fn score_pr(pr: &Pr, old: Option<&DbPrRow>, now: i64, is_new_ci_failure: bool) -
         score += SCORE_WAITING_ON_OTHERS_GREEN;
     }
 
-    // Note: `old` currently unused beyond is_new_ci_failure; keep signature stable for V1.
-    let _ = old;
+    // +40 someone replied to the viewer's comments since they last opened the PR
+    if has_unread_reply(pr, old) {
+        score += SCORE_REPLY_TO_YOU;
+    }
+
+    // +20 the author answered in a review thread the viewer opened, still unresolved
+    if pr.my_threads_awaiting_reply() > 0 {
+        score += SCORE_THREAD_AWAITING_YOU;
+    }
+
+    // +10 someone else's PR the viewer is assigned to
+    if pr.is_assignee && !pr.is_viewer_author {
+        score += SCORE_ASSIGNED;
+    }
+
     score
 }
 
+/// A reply to the viewer's comments landed after they last opened the PR from needle.
+fn was_opened_since_update(pr: &Pr, old: Option<&DbPrRow>) -> bool {
+    old.and_then(|r| r.last_opened_at)
+        .is_some_and(|at| at >= pr.updated_at_unix)
+}
+
+fn has_unread_reply(pr: &Pr, old: Option<&DbPrRow>) -> bool {
+    let opened = old.and_then(|r| r.last_opened_at);
+    pr.last_reply_at_unix
+        .is_some_and(|t| opened.is_none_or(|o| t > o))
+}
+
+fn with_conversation_badge(status: String, reply_to_you: bool, mentioned: bool) -> String {
+    if reply_to_you {
+        format!("{REPLY_BADGE}{status}")
+    } else if mentioned {
+        format!("{MENTION_BADGE}{status}")
+    } else {
+        status
+    }
+}
+
 fn is_ready_to_merge(pr: &Pr) -> bool {
     if !pr.is_viewer_author || pr.is_draft {
         return false;
     }
-    if !matches!(pr.ci_state, CiState::Success) {
-        return false;
-    }
-    if matches!(pr.review_state, ReviewState::Requested) {
+    // Green CI of the previous commit says nothing about what was just pushed.
+    if !matches!(pr.ci_state, CiState::Success) || pr.ci_from_previous_commit {
         return false;
     }
-
-    let is_blocked = pr
-        .merge_state_status
-        .as_deref()
-        .is_some_and(|s| s.eq_ignore_ascii_case("BLOCKED"));
-    let is_conflicting = pr
-        .mergeable
-        .as_deref()
-        .is_some_and(|s| s.eq_ignore_ascii_case("CONFLICTING"));
-    if is_blocked || is_conflicting {
+    if matches!(
+        pr.review_state,
+        ReviewState::Requested | ReviewState::ChangesRequested
+    ) {
         return false;
     }
 
-    // Treat "approved" or "no outstanding review request / not blocked" as ready.
-    true
+    // Branch protection decides: conflicts, missing approvals and required checks that
+    // are not green all keep the PR out. No blockers recorded means nothing blocks it.
+    pr.merge_blockers
+        .as_ref()
+        .is_none_or(MergeBlockers::is_clear)
 }
 
-fn category_for(pr: &Pr, score: i32) -> Category {
+pub(crate) fn category_for(pr: &Pr, score: i32) -> Category {
     if is_ready_to_merge(pr) {
         Category::ReadyToMerge
     } else if score >= CATEGORY_NEEDS_YOU_MIN {
@@ -290,6 +612,89 @@ fn running_for_secs(pr: &Pr, now: i64) -> i64 {
 }
 
 fn status_text(pr: &Pr, now: i64, is_new_ci_failure: bool, is_new_review_request: bool) -> String {
+    let mut status = base_status_text(pr, now, is_new_ci_failure, is_new_review_request);
+    if let Some(waiting) = waiting_on(pr, now) {
+        status = format!("{status} · {waiting}");
+    }
+    if let Some(summary) = checks_summary(pr) {
+        status = format!("{status} · {summary}");
+    }
+    match pr.unresolved_threads() {
+        0 => status,
+        n => format!("{status} · 🧵 {n} unresolved"),
+    }
+}
+
+/// Who the viewer's own PR is waiting on: themselves when a reviewer requested changes,
+/// otherwise the requested reviewers who have not reviewed yet, e.g. `⏳ waiting on @bob (2d)`.
+fn waiting_on(pr: &Pr, now: i64) -> Option<String> {
+    if !pr.is_viewer_author || pr.is_draft || is_ready_to_merge(pr) {
+        return None;
+    }
+    let with_state = |state: &str| -> Vec<String> {
+        pr.reviewers
+            .iter()
+            .filter(|r| r.state == state)
+            .map(|r| format!("@{}", r.login))
+            .collect()
+    };
+    let changes = with_state("CHANGES_REQUESTED");
+    if !changes.is_empty() {
+        return Some(format!(
+            "↩️ waiting on you ({} requested changes)",
+            people(&changes)
+        ));
+    }
+    let pending = with_state("REQUESTED");
+    if pending.is_empty() {
+        return None;
+    }
+    let since = pr
+        .review_requested_at_unix
+        .or(pr.created_at_unix)
+        .unwrap_or(pr.updated_at_unix);
+    let age = human_age(now, since);
+    Some(format!(
+        "⏳ waiting on {} ({})",
+        people(&pending),
+        age.trim_end_matches(" ago")
+    ))
+}
+
+/// `@a`, `@a, @b`, or `@a +2` for longer lists.
+fn people(logins: &[String]) -> String {
+    match logins {
+        [a, b] => format!("{a}, {b}"),
+        [a, rest @ ..] if !rest.is_empty() => format!("{a} +{}", rest.len()),
+        _ => logins.join(", "),
+    }
+}
+
+/// Shape of the viewer's own pipeline, e.g. `2✗ 1🟡 9✓`; counts of zero are left out.
+fn checks_summary(pr: &Pr) -> Option<String> {
+    if !pr.is_viewer_author || pr.ci_checks.is_empty() {
+        return None;
+    }
+    let count =
+        |want: fn(&CiCheckState) -> bool| pr.ci_checks.iter().filter(|c| want(&c.state)).count();
+    let parts: Vec<String> = [
+        (count(|s| matches!(s, CiCheckState::Failure)), "✗"),
+        (count(|s| matches!(s, CiCheckState::Running)), "🟡"),
+        (count(|s| matches!(s, CiCheckState::Success)), "✓"),
+    ]
+    .into_iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, mark)| format!("{n}{mark}"))
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+fn base_status_text(
+    pr: &Pr,
+    now: i64,
+    is_new_ci_failure: bool,
+    is_new_review_request: bool,
+) -> String {
     if is_new_review_request && matches!(pr.review_state, ReviewState::Requested) {
         return "👀 review requested".to_string();
     }
@@ -298,7 +703,11 @@ fn status_text(pr: &Pr, now: i64, is_new_ci_failure: bool, is_new_review_request
         return format!("✅ ready to merge ({})", human_age(now, pr.updated_at_unix));
     }
 
-    match pr.ci_state {
+    if matches!(pr.review_state, ReviewState::ChangesRequested) {
+        return "🛑 changes requested".to_string();
+    }
+
+    let ci = match pr.ci_state {
         CiState::Failure => {
             if is_new_ci_failure {
                 "❌ CI failed (new)".to_string()
@@ -312,6 +721,45 @@ fn status_text(pr: &Pr, now: i64, is_new_ci_failure: bool, is_new_review_request
         }
         CiState::Success => format!("✅ green ({})", human_age(now, pr.updated_at_unix)),
         CiState::None => format!("⏺ none ({})", human_age(now, pr.updated_at_unix)),
+    };
+    if pr.ci_from_previous_commit {
+        format!("{ci} (previous commit)")
+    } else {
+        ci
+    }
+}
+
+/// Record a head SHA change since the previous refresh in `pr_events`.
+///
+/// GitHub only reports the latest force-push; it explains the change when it happened
+/// after the previous refresh. Returns the latest force-push needle has noticed.
+fn record_head_change(
+    conn: &Connection,
+    pr: &Pr,
+    old: Option<&DbPrRow>,
+    last_force_push: Option<i64>,
+    now: i64,
+) -> Result<Option<i64>, String> {
+    let Some(old) = old else {
+        return Ok(last_force_push);
+    };
+    let old_sha = old.last_commit_sha.as_deref();
+    let new_sha = pr.last_commit_sha.as_deref();
+    if old_sha.is_none() || new_sha.is_none() || old_sha == new_sha {
+        return Ok(last_force_push);
+    }
+    let since = old.last_seen_at.unwrap_or(0);
+    let forced = pr.last_force_push_at_unix.is_some_and(|t| t >= since);
+    let kind = if forced { "force_push" } else { "push" };
+    record_pr_event(conn, &pr.pr_key, kind, old_sha, new_sha, now)?;
+    Ok(if forced { Some(now) } else { last_force_push })
+}
+
+/// A force-push landed after the user last opened the PR from needle.
+fn force_pushed_since_view(old: Option<&DbPrRow>, last_force_push: Option<i64>) -> bool {
+    match (old.and_then(|r| r.last_opened_at), last_force_push) {
+        (Some(opened), Some(pushed)) => pushed > opened,
+        _ => false,
     }
 }
 
@@ -324,7 +772,8 @@ fn is_new_review_request(pr: &Pr, old: Option<&DbPrRow>) -> bool {
 }
 
 fn is_new_ci_failure(pr: &Pr, old: Option<&DbPrRow>) -> bool {
-    if !matches!(pr.ci_state, CiState::Failure) {
+    // A failure carried over from the previous commit is old news.
+    if !matches!(pr.ci_state, CiState::Failure) || pr.ci_from_previous_commit {
         return false;
     }
     let Some(old) = old else { return true };
@@ -332,168 +781,610 @@ fn is_new_ci_failure(pr: &Pr, old: Option<&DbPrRow>) -> bool {
     let old_sha = old.last_commit_sha.as_deref();
     let new_sha = pr.last_commit_sha.as_deref();
     let commit_changed = old_sha != new_sha;
+    // The cached failure was the previous commit's; this one is the head's own.
+    let was_previous_commit = db_int_to_bool(old.ci_previous_commit);
     // "CI failed AND state changed since last_seen" OR "New commit pushed (resets CI relevance)"
-    old_ci != Some("failure") || commit_changed
+    old_ci != Some("failure") || commit_changed || was_previous_commit
 }
 
+/// Fetch the attention set from the code host, score it against the cache, and persist it.
+///
+/// Returns PRs sorted pinned-first, then by score and recency.
 pub async fn refresh(
     conn: &Connection,
-    octo: &Octocrab,
+    provider: &dyn Provider,
     cutoff_days: i64,
     scope: &ScopeFilters,
     include_team_requests: bool,
 ) -> Result<Vec<UiPr>, String> {
-    let existing: HashMap<String, DbPrRow> = load_all_prs(conn)?;
-    let now = now_unix();
+    refresh_with_clock(
+        conn,
+        provider,
+        cutoff_days,
+        scope,
+        include_team_requests,
+        &SystemClock,
+    )
+    .await
+}
 
-    let mut out: Vec<UiPr> = Vec::new();
+/// [`refresh`] scored against an explicit clock.
+pub async fn refresh_with_clock(
+    conn: &Connection,
+    provider: &dyn Provider,
+    cutoff_days: i64,
+    scope: &ScopeFilters,
+    include_team_requests: bool,
+    clock: &dyn Clock,
+) -> Result<Vec<UiPr>, String> {
+    let existing: HashMap<String, DbPrRow> = load_all_prs(conn)?;
+    let force_pushes = load_last_event_times(conn, "force_push")?;
+    let now = clock.now_unix();
 
     let cutoff_ts = now.saturating_sub(cutoff_days.saturating_mul(86_400));
-    let prs = fetch_attention_prs(octo, cutoff_ts, include_team_requests).await?;
-
-    let prs: Vec<Pr> = prs
+    let prs = provider
+        .fetch_attention_prs(
+            cutoff_ts,
+            include_team_requests,
+            scope.discussion,
+            scope.mode,
+        )
+        .await?;
+
+    let mut prs: Vec<Pr> = prs
         .into_iter()
         .filter(|p| p.updated_at_unix >= cutoff_ts)
         .filter(|p| scope.matches(p))
         .collect();
-    let keep_keys: Vec<String> = prs.iter().map(|p| p.pr_key.clone()).collect();
 
-    for pr in prs {
-        let old = existing.get(&pr.pr_key);
-        let new_review = is_new_review_request(&pr, old);
-        let new_ci_failure = is_new_ci_failure(&pr, old);
-        let is_pinned = old.and_then(|r| r.pinned).unwrap_or(0) != 0;
+    // Tracked PRs bypass the cutoff and scope filters: the user asked for them explicitly.
+    for key in load_tracked_prs(conn)? {
+        if prs.iter().any(|p| p.pr_key == key) {
+            continue;
+        }
+        let Some((owner, repo, number)) = parse_ref(&key) else {
+            continue;
+        };
+        // A tracked PR that can no longer be fetched (deleted repo, lost access) should not
+        // fail the whole refresh; it simply drops out until it is fetchable again.
+        if let Ok(pr) = provider.fetch_pr(&owner, &repo, number).await {
+            prs.push(pr);
+        }
+    }
+    let mut keep_keys: Vec<String> = prs.iter().map(|p| p.pr_key.clone()).collect();
+
+    // Idle accounts mostly fetch what they fetched last time. Then there is nothing to write
+    // back and no PR went missing; only scoring runs again, since ages and snoozes move on.
+    // Tombstones past their grace period still need the full pass to be dropped.
+    let scope_key = scope.fetch_key(include_team_requests);
+    let digest = fetch_digest(&prs);
+    let unchanged = load_fetch_digest(conn, &scope_key)?.as_deref() == Some(digest.as_str());
+    if unchanged && !existing.values().any(|row| is_expired_tombstone(row, now)) {
+        touch_prs(conn, &keep_keys, now)?;
+        let mut out = score_unchanged(prs, &existing, &force_pushes, now);
+        let repo_ci = refresh_repo_ci(conn, provider, &out, now).await?;
+        mark_red_bases(&mut out, &repo_ci);
+        sort_for_display(&mut out);
+        return Ok(out);
+    }
 
-        let db_row = DbPrRow {
-            pr_key: pr.pr_key.clone(),
-            owner: pr.owner.clone(),
-            repo: pr.repo.clone(),
-            number: pr.number,
-            title: pr.title.clone(),
-            url: pr.url.clone(),
-            author: Some(pr.author.clone()),
-            updated_at_unix: Some(pr.updated_at_unix),
-            last_commit_sha: pr.last_commit_sha.clone(),
-            last_ci_state: Some(ci_to_db(&pr.ci_state).to_string()),
-            last_review_state: Some(review_to_db(&pr.review_state).to_string()),
-            ci_checks_json: ci_checks_to_db_json(&pr.ci_checks),
-            is_draft: Some(draft_to_db(pr.is_draft)),
-            mergeable: pr.mergeable.clone(),
-            merge_state_status: pr.merge_state_status.clone(),
-            author_is_viewer: Some(viewer_author_to_db(pr.is_viewer_author)),
-            last_seen_at: Some(now),
-            last_opened_at: old.and_then(|r| r.last_opened_at),
-            pinned: old.and_then(|r| r.pinned),
-        };
-        upsert_pr(conn, &db_row, now)?;
-
-        let score = score_pr(&pr, old, now, new_ci_failure);
-        let category = category_for(&pr, score);
-        let display_status = status_text(&pr, now, new_ci_failure, new_review);
-
-        out.push(UiPr {
-            pr,
-            score,
-            category,
-            display_status,
-            is_new_ci_failure: new_ci_failure,
-            is_pinned,
-        });
+    let mut out = persist_and_score_all(conn, prs, &existing, &force_pushes, now)?;
+
+    // PRs that should still be in the attention set but are not were usually merged or
+    // closed. The ones the host no longer shows at all (deleted repository, revoked access)
+    // stay listed as greyed-out tombstones for a grace period instead.
+    for row in existing.values() {
+        if keep_keys.contains(&row.pr_key) || is_ignored(row) {
+            continue;
+        }
+        let cached = from_cache(row, force_pushes.get(&row.pr_key).copied(), now);
+        let since = match row.inaccessible_since {
+            Some(since) => since,
+            None if cached.pr.updated_at_unix >= cutoff_ts && scope.matches(&cached.pr) => {
+                // Errors say nothing about the PR; it drops out like before.
+                if provider
+                    .pr_exists(&row.owner, &row.repo, row.number)
+                    .await
+                    .unwrap_or(true)
+                {
+                    continue;
+                }
+                mark_inaccessible(conn, &row.pr_key, now)?;
+                now
+            }
+            None => continue,
+        };
+        if now - since < INACCESSIBLE_GRACE_SECS {
+            keep_keys.push(row.pr_key.clone());
+            out.push(from_cache(
+                &DbPrRow {
+                    inaccessible_since: Some(since),
+                    ..row.clone()
+                },
+                force_pushes.get(&row.pr_key).copied(),
+                now,
+            ));
+        }
     }
 
+    let repo_ci = refresh_repo_ci(conn, provider, &out, now).await?;
+    mark_red_bases(&mut out, &repo_ci);
+
     // Keep cache consistent with the current attention set so cached startup doesn't show stale/irrelevant PRs.
     delete_prs_not_in(conn, &keep_keys)?;
+    save_fetch_mark(conn, &scope_key, cutoff_ts, now)?;
+    save_fetch_digest(conn, &scope_key, &digest)?;
 
-    // Sort: pinned first, then by score desc, then by updated_at desc
-    out.sort_by(|a, b| {
-        b.is_pinned
-            .cmp(&a.is_pinned)
-            .then_with(|| b.score.cmp(&a.score))
-            .then_with(|| b.pr.updated_at_unix.cmp(&a.pr.updated_at_unix))
-    });
+    sort_for_display(&mut out);
 
     Ok(out)
 }
 
-pub fn refresh_demo(
+/// Fingerprint of a fetched attention set (SHA-256, so it stays comparable across builds):
+/// every fetched field of every PR, in key order. Checks, reviewers and merge states change
+/// without bumping `updatedAt`, and anything that changed has to reach the cache.
+fn fetch_digest(prs: &[Pr]) -> String {
+    let mut entries: Vec<(&str, String)> = prs
+        .iter()
+        .map(|p| {
+            (
+                p.pr_key.as_str(),
+                serde_json::to_string(p).unwrap_or_default(),
+            )
+        })
+        .collect();
+    entries.sort();
+    let text: Vec<&str> = entries.iter().map(|(_, json)| json.as_str()).collect();
+    let digest = ring::digest::digest(&ring::digest::SHA256, text.join("\n").as_bytes());
+    digest.as_ref().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// [`persist_and_score_all`] for a refresh that fetched exactly what the cache holds: the
+/// same scores without the writes. Tombstoned PRs in their grace period stay listed.
+fn score_unchanged(
+    prs: Vec<Pr>,
+    existing: &HashMap<String, DbPrRow>,
+    force_pushes: &HashMap<String, i64>,
+    now: i64,
+) -> Vec<UiPr> {
+    let fetched: HashSet<String> = prs.iter().map(|p| p.pr_key.clone()).collect();
+    let prs: Vec<Pr> = prs
+        .into_iter()
+        .filter(|p| !existing.get(&p.pr_key).is_some_and(is_ignored))
+        .collect();
+    let mut out = par_map(prs, |pr| {
+        let old = existing.get(&pr.pr_key);
+        let last_force_push = force_pushes.get(&pr.pr_key).copied();
+        score_fetched(pr, old, last_force_push, now)
+    });
+    out.extend(
+        existing
+            .values()
+            .filter(|row| row.inaccessible_since.is_some() && !fetched.contains(&row.pr_key))
+            .filter(|row| !is_ignored(row))
+            .map(|row| from_cache(row, force_pushes.get(&row.pr_key).copied(), now)),
+    );
+    out
+}
+
+/// Whether the CI of `p`'s target branch matters: a review request the viewer has yet to
+/// do.
+fn wants_base_ci(p: &UiPr) -> bool {
+    matches!(
+        p.pr.review_state,
+        ReviewState::Requested | ReviewState::TeamRequested
+    ) && p.inaccessible_since.is_none()
+}
+
+/// Check the default-branch CI of each repository with a review request, unless it was
+/// checked less than [`REPO_CI_TTL_SECS`] ago. A failed check keeps the last known state.
+async fn refresh_repo_ci(
+    conn: &Connection,
+    provider: &dyn Provider,
+    prs: &[UiPr],
+    now: i64,
+) -> Result<HashMap<String, RepoCi>, String> {
+    let mut known = load_repo_ci(conn)?;
+    let repos: BTreeSet<(&str, &str)> = prs
+        .iter()
+        .filter(|p| wants_base_ci(p))
+        .map(|p| (p.pr.owner.as_str(), p.pr.repo.as_str()))
+        .collect();
+    for (owner, repo) in repos {
+        let key = format!("{owner}/{repo}");
+        if known
+            .get(&key)
+            .is_some_and(|c| now - c.checked_at < REPO_CI_TTL_SECS)
+        {
+            continue;
+        }
+        let Ok(state) = provider.default_branch_ci(owner, repo).await else {
+            continue;
+        };
+        save_repo_ci(conn, &key, state.as_str(), now)?;
+        known.insert(
+            key,
+            RepoCi {
+                ci_state: state.as_str().to_string(),
+                checked_at: now,
+            },
+        );
+    }
+    Ok(known)
+}
+
+/// Badge review requests into a repository whose default branch is red, so their own
+/// failing checks are read with that in mind.
+fn mark_red_bases(prs: &mut [UiPr], repo_ci: &HashMap<String, RepoCi>) {
+    for p in prs.iter_mut().filter(|p| wants_base_ci(p)) {
+        let key = format!("{}/{}", p.pr.owner, p.pr.repo);
+        if repo_ci
+            .get(&key)
+            .is_some_and(|c| c.ci_state == CiState::Failure.as_str())
+        {
+            p.display_status = format!("{BASE_RED_BADGE}{}", p.display_status);
+        }
+    }
+}
+
+/// Before rendering from cache with a larger `--days` than last time, fetch just the
+/// older window the cache is missing, so the first frame already shows those PRs.
+///
+/// Returns how many PRs were added. Does nothing on the first run or when the window did
+/// not grow; the regular refresh covers those.
+pub async fn warm_cache_for_cutoff(
     conn: &Connection,
+    provider: &dyn Provider,
     cutoff_days: i64,
     scope: &ScopeFilters,
-) -> Result<Vec<UiPr>, String> {
-    let existing: HashMap<String, DbPrRow> = load_all_prs(conn)?;
-    let now = now_unix();
+    include_team_requests: bool,
+) -> Result<usize, String> {
+    let now = SystemClock.now_unix();
     let cutoff_ts = now.saturating_sub(cutoff_days.saturating_mul(86_400));
+    let key = scope.fetch_key(include_team_requests);
+    let Some(covered_since) = load_fetch_mark(conn, &key)? else {
+        return Ok(0);
+    };
+    if covered_since <= cutoff_ts {
+        return Ok(0);
+    }
 
-    let tick = next_demo_tick();
-    let prs = generate_demo_prs(now, tick);
-    let prs: Vec<Pr> = prs
-        .into_iter()
-        .filter(|p| p.updated_at_unix >= cutoff_ts)
-        .filter(|p| scope.matches(p))
-        .collect();
-    let keep_keys: Vec<String> = prs.iter().map(|p| p.pr_key.clone()).collect();
+    let prs = provider
+        .fetch_attention_prs_between(
+            cutoff_ts,
+            covered_since,
+            include_team_requests,
+            scope.discussion,
+            scope.mode,
+        )
+        .await?;
+    let existing = load_all_prs(conn)?;
+    let mut added = 0;
+    for pr in prs.iter().filter(|p| scope.matches(p)) {
+        persist_fetched(conn, pr, existing.get(&pr.pr_key), None, now)?;
+        added += 1;
+    }
+    save_fetch_mark(conn, &key, cutoff_ts, now)?;
+    Ok(added)
+}
+
+/// Store a freshly fetched PR in the cache. Returns when it was last force-pushed.
+fn persist_fetched(
+    conn: &Connection,
+    pr: &Pr,
+    old: Option<&DbPrRow>,
+    last_force_push: Option<i64>,
+    now: i64,
+) -> Result<Option<i64>, String> {
+    let last_force_push = record_head_change(conn, pr, old, last_force_push, now)?;
+    if let Some(sha) = pr.last_commit_sha.as_deref() {
+        let checks = pr
+            .ci_checks
+            .iter()
+            .map(|c| (c.name.as_str(), c.state.is_failure()));
+        record_check_states(conn, &pr.pr_key, sha, checks, now)?;
+    }
+    let db_row = DbPrRow {
+        pr_key: pr.pr_key.clone(),
+        owner: pr.owner.clone(),
+        repo: pr.repo.clone(),
+        number: pr.number,
+        title: pr.title.clone(),
+        url: pr.url.clone(),
+        author: Some(pr.author.clone()),
+        updated_at_unix: Some(pr.updated_at_unix),
+        created_at_unix: pr.created_at_unix,
+        last_commit_sha: pr.last_commit_sha.clone(),
+        last_ci_state: Some(ci_to_db(&pr.ci_state).to_string()),
+        last_review_state: Some(review_to_db(&pr.review_state).to_string()),
+        ci_checks_json: ci_checks_to_db_json(&pr.ci_checks),
+        is_draft: Some(draft_to_db(pr.is_draft)),
+        mergeable: pr.mergeable.clone(),
+        merge_state_status: pr.merge_state_status.clone(),
+        author_is_viewer: Some(viewer_author_to_db(pr.is_viewer_author)),
+        is_assignee: Some(i64::from(pr.is_assignee)),
+        is_mentioned: Some(i64::from(pr.is_mentioned)),
+        is_unsubscribed: Some(i64::from(pr.is_unsubscribed)),
+        merge_blockers_json: pr
+            .merge_blockers
+            .as_ref()
+            .and_then(|b| serde_json::to_string(b).ok()),
+        labels_json: labels_to_db_json(&pr.labels),
+        review_requested_at_unix: pr.review_requested_at_unix,
+        head_ref_name: pr.head_ref_name.clone(),
+        base_ref_name: pr.base_ref_name.clone(),
+        milestone: pr.milestone.clone(),
+        project_status: pr.project_status.clone(),
+        ci_previous_commit: Some(i64::from(pr.ci_from_previous_commit)),
+        lines_changed: pr.lines_changed,
+        last_force_push_at_unix: pr.last_force_push_at_unix,
+        last_reply_at_unix: pr.last_reply_at_unix,
+        body_excerpt: pr.body_excerpt.clone(),
+        reviewers_json: reviewers_to_db_json(&pr.reviewers),
+        comments_json: list_to_db_json(&pr.comments),
+        review_threads_json: list_to_db_json(&pr.review_threads),
+        last_seen_at: Some(now),
+        last_opened_at: old.and_then(|r| r.last_opened_at),
+        pinned: old.and_then(|r| r.pinned),
+        snoozed_until: old.and_then(|r| r.snoozed_until),
+        snoozed_sha: old.and_then(|r| r.snoozed_sha.clone()),
+        ignored: old.and_then(|r| r.ignored),
+        inaccessible_since: None,
+    };
+    upsert_pr(conn, &db_row, now)?;
+    Ok(last_force_push)
+}
 
-    let mut out: Vec<UiPr> = Vec::new();
+/// Score a freshly fetched PR against its previous cache row.
+fn score_fetched(pr: Pr, old: Option<&DbPrRow>, last_force_push: Option<i64>, now: i64) -> UiPr {
+    let new_review = is_new_review_request(&pr, old);
+    let new_ci_failure = is_new_ci_failure(&pr, old);
+    let is_pinned = old.and_then(|r| r.pinned).unwrap_or(0) != 0;
+    let snoozed = is_snoozed(&pr, old, now);
+    let force_pushed_since_view = force_pushed_since_view(old, last_force_push);
+
+    let reply_to_you = has_unread_reply(&pr, old);
+    let opened_since_update = was_opened_since_update(&pr, old);
+    let score = score_pr(&pr, old, now, new_ci_failure);
+    let category = category_for(&pr, score);
+    let display_status = with_conversation_badge(
+        status_text(&pr, now, new_ci_failure, new_review),
+        reply_to_you,
+        pr.is_mentioned,
+    );
+
+    UiPr {
+        pr,
+        score,
+        category,
+        display_status,
+        is_new_ci_failure: new_ci_failure,
+        is_pinned,
+        sla: None,
+        draft_ranked: false,
+        bot_section: false,
+        force_pushed_since_view,
+        reply_to_you,
+        opened_since_update,
+        snoozed,
+        inaccessible_since: None,
+        author_priority: None,
+    }
+}
 
+/// Persist fetched PRs one by one (SQLite wants a single writer), then score them in
+/// parallel. Ignored PRs are persisted but left out of the result.
+fn persist_and_score_all(
+    conn: &Connection,
+    prs: Vec<Pr>,
+    existing: &HashMap<String, DbPrRow>,
+    force_pushes: &HashMap<String, i64>,
+    now: i64,
+) -> Result<Vec<UiPr>, String> {
+    let mut fetched = Vec::with_capacity(prs.len());
     for pr in prs {
         let old = existing.get(&pr.pr_key);
-        let new_review = is_new_review_request(&pr, old);
-        let new_ci_failure = is_new_ci_failure(&pr, old);
-        let is_pinned = old.and_then(|r| r.pinned).unwrap_or(0) != 0;
+        let last = force_pushes.get(&pr.pr_key).copied();
+        let last_force_push = persist_fetched(conn, &pr, old, last, now)?;
+        if !old.is_some_and(is_ignored) {
+            fetched.push((pr, last_force_push));
+        }
+    }
+    Ok(par_map(fetched, |(pr, last_force_push)| {
+        let old = existing.get(&pr.pr_key);
+        score_fetched(pr, old, last_force_push, now)
+    }))
+}
 
-        let db_row = DbPrRow {
-            pr_key: pr.pr_key.clone(),
-            owner: pr.owner.clone(),
-            repo: pr.repo.clone(),
-            number: pr.number,
-            title: pr.title.clone(),
-            url: pr.url.clone(),
-            author: Some(pr.author.clone()),
-            updated_at_unix: Some(pr.updated_at_unix),
-            last_commit_sha: pr.last_commit_sha.clone(),
-            last_ci_state: Some(ci_to_db(&pr.ci_state).to_string()),
-            last_review_state: Some(review_to_db(&pr.review_state).to_string()),
-            ci_checks_json: ci_checks_to_db_json(&pr.ci_checks),
-            is_draft: Some(draft_to_db(pr.is_draft)),
-            mergeable: pr.mergeable.clone(),
-            merge_state_status: pr.merge_state_status.clone(),
-            author_is_viewer: Some(viewer_author_to_db(pr.is_viewer_author)),
-            last_seen_at: Some(now),
-            last_opened_at: old.and_then(|r| r.last_opened_at),
-            pinned: old.and_then(|r| r.pinned),
-        };
-        upsert_pr(conn, &db_row, now)?;
-
-        let score = score_pr(&pr, old, now, new_ci_failure);
-        let category = category_for(&pr, score);
-        let display_status = status_text(&pr, now, new_ci_failure, new_review);
-
-        out.push(UiPr {
-            pr,
-            score,
-            category,
-            display_status,
-            is_new_ci_failure: new_ci_failure,
-            is_pinned,
-        });
+/// Below this many items `par_map` stays on the calling thread; spawning would cost
+/// more than it saves.
+const PARALLEL_MIN_ITEMS: usize = 200;
+
+/// Order-preserving parallel map over scoped threads, one chunk per core.
+fn par_map<T: Send, U: Send>(items: Vec<T>, f: impl Fn(T) -> U + Sync) -> Vec<U> {
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if items.len() < PARALLEL_MIN_ITEMS || workers < 2 {
+        return items.into_iter().map(f).collect();
+    }
+    let chunk_len = items.len().div_ceil(workers);
+    let mut chunks: Vec<Vec<T>> = Vec::with_capacity(workers);
+    let mut rest = items.into_iter();
+    loop {
+        let chunk: Vec<T> = rest.by_ref().take(chunk_len).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
     }
+    let f = &f;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| s.spawn(move || chunk.into_iter().map(f).collect::<Vec<U>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("scoring worker panicked"))
+            .collect()
+    })
+}
 
-    delete_prs_not_in(conn, &keep_keys)?;
+/// Re-fetch one PR (the details view auto-refresh) and update its cached row.
+///
+/// Much cheaper than [`refresh`]: a single GraphQL query instead of the attention-set
+/// searches. The rest of the cache is left alone.
+pub async fn refresh_single(
+    conn: &Connection,
+    provider: &dyn Provider,
+    pr_key: &str,
+) -> Result<UiPr, String> {
+    let (owner, repo, number) =
+        parse_ref(pr_key).ok_or_else(|| format!("Not a {} PR link: {pr_key}", provider.name()))?;
+    let mut pr = provider.fetch_pr(&owner, &repo, number).await?;
+    let old = load_all_prs(conn)?.remove(&pr.pr_key);
+    keep_team_request(&mut pr, old.as_ref());
+    let last_force_push = load_last_event_times(conn, "force_push")?
+        .get(&pr.pr_key)
+        .copied();
+    let now = SystemClock.now_unix();
+    let last_force_push = persist_fetched(conn, &pr, old.as_ref(), last_force_push, now)?;
+    let mut ui = score_fetched(pr, old.as_ref(), last_force_push, now);
+    mark_red_bases(std::slice::from_mut(&mut ui), &load_repo_ci(conn)?);
+    Ok(ui)
+}
 
-    // Sort: pinned first, then by score desc, then by updated_at desc
-    out.sort_by(|a, b| {
-        b.is_pinned
-            .cmp(&a.is_pinned)
-            .then_with(|| b.score.cmp(&a.score))
-            .then_with(|| b.pr.updated_at_unix.cmp(&a.pr.updated_at_unix))
-    });
-    Ok(out)
+/// A single PR fetch cannot tell team requests apart, so a PR the last list refresh found
+/// through the viewer's team stays team-requested while a review request is pending.
+fn keep_team_request(pr: &mut Pr, old: Option<&DbPrRow>) {
+    let was_team = old.and_then(|r| r.last_review_state.as_deref()) == Some("team_requested");
+    let pending = pr.reviewers.iter().any(|r| r.state == "REQUESTED");
+    if was_team && pending && !matches!(pr.review_state, ReviewState::Requested) {
+        pr.review_state = ReviewState::TeamRequested;
+    }
 }
 
-#[cfg(test)]
+/// Same as [`refresh_single`], but against generated demo data.
+pub fn refresh_single_demo(conn: &Connection, pr_key: &str) -> Result<UiPr, String> {
+    let now = SystemClock.now_unix();
+    let pr = generate_demo_prs(now, next_demo_tick())
+        .into_iter()
+        .find(|p| p.pr_key == pr_key)
+        .ok_or_else(|| format!("PR {pr_key} not found in demo data"))?;
+    let old = load_all_prs(conn)?.remove(pr_key);
+    let last_force_push = load_last_event_times(conn, "force_push")?
+        .get(pr_key)
+        .copied();
+    let last_force_push = persist_fetched(conn, &pr, old.as_ref(), last_force_push, now)?;
+    Ok(score_fetched(pr, old.as_ref(), last_force_push, now))
+}
+
+/// Fetch and score a single PR by reference (URL or `owner/repo#123`).
+///
+/// Works for PRs outside the attention set; the cache is read for "new" signals but not written.
+pub async fn lookup_pr(
+    conn: &Connection,
+    provider: &dyn Provider,
+    pr_ref: &str,
+) -> Result<UiPr, String> {
+    let (owner, repo, number) =
+        parse_ref(pr_ref).ok_or_else(|| format!("Not a {} PR link: {pr_ref}", provider.name()))?;
+    let pr = provider.fetch_pr(&owner, &repo, number).await?;
+    let existing = load_all_prs(conn)?;
+    let old = existing.get(&pr.pr_key);
+    Ok(score_single(pr, old, SystemClock.now_unix()))
+}
+
+/// Same as [`lookup_pr`], but resolved against generated demo data.
+pub fn lookup_demo_pr(pr_ref: &str) -> Result<UiPr, String> {
+    let (owner, repo, number) =
+        parse_pr_ref(pr_ref).ok_or_else(|| format!("Not a GitHub PR link: {pr_ref}"))?;
+    let key = format!("{owner}/{repo}#{number}");
+    let now = SystemClock.now_unix();
+    generate_demo_prs(now, 0)
+        .into_iter()
+        .find(|p| p.pr_key == key)
+        .map(|pr| score_single(pr, None, now))
+        .ok_or_else(|| format!("PR {key} not found in demo data"))
+}
+
+fn score_single(pr: Pr, old: Option<&DbPrRow>, now: i64) -> UiPr {
+    let new_review = is_new_review_request(&pr, old);
+    let new_ci_failure = is_new_ci_failure(&pr, old);
+    let is_pinned = old.and_then(|r| r.pinned).unwrap_or(0) != 0;
+    let snoozed = is_snoozed(&pr, old, now);
+    let reply_to_you = has_unread_reply(&pr, old);
+    let opened_since_update = was_opened_since_update(&pr, old);
+    let score = score_pr(&pr, old, now, new_ci_failure);
+    let category = category_for(&pr, score);
+    let display_status = with_conversation_badge(
+        status_text(&pr, now, new_ci_failure, new_review),
+        reply_to_you,
+        pr.is_mentioned,
+    );
+    UiPr {
+        pr,
+        score,
+        category,
+        display_status,
+        is_new_ci_failure: new_ci_failure,
+        is_pinned,
+        sla: None,
+        draft_ranked: false,
+        bot_section: false,
+        force_pushed_since_view: false,
+        reply_to_you,
+        opened_since_update,
+        snoozed,
+        inaccessible_since: None,
+        author_priority: None,
+    }
+}
+
+/// Same as [`refresh`], but with generated demo data instead of GitHub.
+pub fn refresh_demo(
+    conn: &Connection,
+    cutoff_days: i64,
+    scope: &ScopeFilters,
+) -> Result<Vec<UiPr>, String> {
+    refresh_demo_with_clock(conn, cutoff_days, scope, &SystemClock)
+}
+
+/// [`refresh_demo`] scored against an explicit clock.
+pub fn refresh_demo_with_clock(
+    conn: &Connection,
+    cutoff_days: i64,
+    scope: &ScopeFilters,
+    clock: &dyn Clock,
+) -> Result<Vec<UiPr>, String> {
+    let existing: HashMap<String, DbPrRow> = load_all_prs(conn)?;
+    let force_pushes = load_last_event_times(conn, "force_push")?;
+    let now = clock.now_unix();
+    let cutoff_ts = now.saturating_sub(cutoff_days.saturating_mul(86_400));
+
+    let tick = next_demo_tick();
+    let prs = generate_demo_prs(now, tick);
+    let prs: Vec<Pr> = prs
+        .into_iter()
+        .filter(|p| p.updated_at_unix >= cutoff_ts)
+        .filter(|p| scope.matches(p))
+        .collect();
+    let keep_keys: Vec<String> = prs.iter().map(|p| p.pr_key.clone()).collect();
+
+    let mut out = persist_and_score_all(conn, prs, &existing, &force_pushes, now)?;
+    save_repo_ci(conn, DEMO_RED_BASE_REPO, CiState::Failure.as_str(), now)?;
+    mark_red_bases(&mut out, &load_repo_ci(conn)?);
+
+    delete_prs_not_in(conn, &keep_keys)?;
+
+    sort_for_display(&mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{CiCheckState, ReviewState};
+    use crate::model::ReviewState;
+    use crate::timeutil::FixedClock;
     fn mk_pr(
         now: i64,
         ci_state: CiState,
@@ -510,6 +1401,7 @@ mod tests {
             title: "Test".to_string(),
             url: "https://example.com".to_string(),
             updated_at_unix: now.saturating_sub(updated_age_secs),
+            created_at_unix: None,
             last_commit_sha: Some("deadbeef".to_string()),
             ci_state,
             ci_checks: checks,
@@ -518,7 +1410,24 @@ mod tests {
             mergeable: None,
             merge_state_status: None,
             is_viewer_author: false,
+            is_assignee: false,
+            is_mentioned: false,
+            is_unsubscribed: false,
             merge_blockers: None,
+            labels: Vec::new(),
+            review_requested_at_unix: None,
+            head_ref_name: None,
+            base_ref_name: None,
+            milestone: None,
+            project_status: None,
+            ci_from_previous_commit: false,
+            lines_changed: None,
+            last_force_push_at_unix: None,
+            last_reply_at_unix: None,
+            body_excerpt: None,
+            reviewers: Vec::new(),
+            comments: Vec::new(),
+            review_threads: Vec::new(),
         }
     }
 
@@ -530,6 +1439,128 @@ mod tests {
         assert!(score >= 50);
     }
 
+    #[test]
+    fn team_review_requests_rank_below_direct_ones() {
+        let now = 1_700_000_000i64;
+        let mut pr = mk_pr(
+            now,
+            CiState::None,
+            ReviewState::TeamRequested,
+            60,
+            Vec::new(),
+        );
+        let score = score_pr(&pr, None, now, false);
+        assert_eq!(score, SCORE_TEAM_REVIEW_REQUESTED);
+        assert_eq!(category_for(&pr, score), Category::Waiting);
+
+        // A single-PR refresh keeps the team request while one is still pending.
+        pr.review_state = ReviewState::None;
+        pr.reviewers = vec![Reviewer {
+            login: "platform".to_string(),
+            state: "REQUESTED".to_string(),
+        }];
+        let old = DbPrRow {
+            last_review_state: Some("team_requested".to_string()),
+            ..DbPrRow::default()
+        };
+        keep_team_request(&mut pr, Some(&old));
+        assert!(matches!(pr.review_state, ReviewState::TeamRequested));
+
+        pr.review_state = ReviewState::Approved;
+        pr.reviewers.clear();
+        keep_team_request(&mut pr, Some(&old));
+        assert!(matches!(pr.review_state, ReviewState::Approved));
+    }
+
+    #[test]
+    fn changes_requested_on_my_pr_needs_me() {
+        let now = 1_700_000_000i64;
+        let mut pr = mk_pr(
+            now,
+            CiState::Success,
+            ReviewState::ChangesRequested,
+            60,
+            Vec::new(),
+        );
+        pr.is_viewer_author = true;
+        let score = score_pr(&pr, None, now, false);
+        assert_eq!(score, SCORE_CHANGES_REQUESTED);
+        assert_eq!(category_for(&pr, score), Category::NeedsYou);
+        assert_eq!(status_text(&pr, now, false, false), "🛑 changes requested");
+
+        // On someone else's PR it is only information.
+        pr.is_viewer_author = false;
+        assert_eq!(score_pr(&pr, None, now, false), 0);
+        assert!(matches!(
+            parse_review_state(Some(ReviewState::ChangesRequested.as_str())),
+            ReviewState::ChangesRequested
+        ));
+    }
+
+    #[test]
+    fn author_rules_boost_and_mute_prs() {
+        let conn = crate::db::TestDb::new("author-priority-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let before: HashMap<String, i32> =
+            prs.iter().map(|p| (p.pr.pr_key.clone(), p.score)).collect();
+        let rules = [
+            AuthorPriority {
+                author: "Mia".to_string(),
+                delta: 500,
+            },
+            AuthorPriority {
+                author: "noah[bot]".to_string(),
+                delta: -500,
+            },
+        ];
+        apply_author_priority(&mut prs, &rules);
+
+        for p in &prs {
+            let delta = match p.pr.author.as_str() {
+                "mia" => Some(500),
+                "noah" => Some(-500),
+                _ => None,
+            };
+            assert_eq!(p.author_priority, delta, "{}", p.pr.pr_key);
+            assert_eq!(p.score, before[&p.pr.pr_key] + delta.unwrap_or(0));
+            if delta == Some(-500) && !is_ready_to_merge(&p.pr) {
+                assert_eq!(p.category, Category::Stale);
+            }
+        }
+        let first_unpinned = prs.iter().find(|p| !p.is_pinned).unwrap();
+        assert_eq!(first_unpinned.pr.author, "mia");
+    }
+
+    #[test]
+    fn ci_of_the_previous_commit_is_marked_and_not_news() {
+        let now = 1_700_000_000i64;
+        let mut pr = mk_pr(now, CiState::Failure, ReviewState::None, 60, Vec::new());
+        pr.ci_from_previous_commit = true;
+        assert!(!is_new_ci_failure(&pr, None));
+        assert_eq!(
+            status_text(&pr, now, false, false),
+            "❌ CI failed (previous commit)"
+        );
+
+        // When the head's own CI fails afterwards, that failure is new.
+        let old = DbPrRow {
+            last_commit_sha: pr.last_commit_sha.clone(),
+            last_ci_state: Some("failure".to_string()),
+            ci_previous_commit: Some(1),
+            ..DbPrRow::default()
+        };
+        pr.ci_from_previous_commit = false;
+        assert!(is_new_ci_failure(&pr, Some(&old)));
+
+        // Green from the previous commit does not make a PR ready to merge.
+        pr.ci_state = CiState::Success;
+        pr.is_viewer_author = true;
+        pr.review_state = ReviewState::Approved;
+        assert!(is_ready_to_merge(&pr));
+        pr.ci_from_previous_commit = true;
+        assert!(!is_ready_to_merge(&pr));
+    }
+
     #[test]
     fn scoring_ci_failure_new_vs_unchanged() {
         let now = 1_700_000_000i64;
@@ -592,6 +1623,7 @@ mod tests {
             url: pr.url.clone(),
             author: None,
             updated_at_unix: None,
+            created_at_unix: None,
             last_commit_sha: Some("aaaaaaa".to_string()),
             last_ci_state: Some("failure".to_string()),
             last_review_state: Some("none".to_string()),
@@ -600,14 +1632,138 @@ mod tests {
             mergeable: None,
             merge_state_status: None,
             author_is_viewer: None,
+            is_assignee: None,
+            is_mentioned: None,
+            is_unsubscribed: None,
+            merge_blockers_json: None,
+            labels_json: None,
+            review_requested_at_unix: None,
+            head_ref_name: None,
+            base_ref_name: None,
+            milestone: None,
+            project_status: None,
+            ci_previous_commit: None,
+            lines_changed: None,
+            last_force_push_at_unix: None,
+            last_reply_at_unix: None,
+            body_excerpt: None,
+            reviewers_json: None,
+            comments_json: None,
+            review_threads_json: None,
             last_seen_at: Some(now - 10),
             last_opened_at: None,
             pinned: None,
+            snoozed_until: None,
+            snoozed_sha: None,
+            ignored: None,
+            inaccessible_since: None,
         };
 
         assert!(is_new_ci_failure(&pr, Some(&old)));
     }
 
+    #[test]
+    fn check_failures_count_reruns_on_the_same_commit() {
+        use crate::db::{CheckFailure, load_check_failures};
+        let conn = crate::db::TestDb::new("check-failures-test");
+        let t0 = 1_700_000_000i64;
+        let record = |sha: &str, failing: bool, at: i64| {
+            record_check_states(
+                &conn,
+                "a/b#1",
+                sha,
+                [("lint", failing), ("unit", false)],
+                at,
+            )
+            .unwrap();
+        };
+        let lint = || load_check_failures(&conn).unwrap()["a/b#1"]["lint"];
+
+        record("abc", true, t0);
+        // Still failing at the next refresh: the same failure.
+        record("abc", true, t0 + 60);
+        // Rerun, then it fails again.
+        record("abc", false, t0 + 120);
+        record("abc", true, t0 + 180);
+        assert_eq!(
+            lint(),
+            CheckFailure {
+                failures: 2,
+                first_failed_at: t0
+            }
+        );
+        assert!(!load_check_failures(&conn).unwrap()["a/b#1"].contains_key("unit"));
+
+        // A new head commit starts over.
+        record("def", true, t0 + 240);
+        assert_eq!(
+            lint(),
+            CheckFailure {
+                failures: 1,
+                first_failed_at: t0 + 240
+            }
+        );
+    }
+
+    #[test]
+    fn answered_threads_i_opened_boost_until_resolved() {
+        let now = 1_700_000_000i64;
+        let mut pr = mk_pr(now, CiState::None, ReviewState::None, 600, Vec::new());
+        let base = score_pr(&pr, None, now, false);
+        let comment = |author: &str| crate::model::Comment {
+            author: author.to_string(),
+            body: "text".to_string(),
+            created_at_unix: now - 600,
+        };
+        pr.review_threads = vec![crate::model::ReviewThread {
+            path: None,
+            is_resolved: false,
+            comments: vec![comment("me"), comment("author")],
+            started_by_viewer: true,
+        }];
+        assert_eq!(pr.my_unresolved_threads().len(), 1);
+        assert_eq!(pr.my_threads_awaiting_reply(), 1);
+        assert_eq!(
+            score_pr(&pr, None, now, false),
+            base + SCORE_THREAD_AWAITING_YOU
+        );
+
+        // My word last, or resolved: nothing waits on me.
+        pr.review_threads[0].comments.push(comment("me"));
+        assert_eq!(pr.my_threads_awaiting_reply(), 0);
+        pr.review_threads[0].comments.pop();
+        pr.review_threads[0].is_resolved = true;
+        assert!(pr.my_unresolved_threads().is_empty());
+        assert_eq!(score_pr(&pr, None, now, false), base);
+    }
+
+    #[test]
+    fn unread_replies_boost_until_the_pr_is_opened() {
+        let now = 1_700_000_000i64;
+        let mut pr = mk_pr(now, CiState::None, ReviewState::None, 600, Vec::new());
+        let base = score_pr(&pr, None, now, false);
+        pr.last_reply_at_unix = Some(now - 300);
+        assert!(has_unread_reply(&pr, None));
+        assert_eq!(score_pr(&pr, None, now, false), base + SCORE_REPLY_TO_YOU);
+        assert_eq!(
+            category_for(&pr, base + SCORE_REPLY_TO_YOU),
+            Category::NeedsYou
+        );
+
+        let conn = crate::db::TestDb::new("reply-test");
+        let seeded =
+            refresh_demo_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(now)).unwrap();
+        let replied = seeded.iter().find(|p| p.reply_to_you).unwrap();
+        assert!(replied.display_status.starts_with(REPLY_BADGE));
+
+        crate::db::mark_opened(&conn, &replied.pr.pr_key, now + 60).unwrap();
+        let row = load_all_prs(&conn)
+            .unwrap()
+            .remove(&replied.pr.pr_key)
+            .unwrap();
+        assert!(!has_unread_reply(&replied.pr, Some(&row)));
+    }
+
     #[test]
     fn ready_to_merge_bucket_for_authored_green_and_approved() {
         let now = 1_700_000_000i64;
@@ -627,6 +1783,75 @@ mod tests {
         assert!(status_text(&pr, now, false, false).starts_with("✅ ready to merge"));
     }
 
+    #[test]
+    fn my_prs_summarize_their_checks_in_the_status() {
+        let now = 1_700_000_000i64;
+        let check = |name: &str, state: CiCheckState| CiCheck {
+            name: name.to_string(),
+            state,
+            url: None,
+            started_at_unix: None,
+        };
+        let mut pr = mk_pr(
+            now,
+            CiState::Failure,
+            ReviewState::None,
+            300,
+            vec![
+                check("lint", CiCheckState::Failure),
+                check("unit", CiCheckState::Failure),
+                check("e2e", CiCheckState::Running),
+                check("build", CiCheckState::Success),
+                check("docs", CiCheckState::Neutral),
+            ],
+        );
+        pr.is_viewer_author = true;
+        assert_eq!(
+            status_text(&pr, now, false, false),
+            "❌ CI failed · 2✗ 1🟡 1✓"
+        );
+
+        pr.is_viewer_author = false;
+        assert_eq!(status_text(&pr, now, false, false), "❌ CI failed");
+    }
+
+    #[test]
+    fn my_prs_say_who_they_are_waiting_on() {
+        let now = 1_700_000_000i64;
+        let reviewer = |login: &str, state: &str| crate::model::Reviewer {
+            login: login.to_string(),
+            state: state.to_string(),
+        };
+        let mut pr = mk_pr(now, CiState::Running, ReviewState::None, 60, Vec::new());
+        pr.is_viewer_author = true;
+        pr.review_requested_at_unix = Some(now - 2 * 86_400);
+        pr.reviewers = vec![reviewer("ana", "COMMENTED"), reviewer("bob", "REQUESTED")];
+        assert_eq!(
+            status_text(&pr, now, false, false),
+            "🟡 CI running (1m) · ⏳ waiting on @bob (2d)"
+        );
+        pr.reviewers.push(reviewer("platform", "REQUESTED"));
+        pr.reviewers.push(reviewer("cy", "REQUESTED"));
+        assert!(status_text(&pr, now, false, false).ends_with("⏳ waiting on @bob +2 (2d)"));
+
+        // Requested changes put the ball back with the author.
+        pr.reviewers[0].state = "CHANGES_REQUESTED".to_string();
+        assert!(
+            status_text(&pr, now, false, false)
+                .ends_with("↩️ waiting on you (@ana requested changes)")
+        );
+
+        // Nothing to say on other people's PRs, drafts, or without reviewers.
+        pr.is_viewer_author = false;
+        assert_eq!(status_text(&pr, now, false, false), "🟡 CI running (1m)");
+        pr.is_viewer_author = true;
+        pr.is_draft = true;
+        assert_eq!(status_text(&pr, now, false, false), "🟡 CI running (1m)");
+        pr.is_draft = false;
+        pr.reviewers.clear();
+        assert_eq!(status_text(&pr, now, false, false), "🟡 CI running (1m)");
+    }
+
     #[test]
     fn ready_to_merge_requires_viewer_authorship_and_green_ci() {
         let now = 1_700_000_000i64;
@@ -669,16 +1894,802 @@ mod tests {
             Vec::new(),
         );
         pr2.is_viewer_author = true;
-        pr2.merge_state_status = Some("BLOCKED".to_string());
+        pr2.merge_blockers = Some(MergeBlockers {
+            required_approvals: Some(2),
+            current_approvals: 1,
+            ..MergeBlockers::default()
+        });
         let category2 = category_for(&pr2, score_pr(&pr2, None, now, false));
         assert!(!matches!(category2, Category::ReadyToMerge));
+        assert!(!status_text(&pr2, now, false, false).starts_with("✅ ready to merge"));
+
+        // The heuristic merge state alone no longer decides; branch protection does.
+        pr2.merge_state_status = Some("BLOCKED".to_string());
+        pr2.merge_blockers = Some(MergeBlockers {
+            required_checks: vec!["build".to_string()],
+            ..MergeBlockers::default()
+        });
+        let category3 = category_for(&pr2, score_pr(&pr2, None, now, false));
+        assert!(matches!(category3, Category::ReadyToMerge));
+
+        pr2.merge_blockers = Some(MergeBlockers {
+            required_checks: vec!["build".to_string(), "e2e".to_string()],
+            failing_required_checks: vec!["e2e".to_string()],
+            ..MergeBlockers::default()
+        });
+        let category4 = category_for(&pr2, score_pr(&pr2, None, now, false));
+        assert!(!matches!(category4, Category::ReadyToMerge));
+    }
+
+    #[test]
+    fn cached_scoring_follows_injected_clock() {
+        let conn = crate::db::TestDb::new("clock-test");
+        let scope = ScopeFilters::default();
+        let t0 = 1_700_000_000i64;
+
+        let seeded = refresh_demo_with_clock(&conn, 30, &scope, &FixedClock(t0)).unwrap();
+        let approved = seeded
+            .iter()
+            .find(|p| {
+                matches!(p.pr.review_state, ReviewState::Approved)
+                    && matches!(p.pr.ci_state, CiState::Success)
+            })
+            .expect("demo data has an approved, green PR")
+            .pr
+            .clone();
+
+        // Two days later the same cached PR crosses the approved-but-unmerged threshold.
+        let later = FixedClock(approved.updated_at_unix + 2 * 86_400);
+        let reloaded = load_cached_with_clock(&conn, 30, &scope, &later).unwrap();
+        let pr = reloaded
+            .iter()
+            .find(|p| p.pr.pr_key == approved.pr_key)
+            .unwrap();
+        assert_eq!(pr.score, score_pr(&approved, None, later.0, false));
+        assert!(pr.score >= SCORE_APPROVED_UNMERGED_OLD);
+
+        // Merge blockers come back from the cache, so READY TO MERGE does not flicker.
+        let blocked = seeded
+            .iter()
+            .find(|p| p.pr.merge_blockers.is_some())
+            .expect("demo data has a blocked PR");
+        let cached = reloaded
+            .iter()
+            .find(|p| p.pr.pr_key == blocked.pr.pr_key)
+            .unwrap();
+        assert_eq!(
+            cached
+                .pr
+                .merge_blockers
+                .as_ref()
+                .map(|b| b.to_descriptions()),
+            blocked
+                .pr
+                .merge_blockers
+                .as_ref()
+                .map(|b| b.to_descriptions())
+        );
+        assert!(!is_ready_to_merge(&cached.pr));
+
+        // And falls out of a 1-day window entirely.
+        let window = load_cached_with_clock(&conn, 1, &scope, &later).unwrap();
+        assert!(window.iter().all(|p| p.pr.pr_key != approved.pr_key));
+    }
+
+    #[test]
+    fn tracked_prs_survive_cutoff_and_scope_on_cached_load() {
+        let conn = crate::db::TestDb::new("tracked-test");
+        let t0 = 1_700_000_000i64;
+        let seeded =
+            refresh_demo_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(t0)).unwrap();
+        let key = seeded[0].pr.pr_key.clone();
+        assert!(crate::db::track_pr(&conn, &key, t0).unwrap());
+        assert!(!crate::db::track_pr(&conn, &key, t0).unwrap());
+
+        let much_later = FixedClock(t0 + 90 * 86_400);
+        let exclude_all = ScopeFilters {
+            orgs: vec!["nobody".to_string()],
+            ..ScopeFilters::default()
+        };
+        let loaded = load_cached_with_clock(&conn, 1, &exclude_all, &much_later).unwrap();
+        let keys: Vec<&str> = loaded.iter().map(|p| p.pr.pr_key.as_str()).collect();
+        assert_eq!(keys, vec![key.as_str()]);
+
+        assert!(crate::db::untrack_pr(&conn, &key).unwrap());
+        assert!(
+            load_cached_with_clock(&conn, 1, &exclude_all, &much_later)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reviewer_mode_leaves_out_the_viewers_own_prs() {
+        let conn = crate::db::TestDb::new("reviewer-mode-test");
+        let t0 = 1_700_000_000i64;
+        let all =
+            refresh_demo_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(t0)).unwrap();
+        assert!(all.iter().any(|p| p.pr.is_viewer_author));
+
+        let reviewer = ScopeFilters {
+            mode: UsageMode::Reviewer,
+            ..ScopeFilters::default()
+        };
+        let cached = load_cached_with_clock(&conn, 30, &reviewer, &FixedClock(t0)).unwrap();
+        assert!(!cached.is_empty());
+        assert!(cached.iter().all(|p| !p.pr.is_viewer_author));
+        assert!(!cached.iter().any(|p| p.category == Category::ReadyToMerge));
+        assert_eq!(reviewer.summary(), vec!["mode:reviewer".to_string()]);
+        assert_ne!(
+            reviewer.fetch_key(false),
+            ScopeFilters::default().fetch_key(false)
+        );
+    }
+
+    #[test]
+    fn milestone_filters_keep_or_drop_prs() {
+        let conn = crate::db::TestDb::new("milestone-test");
+        let all = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let release = ScopeFilters {
+            include_milestones: vec!["V3.2".to_string()],
+            ..ScopeFilters::default()
+        };
+        let prs = load_cached(&conn, 30, &release).unwrap();
+        assert_eq!(prs.len(), 3);
+        assert!(
+            prs.iter()
+                .all(|p| p.pr.milestone.as_deref() == Some("v3.2"))
+        );
+        assert_eq!(release.summary(), vec!["milestone:V3.2".to_string()]);
+
+        let later = ScopeFilters {
+            exclude_milestones: vec!["v3.2".to_string()],
+            ..ScopeFilters::default()
+        };
+        assert_eq!(load_cached(&conn, 30, &later).unwrap().len(), all.len() - 3);
+        assert_ne!(release.fetch_key(false), later.fetch_key(false));
+    }
+
+    #[test]
+    fn opened_prs_stay_marked_until_their_next_update() {
+        let conn = crate::db::TestDb::new("opened-test");
+        let t0 = 1_700_000_000i64;
+        let seeded =
+            refresh_demo_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(t0)).unwrap();
+        assert!(seeded.iter().all(|p| !p.opened_since_update));
+        let mut pr = seeded[0].pr.clone();
+        crate::db::mark_opened(&conn, &pr.pr_key, t0 + 10).unwrap();
+
+        let cached =
+            load_cached_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(t0)).unwrap();
+        let opened: Vec<&str> = cached
+            .iter()
+            .filter(|p| p.opened_since_update)
+            .map(|p| p.pr.pr_key.as_str())
+            .collect();
+        assert_eq!(opened, vec![pr.pr_key.as_str()]);
+
+        let row = load_all_prs(&conn).unwrap().remove(&pr.pr_key).unwrap();
+        pr.updated_at_unix = t0 + 20;
+        assert!(!score_fetched(pr, Some(&row), None, t0 + 30).opened_since_update);
+    }
+
+    #[test]
+    fn force_push_after_last_view_is_flagged() {
+        let conn = crate::db::TestDb::new("force-push-test");
+        let t0 = 1_700_000_000i64;
+        let seeded =
+            refresh_demo_with_clock(&conn, 30, &ScopeFilters::default(), &FixedClock(t0)).unwrap();
+        let mut pr = seeded[0].pr.clone();
+        crate::db::mark_opened(&conn, &pr.pr_key, t0 + 10).unwrap();
+        let row = load_all_prs(&conn).unwrap().remove(&pr.pr_key).unwrap();
+
+        // A regular push moves the head without flagging anything.
+        pr.last_commit_sha = Some("c0ffee".to_string());
+        pr.last_force_push_at_unix = None;
+        let last = record_head_change(&conn, &pr, Some(&row), None, t0 + 20).unwrap();
+        assert!(!force_pushed_since_view(Some(&row), last));
+
+        pr.last_commit_sha = Some("f00d".to_string());
+        pr.last_force_push_at_unix = Some(t0 + 25);
+        let last = record_head_change(&conn, &pr, Some(&row), None, t0 + 30).unwrap();
+        assert_eq!(last, Some(t0 + 30));
+        assert!(force_pushed_since_view(Some(&row), last));
+        assert_eq!(
+            crate::db::load_last_event_times(&conn, "force_push")
+                .unwrap()
+                .get(&pr.pr_key),
+            Some(&(t0 + 30))
+        );
+    }
+
+    #[test]
+    fn ignored_prs_stay_out_of_refreshes_and_the_cache_view() {
+        let conn = crate::db::TestDb::new("ignore-test");
+        let scope = ScopeFilters::default();
+        let seeded = refresh_demo(&conn, 30, &scope).unwrap();
+        let key = seeded[0].pr.pr_key.clone();
+
+        crate::db::set_ignored(&conn, &key, true).unwrap();
+        let refreshed = refresh_demo(&conn, 30, &scope).unwrap();
+        assert_eq!(refreshed.len(), seeded.len() - 1);
+        assert!(refreshed.iter().all(|p| p.pr.pr_key != key));
+        assert!(
+            load_cached(&conn, 30, &scope)
+                .unwrap()
+                .iter()
+                .all(|p| p.pr.pr_key != key)
+        );
+
+        // Pruning keeps the ignored row even when the PR leaves the attention set.
+        crate::db::delete_prs_not_in(&conn, &["other/repo#1".to_string()]).unwrap();
+        let ignored = crate::db::load_ignored_prs(&conn).unwrap();
+        assert_eq!(ignored.len(), 1);
+        assert_eq!(ignored[0].pr_key, key);
+
+        // So does a refresh that comes back empty, and wiping the cache (`--no-cache`).
+        let nothing = ScopeFilters {
+            include_repos: vec!["nobody/nothing".to_string()],
+            ..ScopeFilters::default()
+        };
+        assert!(refresh_demo(&conn, 30, &nothing).unwrap().is_empty());
+        delete_prs_not_in(&conn, &[]).unwrap();
+        let ignored = crate::db::load_ignored_prs(&conn).unwrap();
+        assert_eq!(ignored.len(), 1);
+        assert_eq!(ignored[0].pr_key, key);
+
+        crate::db::set_ignored(&conn, &key, false).unwrap();
+        let restored = refresh_demo(&conn, 30, &scope).unwrap();
+        assert!(restored.iter().any(|p| p.pr.pr_key == key));
+        assert!(crate::db::load_ignored_prs(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn hashed_text_and_retention_keep_the_cache_clean() {
+        let conn = crate::db::TestDb::new("privacy-test");
+        let scope = ScopeFilters::default();
+        let seeded = refresh_demo(&conn, 30, &scope).unwrap();
+        let title = seeded[0].pr.title.clone();
+
+        // Turning hashing on scrubs what is already cached and every later write.
+        crate::db::set_hash_pr_text(&conn, true).unwrap();
+        let refreshed = refresh_demo(&conn, 30, &scope).unwrap();
+        assert_eq!(refreshed[0].pr.title, title, "live data keeps its text");
+        for row in load_all_prs(&conn).unwrap().values() {
+            assert!(is_hashed_text(&row.title), "{}", row.title);
+            assert!(row.body_excerpt.as_deref().is_none_or(is_hashed_text));
+            assert!(row.comments_json.is_none() && row.review_threads_json.is_none());
+        }
+        let cached = load_cached(&conn, 30, &scope).unwrap();
+        assert!(cached.iter().all(|p| p.pr.title == HIDDEN_TITLE));
+        assert!(cached.iter().all(|p| p.pr.body_excerpt.is_none()));
+
+        // Rows not seen since the cutoff go, with their events.
+        let now = SystemClock.now_unix();
+        assert_eq!(
+            crate::db::purge_prs_older_than(&conn, now - 3600).unwrap(),
+            0
+        );
+        let purged = crate::db::purge_prs_older_than(&conn, now + 1).unwrap();
+        assert_eq!(purged, seeded.len());
+        assert!(load_all_prs(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn single_refresh_updates_one_row_and_keeps_pins() {
+        let conn = crate::db::TestDb::new("single-test");
+        let seeded = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let key = seeded[1].pr.pr_key.clone();
+        crate::db::toggle_pin(&conn, &key).unwrap();
+        let before = load_all_prs(&conn).unwrap();
+
+        let fresh = refresh_single_demo(&conn, &key).unwrap();
+        assert_eq!(fresh.pr.pr_key, key);
+        assert!(fresh.is_pinned);
+
+        // The rest of the cache is untouched.
+        let after = load_all_prs(&conn).unwrap();
+        assert_eq!(after.len(), before.len());
+        assert_eq!(after[&key].pinned, Some(1));
+
+        assert!(
+            refresh_single_demo(&conn, "nobody/nothing#1")
+                .unwrap_err()
+                .contains("not found")
+        );
+    }
+
+    #[test]
+    fn par_map_preserves_order_across_workers() {
+        let small: Vec<i64> = (0..10).collect();
+        assert_eq!(
+            par_map(small, |n| n * 2),
+            (0..10).map(|n| n * 2).collect::<Vec<_>>()
+        );
+
+        let large: Vec<i64> = (0..1_000).collect();
+        let doubled = par_map(large, |n| n * 2);
+        assert_eq!(doubled.len(), 1_000);
+        assert!(doubled.iter().enumerate().all(|(i, &n)| n == 2 * i as i64));
+    }
+
+    /// A host listing `prs` whose repositories other than `visible_repo` are gone, and
+    /// whose `red_base` repository has a failing default branch.
+    struct FakeHost {
+        prs: Vec<Pr>,
+        visible_repo: &'static str,
+        red_base: &'static str,
+        /// Default-branch CI lookups made so far.
+        base_checks: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Provider for FakeHost {
+        fn name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn fetch_attention_prs(
+            &self,
+            _cutoff_ts: i64,
+            _include_team_requests: bool,
+            _include_discussions: bool,
+            _mode: UsageMode,
+        ) -> crate::provider::BoxFuture<'_, Result<Vec<Pr>, String>> {
+            Box::pin(async move { Ok(self.prs.clone()) })
+        }
+
+        fn fetch_pr<'a>(
+            &'a self,
+            _owner: &'a str,
+            _repo: &'a str,
+            _number: i64,
+        ) -> crate::provider::BoxFuture<'a, Result<Pr, String>> {
+            Box::pin(async move { Err("not needed".to_string()) })
+        }
+
+        fn pr_exists<'a>(
+            &'a self,
+            _owner: &'a str,
+            repo: &'a str,
+            _number: i64,
+        ) -> crate::provider::BoxFuture<'a, Result<bool, String>> {
+            Box::pin(async move { Ok(repo == self.visible_repo) })
+        }
+
+        fn default_branch_ci<'a>(
+            &'a self,
+            _owner: &'a str,
+            repo: &'a str,
+        ) -> crate::provider::BoxFuture<'a, Result<CiState, String>> {
+            self.base_checks
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Box::pin(async move {
+                Ok(if repo == self.red_base {
+                    CiState::Failure
+                } else {
+                    CiState::Success
+                })
+            })
+        }
+
+        fn check_log<'a>(
+            &'a self,
+            _owner: &'a str,
+            _repo: &'a str,
+            _check: &'a crate::model::CiCheck,
+        ) -> crate::provider::BoxFuture<'a, Result<String, String>> {
+            Box::pin(async move { Err("not needed".to_string()) })
+        }
+
+        fn viewer_login(&self) -> crate::provider::BoxFuture<'_, Result<String, String>> {
+            Box::pin(async move { Ok("me".to_string()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn lost_repositories_leave_tombstones_for_a_grace_period() {
+        let conn = crate::db::TestDb::new("tombstone-test");
+        let scope = ScopeFilters::default();
+        let now = 1_700_000_000;
+        let kept = mk_pr(now, CiState::Success, ReviewState::Requested, 60, vec![]);
+        let mut merged = kept.clone();
+        merged.number = 2;
+        merged.pr_key = "acme/repo#2".to_string();
+        let mut secret = kept.clone();
+        secret.repo = "secret".to_string();
+        secret.pr_key = "acme/secret#1".to_string();
+        let host = |prs: Vec<Pr>| FakeHost {
+            prs,
+            visible_repo: "repo",
+            red_base: "",
+            base_checks: Default::default(),
+        };
+        let refresh_at = |prs: Vec<Pr>, at: i64| {
+            let conn = &conn;
+            let scope = &scope;
+            async move {
+                refresh_with_clock(conn, &host(prs), 30, scope, false, &FixedClock(at))
+                    .await
+                    .unwrap()
+            }
+        };
+        let tombstone = |prs: &[UiPr]| {
+            prs.iter()
+                .find(|p| p.pr.pr_key == secret.pr_key)
+                .map(|p| (p.inaccessible_since, p.display_status.clone()))
+        };
+
+        refresh_at(vec![kept.clone(), merged, secret.clone()], now).await;
+
+        // The merged PR still exists and drops out; the one in the lost repository stays.
+        let prs = refresh_at(vec![kept.clone()], now + 60).await;
+        assert_eq!(prs.len(), 2);
+        assert_eq!(
+            tombstone(&prs),
+            Some((Some(now + 60), "🚫 no access · now".to_string()))
+        );
+        let cached = load_cached_with_clock(&conn, 30, &scope, &FixedClock(now + 7200)).unwrap();
+        assert_eq!(
+            tombstone(&cached),
+            Some((Some(now + 60), "🚫 no access · 1h ago".to_string()))
+        );
+
+        // Access restored: the tombstone is cleared.
+        let prs = refresh_at(vec![kept.clone(), secret.clone()], now + 120).await;
+        assert_eq!(tombstone(&prs).unwrap().0, None);
+
+        // Lost again, then dropped once the grace period is over.
+        refresh_at(vec![kept.clone()], now + 180).await;
+        let later = now + 180 + INACCESSIBLE_GRACE_SECS;
+        assert_eq!(refresh_at(vec![kept.clone()], later - 1).await.len(), 2);
+        let prs = refresh_at(vec![kept], later).await;
+        assert_eq!(tombstone(&prs), None);
+        assert!(!load_all_prs(&conn).unwrap().contains_key("acme/secret#1"));
+    }
+
+    #[tokio::test]
+    async fn review_requests_into_a_red_default_branch_are_badged() {
+        let conn = crate::db::TestDb::new("red-base-test");
+        let scope = ScopeFilters::default();
+        let now = 1_700_000_000;
+        let review = mk_pr(now, CiState::Failure, ReviewState::Requested, 60, vec![]);
+        let mut mine = review.clone();
+        mine.number = 2;
+        mine.pr_key = "acme/repo#2".to_string();
+        mine.review_state = ReviewState::None;
+        mine.is_viewer_author = true;
+        let mut green = review.clone();
+        green.repo = "green".to_string();
+        green.pr_key = "acme/green#1".to_string();
+        let host = FakeHost {
+            prs: vec![review.clone(), mine.clone(), green.clone()],
+            visible_repo: "repo",
+            red_base: "repo",
+            base_checks: Default::default(),
+        };
+        let status = |prs: &[UiPr], key: &str| {
+            prs.iter()
+                .find(|p| p.pr.pr_key == key)
+                .unwrap()
+                .display_status
+                .clone()
+        };
+
+        let prs = refresh_with_clock(&conn, &host, 30, &scope, false, &FixedClock(now))
+            .await
+            .unwrap();
+        assert!(status(&prs, &review.pr_key).starts_with(BASE_RED_BADGE));
+        assert!(!status(&prs, &mine.pr_key).starts_with(BASE_RED_BADGE));
+        assert!(!status(&prs, &green.pr_key).starts_with(BASE_RED_BADGE));
+        // One lookup per repository with a review request; the viewer's own PR needs none.
+        let checks = || host.base_checks.load(std::sync::atomic::Ordering::Relaxed);
+        assert_eq!(checks(), 2);
+
+        // Cached for a while, and shown from the cache too.
+        refresh_with_clock(&conn, &host, 30, &scope, false, &FixedClock(now + 60))
+            .await
+            .unwrap();
+        assert_eq!(checks(), 2);
+        let cached = load_cached_with_clock(&conn, 30, &scope, &FixedClock(now + 60)).unwrap();
+        assert!(status(&cached, &review.pr_key).starts_with(BASE_RED_BADGE));
+
+        refresh_with_clock(
+            &conn,
+            &host,
+            30,
+            &scope,
+            false,
+            &FixedClock(now + REPO_CI_TTL_SECS),
+        )
+        .await
+        .unwrap();
+        assert_eq!(checks(), 4);
+    }
+
+    #[test]
+    fn fetch_digest_hashes_every_field_in_key_order() {
+        let now = 1_700_000_000;
+        let a = mk_pr(now, CiState::Success, ReviewState::Requested, 60, vec![]);
+        let mut b = a.clone();
+        b.pr_key = "o/r#2".to_string();
+        let digest = fetch_digest(&[a.clone(), b.clone()]);
+        assert_eq!(digest.len(), 64);
+        assert_eq!(digest, fetch_digest(&[b.clone(), a.clone()]));
+
+        let mut pushed = a.clone();
+        pushed.last_commit_sha = Some("f00".to_string());
+        assert_ne!(digest, fetch_digest(&[pushed, b.clone()]));
+        let mut labelled = a.clone();
+        labelled.labels.push("hotfix".to_string());
+        assert_ne!(digest, fetch_digest(&[labelled, b]));
+    }
+
+    #[tokio::test]
+    async fn refreshes_that_fetch_nothing_new_skip_the_writes() {
+        let conn = crate::db::TestDb::new("unchanged-test");
+        let scope = ScopeFilters::default();
+        let now = 1_700_000_000;
+        let pr = mk_pr(now, CiState::Success, ReviewState::Requested, 60, vec![]);
+        let host = |prs: Vec<Pr>| FakeHost {
+            prs,
+            visible_repo: "repo",
+            red_base: "",
+            base_checks: Default::default(),
+        };
+        let refresh_at = |prs: Vec<Pr>, at: i64| {
+            let conn = &conn;
+            let scope = &scope;
+            async move {
+                refresh_with_clock(conn, &host(prs), 30, scope, false, &FixedClock(at))
+                    .await
+                    .unwrap()
+            }
+        };
+        let cached_title = || load_all_prs(&conn).unwrap()[&pr.pr_key].title.clone();
+        let summary = |prs: &[UiPr]| -> Vec<(String, i32, String)> {
+            prs.iter()
+                .map(|p| (p.pr.title.clone(), p.score, p.display_status.clone()))
+                .collect()
+        };
+
+        refresh_at(vec![pr.clone()], now).await;
+        conn.execute("UPDATE prs SET title = 'scribbled'", [])
+            .unwrap();
+
+        // Same data: the cached row is left alone, yet scored as a first refresh would.
+        let later = refresh_at(vec![pr.clone()], now + 3600).await;
+        assert_eq!(cached_title(), "scribbled");
+        assert_eq!(
+            load_all_prs(&conn).unwrap()[&pr.pr_key].last_seen_at,
+            Some(now + 3600)
+        );
+        // The full pass over the same two refreshes agrees.
+        let full = crate::db::TestDb::new("unchanged-full-test");
+        for at in [now, now + 3600] {
+            full.execute("DELETE FROM fetch_digests", []).unwrap();
+            let prs = refresh_with_clock(
+                &full,
+                &host(vec![pr.clone()]),
+                30,
+                &scope,
+                false,
+                &FixedClock(at),
+            )
+            .await
+            .unwrap();
+            if at > now {
+                assert_eq!(summary(&later), summary(&prs));
+            }
+        }
+
+        // Anything new goes through the full pass again.
+        let mut pushed = pr.clone();
+        pushed.ci_state = CiState::Failure;
+        refresh_at(vec![pushed], now + 3660).await;
+        assert_eq!(cached_title(), pr.title);
+
+        // A check can finish without bumping `updatedAt` or moving the rollup; it is new too.
+        let check = |name: &str, state: CiCheckState| CiCheck {
+            name: name.to_string(),
+            state,
+            url: None,
+            started_at_unix: None,
+        };
+        let mut red = pr.clone();
+        red.ci_state = CiState::Failure;
+        red.ci_checks = vec![
+            check("lint", CiCheckState::Failure),
+            check("unit", CiCheckState::Failure),
+        ];
+        refresh_at(vec![red.clone()], now + 7200).await;
+        red.ci_checks[1].state = CiCheckState::Success;
+        refresh_at(vec![red], now + 7260).await;
+        let cached = load_all_prs(&conn).unwrap()[&pr.pr_key]
+            .ci_checks_json
+            .clone()
+            .unwrap();
+        assert!(
+            cached.contains(r#""name":"unit","state":"Success""#),
+            "{cached}"
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_runs_end_to_end_against_canned_github() {
+        use crate::github_mock::{
+            MockGitHub, authored_page, default_branch, pr_node, requesting, search_page,
+        };
+        use std::sync::Arc;
+        let conn = crate::db::TestDb::new("mock-github-test");
+        let scope = ScopeFilters::default();
+        let now = 1_710_028_800; // 2024-03-10
+        let canned = |api_ci: &str| {
+            // The second authored page crosses the cutoff, so its "more" is never followed.
+            let old = pr_node("api", 3, "2023-12-01T00:00:00Z", "SUCCESS");
+            let mut dangling = search_page(Vec::new(), None);
+            dangling["data"]["search"]["pageInfo"]["hasNextPage"] = true.into();
+            MockGitHub::default()
+                .respond(
+                    "pullRequests(first",
+                    authored_page(
+                        vec![pr_node("api", 1, "2024-03-09T12:00:00Z", api_ci)],
+                        Some("p2"),
+                    ),
+                )
+                .respond(
+                    "pullRequests(first",
+                    authored_page(
+                        vec![pr_node("api", 2, "2024-03-05T00:00:00Z", "PENDING"), old],
+                        Some("p3"),
+                    ),
+                )
+                .respond(
+                    "review-requested:@me",
+                    search_page(
+                        vec![
+                            requesting(pr_node("web", 10, "2024-03-09T00:00:00Z", "SUCCESS"), "me"),
+                            requesting(
+                                pr_node("web", 11, "2024-03-09T00:00:00Z", "SUCCESS"),
+                                "bob",
+                            ),
+                        ],
+                        None,
+                    ),
+                )
+                // "More" without a cursor ends the search instead of asking again.
+                .respond("assignee:@me", dangling)
+                .respond(
+                    "mentions:@me",
+                    search_page(
+                        vec![pr_node("web", 12, "2024-03-08T00:00:00Z", "SUCCESS")],
+                        None,
+                    ),
+                )
+        };
+        let refresh_at = |mock: &Arc<MockGitHub>, at: i64| {
+            let github = crate::provider::GitHubProvider::new(
+                octocrab::Octocrab::builder().build().unwrap(),
+            )
+            .with_graphql(mock.clone());
+            let conn = &conn;
+            let scope = &scope;
+            async move { refresh_with_clock(conn, &github, 30, scope, false, &FixedClock(at)).await }
+        };
+        let keys = |prs: &[UiPr]| -> BTreeSet<String> {
+            prs.iter().map(|p| p.pr.pr_key.clone()).collect()
+        };
+
+        let mock =
+            Arc::new(canned("SUCCESS").respond("defaultBranchRef", default_branch("SUCCESS")));
+        let prs = refresh_at(&mock, now).await.unwrap();
+        let expected: BTreeSet<String> = ["acme/api#1", "acme/api#2", "acme/web#10", "acme/web#12"]
+            .map(String::from)
+            .into();
+        assert_eq!(keys(&prs), expected);
+        assert_eq!(mock.requests_with("\"cursor\":\"p2\""), 1);
+        assert_eq!(mock.requests_with("\"cursor\":\"p3\""), 0);
+        assert_eq!(mock.requests_with("assignee:@me"), 1);
+        assert_eq!(mock.unused(), 0);
+        let review = prs.iter().find(|p| p.pr.pr_key == "acme/web#10").unwrap();
+        assert_eq!(review.category, Category::NeedsYou);
+        let cached: BTreeSet<String> = load_all_prs(&conn).unwrap().into_keys().collect();
+        assert_eq!(cached, expected);
+
+        // CI on the viewer's PR turns red: scored as a new failure, which is what alerts on.
+        let mock = Arc::new(canned("FAILURE"));
+        let prs = refresh_at(&mock, now + 60).await.unwrap();
+        let failing = prs.iter().find(|p| p.pr.pr_key == "acme/api#1").unwrap();
+        assert!(failing.is_new_ci_failure);
+        assert!(matches!(failing.pr.ci_state, CiState::Failure));
+        assert_eq!(keys(&prs), expected);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_refreshes_fail_and_keep_the_cache() {
+        use crate::github_mock::{MockGitHub, authored_page, pr_node, rate_limited};
+        use std::sync::Arc;
+        let conn = crate::db::TestDb::new("rate-limited-test");
+        let scope = ScopeFilters {
+            mode: UsageMode::Author,
+            ..ScopeFilters::default()
+        };
+        let now = 1_710_028_800;
+        let refresh_with = |mock: MockGitHub| {
+            let github = crate::provider::GitHubProvider::new(
+                octocrab::Octocrab::builder().build().unwrap(),
+            )
+            .with_graphql(Arc::new(mock));
+            let conn = &conn;
+            let scope = &scope;
+            async move { refresh_with_clock(conn, &github, 30, scope, false, &FixedClock(now)).await }
+        };
+
+        let page = authored_page(
+            vec![pr_node("api", 1, "2024-03-09T12:00:00Z", "SUCCESS")],
+            None,
+        );
+        let prs = refresh_with(MockGitHub::default().respond("pullRequests(first", page))
+            .await
+            .unwrap();
+        assert_eq!(prs.len(), 1);
+
+        // The GraphQL budget is spent: GitHub answers with an error and no data.
+        let err = refresh_with(MockGitHub::default().respond("pullRequests(first", rate_limited()))
+            .await
+            .unwrap_err();
+        assert!(err.contains("RATE_LIMITED"), "{err}");
+
+        // A secondary rate limit fails the request itself.
+        let err = refresh_with(MockGitHub::default().fail(
+            "pullRequests(first",
+            "GitHub: You have exceeded a secondary rate limit",
+        ))
+        .await
+        .unwrap_err();
+        assert!(err.contains("secondary rate limit"), "{err}");
+
+        // Either way the last good list is still there to show.
+        let cached = load_cached_with_clock(&conn, 30, &scope, &FixedClock(now)).unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].pr.pr_key, "acme/api#1");
+    }
+
+    #[tokio::test]
+    async fn warm_up_only_runs_when_the_window_grew() {
+        let conn = crate::db::TestDb::new("warm-test");
+        // Never sent: every call below returns before touching the network.
+        let provider =
+            crate::provider::GitHubProvider::new(octocrab::Octocrab::builder().build().unwrap());
+        let scope = ScopeFilters::default();
+        let key = scope.fetch_key(false);
+        assert_ne!(key, scope.fetch_key(true));
+
+        // First run: nothing cached yet, the regular refresh does the work.
+        assert_eq!(
+            warm_cache_for_cutoff(&conn, &provider, 30, &scope, false).await,
+            Ok(0)
+        );
+
+        // The cache already covers 60 days, so 30 days needs nothing new.
+        let now = SystemClock.now_unix();
+        crate::db::save_fetch_mark(&conn, &key, now - 60 * 86_400, now).unwrap();
+        assert_eq!(
+            warm_cache_for_cutoff(&conn, &provider, 30, &scope, false).await,
+            Ok(0)
+        );
+        assert_eq!(
+            crate::db::load_fetch_mark(&conn, &key).unwrap(),
+            Some(now - 60 * 86_400)
+        );
+
+        // Wiping the cache forgets what it covered.
+        delete_prs_not_in(&conn, &[]).unwrap();
+        assert_eq!(crate::db::load_fetch_mark(&conn, &key).unwrap(), None);
     }
 
     #[test]
     fn demo_contains_ready_to_merge_authored_item() {
-        let tmp_path = std::env::temp_dir().join("needle-demo-test.sqlite");
-        let _ = std::fs::remove_file(&tmp_path);
-        let conn = crate::db::open_db(&tmp_path).unwrap();
+        let conn = crate::db::TestDb::new("demo-test");
         let scope = ScopeFilters::default();
         let now_days = 30;
         let prs = refresh_demo(&conn, now_days, &scope).unwrap();
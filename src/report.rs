@@ -0,0 +1,133 @@
+//! Triage report for standup notes (`needle report --format md`).
+//!
+//! Renders the list in the TUI's section order with scores, ages and links, so it can be
+//! pasted into a doc or chat as is.
+
+use crate::RefreshFn;
+use crate::tui::{SECTIONS, human_age, in_bot_section, in_draft_section};
+use needle_core::db::now_unix;
+use needle_core::refresh::UiPr;
+use needle_core::timeutil::unix_to_ymd;
+
+/// Output formats for `needle report`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown: one section per category, one bullet per PR.
+    #[default]
+    Md,
+}
+
+/// Escape the characters that would turn a PR title into Markdown markup.
+fn md_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn push_section(md: &mut String, title: &str, rows: &[&UiPr], now: i64) {
+    if rows.is_empty() {
+        return;
+    }
+    md.push_str(&format!("\n## {title} ({})\n\n", rows.len()));
+    for p in rows {
+        let mut meta = vec![
+            format!("@{}", p.pr.author),
+            format!("score {}", p.score),
+            p.display_status.clone(),
+        ];
+        if let Some(at) = p.pr.review_requested_at_unix {
+            meta.push(format!("review requested {}", human_age(now, at)));
+        }
+        meta.push(format!("updated {}", human_age(now, p.pr.updated_at_unix)));
+        md.push_str(&format!(
+            "- [{}/{}#{}]({}) {} · {}\n",
+            p.pr.owner,
+            p.pr.repo,
+            p.pr.number,
+            p.pr.url,
+            md_escape(&p.pr.title),
+            meta.join(" · ")
+        ));
+    }
+}
+
+/// Markdown report of `prs` as of `now`, sections in the same order as the TUI.
+pub(crate) fn render_markdown(prs: &[UiPr], now: i64) -> String {
+    let date = unix_to_ymd(now)
+        .map(|(y, m, d)| format!(" ({y}-{m:02}-{d:02})"))
+        .unwrap_or_default();
+    let mut md = format!("# needle triage{date}\n\n{} open PRs\n", prs.len());
+
+    let listed = |p: &&UiPr| !p.snoozed && !in_draft_section(p) && !in_bot_section(p);
+    let pinned: Vec<&UiPr> = prs.iter().filter(listed).filter(|p| p.is_pinned).collect();
+    push_section(&mut md, "📌 PINNED", &pinned, now);
+    for section in SECTIONS {
+        let rows: Vec<&UiPr> = prs.iter().filter(|p| section.contains(p)).collect();
+        push_section(&mut md, section.title(), &rows, now);
+    }
+    let drafts: Vec<&UiPr> = prs.iter().filter(|p| in_draft_section(p)).collect();
+    push_section(&mut md, "📝 DRAFT", &drafts, now);
+    let bots: Vec<&UiPr> = prs.iter().filter(|p| in_bot_section(p)).collect();
+    push_section(&mut md, "🤖 BOTS", &bots, now);
+    let snoozed: Vec<&UiPr> = prs.iter().filter(|p| p.snoozed).collect();
+    push_section(&mut md, "💤 SNOOZED", &snoozed, now);
+    md
+}
+
+/// Refresh once and print the report to stdout. When the refresh fails (e.g. offline),
+/// report the cached `prs` instead and say so on stderr.
+pub fn run_report(
+    prs: Vec<UiPr>,
+    refresh_fn: RefreshFn,
+    format: ReportFormat,
+) -> Result<(), String> {
+    let prs = match refresh_fn() {
+        Ok(fresh) => fresh,
+        Err(e) => {
+            eprintln!("Warning: {e}; reporting cached PRs");
+            prs
+        }
+    };
+    match format {
+        ReportFormat::Md => print!("{}", render_markdown(&prs, now_unix())),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::db::TestDb;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn markdown_report_lists_sections_in_tui_order_with_links() {
+        let conn = TestDb::new("report-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        prs[0].pr.title = "Fix *all* [the] things".to_string();
+        let now = prs[0].pr.updated_at_unix + 7200;
+
+        let md = render_markdown(&prs, now);
+        assert!(md.starts_with("# needle triage ("));
+        let ready = md.find("## 🚢 READY TO MERGE (").unwrap();
+        let needs = md.find("## 🔥 NEEDS YOU (").unwrap();
+        let draft = md.find("## 📝 DRAFT (").unwrap();
+        assert!(ready < needs && needs < draft);
+
+        let p = &prs[0];
+        let line = md
+            .lines()
+            .find(|l| l.contains(&p.pr.url))
+            .expect("first PR listed");
+        assert!(line.starts_with(&format!(
+            "- [{}/{}#{}]({}) Fix \\*all\\* \\[the\\] things · @{} · score {}",
+            p.pr.owner, p.pr.repo, p.pr.number, p.pr.url, p.pr.author, p.score
+        )));
+        assert!(line.ends_with("updated 2h ago"));
+    }
+}
@@ -0,0 +1,262 @@
+//! Line-delimited JSON-RPC 2.0 server on stdin/stdout.
+//!
+//! Editor plugins spawn `needle rpc` as a child process and exchange one JSON object per line.
+//! Methods:
+//! - `list` `{category?, filter?}`: PR summaries in display order.
+//! - `details` `{pr_key}`: full PR including CI checks and merge blockers.
+//! - `refresh`: fetch fresh data, then behave like `list`.
+//! - `pin` `{pr_key}`: toggle the pin; returns `{pinned}`.
+
+use crate::RefreshFn;
+use needle_core::db::toggle_pin;
+use needle_core::refresh::{Category, UiPr};
+use needle_core::sla::SlaStatus;
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Write};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const APP_ERROR: i64 = -32000;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct PrSummary<'a> {
+    pr_key: &'a str,
+    repo: String,
+    number: i64,
+    title: &'a str,
+    author: &'a str,
+    url: &'a str,
+    category: Category,
+    score: i32,
+    status: &'a str,
+    is_draft: bool,
+    is_pinned: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sla: Option<SlaStatus>,
+}
+
+impl<'a> From<&'a UiPr> for PrSummary<'a> {
+    fn from(p: &'a UiPr) -> Self {
+        Self {
+            pr_key: &p.pr.pr_key,
+            repo: format!("{}/{}", p.pr.owner, p.pr.repo),
+            number: p.pr.number,
+            title: &p.pr.title,
+            author: &p.pr.author,
+            url: &p.pr.url,
+            category: p.category,
+            score: p.score,
+            status: &p.display_status,
+            is_draft: p.pr.is_draft,
+            is_pinned: p.is_pinned,
+            sla: p.sla,
+        }
+    }
+}
+
+fn parse_category(s: &str) -> Option<Category> {
+    match s {
+        "needs_you" => Some(Category::NeedsYou),
+        "ready_to_merge" => Some(Category::ReadyToMerge),
+        "waiting" => Some(Category::Waiting),
+        "stale" => Some(Category::Stale),
+        _ => None,
+    }
+}
+
+fn str_param<'a>(params: &'a Value, name: &str) -> Option<&'a str> {
+    params.get(name).and_then(Value::as_str)
+}
+
+fn list(prs: &[UiPr], params: &Value) -> Result<Value, RpcError> {
+    let category = match str_param(params, "category") {
+        Some(c) => Some(
+            parse_category(c)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown category: {c}")))?,
+        ),
+        None => None,
+    };
+    let filter = str_param(params, "filter").map(|f| f.to_lowercase());
+    let out: Vec<PrSummary> = prs
+        .iter()
+        .filter(|p| category.is_none_or(|c| p.category == c))
+        .filter(|p| {
+            filter.as_deref().is_none_or(|f| {
+                p.pr.pr_key.to_lowercase().contains(f)
+                    || p.pr.title.to_lowercase().contains(f)
+                    || p.pr.author.to_lowercase().contains(f)
+            })
+        })
+        .map(PrSummary::from)
+        .collect();
+    Ok(json!(out))
+}
+
+fn find<'a>(prs: &'a mut [UiPr], params: &Value) -> Result<&'a mut UiPr, RpcError> {
+    let key = str_param(params, "pr_key")
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing pr_key"))?;
+    prs.iter_mut()
+        .find(|p| p.pr.pr_key == key)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown PR: {key}")))
+}
+
+fn dispatch(
+    conn: &Connection,
+    prs: &mut Vec<UiPr>,
+    refresh_fn: &RefreshFn,
+    method: &str,
+    params: &Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "list" => list(prs, params),
+        "details" => Ok(json!(find(prs, params)?)),
+        "refresh" => {
+            *prs = refresh_fn().map_err(|e| RpcError::new(APP_ERROR, e))?;
+            list(prs, params)
+        }
+        "pin" => {
+            let pr = find(prs, params)?;
+            let pinned =
+                toggle_pin(conn, &pr.pr.pr_key).map_err(|e| RpcError::new(APP_ERROR, e))?;
+            pr.is_pinned = pinned;
+            Ok(json!({ "pinned": pinned }))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {method}"),
+        )),
+    }
+}
+
+fn handle_line(
+    conn: &Connection,
+    prs: &mut Vec<UiPr>,
+    refresh_fn: &RefreshFn,
+    line: &str,
+) -> Option<Value> {
+    let req: Value = match serde_json::from_str(line) {
+        Ok(v) => v,
+        Err(e) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, e.to_string()),
+            ));
+        }
+    };
+    let id = req.get("id").cloned();
+    let Some(method) = req.get("method").and_then(Value::as_str) else {
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "Missing method"),
+        ));
+    };
+    let params = req.get("params").cloned().unwrap_or(Value::Null);
+    let result = dispatch(conn, prs, refresh_fn, method, &params);
+
+    // Requests without an id are notifications: run them, but don't reply.
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, e: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": e.code, "message": e.message },
+    })
+}
+
+/// Serve requests until stdin is closed.
+pub fn run_rpc(conn: &Connection, prs: Vec<UiPr>, refresh_fn: RefreshFn) -> Result<(), String> {
+    let mut prs = prs;
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = line.map_err(|e| format!("Failed to read stdin: {e}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(resp) = handle_line(conn, &mut prs, &refresh_fn, &line) {
+            writeln!(stdout, "{resp}").map_err(|e| format!("Failed to write stdout: {e}"))?;
+            stdout
+                .flush()
+                .map_err(|e| format!("Failed to flush stdout: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::db::TestDb;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+    use std::sync::Arc;
+
+    fn setup(name: &str) -> (TestDb, Vec<UiPr>, RefreshFn) {
+        let conn = TestDb::new(&format!("rpc-{name}"));
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let refresh_fn: RefreshFn = Arc::new(|| Ok(Vec::new()));
+        (conn, prs, refresh_fn)
+    }
+
+    #[test]
+    fn list_filters_by_category() {
+        let (conn, mut prs, rf) = setup("list");
+        let line =
+            r#"{"jsonrpc":"2.0","id":1,"method":"list","params":{"category":"ready_to_merge"}}"#;
+        let resp = handle_line(&conn, &mut prs, &rf, line).unwrap();
+        let items = resp["result"].as_array().unwrap();
+        assert!(!items.is_empty());
+        assert!(items.iter().all(|i| i["category"] == "ready_to_merge"));
+        assert_eq!(resp["id"], 1);
+    }
+
+    #[test]
+    fn pin_toggles_and_details_reflects_it() {
+        let (conn, mut prs, rf) = setup("pin");
+        let key = prs[0].pr.pr_key.clone();
+        let pin = format!(r#"{{"id":"a","method":"pin","params":{{"pr_key":"{key}"}}}}"#);
+        let resp = handle_line(&conn, &mut prs, &rf, &pin).unwrap();
+        assert_eq!(resp["result"]["pinned"], true);
+
+        let details = format!(r#"{{"id":"b","method":"details","params":{{"pr_key":"{key}"}}}}"#);
+        let resp = handle_line(&conn, &mut prs, &rf, &details).unwrap();
+        assert_eq!(resp["result"]["is_pinned"], true);
+        assert_eq!(resp["result"]["pr"]["pr_key"], key.as_str());
+    }
+
+    #[test]
+    fn errors_and_notifications() {
+        let (conn, mut prs, rf) = setup("errors");
+        let resp = handle_line(&conn, &mut prs, &rf, "not json").unwrap();
+        assert_eq!(resp["error"]["code"], PARSE_ERROR);
+
+        let resp = handle_line(&conn, &mut prs, &rf, r#"{"id":2,"method":"nope"}"#).unwrap();
+        assert_eq!(resp["error"]["code"], METHOD_NOT_FOUND);
+
+        // No id: notification, no response. Refresh still runs.
+        assert!(handle_line(&conn, &mut prs, &rf, r#"{"method":"refresh"}"#).is_none());
+        assert!(prs.is_empty());
+    }
+}
@@ -0,0 +1,702 @@
+//! Scripted TUI replays for demos.
+//!
+//! A script is a plain-text list of steps that is fed into the real event loop
+//! (rendered into an off-screen buffer), with every changed frame written to an
+//! [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! size 120x36      # terminal size (default 120x36)
+//! wait 800         # pause for N milliseconds
+//! key Down         # press a key: Up, Down, Left, Right, Enter, Tab, Esc,
+//!                  #   Backspace, Space, a single char, or Ctrl+<char>
+//! type needle      # press each character in turn
+//! ```
+
+use crate::tui::{AppState, EventSource, Input, LoopOptions, RefreshIntervals, run_loop};
+use crate::{RefreshFn, RefreshPrFn};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use needle_core::db::now_unix;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use rusqlite::Connection;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+const DEFAULT_WIDTH: u16 = 120;
+const DEFAULT_HEIGHT: u16 = 36;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Wait(Duration),
+    Key(KeyEvent),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Script {
+    width: u16,
+    height: u16,
+    steps: Vec<Step>,
+}
+
+fn parse_script(text: &str) -> Result<Script, String> {
+    let mut script = Script {
+        width: DEFAULT_WIDTH,
+        height: DEFAULT_HEIGHT,
+        steps: Vec::new(),
+    };
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = match raw.split_once(" #") {
+            Some((before, _)) => before,
+            None => raw,
+        }
+        .trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (cmd, arg) = match line.split_once(char::is_whitespace) {
+            Some((c, a)) => (c, a.trim()),
+            None => (line, ""),
+        };
+
+        match cmd {
+            "size" => {
+                let (w, h) = arg
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse::<u16>().ok()?, h.parse::<u16>().ok()?)))
+                    .filter(|(w, h)| *w > 0 && *h > 0)
+                    .ok_or_else(|| format!("line {line_no}: expected `size <cols>x<rows>`"))?;
+                script.width = w;
+                script.height = h;
+            }
+            "wait" => {
+                let ms = arg
+                    .parse::<u64>()
+                    .map_err(|_| format!("line {line_no}: expected `wait <milliseconds>`"))?;
+                script.steps.push(Step::Wait(Duration::from_millis(ms)));
+            }
+            "key" => {
+                let key =
+                    parse_key(arg).ok_or_else(|| format!("line {line_no}: unknown key `{arg}`"))?;
+                script.steps.push(Step::Key(key));
+            }
+            "type" => {
+                // Keep the raw remainder so leading/trailing spaces survive.
+                let text = raw.trim_start().strip_prefix("type ").unwrap_or("");
+                for c in text.chars() {
+                    script.steps.push(Step::Key(KeyEvent::new(
+                        KeyCode::Char(c),
+                        KeyModifiers::NONE,
+                    )));
+                }
+            }
+            other => return Err(format!("line {line_no}: unknown command `{other}`")),
+        }
+    }
+
+    Ok(script)
+}
+
+fn parse_key(name: &str) -> Option<KeyEvent> {
+    if let Some(rest) = name
+        .strip_prefix("Ctrl+")
+        .or_else(|| name.strip_prefix("ctrl+"))
+    {
+        let mut chars = rest.chars();
+        let c = chars.next()?;
+        if chars.next().is_some() {
+            return None;
+        }
+        return Some(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let mut chars = name.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c)
+        }
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// Feeds script steps to the event loop, sleeping through `wait` steps.
+struct ScriptEvents {
+    steps: VecDeque<Step>,
+    /// Give the loop one more frame after the last step before ending.
+    drained: bool,
+}
+
+impl EventSource for ScriptEvents {
+    fn next(&mut self, timeout: Duration) -> Result<Input, String> {
+        match self.steps.pop_front() {
+            Some(Step::Key(k)) => Ok(Input::Key(k)),
+            Some(Step::Wait(d)) => {
+                // Sleep in slices so background refreshes and redraws keep running.
+                let slice = d.min(timeout);
+                std::thread::sleep(slice);
+                if d > slice {
+                    self.steps.push_front(Step::Wait(d - slice));
+                }
+                Ok(Input::Idle)
+            }
+            None if !self.drained => {
+                self.drained = true;
+                Ok(Input::Idle)
+            }
+            None => Ok(Input::End),
+        }
+    }
+}
+
+/// Collects changed frames as asciicast v2 output events.
+struct CastRecorder {
+    width: u16,
+    height: u16,
+    started: Instant,
+    last_frame: Option<String>,
+    events: Vec<(f64, String)>,
+}
+
+impl CastRecorder {
+    fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            started: Instant::now(),
+            last_frame: None,
+            events: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, buf: &Buffer) {
+        let frame = buffer_to_ansi(buf);
+        if self.last_frame.as_deref() == Some(frame.as_str()) {
+            return;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        self.events.push((elapsed, format!("\x1b[H\x1b[2J{frame}")));
+        self.last_frame = Some(frame);
+    }
+
+    fn to_cast(&self) -> String {
+        let header = serde_json::json!({
+            "version": 2,
+            "width": self.width,
+            "height": self.height,
+            "timestamp": now_unix(),
+        });
+        let mut out = header.to_string();
+        out.push('\n');
+        for (t, data) in &self.events {
+            out.push_str(
+                &serde_json::json!([(t * 1000.0).round() / 1000.0, "o", data]).to_string(),
+            );
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn sgr_color(color: Color, background: bool) -> String {
+    let base = if background { 10 } else { 0 };
+    match color {
+        Color::Reset => (39 + base).to_string(),
+        Color::Black => (30 + base).to_string(),
+        Color::Red => (31 + base).to_string(),
+        Color::Green => (32 + base).to_string(),
+        Color::Yellow => (33 + base).to_string(),
+        Color::Blue => (34 + base).to_string(),
+        Color::Magenta => (35 + base).to_string(),
+        Color::Cyan => (36 + base).to_string(),
+        Color::Gray => (37 + base).to_string(),
+        Color::DarkGray => (90 + base).to_string(),
+        Color::LightRed => (91 + base).to_string(),
+        Color::LightGreen => (92 + base).to_string(),
+        Color::LightYellow => (93 + base).to_string(),
+        Color::LightBlue => (94 + base).to_string(),
+        Color::LightMagenta => (95 + base).to_string(),
+        Color::LightCyan => (96 + base).to_string(),
+        Color::White => (97 + base).to_string(),
+        Color::Indexed(i) => format!("{};5;{i}", 38 + base),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", 38 + base),
+    }
+}
+
+fn sgr_for(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string(), sgr_color(fg, false), sgr_color(bg, true)];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// Render a buffer as ANSI text (one terminal row per line).
+fn buffer_to_ansi(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        if y > area.top() {
+            out.push_str("\r\n");
+        }
+        let mut current: Option<(Color, Color, Modifier)> = None;
+        let mut skip = 0usize;
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr_for(cell.fg, cell.bg, cell.modifier));
+                current = Some(style);
+            }
+            let symbol = cell.symbol();
+            out.push_str(symbol);
+            skip = symbol.width().saturating_sub(1);
+        }
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+/// Replay `script_path` against the TUI and write the recording to `cast_path`.
+pub fn run_script(
+    conn: &Connection,
+    state: AppState,
+    refresh_fn: RefreshFn,
+    start_refresh_immediately: bool,
+    refresh_intervals: RefreshIntervals,
+    script_path: &Path,
+    cast_path: &Path,
+) -> Result<(), String> {
+    let text = std::fs::read_to_string(script_path)
+        .map_err(|e| format!("Failed to read script {}: {e}", script_path.display()))?;
+    let script = parse_script(&text).map_err(|e| format!("{}: {e}", script_path.display()))?;
+
+    let recorder = replay(
+        conn,
+        state,
+        refresh_fn,
+        start_refresh_immediately,
+        refresh_intervals,
+        script,
+    )?;
+
+    std::fs::write(cast_path, recorder.to_cast())
+        .map_err(|e| format!("Failed to write {}: {e}", cast_path.display()))?;
+    eprintln!(
+        "Recorded {} frames to {}",
+        recorder.events.len(),
+        cast_path.display()
+    );
+    Ok(())
+}
+
+fn replay(
+    conn: &Connection,
+    state: AppState,
+    refresh_fn: RefreshFn,
+    start_refresh_immediately: bool,
+    refresh_intervals: RefreshIntervals,
+    script: Script,
+) -> Result<CastRecorder, String> {
+    let backend = TestBackend::new(script.width, script.height);
+    let mut terminal =
+        Terminal::new(backend).map_err(|e| format!("Failed to init terminal: {e}"))?;
+    let mut recorder = CastRecorder::new(script.width, script.height);
+    let mut events = ScriptEvents {
+        steps: script.steps.into(),
+        drained: false,
+    };
+
+    // Scripts only get the full refresh; pick the shown PR out of it.
+    let full_refresh = Arc::clone(&refresh_fn);
+    let refresh_pr_fn: RefreshPrFn = Arc::new(move |key| {
+        full_refresh()?
+            .into_iter()
+            .find(|p| p.pr.pr_key == key)
+            .ok_or_else(|| format!("{key} is no longer in the refreshed list"))
+    });
+    run_loop(
+        &mut terminal,
+        conn,
+        state,
+        refresh_fn,
+        LoopOptions {
+            refresh_pr_fn,
+            window_fn: None,
+            // The clipboard is not part of the script, so keep replays deterministic.
+            lookup_fn: Arc::new(|_| {
+                Err("PR lookup is disabled while replaying a script".to_string())
+            }),
+            check_log_fn: Arc::new(|_, _| {
+                Err("Check logs are not fetched while replaying a script".to_string())
+            }),
+            merge_fn: Arc::new(|_, _| {
+                Err("Merging is disabled while replaying a script".to_string())
+            }),
+            comment_fn: Arc::new(|_, _| {
+                Err("Commenting is disabled while replaying a script".to_string())
+            }),
+            update_branch_fn: Arc::new(|_| {
+                Err("Updating branches is disabled while replaying a script".to_string())
+            }),
+            set_draft_fn: Arc::new(|_, _| {
+                Err("Changing drafts is disabled while replaying a script".to_string())
+            }),
+            decline_fn: Arc::new(|_, _| {
+                Err("Declining reviews is disabled while replaying a script".to_string())
+            }),
+            approve_fn: Arc::new(|_| {
+                Err("Approving is disabled while replaying a script".to_string())
+            }),
+            mark_read_fn: None,
+            start_refresh_immediately,
+            bell_events: Vec::new(),
+            check_updates: false,
+            remember_ui_state: false,
+            refresh_intervals,
+            // A replay is deterministic; config edits do not leak into it.
+            config_reload: None,
+        },
+        &mut events,
+        &mut |buf| recorder.record(buf),
+    )?;
+
+    Ok(recorder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::{TailColumns, UiPrefs};
+    use needle_core::db::TestDb;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn parses_steps_sizes_and_comments() {
+        let script =
+            parse_script("# demo\nsize 80x24\nwait 250  # settle\nkey Down\nkey Ctrl+c\ntype ab\n")
+                .unwrap();
+        assert_eq!((script.width, script.height), (80, 24));
+        assert_eq!(
+            script.steps,
+            vec![
+                Step::Wait(Duration::from_millis(250)),
+                Step::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+                Step::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+                Step::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE)),
+                Step::Key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_bad_lines_with_line_numbers() {
+        assert_eq!(
+            parse_script("key Down\nkey Sideways").unwrap_err(),
+            "line 2: unknown key `Sideways`"
+        );
+        assert!(parse_script("size big").unwrap_err().starts_with("line 1:"));
+        assert!(
+            parse_script("jump 3")
+                .unwrap_err()
+                .contains("unknown command")
+        );
+    }
+
+    #[test]
+    fn replay_drives_real_tui_and_records_cast() {
+        let conn = TestDb::new("script-replay");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+
+        let script = parse_script("size 100x30\nwait 60\nkey Tab\nwait 60\n").unwrap();
+        let refresh_fn: RefreshFn = Arc::new(|| Ok(Vec::new()));
+        let recorder = replay(
+            &conn,
+            AppState::new(
+                prs,
+                UiPrefs {
+                    hide_pr_numbers: false,
+                    hide_repo: false,
+                    hide_author: false,
+                    keep_tail: TailColumns::default(),
+                },
+            ),
+            refresh_fn,
+            false,
+            RefreshIntervals {
+                list_secs: 180,
+                details_secs: 30,
+            },
+            script,
+        )
+        .unwrap();
+
+        assert!(
+            recorder.events.len() >= 2,
+            "expected list and details frames"
+        );
+        assert!(recorder.last_frame.as_deref().unwrap().contains("DETAILS"));
+
+        let cast = recorder.to_cast();
+        let mut lines = cast.lines();
+        let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 100);
+        let first: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(first[1], "o");
+    }
+
+    #[test]
+    fn idle_loop_only_draws_on_changes() {
+        let conn = TestDb::new("script-idle");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+
+        // 500ms of idle time (10 loop ticks) around a single key press.
+        let script = parse_script("size 80x24\nwait 250\nkey Down\nwait 250\n").unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(script.width, script.height)).unwrap();
+        let mut events = ScriptEvents {
+            steps: script.steps.into(),
+            drained: false,
+        };
+        let mut frames = 0;
+        run_loop(
+            &mut terminal,
+            &conn,
+            AppState::new(prs, UiPrefs::default()),
+            Arc::new(|| Ok(Vec::new())),
+            LoopOptions {
+                refresh_pr_fn: Arc::new(|_| Err("disabled".to_string())),
+                window_fn: None,
+                lookup_fn: Arc::new(|_| Err("disabled".to_string())),
+                check_log_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                merge_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                comment_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                update_branch_fn: Arc::new(|_| Err("disabled".to_string())),
+                set_draft_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                decline_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                approve_fn: Arc::new(|_| Err("disabled".to_string())),
+                mark_read_fn: None,
+                start_refresh_immediately: false,
+                bell_events: Vec::new(),
+                check_updates: false,
+                remember_ui_state: false,
+                refresh_intervals: RefreshIntervals {
+                    list_secs: 180,
+                    details_secs: 30,
+                },
+                config_reload: None,
+            },
+            &mut events,
+            &mut |_| frames += 1,
+        )
+        .unwrap();
+
+        // The first frame, then one for the key press.
+        assert_eq!(frames, 2);
+    }
+
+    #[test]
+    fn idle_loop_polls_slowly() {
+        struct Recording(Vec<Duration>);
+        impl EventSource for Recording {
+            fn next(&mut self, timeout: Duration) -> Result<Input, String> {
+                self.0.push(timeout);
+                Ok(if self.0.len() < 3 {
+                    Input::Idle
+                } else {
+                    Input::End
+                })
+            }
+        }
+
+        let conn = TestDb::new("script-poll");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut events = Recording(Vec::new());
+        run_loop(
+            &mut terminal,
+            &conn,
+            AppState::new(prs, UiPrefs::default()),
+            Arc::new(|| Ok(Vec::new())),
+            LoopOptions {
+                refresh_pr_fn: Arc::new(|_| Err("disabled".to_string())),
+                window_fn: None,
+                lookup_fn: Arc::new(|_| Err("disabled".to_string())),
+                check_log_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                merge_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                comment_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                update_branch_fn: Arc::new(|_| Err("disabled".to_string())),
+                set_draft_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                decline_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                approve_fn: Arc::new(|_| Err("disabled".to_string())),
+                mark_read_fn: None,
+                start_refresh_immediately: false,
+                bell_events: Vec::new(),
+                check_updates: false,
+                remember_ui_state: false,
+                refresh_intervals: RefreshIntervals {
+                    list_secs: 180,
+                    details_secs: 30,
+                },
+                config_reload: None,
+            },
+            &mut events,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert!(events.0.iter().all(|t| *t >= Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn day_window_key_lists_the_cache_then_backfills_wider_windows() {
+        let conn = TestDb::new("script-window");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let calls_in_fn = Arc::clone(&calls);
+
+        // 30 days → 90 (wider: listed, then backfilled) → back round to 7 (cache only).
+        let script = parse_script("size 80x24\nkey w\nwait 200\nkey w\nwait 100\n").unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(script.width, script.height)).unwrap();
+        let mut events = ScriptEvents {
+            steps: script.steps.into(),
+            drained: false,
+        };
+        run_loop(
+            &mut terminal,
+            &conn,
+            AppState::new(prs, UiPrefs::default()).with_scope(&ScopeFilters::default(), 30),
+            Arc::new(|| Ok(Vec::new())),
+            LoopOptions {
+                refresh_pr_fn: Arc::new(|_| Err("disabled".to_string())),
+                window_fn: Some(Arc::new(move |days, backfill| {
+                    calls_in_fn.lock().unwrap().push((days, backfill));
+                    Ok(Vec::new())
+                })),
+                lookup_fn: Arc::new(|_| Err("disabled".to_string())),
+                check_log_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                merge_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                comment_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                update_branch_fn: Arc::new(|_| Err("disabled".to_string())),
+                set_draft_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                decline_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                approve_fn: Arc::new(|_| Err("disabled".to_string())),
+                mark_read_fn: None,
+                start_refresh_immediately: false,
+                bell_events: Vec::new(),
+                check_updates: false,
+                remember_ui_state: false,
+                refresh_intervals: RefreshIntervals {
+                    list_secs: 180,
+                    details_secs: 30,
+                },
+                config_reload: None,
+            },
+            &mut events,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![(90, false), (90, true), (7, false)]
+        );
+    }
+
+    #[test]
+    fn select_mode_approves_every_marked_pr_after_confirming() {
+        let conn = TestDb::new("script-bulk");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let mut expected: Vec<String> = prs
+            .iter()
+            .filter(|p| !p.pr.is_viewer_author && !p.snoozed)
+            .map(|p| p.pr.pr_key.clone())
+            .collect();
+        expected.sort();
+        let approved = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let approved_in_fn = Arc::clone(&approved);
+
+        // The first A is cancelled by n; the second one goes through. The viewer's own PRs
+        // are skipped.
+        let script =
+            parse_script("size 100x30\nkey V\nkey *\nkey A\nkey n\nkey A\nkey y\nwait 200\n")
+                .unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(script.width, script.height)).unwrap();
+        let mut events = ScriptEvents {
+            steps: script.steps.into(),
+            drained: false,
+        };
+        run_loop(
+            &mut terminal,
+            &conn,
+            AppState::new(prs, UiPrefs::default()),
+            Arc::new(|| Ok(Vec::new())),
+            LoopOptions {
+                refresh_pr_fn: Arc::new(|_| Err("disabled".to_string())),
+                window_fn: None,
+                lookup_fn: Arc::new(|_| Err("disabled".to_string())),
+                check_log_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                merge_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                comment_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                update_branch_fn: Arc::new(|_| Err("disabled".to_string())),
+                set_draft_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                decline_fn: Arc::new(|_, _| Err("disabled".to_string())),
+                approve_fn: Arc::new(move |pr| {
+                    approved_in_fn.lock().unwrap().push(pr.pr_key.clone());
+                    Ok(())
+                }),
+                mark_read_fn: None,
+                start_refresh_immediately: false,
+                bell_events: Vec::new(),
+                check_updates: false,
+                remember_ui_state: false,
+                refresh_intervals: RefreshIntervals {
+                    list_secs: 180,
+                    details_secs: 30,
+                },
+                config_reload: None,
+            },
+            &mut events,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let mut approved = approved.lock().unwrap().clone();
+        approved.sort();
+        assert!(!approved.is_empty());
+        assert_eq!(approved, expected);
+    }
+}
@@ -0,0 +1,549 @@
+//! Read-only HTML dashboard served over HTTP (`needle serve`).
+//!
+//! Renders the current snapshot grouped by category and pushes a `refresh` server-sent event
+//! whenever a background refresh lands, so wall displays stay current without polling.
+//! `/metrics` exposes the same snapshot as Prometheus gauges, optionally on a separate
+//! address of its own (`--metrics :9878`) for scrapers.
+
+use crate::RefreshFn;
+use crate::tui::category_title;
+use needle_core::db::now_unix;
+use needle_core::github::{RateLimit, last_rate_limit};
+use needle_core::model::CiState;
+use needle_core::refresh::{Category, UiPr};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const SSE_PING_SECS: u64 = 15;
+
+/// The data currently served, replaced wholesale after each refresh.
+#[derive(Default)]
+struct Snapshot {
+    prs: Vec<UiPr>,
+    generation: u64,
+    refreshed_at: Option<i64>,
+    last_error: Option<String>,
+    last_refresh_duration_secs: Option<f64>,
+    refreshes_total: u64,
+    refresh_failures_total: u64,
+}
+
+type Shared = Arc<Mutex<Snapshot>>;
+
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn render_section(html: &mut String, title: &str, css: &str, rows: &[&UiPr]) {
+    if rows.is_empty() {
+        return;
+    }
+    html.push_str(&format!(
+        "<section class=\"{css}\"><h2>{}</h2><table>\n",
+        html_escape(title)
+    ));
+    for p in rows {
+        html.push_str(&format!(
+            "<tr{}><td class=\"repo\">{}/{}</td><td class=\"author\">{}</td>\
+             <td><a href=\"{}\">#{}</a></td><td class=\"title\">{}</td><td class=\"status\">{}</td></tr>\n",
+            if p.pr.is_draft { " class=\"draft\"" } else { "" },
+            html_escape(&p.pr.owner),
+            html_escape(&p.pr.repo),
+            html_escape(&p.pr.author),
+            html_escape(&p.pr.url),
+            p.pr.number,
+            html_escape(&p.pr.title),
+            html_escape(&p.display_status),
+        ));
+    }
+    html.push_str("</table></section>\n");
+}
+
+fn render_dashboard(snap: &Snapshot, now: i64) -> String {
+    let mut html = String::from(
+        r#"<!doctype html>
+<html><head><meta charset="utf-8"><title>needle</title>
+<style>
+body { background: #111; color: #ddd; font: 15px/1.4 ui-monospace, monospace; margin: 2em; }
+h1 { color: #fc3; margin: 0 0 .2em; } .meta { color: #888; margin-bottom: 1.5em; }
+h2 { font-size: 1.1em; border-bottom: 1px solid #444; padding-bottom: .2em; }
+section.needs_you h2 { color: #fc3; } section.ready_to_merge h2 { color: #6c6; }
+section.waiting h2 { color: #6cc; } section.stale h2 { color: #c6c; } section.draft h2 { color: #888; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+td { padding: .15em .8em .15em 0; white-space: nowrap; } td.title { white-space: normal; width: 100%; }
+td.repo { color: #6cc; } td.author { color: #c6c; } td.status { font-weight: bold; }
+a { color: #69f; } tr.draft { opacity: .5; } .error { color: #f66; }
+</style></head><body>
+<h1>needle</h1>
+"#,
+    );
+    let updated = match snap.refreshed_at {
+        Some(t) => format!("updated {}s ago", now.saturating_sub(t)),
+        None => "showing cached data".to_string(),
+    };
+    html.push_str(&format!(
+        "<div class=\"meta\">{} PRs · {}</div>\n",
+        snap.prs.len(),
+        updated
+    ));
+    if let Some(err) = &snap.last_error {
+        html.push_str(&format!(
+            "<div class=\"error\">Last refresh failed: {}</div>\n",
+            html_escape(err)
+        ));
+    }
+
+    let pinned: Vec<&UiPr> = snap
+        .prs
+        .iter()
+        .filter(|p| p.is_pinned && !p.pr.is_draft)
+        .collect();
+    render_section(&mut html, "📌 PINNED", "pinned", &pinned);
+    for (cat, css) in [
+        (Category::ReadyToMerge, "ready_to_merge"),
+        (Category::NeedsYou, "needs_you"),
+        (Category::Waiting, "waiting"),
+        (Category::Stale, "stale"),
+    ] {
+        let rows: Vec<&UiPr> = snap
+            .prs
+            .iter()
+            .filter(|p| !p.pr.is_draft && !p.is_pinned && p.category == cat)
+            .collect();
+        render_section(&mut html, category_title(cat), css, &rows);
+    }
+    let drafts: Vec<&UiPr> = snap.prs.iter().filter(|p| p.pr.is_draft).collect();
+    render_section(&mut html, "📝 DRAFT", "draft", &drafts);
+
+    html.push_str(
+        r#"<script>
+new EventSource("/events").addEventListener("refresh", () => location.reload());
+</script>
+</body></html>
+"#,
+    );
+    html
+}
+
+fn push_metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(&str, f64)]) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+    for (labels, value) in samples {
+        out.push_str(&format!("{name}{labels} {value}\n"));
+    }
+}
+
+/// Prometheus text exposition of the snapshot as of `now` (format 0.0.4).
+fn render_metrics(snap: &Snapshot, rate_limit: Option<RateLimit>, now: i64) -> String {
+    let mut out = String::new();
+    let count = |cat: Category| {
+        snap.prs
+            .iter()
+            .filter(|p| !p.pr.is_draft && p.category == cat)
+            .count() as f64
+    };
+    push_metric(
+        &mut out,
+        "needle_prs",
+        "gauge",
+        "Open PRs in the attention set, by category (drafts counted separately).",
+        &[
+            ("{category=\"needs_you\"}", count(Category::NeedsYou)),
+            (
+                "{category=\"ready_to_merge\"}",
+                count(Category::ReadyToMerge),
+            ),
+            ("{category=\"waiting\"}", count(Category::Waiting)),
+            ("{category=\"stale\"}", count(Category::Stale)),
+            (
+                "{category=\"draft\"}",
+                snap.prs.iter().filter(|p| p.pr.is_draft).count() as f64,
+            ),
+        ],
+    );
+    push_metric(
+        &mut out,
+        "needle_prs_failing_ci",
+        "gauge",
+        "Open PRs in the attention set whose CI is failing.",
+        &[(
+            "",
+            snap.prs
+                .iter()
+                .filter(|p| matches!(p.pr.ci_state, CiState::Failure))
+                .count() as f64,
+        )],
+    );
+    push_metric(
+        &mut out,
+        "needle_refreshes_total",
+        "counter",
+        "Background refreshes attempted.",
+        &[("", snap.refreshes_total as f64)],
+    );
+    push_metric(
+        &mut out,
+        "needle_refresh_failures_total",
+        "counter",
+        "Background refreshes that returned an error.",
+        &[("", snap.refresh_failures_total as f64)],
+    );
+    if let Some(d) = snap.last_refresh_duration_secs {
+        push_metric(
+            &mut out,
+            "needle_last_refresh_duration_seconds",
+            "gauge",
+            "Wall time of the most recent refresh.",
+            &[("", d)],
+        );
+    }
+    if let Some(t) = snap.refreshed_at {
+        push_metric(
+            &mut out,
+            "needle_last_refresh_success_timestamp_seconds",
+            "gauge",
+            "Unix time of the most recent successful refresh.",
+            &[("", t as f64)],
+        );
+        push_metric(
+            &mut out,
+            "needle_last_refresh_age_seconds",
+            "gauge",
+            "Seconds since the most recent successful refresh.",
+            &[("", now.saturating_sub(t) as f64)],
+        );
+    }
+    if let Some(rl) = rate_limit {
+        push_metric(
+            &mut out,
+            "needle_github_rate_limit_remaining",
+            "gauge",
+            "GitHub GraphQL points left in the current window.",
+            &[("", rl.remaining as f64)],
+        );
+        push_metric(
+            &mut out,
+            "needle_github_rate_limit_limit",
+            "gauge",
+            "GitHub GraphQL points per window.",
+            &[("", rl.limit as f64)],
+        );
+        push_metric(
+            &mut out,
+            "needle_github_rate_limit_reset_timestamp_seconds",
+            "gauge",
+            "Unix time when the GitHub GraphQL window resets.",
+            &[("", rl.reset_at_unix as f64)],
+        );
+    }
+    out
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+/// Hold the connection open and emit `refresh` whenever the snapshot generation changes.
+fn stream_events(mut stream: TcpStream, shared: Shared) {
+    let header = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n";
+    if stream.write_all(header.as_bytes()).is_err() {
+        return;
+    }
+    let mut seen = shared.lock().map(|s| s.generation).unwrap_or(0);
+    let mut last_write = Instant::now();
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+        let current = shared.lock().map(|s| s.generation).unwrap_or(seen);
+        let msg = if current != seen {
+            seen = current;
+            format!("event: refresh\ndata: {current}\n\n")
+        } else if last_write.elapsed() >= Duration::from_secs(SSE_PING_SECS) {
+            ": ping\n\n".to_string()
+        } else {
+            continue;
+        };
+        if stream.write_all(msg.as_bytes()).is_err() || stream.flush().is_err() {
+            return;
+        }
+        last_write = Instant::now();
+    }
+}
+
+/// Answer one request. The `--metrics` listener (`metrics_only`) serves nothing but
+/// `/metrics`.
+fn handle_conn(mut stream: TcpStream, shared: Shared, metrics_only: bool) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let Ok(read_half) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(read_half);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // Headers are not needed; drain them so the client sees a clean response.
+    loop {
+        let mut h = String::new();
+        match reader.read_line(&mut h) {
+            Ok(0) => break,
+            Ok(_) if h.trim().is_empty() => break,
+            Ok(_) => {}
+            Err(_) => return,
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("/");
+    let path = target.split('?').next().unwrap_or("/");
+    if method != "GET" {
+        respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n",
+        );
+        return;
+    }
+
+    match path {
+        "/metrics" => {
+            let body = match shared.lock() {
+                Ok(snap) => render_metrics(&snap, last_rate_limit(), now_unix()),
+                Err(_) => return,
+            };
+            respond(&mut stream, "200 OK", "text/plain; version=0.0.4", &body);
+        }
+        _ if metrics_only => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+        "/" => {
+            let html = match shared.lock() {
+                Ok(snap) => render_dashboard(&snap, now_unix()),
+                Err(_) => return,
+            };
+            respond(&mut stream, "200 OK", "text/html; charset=utf-8", &html);
+        }
+        "/prs.json" => {
+            let body = match shared.lock() {
+                Ok(snap) => serde_json::to_string(&snap.prs).unwrap_or_else(|_| "[]".to_string()),
+                Err(_) => return,
+            };
+            respond(&mut stream, "200 OK", "application/json", &body);
+        }
+        "/events" => stream_events(stream, shared),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+    }
+}
+
+fn spawn_refresher(
+    shared: Shared,
+    refresh_fn: RefreshFn,
+    refresh_secs: u64,
+    start_refresh_immediately: bool,
+) {
+    std::thread::spawn(move || {
+        let mut first = start_refresh_immediately;
+        loop {
+            if !first {
+                // Back off while the GitHub API budget is low.
+                let wait = last_rate_limit().map_or(refresh_secs, |rl| {
+                    rl.refresh_interval_secs(refresh_secs, now_unix())
+                });
+                std::thread::sleep(Duration::from_secs(wait.max(1)));
+            }
+            first = false;
+            let started = Instant::now();
+            let res = refresh_fn();
+            let Ok(mut snap) = shared.lock() else {
+                return;
+            };
+            snap.last_refresh_duration_secs = Some(started.elapsed().as_secs_f64());
+            snap.refreshes_total += 1;
+            match res {
+                Ok(prs) => {
+                    snap.prs = prs;
+                    snap.refreshed_at = Some(now_unix());
+                    snap.last_error = None;
+                }
+                Err(e) => {
+                    snap.last_error = Some(e);
+                    snap.refresh_failures_total += 1;
+                }
+            }
+            snap.generation = snap.generation.wrapping_add(1);
+        }
+    });
+}
+
+/// Where `--metrics` listens: a socket address (`0.0.0.0:9878`, `[::1]:9878`), or `:port` on
+/// the dashboard's `host`.
+fn metrics_addr(spec: &str, host: &str) -> Result<(String, u16), String> {
+    if let Ok(addr) = spec.parse::<SocketAddr>() {
+        return Ok((addr.ip().to_string(), addr.port()));
+    }
+    let port = spec.strip_prefix(':').ok_or_else(|| {
+        format!(
+            "Invalid --metrics address `{spec}` (expected :9878 or an address like 0.0.0.0:9878)"
+        )
+    })?;
+    let port = port
+        .parse::<u16>()
+        .map_err(|e| format!("Invalid --metrics port in `{spec}`: {e}"))?;
+    Ok((host.to_string(), port))
+}
+
+fn accept(listener: TcpListener, shared: Shared, metrics_only: bool) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let shared = Arc::clone(&shared);
+        std::thread::spawn(move || handle_conn(stream, shared, metrics_only));
+    }
+}
+
+/// Serve the dashboard on `host:port` (and, with `metrics`, only `/metrics` on that address)
+/// until the process is killed.
+pub fn run_serve(
+    prs: Vec<UiPr>,
+    refresh_fn: RefreshFn,
+    start_refresh_immediately: bool,
+    refresh_secs: u64,
+    host: &str,
+    port: u16,
+    metrics: Option<&str>,
+) -> Result<(), String> {
+    let listener = TcpListener::bind((host, port))
+        .map_err(|e| format!("Failed to bind {host}:{port}: {e}"))?;
+    eprintln!("needle dashboard on http://{host}:{port}/");
+    let metrics_listener = match metrics {
+        Some(spec) => {
+            let (mhost, mport) = metrics_addr(spec, host)?;
+            let l = TcpListener::bind((mhost.as_str(), mport))
+                .map_err(|e| format!("Failed to bind {mhost}:{mport}: {e}"))?;
+            eprintln!("needle metrics on http://{mhost}:{mport}/metrics");
+            Some(l)
+        }
+        None => None,
+    };
+
+    let shared: Shared = Arc::new(Mutex::new(Snapshot {
+        prs,
+        ..Default::default()
+    }));
+    spawn_refresher(
+        Arc::clone(&shared),
+        refresh_fn,
+        refresh_secs,
+        start_refresh_immediately,
+    );
+
+    if let Some(l) = metrics_listener {
+        let shared = Arc::clone(&shared);
+        std::thread::spawn(move || accept(l, shared, true));
+    }
+    accept(listener, shared, false);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::db::TestDb;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    fn demo_snapshot() -> Snapshot {
+        let conn = TestDb::new("serve-test");
+        Snapshot {
+            prs: refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap(),
+            last_error: Some("boom <b>".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dashboard_groups_by_category_and_escapes() {
+        let html = render_dashboard(&demo_snapshot(), 0);
+        let needs = html.find("🔥 NEEDS YOU").unwrap();
+        let ready = html.find("🚢 READY TO MERGE").unwrap();
+        let draft = html.find("📝 DRAFT").unwrap();
+        assert!(ready < needs && needs < draft);
+        assert!(html.contains("Last refresh failed: boom &lt;b&gt;"));
+        assert!(html.contains("new EventSource(\"/events\")"));
+    }
+
+    #[test]
+    fn escape_covers_html_metacharacters() {
+        assert_eq!(
+            html_escape(r#"<a href="x">&'"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+
+    #[test]
+    fn metrics_expose_category_gauges_and_rate_limit() {
+        let mut snap = demo_snapshot();
+        snap.refreshes_total = 3;
+        snap.refresh_failures_total = 1;
+        snap.last_refresh_duration_secs = Some(1.5);
+        snap.refreshed_at = Some(1_700_000_000);
+        let needs = snap
+            .prs
+            .iter()
+            .filter(|p| !p.pr.is_draft && p.category == Category::NeedsYou)
+            .count();
+        let rl = RateLimit {
+            limit: 5000,
+            remaining: 4321,
+            reset_at_unix: 1_700_000_000,
+        };
+        let failing = snap
+            .prs
+            .iter()
+            .filter(|p| matches!(p.pr.ci_state, CiState::Failure))
+            .count();
+        assert!(failing > 0);
+        let text = render_metrics(&snap, Some(rl), 1_700_000_090);
+        assert!(text.contains(&format!("needle_prs{{category=\"needs_you\"}} {needs}\n")));
+        assert!(text.contains("# TYPE needle_refreshes_total counter\nneedle_refreshes_total 3\n"));
+        assert!(text.contains("needle_refresh_failures_total 1\n"));
+        assert!(text.contains("needle_last_refresh_duration_seconds 1.5\n"));
+        assert!(text.contains("needle_github_rate_limit_remaining 4321\n"));
+        assert!(text.contains(&format!("needle_prs_failing_ci {failing}\n")));
+        assert!(text.contains("needle_last_refresh_age_seconds 90\n"));
+        assert!(!render_metrics(&snap, None, 0).contains("rate_limit"));
+        snap.refreshed_at = None;
+        assert!(!render_metrics(&snap, None, 0).contains("refresh_age"));
+    }
+
+    #[test]
+    fn metrics_address_defaults_to_the_dashboard_host() {
+        assert_eq!(
+            metrics_addr(":9878", "127.0.0.1"),
+            Ok(("127.0.0.1".to_string(), 9878))
+        );
+        assert_eq!(
+            metrics_addr("0.0.0.0:9100", "127.0.0.1"),
+            Ok(("0.0.0.0".to_string(), 9100))
+        );
+        assert_eq!(
+            metrics_addr("[::1]:9878", "127.0.0.1"),
+            Ok(("::1".to_string(), 9878))
+        );
+        assert!(metrics_addr("9878", "127.0.0.1").is_err());
+        assert!(metrics_addr(":http", "127.0.0.1").is_err());
+    }
+}
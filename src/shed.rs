@@ -0,0 +1,141 @@
+//! Review load shedding: which review requests are safest to decline or hand off.
+//!
+//! Only direct requests count; a team request is not the viewer's alone to drop. A small
+//! PR that someone else is already reviewing can do without the viewer, and so can a bot
+//! PR with another reviewer. A bot PR nobody else looks at is better handed to someone.
+//! Everything else is left alone.
+
+use crate::bots::is_bot;
+use crate::model::ReviewState;
+use crate::refresh::UiPr;
+
+/// At most this many changed lines (additions plus deletions) counts as a small PR.
+pub const SMALL_PR_LINES: i64 = 100;
+
+/// What to do with a review request the viewer could shed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShedAction {
+    /// Remove the viewer from the requested reviewers.
+    Decline,
+    /// Ask a collaborator to take the review over.
+    HandOff,
+}
+
+impl ShedAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            ShedAction::Decline => "decline",
+            ShedAction::HandOff => "hand off",
+        }
+    }
+}
+
+/// One review request worth shedding, with the reasons it is safe to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShedSuggestion {
+    pub pr_key: String,
+    pub title: String,
+    pub action: ShedAction,
+    pub reason: String,
+}
+
+/// Suggestions for the review requests in `prs`, declines first, then least urgent first.
+pub fn suggest_shedding(prs: &[UiPr], viewer: Option<&str>) -> Vec<ShedSuggestion> {
+    let mut picked: Vec<(&UiPr, ShedSuggestion)> = prs
+        .iter()
+        .filter(|p| {
+            matches!(p.pr.review_state, ReviewState::Requested)
+                && !p.pr.is_viewer_author
+                && !p.snoozed
+                && p.inaccessible_since.is_none()
+        })
+        .filter_map(|p| Some((p, suggestion(p, viewer)?)))
+        .collect();
+    picked.sort_by_key(|(p, s)| (s.action != ShedAction::Decline, p.score));
+    picked.into_iter().map(|(_, s)| s).collect()
+}
+
+fn suggestion(p: &UiPr, viewer: Option<&str>) -> Option<ShedSuggestion> {
+    let pr = &p.pr;
+    let others: Vec<&str> = pr
+        .reviewers
+        .iter()
+        .map(|r| r.login.as_str())
+        .filter(|login| {
+            !login.eq_ignore_ascii_case(&pr.author)
+                && !viewer.is_some_and(|v| login.eq_ignore_ascii_case(v))
+        })
+        .collect();
+    let bot = is_bot(&pr.author);
+    let small = pr.lines_changed.filter(|n| *n <= SMALL_PR_LINES);
+
+    let action = match (others.is_empty(), bot, small.is_some()) {
+        (false, true, _) | (false, _, true) => ShedAction::Decline,
+        (true, true, _) => ShedAction::HandOff,
+        _ => return None,
+    };
+    let mut reasons = Vec::new();
+    if bot {
+        reasons.push(format!("bot PR by {}", pr.author));
+    }
+    if let Some(n) = small {
+        reasons.push(format!("{n} lines"));
+    }
+    match others.as_slice() {
+        [] => reasons.push("no other reviewers".to_string()),
+        [one] => reasons.push(format!("@{one} also reviewing")),
+        many => reasons.push(format!("{} other reviewers", many.len())),
+    }
+    Some(ShedSuggestion {
+        pr_key: pr.pr_key.clone(),
+        title: pr.title.clone(),
+        action,
+        reason: reasons.join(", "),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::demo::DEMO_VIEWER_LOGIN;
+    use crate::model::Reviewer;
+    use crate::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn small_and_bot_review_requests_are_suggested() {
+        let conn = TestDb::new("shed-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let viewer = Some(DEMO_VIEWER_LOGIN);
+
+        // The demo's idempotency fix is small and mira reviews it too.
+        let out = suggest_shedding(&prs, viewer);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].pr_key, "acme-inc/billing-api#842");
+        assert_eq!(out[0].action, ShedAction::Decline);
+        assert_eq!(out[0].reason, "24 lines, @mira also reviewing");
+
+        // A bot PR with nobody else on it goes to a collaborator instead.
+        let i = prs
+            .iter()
+            .position(|p| p.pr.pr_key == "acme-inc/monorepo#2551")
+            .unwrap();
+        prs[i].pr.author = "renovate[bot]".to_string();
+        prs[i].pr.reviewers = vec![Reviewer {
+            login: DEMO_VIEWER_LOGIN.to_string(),
+            state: "REQUESTED".to_string(),
+        }];
+        let out = suggest_shedding(&prs, viewer);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].action, ShedAction::Decline);
+        assert_eq!(out[1].pr_key, "acme-inc/monorepo#2551");
+        assert_eq!(out[1].action, ShedAction::HandOff);
+        assert_eq!(out[1].reason, "bot PR by renovate[bot], no other reviewers");
+
+        // Nothing is suggested for PRs the viewer is not directly asked to review.
+        for p in prs.iter_mut() {
+            p.pr.review_state = ReviewState::TeamRequested;
+        }
+        assert!(suggest_shedding(&prs, viewer).is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+//! Review SLAs: per-repo or per-label due dates for requested reviews.
+//!
+//! A rule such as "reviews in `acme/*` are due within 24 business hours" gives every PR
+//! awaiting the viewer's review a due date. PRs close to or past it get a badge in their
+//! status line and a score boost, so they climb (and eventually land in NEEDS YOU).
+
+use crate::model::{Pr, ReviewState};
+use crate::refresh::{UiPr, category_for, sort_for_display};
+use chrono::{DateTime, Datelike, Weekday};
+
+pub const SCORE_SLA_APPROACHING: i32 = 15;
+pub const SCORE_SLA_OVERDUE: i32 = 35;
+
+/// Remaining fraction of the SLA window below which a PR counts as approaching.
+const APPROACHING_FRACTION: f64 = 0.25;
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// One SLA definition. Empty `repos`/`labels` match everything.
+#[derive(Debug, Clone, Default)]
+pub struct SlaRule {
+    pub repos: Vec<String>,  // owner/repo or owner/*
+    pub labels: Vec<String>, // any label matches (case-insensitive)
+    pub hours: u32,
+    pub business_hours: bool, // skip Saturdays and Sundays (UTC)
+}
+
+impl SlaRule {
+    fn matches(&self, pr: &Pr) -> bool {
+        let full = format!("{}/{}", pr.owner, pr.repo);
+        let repo_ok = self.repos.is_empty()
+            || self.repos.iter().any(|r| match r.strip_suffix("/*") {
+                Some(owner) => owner.eq_ignore_ascii_case(&pr.owner),
+                None => r.eq_ignore_ascii_case(&full),
+            });
+        let label_ok = self.labels.is_empty()
+            || self
+                .labels
+                .iter()
+                .any(|l| pr.labels.iter().any(|pl| pl.eq_ignore_ascii_case(l)));
+        repo_ok && label_ok
+    }
+
+    fn due_at(&self, start: i64) -> i64 {
+        let secs = i64::from(self.hours) * 3600;
+        if self.business_hours {
+            add_business_secs(start, secs)
+        } else {
+            start + secs
+        }
+    }
+}
+
+/// Where a PR stands against its SLA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SlaStatus {
+    OnTrack { due_at_unix: i64 },
+    Approaching { due_at_unix: i64 },
+    Overdue { due_at_unix: i64 },
+}
+
+impl SlaStatus {
+    pub fn due_at_unix(&self) -> i64 {
+        match *self {
+            SlaStatus::OnTrack { due_at_unix }
+            | SlaStatus::Approaching { due_at_unix }
+            | SlaStatus::Overdue { due_at_unix } => due_at_unix,
+        }
+    }
+
+    /// True for PRs that deserve attention now (approaching or overdue).
+    pub fn is_at_risk(&self) -> bool {
+        !matches!(self, SlaStatus::OnTrack { .. })
+    }
+
+    fn score_boost(&self) -> i32 {
+        match self {
+            SlaStatus::OnTrack { .. } => 0,
+            SlaStatus::Approaching { .. } => SCORE_SLA_APPROACHING,
+            SlaStatus::Overdue { .. } => SCORE_SLA_OVERDUE,
+        }
+    }
+
+    /// Badge prefixed to the status line, e.g. `⏰ 3h left`.
+    pub fn badge(&self, now: i64) -> Option<String> {
+        match *self {
+            SlaStatus::OnTrack { .. } => None,
+            SlaStatus::Approaching { due_at_unix } => {
+                Some(format!("⏰ {} left", short_duration(due_at_unix - now)))
+            }
+            SlaStatus::Overdue { due_at_unix } => {
+                Some(format!("🚨 {} overdue", short_duration(now - due_at_unix)))
+            }
+        }
+    }
+}
+
+/// First rule matching the PR, if any.
+pub fn rule_for<'a>(rules: &'a [SlaRule], pr: &Pr) -> Option<&'a SlaRule> {
+    rules.iter().find(|r| r.matches(pr))
+}
+
+/// SLA status of a PR awaiting the viewer's review; `None` when no rule applies.
+///
+/// The clock starts at the latest review request, falling back to the last update.
+pub fn sla_status(rules: &[SlaRule], pr: &Pr, now: i64) -> Option<SlaStatus> {
+    if !matches!(pr.review_state, ReviewState::Requested) {
+        return None;
+    }
+    let rule = rule_for(rules, pr)?;
+    let start = pr.review_requested_at_unix.unwrap_or(pr.updated_at_unix);
+    let due_at_unix = rule.due_at(start);
+    let span = (due_at_unix - start).max(1);
+    let left = due_at_unix - now;
+    Some(if left <= 0 {
+        SlaStatus::Overdue { due_at_unix }
+    } else if (left as f64) < span as f64 * APPROACHING_FRACTION {
+        SlaStatus::Approaching { due_at_unix }
+    } else {
+        SlaStatus::OnTrack { due_at_unix }
+    })
+}
+
+/// Annotate scored PRs with their SLA status: boost scores, badge the status line,
+/// recompute categories, and restore display order.
+pub fn apply_sla(prs: &mut [UiPr], rules: &[SlaRule], now: i64) {
+    if rules.is_empty() {
+        return;
+    }
+    for p in prs.iter_mut() {
+        let Some(status) = sla_status(rules, &p.pr, now) else {
+            continue;
+        };
+        p.score += status.score_boost();
+        p.category = category_for(&p.pr, p.score);
+        if let Some(badge) = status.badge(now) {
+            p.display_status = format!("{badge} · {}", p.display_status);
+        }
+        p.sla = Some(status);
+    }
+    sort_for_display(prs);
+}
+
+fn is_weekend(ts: i64) -> bool {
+    DateTime::from_timestamp(ts, 0)
+        .map(|dt| matches!(dt.weekday(), Weekday::Sat | Weekday::Sun))
+        .unwrap_or(false)
+}
+
+/// Add `secs` of weekday time to `start`, skipping Saturdays and Sundays (UTC).
+fn add_business_secs(start: i64, secs: i64) -> i64 {
+    let mut t = start;
+    let mut remaining = secs;
+    loop {
+        if is_weekend(t) {
+            t = t - t.rem_euclid(DAY_SECS) + DAY_SECS;
+            continue;
+        }
+        let day_left = DAY_SECS - t.rem_euclid(DAY_SECS);
+        if remaining <= day_left {
+            return t + remaining;
+        }
+        remaining -= day_left;
+        t += day_left;
+    }
+}
+
+fn short_duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 3600 {
+        format!("{}m", (secs / 60).max(1))
+    } else if secs < 2 * DAY_SECS {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / DAY_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+    use crate::refresh::Category;
+
+    // Friday 2024-03-08 12:00:00 UTC.
+    const FRIDAY_NOON: i64 = 1_709_899_200;
+
+    fn requested_pr(repo: &str, labels: &[&str], requested_at: i64) -> Pr {
+        let mut pr = generate_demo_prs(requested_at, 0)
+            .into_iter()
+            .find(|p| matches!(p.review_state, ReviewState::Requested))
+            .unwrap();
+        let (owner, name) = repo.split_once('/').unwrap();
+        pr.owner = owner.to_string();
+        pr.repo = name.to_string();
+        pr.labels = labels.iter().map(|l| l.to_string()).collect();
+        pr.review_requested_at_unix = Some(requested_at);
+        pr
+    }
+
+    fn rule(repos: &[&str], labels: &[&str], hours: u32, business_hours: bool) -> SlaRule {
+        SlaRule {
+            repos: repos.iter().map(|r| r.to_string()).collect(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            hours,
+            business_hours,
+        }
+    }
+
+    #[test]
+    fn business_hours_skip_weekends() {
+        // 24 business hours from Friday noon lands on Monday noon.
+        assert_eq!(
+            add_business_secs(FRIDAY_NOON, 24 * 3600),
+            FRIDAY_NOON + 3 * DAY_SECS
+        );
+        // Starting on Saturday counts from Monday midnight.
+        let saturday = FRIDAY_NOON + DAY_SECS;
+        let monday_midnight = FRIDAY_NOON + 3 * DAY_SECS - 12 * 3600;
+        assert_eq!(add_business_secs(saturday, 3600), monday_midnight + 3600);
+        assert_eq!(add_business_secs(FRIDAY_NOON, 3600), FRIDAY_NOON + 3600);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = vec![
+            rule(&["acme/api"], &["hotfix"], 4, false),
+            rule(&["acme/*"], &[], 24, true),
+        ];
+        let hotfix = requested_pr("acme/api", &["Hotfix"], FRIDAY_NOON);
+        assert_eq!(rule_for(&rules, &hotfix).unwrap().hours, 4);
+        let other = requested_pr("acme/web", &[], FRIDAY_NOON);
+        assert_eq!(rule_for(&rules, &other).unwrap().hours, 24);
+        let elsewhere = requested_pr("other/web", &[], FRIDAY_NOON);
+        assert!(rule_for(&rules, &elsewhere).is_none());
+    }
+
+    #[test]
+    fn status_escalates_as_the_deadline_nears() {
+        let rules = vec![rule(&[], &[], 8, false)];
+        let pr = requested_pr("acme/api", &[], FRIDAY_NOON);
+        let due = FRIDAY_NOON + 8 * 3600;
+        assert_eq!(
+            sla_status(&rules, &pr, FRIDAY_NOON + 3600),
+            Some(SlaStatus::OnTrack { due_at_unix: due })
+        );
+        assert_eq!(
+            sla_status(&rules, &pr, due - 3600),
+            Some(SlaStatus::Approaching { due_at_unix: due })
+        );
+        assert_eq!(
+            sla_status(&rules, &pr, due + 5 * 3600),
+            Some(SlaStatus::Overdue { due_at_unix: due })
+        );
+
+        let mut approved = pr.clone();
+        approved.review_state = ReviewState::Approved;
+        assert_eq!(sla_status(&rules, &approved, due + 3600), None);
+    }
+
+    #[test]
+    fn apply_sla_boosts_badges_and_recategorizes() {
+        let rules = vec![rule(&[], &[], 8, false)];
+        let now = FRIDAY_NOON + 13 * 3600;
+        let mut prs: Vec<UiPr> = generate_demo_prs(now, 0)
+            .into_iter()
+            .map(|pr| UiPr {
+                score: 0,
+                category: Category::Waiting,
+                display_status: "review requested".to_string(),
+                is_new_ci_failure: false,
+                is_pinned: false,
+                sla: None,
+                draft_ranked: false,
+                bot_section: false,
+                force_pushed_since_view: false,
+                reply_to_you: false,
+                snoozed: false,
+                opened_since_update: false,
+                inaccessible_since: None,
+                author_priority: None,
+                pr,
+            })
+            .collect();
+        let idx = prs
+            .iter()
+            .position(|p| matches!(p.pr.review_state, ReviewState::Requested))
+            .unwrap();
+        prs[idx].pr.review_requested_at_unix = Some(FRIDAY_NOON);
+        let key = prs[idx].pr.pr_key.clone();
+
+        apply_sla(&mut prs, &rules, now);
+
+        let p = prs.iter().find(|p| p.pr.pr_key == key).unwrap();
+        assert!(matches!(p.sla, Some(SlaStatus::Overdue { .. })));
+        assert_eq!(p.display_status, "🚨 5h overdue · review requested");
+        assert!(p.score >= SCORE_SLA_OVERDUE);
+        assert!(prs.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+}
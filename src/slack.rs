@@ -0,0 +1,153 @@
+//! Post alerts to a Slack channel through an incoming webhook (`[slack]` in config).
+//!
+//! Layered on the refresh function, so whichever frontend is refreshing (TUI, `watch`,
+//! `serve`, `rpc`) posts. The cache remembers what went out, so an alert is posted once even
+//! across restarts or with several frontends running.
+
+use crate::RefreshFn;
+use crate::watch::{Alert, Reminders, alerts};
+use needle_core::db::{db_path, mark_slack_posted, now_unix, open_db, opened_at, slack_posted};
+use needle_core::refresh::UiPr;
+use octocrab::Octocrab;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Handle;
+
+const DEFAULT_EVENTS: [&str; 3] = ["needs_you", "ci_failed", "reminder"];
+
+/// Where to post and which alerts.
+#[derive(Debug, Clone)]
+pub(crate) struct SlackHook {
+    webhook_url: String,
+    events: Vec<String>,
+}
+
+impl SlackHook {
+    pub(crate) fn new(webhook_url: String, events: Option<Vec<String>>) -> Self {
+        let events = events.unwrap_or_else(|| DEFAULT_EVENTS.map(String::from).to_vec());
+        Self {
+            webhook_url,
+            events,
+        }
+    }
+
+    fn wants(&self, alert: &Alert) -> bool {
+        self.events.iter().any(|e| alert.is(e))
+    }
+}
+
+/// Escape the characters Slack treats as markup (`&`, `<`, `>`).
+fn slack_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Message text (Slack mrkdwn) for `alert` on `pr`.
+fn message(alert: &Alert, pr: &UiPr) -> String {
+    format!(
+        "*{}*: <{}|{}> {}",
+        alert.headline(pr),
+        pr.pr.url,
+        pr.pr.pr_key,
+        slack_escape(&pr.pr.title)
+    )
+}
+
+async fn post(webhook_url: &str, text: &str) -> Result<(), String> {
+    // No credentials: the token never leaves for other hosts.
+    let client = Octocrab::builder()
+        .build()
+        .map_err(|e| format!("Failed to init HTTP client: {e}"))?;
+    let body = serde_json::json!({ "text": text });
+    let res = client
+        ._post(webhook_url, Some(&body))
+        .await
+        .map_err(|e| format!("Slack webhook request failed: {e}"))?;
+    if !res.status().is_success() {
+        return Err(format!("Slack webhook returned HTTP {}", res.status()));
+    }
+    Ok(())
+}
+
+/// Post `(pr_key, event_key, text)` messages not posted before. Failed posts are not
+/// recorded, so the next occurrence of the alert tries again.
+fn post_new(hook: &SlackHook, handle: &Handle, posts: Vec<(String, String, String)>) {
+    let Ok(conn) = db_path().and_then(|p| open_db(&p)) else {
+        return;
+    };
+    for (pr_key, event_key, text) in posts {
+        if slack_posted(&conn, &pr_key, &event_key).unwrap_or(true) {
+            continue;
+        }
+        if handle.block_on(post(&hook.webhook_url, &text)).is_ok() {
+            let _ = mark_slack_posted(&conn, &pr_key, &event_key, now_unix());
+        }
+    }
+}
+
+/// Wrap `refresh_fn` so alerts between refreshes go to Slack. `prs` is the baseline.
+/// Posting happens on its own thread and never fails the refresh.
+pub(crate) fn with_slack(
+    refresh_fn: RefreshFn,
+    hook: SlackHook,
+    prs: Vec<UiPr>,
+    reminders: Reminders,
+    handle: Handle,
+) -> RefreshFn {
+    let last = Mutex::new((prs, reminders));
+    let hook = Arc::new(hook);
+    Arc::new(move || {
+        let new_prs = refresh_fn()?;
+        let mut last = last.lock().unwrap_or_else(|e| e.into_inner());
+        let (last_prs, reminders) = &mut *last;
+        let mut raised = alerts(last_prs, &new_prs);
+        let conn = db_path().and_then(|p| open_db(&p)).ok();
+        raised.extend(reminders.check(&raised, &new_prs, now_unix(), |key| {
+            conn.as_ref().and_then(|c| opened_at(c, key).ok().flatten())
+        }));
+        let posts: Vec<(String, String, String)> = raised
+            .iter()
+            .filter(|a| hook.wants(a))
+            .filter_map(|a| {
+                let pr = new_prs.iter().find(|p| p.pr.pr_key == a.pr_key())?;
+                Some((pr.pr.pr_key.clone(), a.event_key(pr), message(a, pr)))
+            })
+            .collect();
+        *last_prs = new_prs.clone();
+        if !posts.is_empty() {
+            let hook = Arc::clone(&hook);
+            let handle = handle.clone();
+            std::thread::spawn(move || post_new(&hook, &handle, posts));
+        }
+        Ok(new_prs)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn messages_link_the_pr_and_events_identify_the_occurrence() {
+        let conn = needle_core::db::TestDb::new("slack-test");
+        let mut pr = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap()[0].clone();
+        pr.pr.title = "Fix <script> & co".to_string();
+        pr.pr.last_commit_sha = Some("abc123".to_string());
+        let key = pr.pr.pr_key.clone();
+
+        let alert = Alert::CiFailed(key.clone());
+        assert_eq!(
+            message(&alert, &pr),
+            format!(
+                "*CI failed*: <{}|{key}> Fix &lt;script&gt; &amp; co",
+                pr.pr.url
+            )
+        );
+        assert_eq!(alert.event_key(&pr), "ci_failed:abc123");
+
+        let hook = SlackHook::new("https://hooks.slack.com/x".to_string(), None);
+        assert!(hook.wants(&alert));
+        assert!(!hook.wants(&Alert::Reply(key)));
+    }
+}
@@ -0,0 +1,99 @@
+//! Snoozing: park a PR for a while (`z` in the TUI).
+//!
+//! A snooze runs either until a point in time or until the next push to the PR's head
+//! branch. Snoozed PRs leave the regular sections for a collapsed SNOOZED section and
+//! never ring the bell; once the snooze runs out they come back on their own.
+
+use crate::db::{DbPrRow, snooze_pr};
+use crate::model::Pr;
+use rusqlite::Connection;
+
+/// How long to snooze a PR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnoozeChoice {
+    OneHour,
+    FourHours,
+    OneDay,
+    UntilNextPush,
+}
+
+impl SnoozeChoice {
+    pub const ALL: [SnoozeChoice; 4] = [
+        SnoozeChoice::OneHour,
+        SnoozeChoice::FourHours,
+        SnoozeChoice::OneDay,
+        SnoozeChoice::UntilNextPush,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnoozeChoice::OneHour => "1 hour",
+            SnoozeChoice::FourHours => "4 hours",
+            SnoozeChoice::OneDay => "1 day",
+            SnoozeChoice::UntilNextPush => "until next push",
+        }
+    }
+
+    /// Wake-up time for timed snoozes; `None` for [`SnoozeChoice::UntilNextPush`].
+    pub fn until(&self, now: i64) -> Option<i64> {
+        match self {
+            SnoozeChoice::OneHour => Some(now + 3600),
+            SnoozeChoice::FourHours => Some(now + 4 * 3600),
+            SnoozeChoice::OneDay => Some(now + 24 * 3600),
+            SnoozeChoice::UntilNextPush => None,
+        }
+    }
+}
+
+/// True while the snooze stored in `row` still applies to `pr`.
+pub fn is_snoozed(pr: &Pr, row: Option<&DbPrRow>, now: i64) -> bool {
+    let Some(row) = row else { return false };
+    let timed = row.snoozed_until.is_some_and(|until| until > now);
+    let until_push = row.snoozed_sha.is_some() && row.snoozed_sha == pr.last_commit_sha;
+    timed || until_push
+}
+
+/// Snooze `pr` with `choice` and persist it.
+pub fn snooze(conn: &Connection, pr: &Pr, choice: SnoozeChoice, now: i64) -> Result<(), String> {
+    match choice.until(now) {
+        Some(until) => snooze_pr(conn, &pr.pr_key, Some(until), None),
+        None => {
+            let sha = pr
+                .last_commit_sha
+                .as_deref()
+                .ok_or_else(|| format!("{} has no known head commit", pr.pr_key))?;
+            snooze_pr(conn, &pr.pr_key, None, Some(sha))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo::generate_demo_prs;
+
+    fn row(until: Option<i64>, sha: Option<&str>) -> DbPrRow {
+        DbPrRow {
+            snoozed_until: until,
+            snoozed_sha: sha.map(str::to_string),
+            ..DbPrRow::default()
+        }
+    }
+
+    #[test]
+    fn timed_snoozes_expire_and_push_snoozes_end_on_a_new_head() {
+        let now = 1_700_000_000;
+        let mut pr = generate_demo_prs(now, 0).remove(0);
+        pr.last_commit_sha = Some("abc".to_string());
+
+        assert!(!is_snoozed(&pr, None, now));
+        let timed = row(SnoozeChoice::FourHours.until(now), None);
+        assert!(is_snoozed(&pr, Some(&timed), now + 3 * 3600));
+        assert!(!is_snoozed(&pr, Some(&timed), now + 4 * 3600));
+
+        let until_push = row(None, Some("abc"));
+        assert!(is_snoozed(&pr, Some(&until_push), now + 30 * 24 * 3600));
+        pr.last_commit_sha = Some("def".to_string());
+        assert!(!is_snoozed(&pr, Some(&until_push), now));
+    }
+}
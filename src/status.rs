@@ -0,0 +1,102 @@
+//! Counts for shell prompts and status bars (`needle status --short`).
+//!
+//! Read from the cache only, so it is fast enough to run on every prompt; the TUI,
+//! `needle watch` or any other refresh keeps the cache current.
+
+use crate::tui::Section;
+use needle_core::model::CiState;
+use needle_core::refresh::{Category, UiPr};
+
+/// What the status line counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct StatusCounts {
+    /// PRs in the NEEDS YOU section.
+    pub needs_you: usize,
+    /// The viewer's PRs with failing CI (snoozed ones left out).
+    pub failing: usize,
+}
+
+pub(crate) fn status_counts(prs: &[UiPr]) -> StatusCounts {
+    let needs_you = Section::Category(Category::NeedsYou);
+    StatusCounts {
+        needs_you: prs.iter().filter(|p| needs_you.contains(p)).count(),
+        failing: prs
+            .iter()
+            .filter(|p| !p.snoozed && p.pr.is_viewer_author)
+            .filter(|p| matches!(p.pr.ci_state, CiState::Failure))
+            .count(),
+    }
+}
+
+/// `🔥3 ❌1`, leaving out zero counts; empty when nothing is pending, so prompts stay clean.
+pub(crate) fn render_short(c: StatusCounts) -> String {
+    let mut parts = Vec::new();
+    if c.needs_you > 0 {
+        parts.push(format!("🔥{}", c.needs_you));
+    }
+    if c.failing > 0 {
+        parts.push(format!("❌{}", c.failing));
+    }
+    parts.join(" ")
+}
+
+fn render_long(c: StatusCounts) -> String {
+    format!(
+        "{} need you · {} of your PRs failing CI",
+        c.needs_you, c.failing
+    )
+}
+
+/// Print the counts for the cached `prs` to stdout.
+pub fn run_status(prs: &[UiPr], short: bool) -> Result<(), String> {
+    let counts = status_counts(prs);
+    if short {
+        println!("{}", render_short(counts));
+    } else {
+        println!("{}", render_long(counts));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::db::TestDb;
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn short_status_counts_needs_you_and_red_prs() {
+        let conn = TestDb::new("status-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let needs_you = prs
+            .iter()
+            .filter(|p| Section::Category(Category::NeedsYou).contains(p))
+            .count();
+        assert!(needs_you > 0);
+        // The demo viewer's own PRs are all green; make one of the red ones theirs.
+        for p in prs
+            .iter_mut()
+            .filter(|p| p.pr.pr_key.ends_with("/infra#317"))
+        {
+            p.pr.is_viewer_author = true;
+        }
+
+        let counts = status_counts(&prs);
+        assert_eq!(
+            counts,
+            StatusCounts {
+                needs_you,
+                failing: 1
+            }
+        );
+        assert_eq!(render_short(counts), format!("🔥{needs_you} ❌1"));
+        assert_eq!(
+            render_short(StatusCounts {
+                needs_you: 0,
+                failing: 2
+            }),
+            "❌2"
+        );
+        assert_eq!(render_short(StatusCounts::default()), "");
+    }
+}
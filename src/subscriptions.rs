@@ -0,0 +1,86 @@
+//! PRs the viewer unsubscribed from on GitHub (`unsubscribed_prs` in the config).
+//!
+//! Unsubscribing from a PR's notifications on github.com (or ignoring them) usually means
+//! it is no longer the viewer's business. GitHub reports that per PR, so needle can agree
+//! with the notification inbox: sink such PRs below everything else, or leave them out. By
+//! default they are listed as usual.
+
+use crate::refresh::{UiPr, category_for, sort_for_display};
+
+/// Enough to sink below any combination of regular signals.
+pub const SCORE_UNSUBSCRIBED: i32 = -100;
+
+/// What happens to PRs the viewer unsubscribed from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsubscribedMode {
+    /// Listed like any other PR.
+    #[default]
+    Show,
+    /// Badged and ranked last.
+    Demote,
+    /// Left out of the list.
+    Hide,
+}
+
+/// Demote unsubscribed PRs when `mode` says so, and restore display order.
+///
+/// Hidden PRs are left in place; drop them with [`retain_subscribed`].
+pub fn apply_unsubscribed(prs: &mut [UiPr], mode: UnsubscribedMode) {
+    if mode != UnsubscribedMode::Demote {
+        return;
+    }
+    for p in prs.iter_mut().filter(|p| p.pr.is_unsubscribed) {
+        p.score += SCORE_UNSUBSCRIBED;
+        p.category = category_for(&p.pr, p.score);
+        p.display_status = format!("🔕 unsubscribed · {}", p.display_status);
+    }
+    sort_for_display(prs);
+}
+
+/// Drop unsubscribed PRs when `mode` hides them.
+pub fn retain_subscribed(prs: &mut Vec<UiPr>, mode: UnsubscribedMode) {
+    if mode == UnsubscribedMode::Hide {
+        prs.retain(|p| !p.pr.is_unsubscribed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::refresh::{Category, ScopeFilters, refresh_demo};
+
+    #[test]
+    fn unsubscribed_prs_sink_or_go() {
+        let conn = TestDb::new("subscriptions-test");
+        let prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let key = "honeycombio/otel-collector#77";
+        assert!(
+            prs.iter()
+                .any(|p| p.pr.pr_key == key && p.pr.is_unsubscribed)
+        );
+
+        let summary = |prs: &[UiPr]| -> Vec<(String, i32, String)> {
+            prs.iter()
+                .map(|p| (p.pr.pr_key.clone(), p.score, p.display_status.clone()))
+                .collect()
+        };
+        let mut shown = prs.clone();
+        apply_unsubscribed(&mut shown, UnsubscribedMode::Show);
+        retain_subscribed(&mut shown, UnsubscribedMode::Show);
+        assert_eq!(summary(&shown), summary(&prs));
+
+        let mut demoted = prs.clone();
+        apply_unsubscribed(&mut demoted, UnsubscribedMode::Demote);
+        let last = demoted.last().unwrap();
+        assert_eq!(last.pr.pr_key, key);
+        assert_eq!(last.category, Category::Stale);
+        assert!(last.display_status.starts_with("🔕 unsubscribed · "));
+
+        let mut hidden = prs.clone();
+        retain_subscribed(&mut hidden, UnsubscribedMode::Hide);
+        assert_eq!(hidden.len(), prs.len() - 1);
+        assert!(hidden.iter().all(|p| p.pr.pr_key != key));
+    }
+}
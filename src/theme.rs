@@ -0,0 +1,234 @@
+//! Terminal color depth (`color_mode`): what the terminal can show and how frames degrade.
+//!
+//! The TUI draws with the 16 named colors plus the dim modifier. Each frame is passed through
+//! [`ColorMode::degrade`] before it is flushed: 256-color terminals get any RGB color as the
+//! nearest palette entry, 16-color terminals get the nearest named color and dark gray in
+//! place of dim (which many of them render as normal text), and `mono` drops colors
+//! altogether, leaving bold and reverse video.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+/// How many colors the terminal shows (`color_mode` in the config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum ColorMode {
+    /// Detect from `NO_COLOR`, `COLORTERM` and `TERM`.
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 24-bit RGB.
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// The xterm 256-color palette.
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 named ANSI colors.
+    #[serde(rename = "16")]
+    Ansi16,
+    /// No colors.
+    #[serde(rename = "mono")]
+    Mono,
+}
+
+/// xterm's default RGB values for the 16 named colors, in palette order.
+const NAMED: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the 6x6x6 color cube (palette entries 16 to 231).
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorMode {
+    /// The mode to draw with: [`ColorMode::Auto`] becomes what the environment reports.
+    pub fn resolve(self) -> ColorMode {
+        match self {
+            ColorMode::Auto => detect(|name| std::env::var(name).ok()),
+            mode => mode,
+        }
+    }
+
+    /// Rewrite the colors and modifiers in `buf` to ones this mode can show.
+    pub fn degrade(self, buf: &mut Buffer) {
+        if matches!(self, ColorMode::Auto | ColorMode::TrueColor) {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            cell.fg = self.color(cell.fg);
+            cell.bg = self.color(cell.bg);
+            if matches!(self, ColorMode::Ansi16 | ColorMode::Mono)
+                && cell.modifier.contains(Modifier::DIM)
+            {
+                cell.modifier.remove(Modifier::DIM);
+                if self == ColorMode::Ansi16 && matches!(cell.fg, Color::Reset | Color::Gray) {
+                    cell.fg = Color::DarkGray;
+                }
+            }
+        }
+    }
+
+    /// Glyphs of the refresh shimmer: the moving block and the track it moves along.
+    /// Fonts of 16-color consoles often lack the shade blocks.
+    pub fn shimmer_glyphs(self) -> (char, char) {
+        match self {
+            ColorMode::Ansi16 | ColorMode::Mono => ('#', '-'),
+            _ => ('▓', '░'),
+        }
+    }
+
+    fn color(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorMode::Mono, _) => Color::Reset,
+            (ColorMode::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed(r, g, b)),
+            (ColorMode::Ansi16, Color::Rgb(r, g, b)) => nearest_named(r, g, b),
+            (ColorMode::Ansi16, Color::Indexed(i)) => {
+                let (r, g, b) = indexed_rgb(i);
+                nearest_named(r, g, b)
+            }
+            (_, color) => color,
+        }
+    }
+}
+
+/// The color depth the terminal advertises, read through `env`.
+fn detect(env: impl Fn(&str) -> Option<String>) -> ColorMode {
+    if env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return ColorMode::Mono;
+    }
+    let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" || env("WT_SESSION").is_some() {
+        return ColorMode::TrueColor;
+    }
+    match env("TERM").unwrap_or_default().as_str() {
+        "dumb" => ColorMode::Mono,
+        term if term.contains("256color") => ColorMode::Ansi256,
+        _ => ColorMode::Ansi16,
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => NAMED[usize::from(i)].1,
+        16..=231 => {
+            let i = usize::from(i - 16);
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        _ => {
+            let level = 8 + (i - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn nearest_named(r: u8, g: u8, b: u8) -> Color {
+    NAMED
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// Nearest entry of the color cube or the gray ramp (the first 16 vary by terminal).
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    (16..=255)
+        .min_by_key(|i| distance(indexed_rgb(*i), (r, g, b)))
+        .unwrap_or(16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn detects_depth_and_degrades_frames() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            detect(env(&[("COLORTERM", "truecolor"), ("TERM", "xterm")])),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            detect(env(&[("TERM", "xterm-256color")])),
+            ColorMode::Ansi256
+        );
+        assert_eq!(detect(env(&[("TERM", "linux")])), ColorMode::Ansi16);
+        assert_eq!(
+            detect(env(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")])),
+            ColorMode::Mono
+        );
+
+        let frame = || {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+            buf.set_string(0, 0, "a", Style::default().fg(Color::Rgb(250, 10, 10)));
+            buf.set_string(1, 0, "b", Style::default().add_modifier(Modifier::DIM));
+            buf.set_string(2, 0, "c", Style::default().fg(Color::Indexed(51)));
+            buf
+        };
+        let cells = |mode: ColorMode| {
+            let mut buf = frame();
+            mode.degrade(&mut buf);
+            buf.content
+                .iter()
+                .map(|c| (c.fg, c.modifier.contains(Modifier::DIM)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            cells(ColorMode::TrueColor),
+            [
+                (Color::Rgb(250, 10, 10), false),
+                (Color::Reset, true),
+                (Color::Indexed(51), false)
+            ]
+        );
+        assert_eq!(
+            cells(ColorMode::Ansi256),
+            [
+                (Color::Indexed(196), false),
+                (Color::Reset, true),
+                (Color::Indexed(51), false)
+            ]
+        );
+        assert_eq!(
+            cells(ColorMode::Ansi16),
+            [
+                (Color::LightRed, false),
+                (Color::DarkGray, false),
+                (Color::LightCyan, false)
+            ]
+        );
+        assert_eq!(
+            cells(ColorMode::Mono),
+            [
+                (Color::Reset, false),
+                (Color::Reset, false),
+                (Color::Reset, false)
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! The "Today" view: the few things worth doing today, as a short ordered to-do list.
+//!
+//! Overdue review requests come first (longest waiting first), then the viewer's PRs with
+//! failing CI, then their approved PRs that only need merging. Items marked done drop out
+//! for the rest of the day; skipped ones move to the end, so the list can be worked top
+//! down. Marks are kept per local day in the cache.
+
+use crate::model::{CiState, ReviewState};
+use crate::refresh::UiPr;
+use crate::sla::SlaStatus;
+use std::collections::HashMap;
+
+/// How many items the list shows unless `today_limit` says otherwise.
+pub const DEFAULT_TODAY_LIMIT: usize = 10;
+
+/// Without an SLA rule, a review request waiting this long counts as overdue.
+pub const OVERDUE_REVIEW_SECS: i64 = 24 * 3600;
+
+/// Why a PR is on today's list, in list order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TodayReason {
+    /// A review request past its SLA (or a day old without one).
+    OverdueReview,
+    /// The viewer's PR with failing CI.
+    RedPr,
+    /// The viewer's approved PR, waiting to be merged.
+    ReadyToMerge,
+}
+
+impl TodayReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            TodayReason::OverdueReview => "review overdue",
+            TodayReason::RedPr => "fix CI",
+            TodayReason::ReadyToMerge => "merge",
+        }
+    }
+}
+
+/// How a PR was dealt with today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodayMark {
+    Done,
+    Skipped,
+}
+
+impl TodayMark {
+    /// Name stored in the cache.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodayMark::Done => "done",
+            TodayMark::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<TodayMark> {
+        match s {
+            "done" => Some(TodayMark::Done),
+            "skipped" => Some(TodayMark::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// One entry of the list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodayItem {
+    pub pr_key: String,
+    pub title: String,
+    pub reason: TodayReason,
+    /// When the PR started waiting on the viewer (review requested, last update).
+    pub since_unix: i64,
+    pub skipped: bool,
+}
+
+/// Cache key for marks made on the local day of `now`, e.g. `2024-03-10`.
+pub fn today_key(now: i64) -> String {
+    use chrono::TimeZone;
+    chrono::Local
+        .timestamp_opt(now, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn reason(p: &UiPr, now: i64) -> Option<(TodayReason, i64)> {
+    let pr = &p.pr;
+    if p.snoozed || p.inaccessible_since.is_some() {
+        return None;
+    }
+    if matches!(pr.review_state, ReviewState::Requested) && !pr.is_viewer_author {
+        let since = pr.review_requested_at_unix.unwrap_or(pr.updated_at_unix);
+        let overdue = match p.sla {
+            Some(sla) => matches!(sla, SlaStatus::Overdue { .. }),
+            None => now - since >= OVERDUE_REVIEW_SECS,
+        };
+        return overdue.then_some((TodayReason::OverdueReview, since));
+    }
+    if !pr.is_viewer_author || pr.is_draft {
+        return None;
+    }
+    if matches!(pr.ci_state, CiState::Failure) {
+        return Some((TodayReason::RedPr, pr.updated_at_unix));
+    }
+    matches!(pr.review_state, ReviewState::Approved)
+        .then_some((TodayReason::ReadyToMerge, pr.updated_at_unix))
+}
+
+/// Today's list from `prs`: at most `limit` items, in order, leaving out PRs marked done.
+pub fn today_items(
+    prs: &[UiPr],
+    marks: &HashMap<String, TodayMark>,
+    limit: usize,
+    now: i64,
+) -> Vec<TodayItem> {
+    let mut items: Vec<TodayItem> = prs
+        .iter()
+        .filter(|p| marks.get(&p.pr.pr_key) != Some(&TodayMark::Done))
+        .filter_map(|p| {
+            let (reason, since_unix) = reason(p, now)?;
+            Some(TodayItem {
+                pr_key: p.pr.pr_key.clone(),
+                title: p.pr.title.clone(),
+                reason,
+                since_unix,
+                skipped: marks.get(&p.pr.pr_key) == Some(&TodayMark::Skipped),
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        (a.skipped, a.reason, a.since_unix, &a.pr_key).cmp(&(
+            b.skipped,
+            b.reason,
+            b.since_unix,
+            &b.pr_key,
+        ))
+    });
+    items.truncate(limit);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn todays_list_is_ordered_capped_and_worked_off() {
+        let conn = TestDb::new("today-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        // The demo viewer's own PRs are all green; make one of the red ones theirs.
+        for p in prs
+            .iter_mut()
+            .filter(|p| p.pr.pr_key.ends_with("/infra#317"))
+        {
+            p.pr.is_viewer_author = true;
+        }
+        let now = prs.iter().map(|p| p.pr.updated_at_unix).max().unwrap() + 3 * 86_400;
+        let mut marks = HashMap::new();
+
+        let items = today_items(&prs, &marks, 100, now);
+        let reasons: Vec<TodayReason> = items.iter().map(|i| i.reason).collect();
+        let mut sorted = reasons.clone();
+        sorted.sort();
+        assert_eq!(reasons, sorted);
+        for reason in [
+            TodayReason::OverdueReview,
+            TodayReason::RedPr,
+            TodayReason::ReadyToMerge,
+        ] {
+            assert!(reasons.contains(&reason), "{reason:?}");
+        }
+        assert_eq!(today_items(&prs, &marks, 2, now), items[..2]);
+
+        // Done drops out, skipped moves to the end.
+        marks.insert(items[0].pr_key.clone(), TodayMark::Done);
+        marks.insert(items[1].pr_key.clone(), TodayMark::Skipped);
+        let worked = today_items(&prs, &marks, 100, now);
+        assert_eq!(worked.len(), items.len() - 1);
+        assert!(worked.iter().all(|i| i.pr_key != items[0].pr_key));
+        let last = worked.last().unwrap();
+        assert_eq!(last.pr_key, items[1].pr_key);
+        assert!(last.skipped);
+        assert_eq!(worked[0].pr_key, items[2].pr_key);
+    }
+}
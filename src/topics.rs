@@ -0,0 +1,88 @@
+//! Topics for the topic layout (`layout = "topics"`): PRs that share a label, or that target
+//! the same branch other than the usual default ones, are listed together so related
+//! reviews can be done in one go.
+//!
+//! Each PR joins its most widely shared topic. A label or base branch only makes a topic
+//! when at least two PRs have it; ties go to the alphabetically first one.
+
+use crate::refresh::UiPr;
+use std::collections::HashMap;
+
+/// Base branches most PRs target; sharing one says nothing about the PRs.
+const DEFAULT_BRANCHES: [&str; 5] = ["main", "master", "develop", "development", "trunk"];
+
+/// Name of the block for PRs without a shared topic.
+pub const OTHER_TOPIC: &str = "other";
+
+/// Candidate topics of one PR: its labels and its base branch (`into release/3.2`).
+fn candidates(p: &UiPr) -> Vec<String> {
+    let mut out: Vec<String> = p.pr.labels.iter().map(|l| l.to_lowercase()).collect();
+    let base = p.pr.base_ref_name.as_deref();
+    if let Some(base) = base.filter(|b| !DEFAULT_BRANCHES.contains(b)) {
+        out.push(format!("into {base}"));
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// The topic of every PR in `prs` (same order), `None` when it shares nothing.
+pub fn topics(prs: &[UiPr]) -> Vec<Option<String>> {
+    let per_pr: Vec<Vec<String>> = prs.iter().map(candidates).collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for topic in per_pr.iter().flatten() {
+        *counts.entry(topic.as_str()).or_default() += 1;
+    }
+    per_pr
+        .iter()
+        .map(|cands| {
+            // Candidates are sorted, so the first of the most shared wins ties.
+            let mut best: Option<(&String, usize)> = None;
+            for topic in cands {
+                let n = counts[topic.as_str()];
+                if n >= 2 && best.is_none_or(|(_, m)| n > m) {
+                    best = Some((topic, n));
+                }
+            }
+            best.map(|(topic, _)| topic.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TestDb;
+    use crate::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn prs_join_their_most_shared_topic() {
+        let conn = TestDb::new("topics-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let topic_of = |prs: &[UiPr], key: &str| {
+            let i = prs.iter().position(|p| p.pr.pr_key.ends_with(key)).unwrap();
+            topics(prs)[i].clone()
+        };
+
+        assert_eq!(topic_of(&prs, "/api#1104").as_deref(), Some("reliability"));
+        assert_eq!(
+            topic_of(&prs, "/mobile#523").as_deref(),
+            Some("into release/3.2")
+        );
+        // Only one PR is labelled incident, and main is nobody's topic.
+        assert_eq!(topic_of(&prs, "/payments-worker#219"), None);
+        assert_eq!(topic_of(&prs, "/docs#42"), None);
+
+        // A second incident PR makes a topic, which wins over the smaller onboarding one.
+        for p in prs.iter_mut() {
+            if p.pr.pr_key.ends_with("/web#1940") || p.pr.pr_key.ends_with("/product#12") {
+                p.pr.labels.push("Incident".to_string());
+            }
+        }
+        assert_eq!(topic_of(&prs, "/web#1940").as_deref(), Some("incident"));
+        assert_eq!(
+            topic_of(&prs, "/payments-worker#219").as_deref(),
+            Some("incident")
+        );
+    }
+}
@@ -1,28 +1,60 @@
-use crate::db::{now_unix, toggle_pin};
-use crate::refresh::{
-    APPROVED_UNMERGED_OLD_SECS, CATEGORY_NEEDS_YOU_MIN, CATEGORY_NO_ACTION_MIN,
-    CI_RUNNING_LONG_SECS, Category, SCORE_APPROVED_UNMERGED_OLD, SCORE_CI_FAILED_NEW,
-    SCORE_CI_FAILED_UNCHANGED, SCORE_CI_RUNNING_LONG, SCORE_REVIEW_REQUESTED,
-    SCORE_WAITING_ON_OTHERS_GREEN, UiPr,
+use crate::callbacks::Callbacks;
+use crate::macros::{MacroKey, Macros};
+use crate::theme::ColorMode;
+use crate::watch::{Alert, Reminders, alerts, unannounced};
+use crate::{
+    ApproveFn, CheckLogFn, CommentFn, DeclineFn, LookupFn, MarkReadFn, MergeFn, RefreshPrFn,
+    SetDraftFn, UpdateBranchFn, WindowFn,
 };
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use chrono::TimeZone;
+use clap::ValueEnum;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::style::Print;
 use crossterm::terminal::{
-    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
 };
 use crossterm::tty::IsTty;
+use needle_core::bots::is_bot;
+use needle_core::columns::CustomColumn;
+use needle_core::db::{
+    CheckFailure, load_addressed_threads, load_check_failures, load_ignored_prs, load_today_marks,
+    load_ui_state, mark_opened, now_unix, opened_at, save_ui_state, set_ignored,
+    set_thread_addressed, set_today_mark, toggle_pin, track_pr, unsnooze_pr, untrack_pr,
+};
+use needle_core::github::{RateLimit, last_rate_limit, take_partial_errors};
+use needle_core::github_write::{
+    MergeMethod, approve_readiness, draft_toggle, merge_readiness, update_branch_readiness,
+};
+use needle_core::model::{
+    CiCheck, CiCheckState, Comment, Pr, ReviewState, ReviewThread, Reviewer, UsageMode,
+};
+use needle_core::provider::parse_ref;
+use needle_core::refresh::{
+    APPROVED_UNMERGED_OLD_SECS, CATEGORY_NEEDS_YOU_MIN, CATEGORY_NO_ACTION_MIN,
+    CI_RUNNING_LONG_SECS, Category, MENTION_BADGE, REPLY_BADGE, SCORE_APPROVED_UNMERGED_OLD,
+    SCORE_ASSIGNED, SCORE_CHANGES_REQUESTED, SCORE_CI_FAILED_NEW, SCORE_CI_FAILED_UNCHANGED,
+    SCORE_CI_RUNNING_LONG, SCORE_REPLY_TO_YOU, SCORE_REVIEW_REQUESTED, SCORE_TEAM_REVIEW_REQUESTED,
+    SCORE_THREAD_AWAITING_YOU, SCORE_WAITING_ON_OTHERS_GREEN, ScopeFilters, UiPr, cached_title,
+    sort_for_display,
+};
+use needle_core::shed::{ShedAction, ShedSuggestion, suggest_shedding};
+use needle_core::sla::{SCORE_SLA_APPROACHING, SCORE_SLA_OVERDUE};
+use needle_core::snooze::{SnoozeChoice, snooze};
+use needle_core::today::{DEFAULT_TODAY_LIMIT, TodayItem, TodayMark, today_items, today_key};
+use needle_core::topics::{OTHER_TOPIC, topics};
 use ratatui::Terminal;
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::buffer::Buffer;
 use ratatui::layout::Alignment;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use rusqlite::Connection;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::io::{self, Stdout};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::sync::mpsc::TryRecvError;
 use std::sync::{Arc, mpsc};
 use std::time::Duration;
@@ -36,11 +68,12 @@ pub enum ViewMode {
     Details,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UiPrefs {
     pub hide_pr_numbers: bool,
     pub hide_repo: bool,
     pub hide_author: bool,
+    pub keep_tail: TailColumns,
 }
 
 impl Default for UiPrefs {
@@ -49,8 +82,312 @@ impl Default for UiPrefs {
             hide_pr_numbers: false,
             hide_repo: false,
             hide_author: false,
+            keep_tail: TailColumns::default(),
+        }
+    }
+}
+
+/// List columns whose truncation keeps a trailing issue key or `[tag]` visible
+/// (`truncate_keep_tail`); the others are cut at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TailColumns {
+    pub title: bool,
+    pub repo: bool,
+    pub author: bool,
+    pub status: bool,
+}
+
+impl Default for TailColumns {
+    fn default() -> Self {
+        Self {
+            title: true,
+            repo: false,
+            author: false,
+            status: false,
+        }
+    }
+}
+
+impl TailColumns {
+    pub const NAMES: [&str; 4] = ["title", "repo", "author", "status"];
+
+    /// Exactly the named columns.
+    pub fn parse(names: &[String]) -> Result<Self, String> {
+        let mut out = Self {
+            title: false,
+            repo: false,
+            author: false,
+            status: false,
+        };
+        for name in names {
+            match name.as_str() {
+                "title" => out.title = true,
+                "repo" => out.repo = true,
+                "author" => out.author = true,
+                "status" => out.status = true,
+                _ => {
+                    return Err(format!(
+                        "Unknown column `{name}` (known: {})",
+                        Self::NAMES.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// How the list view is arranged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ListLayout {
+    /// One list in score-based sections (NEEDS YOU, READY TO MERGE, ...).
+    #[default]
+    Categories,
+    /// "Your PRs" above "Needs your review", each with its own selection.
+    Split,
+    /// One block per repository (alphabetical), each collapsible.
+    Repos,
+    /// One block per topic (a shared label or base branch), each collapsible.
+    Topics,
+}
+
+/// CI checks that collapse into one row in details view.
+///
+/// Matrix jobs named `name (a, b)` collapse by `name`. Configured patterns (`check_groups`)
+/// use `*` for any text, are matched case-insensitively and collapse under the pattern.
+#[derive(Debug, Clone, Default)]
+pub struct CheckGroups {
+    patterns: Vec<String>,
+}
+
+impl CheckGroups {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self { patterns }
+    }
+
+    fn glob_matches(pattern: &str, name: &str) -> bool {
+        let pattern = pattern.to_ascii_lowercase();
+        let name = name.to_ascii_lowercase();
+        let mut parts = pattern.split('*');
+        let Some(mut rest) = name.strip_prefix(parts.next().unwrap_or("")) else {
+            return false;
+        };
+        let parts: Vec<&str> = parts.collect();
+        let Some((last, middle)) = parts.split_last() else {
+            return rest.is_empty();
+        };
+        for part in middle {
+            match rest.find(part) {
+                Some(i) => rest = &rest[i + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    }
+
+    /// Row label `name` collapses under, if any.
+    fn label(&self, name: &str) -> Option<String> {
+        if let Some(p) = self.patterns.iter().find(|p| Self::glob_matches(p, name)) {
+            return Some(p.clone());
+        }
+        let (base, _) = name.strip_suffix(')')?.rsplit_once(" (")?;
+        (!base.trim().is_empty()).then(|| base.to_string())
+    }
+}
+
+/// One configured quick link (`[[links]]`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickLink {
+    /// `owner/repo` or `owner/*` patterns; empty means every repo.
+    pub repos: Vec<String>,
+    pub name: String,
+    /// `{owner}`, `{repo}` and `{number}` are replaced with the PR's.
+    pub url: String,
+}
+
+/// Per-repo quick links (dashboards, deploy pages, runbooks) listed in details view and
+/// opened with the digit keys.
+#[derive(Debug, Clone, Default)]
+pub struct QuickLinks {
+    links: Vec<QuickLink>,
+}
+
+impl QuickLinks {
+    /// One per digit key.
+    const MAX: usize = 9;
+
+    pub fn new(links: Vec<QuickLink>) -> Self {
+        Self { links }
+    }
+
+    /// `(name, url)` of the links that apply to `pr`, in config order.
+    fn for_pr(&self, pr: &UiPr) -> Vec<(String, String)> {
+        let repo = format!("{}/{}", pr.pr.owner, pr.pr.repo);
+        self.links
+            .iter()
+            .filter(|l| {
+                l.repos.is_empty()
+                    || l.repos
+                        .iter()
+                        .any(|p| RepoGroups::pattern_matches(p, &repo))
+            })
+            .take(Self::MAX)
+            .map(|l| {
+                let url = l
+                    .url
+                    .replace("{owner}", &pr.pr.owner)
+                    .replace("{repo}", &pr.pr.repo)
+                    .replace("{number}", &pr.pr.number.to_string());
+                (l.name.clone(), url)
+            })
+            .collect()
+    }
+}
+
+/// A row of the details view checks list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CiRow {
+    /// `ci_checks[idx]`; `nested` under an expanded group.
+    Check { idx: usize, nested: bool },
+    /// Two or more `ci_checks` sharing a [`CheckGroups`] label.
+    Group {
+        label: String,
+        members: Vec<usize>,
+        expanded: bool,
+    },
+}
+
+/// Checks list rows: groups sit where their first (worst, given the sort) member was.
+fn ci_rows(checks: &[CiCheck], groups: &CheckGroups, expanded: &HashSet<String>) -> Vec<CiRow> {
+    let labels: Vec<Option<String>> = checks.iter().map(|c| groups.label(&c.name)).collect();
+    let mut members: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, label) in labels.iter().enumerate() {
+        if let Some(l) = label {
+            members.entry(l.as_str()).or_default().push(idx);
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (idx, label) in labels.iter().enumerate() {
+        let group = label
+            .as_deref()
+            .and_then(|l| members.get(l).map(|m| (l, m)));
+        match group {
+            Some((l, m)) if m.len() > 1 => {
+                if m[0] != idx {
+                    continue;
+                }
+                let open = expanded.contains(l);
+                rows.push(CiRow::Group {
+                    label: l.to_string(),
+                    members: m.clone(),
+                    expanded: open,
+                });
+                if open {
+                    rows.extend(m.iter().map(|&idx| CiRow::Check { idx, nested: true }));
+                }
+            }
+            _ => rows.push(CiRow::Check { idx, nested: false }),
+        }
+    }
+    rows
+}
+
+/// One state for a group: failed if any failed, else running, else success.
+fn aggregate_check_state<'a>(checks: impl Iterator<Item = &'a CiCheck>) -> CiCheckState {
+    let rank = |s: &CiCheckState| match s {
+        CiCheckState::Failure => 0,
+        CiCheckState::Running => 1,
+        CiCheckState::Success => 2,
+        CiCheckState::Neutral => 3,
+        CiCheckState::None => 4,
+    };
+    checks
+        .map(|c| &c.state)
+        .min_by_key(|s| rank(s))
+        .cloned()
+        .unwrap_or(CiCheckState::None)
+}
+
+/// A pane of the split layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SplitPane {
+    #[default]
+    Mine,
+    Review,
+}
+
+impl SplitPane {
+    fn title(self) -> &'static str {
+        match self {
+            SplitPane::Mine => "YOUR PRS",
+            SplitPane::Review => "NEEDS YOUR REVIEW",
+        }
+    }
+
+    fn holds(self, pr: &UiPr) -> bool {
+        pr.pr.is_viewer_author == (self == SplitPane::Mine)
+    }
+
+    fn other(self) -> SplitPane {
+        match self {
+            SplitPane::Mine => SplitPane::Review,
+            SplitPane::Review => SplitPane::Mine,
+        }
+    }
+}
+
+/// Named repo groups from config (`[groups.<name>] repos = [...]`).
+///
+/// Patterns are `owner/repo` or `owner/*`, matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct RepoGroups {
+    groups: Vec<(String, Vec<String>)>,
+}
+
+impl RepoGroups {
+    pub fn new(groups: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        Self {
+            groups: groups.into_iter().collect(),
+        }
+    }
+
+    fn pattern_matches(pattern: &str, repo: &str) -> bool {
+        match pattern.strip_suffix("/*") {
+            Some(owner) => repo
+                .split_once('/')
+                .is_some_and(|(o, _)| o.eq_ignore_ascii_case(owner)),
+            None => pattern.eq_ignore_ascii_case(repo),
         }
     }
+
+    /// Whether `repo` (`owner/repo`) belongs to the group called `name`.
+    fn contains(&self, name: &str, repo: &str) -> bool {
+        self.groups
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .any(|(_, pats)| pats.iter().any(|p| Self::pattern_matches(p, repo)))
+    }
+
+    /// Index of the first group containing `repo` (ungrouped repos sort last).
+    fn rank_of(&self, repo: &str) -> usize {
+        self.groups
+            .iter()
+            .position(|(_, pats)| pats.iter().any(|p| Self::pattern_matches(p, repo)))
+            .unwrap_or(self.groups.len())
+    }
+
+    fn label(&self, rank: usize) -> &str {
+        self.groups
+            .get(rank)
+            .map(|(n, _)| n.as_str())
+            .unwrap_or("other")
+    }
+
+    fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
 }
 
 /// Configurable refresh intervals.
@@ -71,6 +408,44 @@ impl Default for RefreshIntervals {
     }
 }
 
+impl RefreshIntervals {
+    /// Stretched to spare a low GitHub API budget (see [`RateLimit::refresh_interval_secs`]).
+    fn for_budget(self, rate_limit: Option<RateLimit>, now: i64) -> Self {
+        match rate_limit {
+            Some(rl) => Self {
+                list_secs: rl.refresh_interval_secs(self.list_secs, now),
+                details_secs: rl.refresh_interval_secs(self.details_secs, now),
+            },
+            None => self,
+        }
+    }
+}
+
+/// Settings taken over when config.toml changes while the TUI runs. Everything else
+/// (scope, provider, scoring, SLAs) still needs a restart.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
+    pub ui: UiPrefs,
+    pub refresh_intervals: RefreshIntervals,
+    /// Alert kinds that ring the bell; empty when the bell is off.
+    pub bell_events: Vec<String>,
+    pub check_groups: CheckGroups,
+    pub handoff_message: Option<String>,
+    pub quick_links: QuickLinks,
+    pub title_status: TitleStatus,
+}
+
+/// Where the NEEDS YOU count is shown outside the list (`terminal_title`, `tmux_status`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TitleStatus {
+    pub terminal: bool,
+    pub tmux: bool,
+}
+
+/// A reloaded config: its settings and a warning (e.g. an unreachable team config), or
+/// why it could not be read.
+pub type ConfigReload = Result<(LiveSettings, Option<String>), String>;
+
 pub struct AppState {
     pub prs: Vec<UiPr>,
     pub selected_idx: usize, // index into visible_pr_indices
@@ -78,7 +453,13 @@ pub struct AppState {
     pub(crate) details_pr_key: Option<String>,
     pub(crate) refreshing: bool,
     pub(crate) shimmer_phase: u8,
+    /// Index into the details checks rows ([`AppState::details_ci_rows`]).
     pub(crate) details_ci_selected: usize,
+    /// Check groups expanded in details view (by label).
+    pub(crate) details_ci_expanded: HashSet<String>,
+    pub(crate) details_conversation_scroll: usize,
+    /// Selected row of the requested-changes checklist (`[`/`]`).
+    pub(crate) details_thread_selected: usize,
     pub(crate) details_last_auto_refresh: Option<Instant>,
     pub(crate) last_refresh_started: Option<Instant>,
     pub(crate) ui: UiPrefs,
@@ -92,10 +473,274 @@ pub struct AppState {
     pub(crate) only_needs_you: bool,
     pub(crate) only_failing_ci: bool,
     pub(crate) only_review_requested: bool,
+    /// Only PRs approaching or past their review SLA.
+    pub(crate) only_sla: bool,
+    pub(crate) only_mentioned: bool,
+    /// Only bot PRs, for batch-merging dependency bumps.
+    pub(crate) only_bots: bool,
     pub(crate) update_notice: Option<String>,
+    /// Why the last refresh failed; shown as a red banner in list view until dismissed
+    /// (`Esc`) or the next refresh succeeds.
+    pub(crate) last_refresh_error: Option<String>,
+    /// Recent refresh failures, newest last (`e` shows them).
+    pub(crate) error_log: VecDeque<(i64, String)>,
+    pub(crate) error_log_open: bool,
+    /// Follow-up alerts for NEEDS YOU PRs left unopened (`remind_after_mins`).
+    pub(crate) reminders: Reminders,
+    /// GitHub API budget after the last refresh (GraphQL `rateLimit`).
+    pub(crate) rate_limit: Option<RateLimit>,
+    /// When the last list refresh succeeded (unix seconds), shown in the header.
+    pub(crate) last_refreshed_at: Option<i64>,
+    /// Transient footer message (clipboard lookups); cleared on the next key press.
+    pub(crate) status_message: Option<String>,
+    /// PRs opened from a pasted link that are not in the attention set.
+    pub(crate) lookups: Vec<UiPr>,
+    pub(crate) groups: RepoGroups,
+    pub(crate) check_groups: CheckGroups,
+    pub(crate) quick_links: QuickLinks,
+    pub(crate) custom_columns: Vec<CustomColumn>,
+    /// Sub-group list sections by repo group (toggled with `g`).
+    pub(crate) group_rows: bool,
+    /// Repositories or topics folded into one line in the repo and topic layouts (`←` / `→`).
+    pub(crate) collapsed_blocks: HashSet<String>,
+    /// `--oncall` is active (badged on the frame).
+    pub(crate) oncall: bool,
+    /// Where the NEEDS YOU count is published besides the list.
+    pub(crate) title_status: TitleStatus,
+    /// Color depth frames are degraded to before they are drawn.
+    pub(crate) color_mode: ColorMode,
+    /// No GitHub token: showing the cached snapshot, refreshes and writes fail.
+    pub(crate) offline: bool,
+    /// Login the token authenticated as (checked at startup), shown in the header.
+    pub(crate) viewer_login: Option<String>,
+    /// Active scope filters (`ScopeFilters::summary`), shown in the header.
+    pub(crate) scope_summary: Vec<String>,
+    /// `--days` window, shown in the header.
+    pub(crate) days: Option<i64>,
+    /// Merge method picker in details view (index into `MergeMethod::ALL`).
+    pub(crate) merge_picker: Option<usize>,
+    /// Quick preview of the selected row (`Space`); closed by the next key.
+    pub(crate) preview_open: bool,
+    /// Snooze picker (`z`): the PR being snoozed and an index into `SnoozeChoice::ALL`.
+    pub(crate) snooze_picker: Option<(String, usize)>,
+    /// Show the rows of the SNOOZED section (toggled with `Z`).
+    pub(crate) snoozed_open: bool,
+    /// Multi-select mode (`V`): Space marks rows for bulk actions.
+    pub(crate) select_mode: bool,
+    /// PRs marked in select mode, by key.
+    pub(crate) marked: HashSet<String>,
+    /// Waiting for `y` to approve the marked PRs.
+    pub(crate) confirm_approve: bool,
+    /// PRs `O` is about to open, waiting for `y` (more than [`OPEN_ALL_CONFIRM_OVER`]).
+    pub(crate) confirm_open_all: Option<Vec<String>>,
+    /// Ignored PRs, listed for restoring (`I`).
+    pub(crate) ignored_view: Option<IgnoredView>,
+    /// Review requests suggested for declining or handing off (`S`).
+    pub(crate) shed_view: Option<ShedView>,
+    /// Today's to-do list (`T`).
+    pub(crate) today_view: Option<TodayView>,
+    /// Most items on today's list (`today_limit`).
+    pub(crate) today_limit: usize,
+    /// Tail of a failing check's job log (`l` in details view).
+    pub(crate) log_view: Option<LogView>,
+    /// Comment being written in details view (`c`).
+    pub(crate) composer: Option<Composer>,
+    /// Collaborator picker for handing the details PR off (`h`).
+    pub(crate) handoff_picker: Option<HandoffPicker>,
+    /// Comment template for handoffs; `{login}` is the picked collaborator.
+    pub(crate) handoff_message: String,
+    /// List arrangement (`L` toggles).
+    pub(crate) layout: ListLayout,
+    /// `mode` in the config: with the viewer's own PRs or their review requests not
+    /// fetched, sections and layouts built around both are left out.
+    pub(crate) usage_mode: UsageMode,
+    /// Split layout: the pane `selected_idx` belongs to (`←`/`→` switch).
+    pub(crate) split_focus: SplitPane,
+    /// Split layout: selection of the other pane, restored when it gets focus.
+    pub(crate) split_parked: usize,
+    /// Requested-changes threads checked off as addressed, per PR (loaded from the cache).
+    pub(crate) addressed_threads: HashMap<String, HashSet<String>>,
+    /// Check failures on each PR's head commit (reloaded from the cache after refreshes).
+    pub(crate) check_failures: HashMap<String, HashMap<String, CheckFailure>>,
+    /// Bumped by [`AppState::prs_mut`]; the list layout cache is keyed on it.
+    pub(crate) list_version: u64,
+    /// Keyboard macro registers and any recording or replay in progress.
+    pub(crate) macros: Macros,
+    /// PR selected when needle last quit, selected once it is listed (until a key press).
+    pub(crate) pending_selection: Option<String>,
+}
+
+/// The ignored-PR list opened with `I`: `(pr_key, title)` rows and the selection.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoredView {
+    pub(crate) rows: Vec<(String, String)>,
+    pub(crate) selected: usize,
+}
+
+/// Review requests that are safe to shed (`S`) and the selection.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ShedView {
+    pub(crate) rows: Vec<ShedSuggestion>,
+    pub(crate) selected: usize,
+}
+
+/// Today's to-do list (`T`), the selection and how many items are done today.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TodayView {
+    pub(crate) items: Vec<TodayItem>,
+    pub(crate) selected: usize,
+    pub(crate) done: usize,
+}
+
+impl TodayView {
+    /// Today's list from the attention set and the marks saved for today.
+    fn load(state: &AppState, conn: &Connection, now: i64) -> Self {
+        // Unreadable marks just show the full list.
+        let marks: HashMap<String, TodayMark> = load_today_marks(conn, &today_key(now))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(key, mark)| Some((key, TodayMark::parse(&mark)?)))
+            .collect();
+        TodayView {
+            items: today_items(&state.prs, &marks, state.today_limit, now),
+            selected: 0,
+            done: marks.values().filter(|m| **m == TodayMark::Done).count(),
+        }
+    }
+}
+
+/// The last lines of a CI job log (`l`), scrolled up from the end.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LogView {
+    pub(crate) check: String,
+    pub(crate) lines: Vec<String>,
+    /// Lines scrolled up from the end; 0 shows the last lines.
+    pub(crate) from_bottom: usize,
+    /// Rows the last render had room for, so scrolling stops at the first line.
+    pub(crate) page: std::cell::Cell<usize>,
+}
+
+/// How far `PgUp` / `PgDn` scroll a job log.
+const LOG_SCROLL_PAGE: usize = 20;
+
+/// Collaborators offered for handing a PR off (`h`), and the selection.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HandoffPicker {
+    pub(crate) pr_key: String,
+    pub(crate) logins: Vec<String>,
+    pub(crate) selected: usize,
+}
+
+/// Handoff comment used unless `handoff_message` is configured.
+const DEFAULT_HANDOFF_MESSAGE: &str =
+    "@{login} could you take this review over? I'm out of office.";
+
+/// How many collaborators the handoff picker offers.
+const HANDOFF_CANDIDATES: usize = 8;
+
+/// People to hand a PR by `pr_author` off to: whoever recently authored, reviewed or
+/// commented on one of `prs`, most recent first. Leaves out the viewer, the author and bots.
+fn recent_collaborators<'a>(
+    prs: impl Iterator<Item = &'a UiPr>,
+    pr_author: &str,
+    viewer: Option<&str>,
+) -> Vec<String> {
+    let mut last_seen: HashMap<&str, i64> = HashMap::new();
+    for p in prs {
+        let pr = &p.pr;
+        // Pending requests may name a team, so only count submitted reviews.
+        let reviewers = pr
+            .reviewers
+            .iter()
+            .filter(|r| r.state != "REQUESTED")
+            .map(|r| (r.login.as_str(), pr.updated_at_unix));
+        let comments = pr
+            .comments
+            .iter()
+            .chain(pr.review_threads.iter().flat_map(|t| t.comments.iter()))
+            .map(|c| (c.author.as_str(), c.created_at_unix));
+        for (login, at) in std::iter::once((pr.author.as_str(), pr.updated_at_unix))
+            .chain(reviewers)
+            .chain(comments)
+        {
+            let seen = last_seen.entry(login).or_insert(at);
+            *seen = (*seen).max(at);
+        }
+    }
+    let mut out: Vec<(&str, i64)> = last_seen
+        .into_iter()
+        .filter(|(login, _)| {
+            !login.is_empty()
+                && !is_bot(login)
+                && !login.eq_ignore_ascii_case(pr_author)
+                && !viewer.is_some_and(|v| login.eq_ignore_ascii_case(v))
+        })
+        .collect();
+    out.sort_by_key(|&(login, at)| (std::cmp::Reverse(at), login));
+    out.into_iter()
+        .take(HANDOFF_CANDIDATES)
+        .map(|(login, _)| login.to_string())
+        .collect()
+}
+
+/// Open the handoff picker for `pr_key`, or say why there is nobody to offer.
+fn open_handoff_picker(state: &mut AppState, pr_key: &str) {
+    let Some(author) = state
+        .prs
+        .iter()
+        .chain(state.lookups.iter())
+        .find(|p| p.pr.pr_key == pr_key)
+        .map(|p| p.pr.author.clone())
+    else {
+        return;
+    };
+    let logins = recent_collaborators(
+        state.prs.iter().chain(state.lookups.iter()),
+        &author,
+        state.viewer_login.as_deref(),
+    );
+    if logins.is_empty() {
+        state.status_message = Some("No recent collaborators to hand off to".to_string());
+    } else {
+        state.handoff_picker = Some(HandoffPicker {
+            pr_key: pr_key.to_string(),
+            logins,
+            selected: 0,
+        });
+    }
+}
+
+/// Multi-line comment draft for one PR. Typing appends at the end; `Enter` starts a new
+/// line and `Backspace` deletes the last character.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Composer {
+    pub(crate) pr_key: String,
+    pub(crate) text: String,
+}
+
+impl Composer {
+    /// Apply an editing key. Returns false for keys the composer does not handle.
+    fn edit(&mut self, k: &KeyEvent) -> bool {
+        match k.code {
+            KeyCode::Enter => self.text.push('\n'),
+            KeyCode::Backspace => {
+                self.text.pop();
+            }
+            KeyCode::Tab => self.text.push_str("  "),
+            KeyCode::Char(ch) if !k.modifiers.contains(KeyModifiers::CONTROL) => self.text.push(ch),
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl AppState {
+    /// Mutable access to the attention set. Invalidates the cached list layout.
+    pub(crate) fn prs_mut(&mut self) -> &mut Vec<UiPr> {
+        self.list_version += 1;
+        &mut self.prs
+    }
+
     pub fn new(prs: Vec<UiPr>, ui: UiPrefs) -> Self {
         Self {
             prs,
@@ -105,6 +750,9 @@ impl AppState {
             refreshing: false,
             shimmer_phase: 0,
             details_ci_selected: 0,
+            details_ci_expanded: HashSet::new(),
+            details_conversation_scroll: 0,
+            details_thread_selected: 0,
             details_last_auto_refresh: None,
             last_refresh_started: Some(Instant::now()),
             ui,
@@ -116,11 +764,352 @@ impl AppState {
             only_needs_you: false,
             only_failing_ci: false,
             only_review_requested: false,
+            only_sla: false,
+            only_mentioned: false,
+            only_bots: false,
             update_notice: None,
+            rate_limit: None,
+            last_refreshed_at: None,
+            reminders: Reminders::default(),
+            last_refresh_error: None,
+            error_log: VecDeque::new(),
+            error_log_open: false,
+            status_message: None,
+            lookups: Vec::new(),
+            groups: RepoGroups::default(),
+            check_groups: CheckGroups::default(),
+            quick_links: QuickLinks::default(),
+            custom_columns: Vec::new(),
+            group_rows: false,
+            collapsed_blocks: HashSet::new(),
+            oncall: false,
+            title_status: TitleStatus::default(),
+            offline: false,
+            viewer_login: None,
+            scope_summary: Vec::new(),
+            days: None,
+            merge_picker: None,
+            preview_open: false,
+            snooze_picker: None,
+            select_mode: false,
+            marked: HashSet::new(),
+            confirm_approve: false,
+            confirm_open_all: None,
+            snoozed_open: false,
+            ignored_view: None,
+            shed_view: None,
+            today_view: None,
+            today_limit: DEFAULT_TODAY_LIMIT,
+            log_view: None,
+            composer: None,
+            handoff_picker: None,
+            handoff_message: DEFAULT_HANDOFF_MESSAGE.to_string(),
+            layout: ListLayout::default(),
+            usage_mode: UsageMode::Full,
+            split_focus: SplitPane::default(),
+            split_parked: 0,
+            addressed_threads: HashMap::new(),
+            check_failures: HashMap::new(),
+            list_version: 0,
+            macros: Macros::default(),
+            color_mode: ColorMode::TrueColor,
+            pending_selection: None,
+        }
+    }
+
+    /// Use the configured repo groups for `group:` filters and list grouping.
+    pub fn with_groups(mut self, groups: RepoGroups, group_rows: bool) -> Self {
+        self.groups = groups;
+        self.group_rows = group_rows;
+        self
+    }
+
+    /// Collapse matching CI checks in details view.
+    pub fn with_check_groups(mut self, check_groups: CheckGroups) -> Self {
+        self.check_groups = check_groups;
+        self
+    }
+
+    /// List and open these links in details view.
+    pub fn with_quick_links(mut self, quick_links: QuickLinks) -> Self {
+        self.quick_links = quick_links;
+        self
+    }
+
+    /// Add computed columns (`[[columns]]`) to the list.
+    pub fn with_custom_columns(mut self, columns: Vec<CustomColumn>) -> Self {
+        self.custom_columns = columns;
+        self
+    }
+
+    /// Use `message` (with `{login}`) for handoff comments instead of the default.
+    pub fn with_handoff_message(mut self, message: Option<String>) -> Self {
+        if let Some(message) = message {
+            self.handoff_message = message;
+        }
+        self
+    }
+
+    /// Take over the TUI-side settings of a reloaded config.
+    fn apply_live_settings(&mut self, settings: LiveSettings) {
+        self.ui = settings.ui;
+        self.check_groups = settings.check_groups;
+        self.quick_links = settings.quick_links;
+        self.title_status = settings.title_status;
+        self.handoff_message = settings
+            .handoff_message
+            .unwrap_or_else(|| DEFAULT_HANDOFF_MESSAGE.to_string());
+    }
+
+    /// Ring the bell again for NEEDS YOU PRs left unopened for `grace_secs`.
+    pub fn with_reminders(mut self, grace_secs: Option<i64>) -> Self {
+        self.reminders = Reminders::new(grace_secs);
+        self
+    }
+
+    /// Badge the UI while the on-call profile is active.
+    pub fn with_oncall(mut self, oncall: bool) -> Self {
+        self.oncall = oncall;
+        self
+    }
+
+    /// Keep the NEEDS YOU count in the terminal title and/or a tmux option.
+    pub fn with_title_status(mut self, title_status: TitleStatus) -> Self {
+        self.title_status = title_status;
+        self
+    }
+
+    /// Run read-only from the cache and explain how to sign in.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Show who needle is signed in as.
+    pub fn with_viewer_login(mut self, login: Option<String>) -> Self {
+        self.viewer_login = login;
+        self
+    }
+
+    /// Draw for a terminal with this color depth (see [`ColorMode::resolve`]).
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode.resolve();
+        self
+    }
+
+    /// Start in the given list layout.
+    pub fn with_layout(mut self, layout: ListLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Pick up where the last session left off: filter, toggles, layout (unless
+    /// `keep_layout`) and the selected PR, as saved in the cache on quit.
+    pub fn with_saved_ui_state(mut self, conn: &Connection, keep_layout: bool) -> Self {
+        // A missing or unreadable record just starts fresh.
+        let saved = load_ui_state(conn).unwrap_or_default();
+        let on = |key: &str| saved.get(key).is_some_and(|v| v == "1");
+        self.filter_query = saved.get("filter").cloned().unwrap_or_default();
+        self.only_needs_you = on("only_needs_you");
+        self.only_failing_ci = on("only_failing_ci");
+        self.only_review_requested = on("only_review_requested");
+        self.only_sla = on("only_sla");
+        self.only_mentioned = on("only_mentioned");
+        self.only_bots = on("only_bots");
+        self.snoozed_open = on("snoozed_open");
+        let layout = saved
+            .get("layout")
+            .and_then(|v| ListLayout::from_str(v, true).ok());
+        if let Some(layout) = layout.filter(|_| !keep_layout) {
+            self.layout = layout;
+        }
+        self.pending_selection = saved.get("selected").cloned();
+        self
+    }
+
+    /// What [`AppState::with_saved_ui_state`] restores, with `selected` as the selection.
+    fn ui_state_entries(&self, selected: Option<&str>) -> Vec<(&'static str, String)> {
+        let flag = |on: bool| if on { "1" } else { "0" }.to_string();
+        let layout = self
+            .layout
+            .to_possible_value()
+            .map(|v| v.get_name().to_string());
+        let mut entries = vec![
+            ("filter", self.filter_query.clone()),
+            ("only_needs_you", flag(self.only_needs_you)),
+            ("only_failing_ci", flag(self.only_failing_ci)),
+            ("only_review_requested", flag(self.only_review_requested)),
+            ("only_sla", flag(self.only_sla)),
+            ("only_mentioned", flag(self.only_mentioned)),
+            ("only_bots", flag(self.only_bots)),
+            ("snoozed_open", flag(self.snoozed_open)),
+        ];
+        entries.extend(layout.map(|v| ("layout", v)));
+        entries.extend(selected.map(|key| ("selected", key.to_string())));
+        entries
+    }
+
+    /// List at most `limit` items in the Today view.
+    pub fn with_today_limit(mut self, limit: usize) -> Self {
+        self.today_limit = limit;
+        self
+    }
+
+    /// Show the scope filters and days window the queue was fetched with.
+    pub fn with_scope(mut self, scope: &ScopeFilters, days: i64) -> Self {
+        self.scope_summary = scope.summary();
+        self.usage_mode = scope.mode;
+        if self.usage_mode != UsageMode::Full && self.layout == ListLayout::Split {
+            self.layout = ListLayout::Categories;
+        }
+        self.days = Some(days);
+        self
+    }
+
+    /// Rows passing the current filter and toggles, in list order.
+    fn filtered(&self) -> Vec<usize> {
+        filtered_indices(
+            &self.prs,
+            &self.filter_query,
+            self.only_needs_you,
+            self.only_failing_ci,
+            self.only_review_requested,
+            self.only_sla,
+            self.only_mentioned,
+            self.only_bots,
+            &self.groups,
+            now_unix(),
+        )
+    }
+
+    /// Marked PRs still in the list, in list order.
+    fn marked_prs(&self) -> Vec<&UiPr> {
+        self.prs
+            .iter()
+            .filter(|p| self.marked.contains(&p.pr.pr_key))
+            .collect()
+    }
+
+    /// How many PRs are in NEEDS YOU, whatever the filter.
+    fn needs_you_count(&self) -> usize {
+        self.prs
+            .iter()
+            .filter(|p| Section::Category(Category::NeedsYou).contains(p))
+            .count()
+    }
+
+    /// Keys of the PRs the NEEDS YOU section shows under the current filter, in list order.
+    fn needs_you_keys(&self) -> Vec<String> {
+        self.filtered()
+            .into_iter()
+            .filter_map(|i| self.prs.get(i))
+            .filter(|p| Section::Category(Category::NeedsYou).contains(p))
+            .map(|p| p.pr.pr_key.clone())
+            .collect()
+    }
+
+    /// Leave select mode, dropping the marks.
+    fn end_select_mode(&mut self) {
+        self.select_mode = false;
+        self.marked.clear();
+        self.confirm_approve = false;
+    }
+
+    /// The PR shown in details view (attention set first, then looked-up PRs).
+    pub(crate) fn details_pr(&self) -> Option<&UiPr> {
+        let key = self.details_pr_key.as_deref()?;
+        self.prs
+            .iter()
+            .chain(self.lookups.iter())
+            .find(|p| p.pr.pr_key == key)
+    }
+
+    /// Show a failed refresh in the banner and keep it in the error log.
+    fn record_refresh_error(&mut self, error: String, now: i64) {
+        self.log_error(error.clone(), now);
+        self.last_refresh_error = Some(error);
+    }
+
+    /// Keep `error` in the error log only, e.g. PRs a refresh had to skip.
+    fn log_error(&mut self, error: String, now: i64) {
+        if self.error_log.len() == ERROR_LOG_LEN {
+            self.error_log.pop_front();
+        }
+        self.error_log.push_back((now, error));
+    }
+
+    /// Checks list rows of the PR open in details view.
+    pub(crate) fn details_ci_rows(&self) -> Vec<CiRow> {
+        self.details_pr()
+            .map(|p| {
+                ci_rows(
+                    &p.pr.ci_checks,
+                    &self.check_groups,
+                    &self.details_ci_expanded,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Swap in a re-fetched PR wherever it is shown (attention set or lookups).
+    fn replace_pr(&mut self, pr: UiPr) {
+        if let Some(i) = self.prs.iter().position(|p| p.pr.pr_key == pr.pr.pr_key) {
+            let prs = self.prs_mut();
+            prs[i] = pr;
+            sort_for_display(prs);
+        } else if let Some(slot) = self
+            .lookups
+            .iter_mut()
+            .find(|p| p.pr.pr_key == pr.pr.pr_key)
+        {
+            *slot = pr;
+        }
+    }
+
+    fn open_details(&mut self, pr_key: String) {
+        self.details_pr_key = Some(pr_key);
+        self.mode = ViewMode::Details;
+        self.details_ci_selected = 0;
+        self.details_ci_expanded.clear();
+        self.details_conversation_scroll = 0;
+        self.details_thread_selected = 0;
+        self.details_last_auto_refresh = Some(Instant::now());
+    }
+}
+
+/// Remember that the PR was looked at, so later force-pushes are flagged relative to now
+/// and the row stays dimmed until the PR is updated.
+fn mark_viewed(conn: &Connection, pr: &mut UiPr) {
+    if mark_opened(conn, &pr.pr.pr_key, now_unix()).is_ok() {
+        pr.force_pushed_since_view = false;
+        pr.opened_since_update = true;
+        if pr.reply_to_you {
+            pr.reply_to_you = false;
+            let badge = if pr.pr.is_mentioned {
+                MENTION_BADGE
+            } else {
+                ""
+            };
+            pr.display_status = pr.display_status.replacen(REPLY_BADGE, badge, 1);
         }
     }
 }
 
+/// Mark `pr` read in the GitHub inbox on a worker thread (`mark_read_on_open`). Only
+/// failures are reported, through `tx`.
+fn spawn_mark_read(mark_read_fn: Option<&MarkReadFn>, pr: &Pr, tx: &mpsc::Sender<String>) {
+    let Some(mf) = mark_read_fn.cloned() else {
+        return;
+    };
+    let pr = pr.clone();
+    let tx = tx.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = mf(&pr) {
+            let _ = tx.send(e);
+        }
+    });
+}
+
 fn spawn_update_check() -> Option<mpsc::Receiver<String>> {
     let (tx, rx) = mpsc::channel();
     std::thread::spawn(move || {
@@ -141,7 +1130,7 @@ fn spawn_update_check() -> Option<mpsc::Receiver<String>> {
     Some(rx)
 }
 
-fn category_title(cat: Category) -> &'static str {
+pub(crate) fn category_title(cat: Category) -> &'static str {
     match cat {
         Category::NeedsYou => "🔥 NEEDS YOU",
         Category::ReadyToMerge => "🚢 READY TO MERGE",
@@ -150,6 +1139,54 @@ fn category_title(cat: Category) -> &'static str {
     }
 }
 
+/// A list section between PINNED and DRAFT: a score category, or review requests to the
+/// viewer's teams, which are kept apart from their category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Section {
+    Category(Category),
+    TeamReview,
+}
+
+/// Sections in list order.
+pub(crate) const SECTIONS: [Section; 5] = [
+    Section::Category(Category::ReadyToMerge),
+    Section::Category(Category::NeedsYou),
+    Section::TeamReview,
+    Section::Category(Category::Waiting),
+    Section::Category(Category::Stale),
+];
+
+impl Section {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Section::Category(cat) => category_title(cat),
+            Section::TeamReview => "👥 TEAM REVIEW",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Section::Category(cat) => category_style(cat),
+            Section::TeamReview => Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Whether `pr` is listed here. Pinned, snoozed, DRAFT- and BOTS-section PRs have their
+    /// own.
+    pub(crate) fn contains(self, pr: &UiPr) -> bool {
+        if pr.is_pinned || pr.snoozed || in_draft_section(pr) || in_bot_section(pr) {
+            return false;
+        }
+        let team = matches!(pr.pr.review_state, ReviewState::TeamRequested);
+        match self {
+            Section::Category(cat) => !team && pr.category == cat,
+            Section::TeamReview => team,
+        }
+    }
+}
+
 fn category_style(cat: Category) -> Style {
     match cat {
         Category::NeedsYou => Style::default()
@@ -167,11 +1204,79 @@ fn category_style(cat: Category) -> Style {
     }
 }
 
-fn truncate_ellipsis(s: &str, max_width: usize) -> String {
-    if max_width == 0 {
-        return String::new();
+/// Byte offset where a trailing issue key (`PROJ-123`, `#42`) or bracketed tag (`[PROJ-123]`,
+/// `(mobile)`) starts, when the text ends with one after some leading text.
+fn trailing_keyword(s: &str) -> Option<usize> {
+    let open = match s.chars().last()? {
+        ']' => s.rfind('['),
+        ')' => s.rfind('('),
+        _ => None,
+    };
+    if let Some(at) = open.filter(|&at| at > 0) {
+        return Some(at);
     }
-    if UnicodeWidthStr::width(s) <= max_width {
+    let (space, c) = s.char_indices().rev().find(|(_, c)| c.is_whitespace())?;
+    let at = space + c.len_utf8();
+    let word = &s[at..];
+    let is_number = |n: &str| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit());
+    let is_key = match word.strip_prefix('#') {
+        Some(n) => is_number(n),
+        None => word.split_once('-').is_some_and(|(project, n)| {
+            project.starts_with(|c: char| c.is_ascii_uppercase())
+                && project
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                && is_number(n)
+        }),
+    };
+    is_key.then_some(at)
+}
+
+/// Like [`truncate_ellipsis`], but cut in the middle so a trailing issue key or tag stays
+/// visible: `Fix login redirect… [PROJ-123]`. Falls back to cutting the end when the tail
+/// would leave less than a few characters of the text before it.
+fn truncate_keep_tail(s: &str, max_width: usize) -> String {
+    const MIN_HEAD: usize = 4;
+    if UnicodeWidthStr::width(s) <= max_width {
+        return s.to_string();
+    }
+    let trimmed = s.trim_end();
+    let Some(at) = trailing_keyword(trimmed) else {
+        return truncate_ellipsis(s, max_width);
+    };
+    let (head, tail) = (trimmed[..at].trim_end(), &trimmed[at..]);
+    // `…` plus the space that separated head and tail.
+    let head_w = max_width.saturating_sub(UnicodeWidthStr::width(tail) + 2);
+    if head_w < MIN_HEAD {
+        return truncate_ellipsis(s, max_width);
+    }
+    let mut out = String::new();
+    let mut w = 0usize;
+    for ch in head.chars() {
+        let cw = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if w + cw > head_w {
+            break;
+        }
+        out.push(ch);
+        w += cw;
+    }
+    format!("{}… {tail}", out.trim_end())
+}
+
+/// Truncate one list cell, keeping the tail when the column is set up for it.
+fn truncate_cell(s: &str, max_width: usize, keep_tail: bool) -> String {
+    if keep_tail {
+        truncate_keep_tail(s, max_width)
+    } else {
+        truncate_ellipsis(s, max_width)
+    }
+}
+
+fn truncate_ellipsis(s: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    if UnicodeWidthStr::width(s) <= max_width {
         return s.to_string();
     }
 
@@ -216,7 +1321,7 @@ fn pad_right(s: &str, width: usize) -> String {
     }
 }
 
-fn human_age(now: i64, then: i64) -> String {
+pub(crate) fn human_age(now: i64, then: i64) -> String {
     let d = now.saturating_sub(then);
     if d < 60 {
         "now".to_string()
@@ -240,6 +1345,102 @@ fn human_duration(secs: i64) -> String {
     }
 }
 
+/// How loudly the footer note is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tone {
+    /// Dim gray.
+    Quiet,
+    /// Bold yellow.
+    Loud,
+    /// Bold red.
+    Alarm,
+}
+
+/// Left side of the footer and how loud it is: status messages first, then a low API budget
+/// warning, the update notice, and otherwise how old the list is plus the API budget.
+fn footer_note(state: &AppState, list_secs: u64, now: i64) -> Option<(String, Tone)> {
+    if let Some(msg) = &state.status_message {
+        return Some((msg.clone(), Tone::Loud));
+    }
+    if let Some(rl) = state.rate_limit.filter(|rl| rl.is_low()) {
+        let resets_in = human_duration(rl.reset_at_unix.saturating_sub(now));
+        let note = if rl.remaining <= 0 {
+            format!(
+                "⚠ GitHub API budget used up; auto-refresh paused until it resets in {resets_in}"
+            )
+        } else {
+            let every = rl.refresh_interval_secs(list_secs, now) as i64;
+            format!(
+                "⚠ GitHub API {}/{} left (resets in {resets_in}); auto-refresh every {}",
+                rl.remaining,
+                rl.limit,
+                human_duration(every)
+            )
+        };
+        return Some((note, Tone::Loud));
+    }
+    if let Some(reg) = state.macros.recording() {
+        return Some((format!("● recording macro @{reg} (Q stops)"), Tone::Loud));
+    }
+    if let Some(notice) = &state.update_notice {
+        return Some((notice.clone(), Tone::Loud));
+    }
+    let mut parts = Vec::new();
+    let mut tone = Tone::Quiet;
+    if let Some(at) = state.last_refreshed_at {
+        parts.push(match human_age(now, at).as_str() {
+            "now" => "updated just now".to_string(),
+            age => format!("updated {age}"),
+        });
+        // Measured against the interval auto-refresh actually runs at, so a slowed-down
+        // refresh on a low budget does not read as stale.
+        let every = state
+            .rate_limit
+            .map_or(list_secs, |rl| rl.refresh_interval_secs(list_secs, now));
+        let age = u64::try_from(now.saturating_sub(at)).unwrap_or(0);
+        tone = if age > every * 4 {
+            Tone::Alarm
+        } else if age > every * 2 {
+            Tone::Loud
+        } else {
+            Tone::Quiet
+        };
+    }
+    if state.last_refresh_error.is_some() {
+        tone = Tone::Alarm;
+    }
+    if let Some(rl) = state.rate_limit {
+        parts.push(format!("API {}/{}", rl.remaining, rl.limit));
+    }
+    (!parts.is_empty()).then(|| (parts.join(" · "), tone))
+}
+
+/// Read text from the system clipboard via the platform's CLI tools.
+fn read_clipboard() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    let candidates: &[(&str, &[&str])] = &[("pbpaste", &[])];
+    #[cfg(target_os = "linux")]
+    let candidates: &[(&str, &[&str])] = &[
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+        ("xsel", &["--clipboard", "--output"]),
+    ];
+    #[cfg(target_os = "windows")]
+    let candidates: &[(&str, &[&str])] =
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])];
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    let candidates: &[(&str, &[&str])] = &[];
+
+    candidates.iter().find_map(|(bin, args)| {
+        let out = Command::new(bin).args(*args).output().ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    })
+}
+
 fn open_in_browser(url: &str) {
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
@@ -271,39 +1472,247 @@ fn open_in_browser(url: &str) {
     let _ = cmd.spawn();
 }
 
-fn matches_filter(pr: &UiPr, query: &str) -> bool {
-    if query.trim().is_empty() {
+/// The tmux option holding the NEEDS YOU count (`tmux_status`).
+const TMUX_OPTION: &str = "@needle_needs_you";
+
+/// Keeps the NEEDS YOU count in the terminal title and the tmux option, as the
+/// [`TitleStatus`] of the moment asks, and puts things back when switched off or on exit.
+#[derive(Default)]
+struct TitlePublisher {
+    /// What was published last.
+    last: Option<(TitleStatus, usize)>,
+    /// Where the count is published right now.
+    shown: TitleStatus,
+}
+
+impl TitlePublisher {
+    fn update(&mut self, to: TitleStatus, needs_you: usize) {
+        if self.last == Some((to, needs_you)) {
+            return;
+        }
+        self.last = Some((to, needs_you));
+        let to = TitleStatus {
+            tmux: to.tmux && std::env::var_os("TMUX").is_some(),
+            ..to
+        };
+        if to.terminal != self.shown.terminal {
+            // xterm's title stack (22 saves, 23 restores); terminals without it ignore both.
+            let op = if to.terminal { "22" } else { "23" };
+            let _ = execute!(io::stdout(), Print(format!("\x1b[{op};0t")));
+        }
+        if to.terminal {
+            let _ = execute!(io::stdout(), SetTitle(format!("needle ({needs_you})")));
+        }
+        if to.tmux {
+            tmux(&["set-option", "-gq", TMUX_OPTION, &needs_you.to_string()]);
+        } else if self.shown.tmux {
+            tmux(&["set-option", "-gqu", TMUX_OPTION]);
+        }
+        self.shown = to;
+    }
+
+    /// Restore the terminal title and drop the tmux option.
+    fn clear(&mut self) {
+        self.update(TitleStatus::default(), 0);
+    }
+}
+
+fn tmux(args: &[&str]) {
+    let _ = Command::new("tmux")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// `O` asks before opening more tabs than this.
+const OPEN_ALL_CONFIRM_OVER: usize = 5;
+
+/// Pause between browser tabs when opening several PRs, so the OS and browser keep up.
+const OPEN_TAB_GAP: Duration = Duration::from_millis(250);
+
+/// Open the PRs with `keys` in browser tabs (one by one, in the background) and mark them
+/// opened. Returns how many were found.
+fn open_prs(
+    conn: &Connection,
+    state: &mut AppState,
+    keys: &[String],
+    mark_read_fn: Option<&MarkReadFn>,
+    mark_read_tx: &mpsc::Sender<String>,
+) -> usize {
+    let mut urls = Vec::new();
+    for key in keys {
+        if let Some(pr) = state.prs_mut().iter_mut().find(|p| &p.pr.pr_key == key) {
+            mark_viewed(conn, pr);
+            spawn_mark_read(mark_read_fn, &pr.pr, mark_read_tx);
+            urls.push(pr.pr.url.clone());
+        }
+    }
+    let n = urls.len();
+    std::thread::spawn(move || {
+        for (i, url) in urls.iter().enumerate() {
+            if i > 0 {
+                std::thread::sleep(OPEN_TAB_GAP);
+            }
+            open_in_browser(url);
+        }
+    });
+    n
+}
+
+/// Whether `pr` passes one `field:value` term of the filter, or `None` when `field` is not
+/// a filter field (the term is then free text). A term without a value yet (`ci:` while
+/// typing) passes everything.
+fn field_matches(pr: &UiPr, field: &str, value: &str, now: i64) -> Option<bool> {
+    let value = value.to_lowercase();
+    let matches = match field.to_lowercase().as_str() {
+        "repo" | "author" | "is" | "ci" | "review" | "age" if value.is_empty() => true,
+        "repo" => format!("{}/{}", pr.pr.owner, pr.pr.repo)
+            .to_lowercase()
+            .contains(&value),
+        "author" => pr
+            .pr
+            .author
+            .eq_ignore_ascii_case(value.trim_start_matches('@')),
+        "is" => match value.as_str() {
+            "draft" => pr.pr.is_draft,
+            "ready" => !pr.pr.is_draft,
+            "mine" => pr.pr.is_viewer_author,
+            "pinned" => pr.is_pinned,
+            "mentioned" => pr.pr.is_mentioned,
+            "bot" => is_bot(&pr.pr.author),
+            _ => false,
+        },
+        "ci" => {
+            use needle_core::model::CiState;
+            match value.as_str() {
+                "failing" | "failed" | "red" => matches!(pr.pr.ci_state, CiState::Failure),
+                "passing" | "green" => matches!(pr.pr.ci_state, CiState::Success),
+                "running" | "pending" => matches!(pr.pr.ci_state, CiState::Running),
+                "none" => matches!(pr.pr.ci_state, CiState::None),
+                _ => false,
+            }
+        }
+        "review" => match value.as_str() {
+            "requested" => matches!(pr.pr.review_state, ReviewState::Requested),
+            "team" => matches!(pr.pr.review_state, ReviewState::TeamRequested),
+            "approved" => matches!(pr.pr.review_state, ReviewState::Approved),
+            "changes" => matches!(pr.pr.review_state, ReviewState::ChangesRequested),
+            "none" => matches!(pr.pr.review_state, ReviewState::None),
+            _ => false,
+        },
+        "age" => {
+            let opened = pr.pr.created_at_unix.unwrap_or(pr.pr.updated_at_unix);
+            let age = now.saturating_sub(opened);
+            let (younger, span) = match value.strip_prefix('<') {
+                Some(span) => (true, span),
+                None => (false, value.trim_start_matches('>')),
+            };
+            match filter_span_secs(span) {
+                Some(secs) if younger => age < secs,
+                Some(secs) => age > secs,
+                None => false,
+            }
+        }
+        _ => return None,
+    };
+    Some(matches)
+}
+
+/// Seconds in an `age:` span: `90m`, `12h`, `3d` or `2w`. `None` when it is not one, or
+/// too long to count.
+fn filter_span_secs(span: &str) -> Option<i64> {
+    let unit = match span.chars().last()? {
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86_400,
+        'w' => 7 * 86_400,
+        _ => return None,
+    };
+    let n: i64 = span[..span.len() - 1].parse().ok()?;
+    n.checked_mul(unit)
+}
+
+/// The `/` filter: `group:<name>` terms select repo groups (any of them), field terms
+/// (`repo:`, `author:`, `is:`, `ci:`, `review:`, `age:`) must all hold, and the remaining
+/// words are a substring query on repo, author, title and number.
+fn matches_filter(pr: &UiPr, query: &str, groups: &RepoGroups, now: i64) -> bool {
+    let (group_tokens, rest): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|t| t.to_lowercase().starts_with("group:"));
+    let repo = format!("{}/{}", pr.pr.owner, pr.pr.repo);
+    if !group_tokens.is_empty()
+        && !group_tokens
+            .iter()
+            .any(|t| groups.contains(&t["group:".len()..], &repo))
+    {
+        return false;
+    }
+
+    let mut text = Vec::new();
+    for token in rest {
+        let field = token
+            .split_once(':')
+            .and_then(|(field, value)| field_matches(pr, field, value, now));
+        match field {
+            Some(false) => return false,
+            Some(true) => {}
+            None => text.push(token),
+        }
+    }
+
+    let text = text.join(" ");
+    if text.is_empty() {
         return true;
     }
-    let q = query.trim().to_lowercase();
-    let repo = format!("{}/{}", pr.pr.owner, pr.pr.repo).to_lowercase();
+    let q = text.to_lowercase();
+    let repo = repo.to_lowercase();
     let author = pr.pr.author.to_lowercase();
     let title = pr.pr.title.to_lowercase();
     let num = format!("#{}", pr.pr.number);
-    repo.contains(&q) || author.contains(&q) || title.contains(&q) || num.contains(query.trim())
+    repo.contains(&q) || author.contains(&q) || title.contains(&q) || num.contains(text.as_str())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn filtered_indices(
     state_prs: &[UiPr],
     query: &str,
     only_needs_you: bool,
     only_failing_ci: bool,
     only_review_requested: bool,
+    only_sla: bool,
+    only_mentioned: bool,
+    only_bots: bool,
+    groups: &RepoGroups,
+    now: i64,
 ) -> Vec<usize> {
     let mut out = Vec::new();
     for (idx, pr) in state_prs.iter().enumerate() {
         if only_needs_you && pr.category != Category::NeedsYou {
             continue;
         }
-        if only_failing_ci && !matches!(pr.pr.ci_state, crate::model::CiState::Failure) {
+        if only_failing_ci && !matches!(pr.pr.ci_state, needle_core::model::CiState::Failure) {
             continue;
         }
         if only_review_requested
-            && !matches!(pr.pr.review_state, crate::model::ReviewState::Requested)
+            && !matches!(
+                pr.pr.review_state,
+                needle_core::model::ReviewState::Requested
+            )
         {
             continue;
         }
-        if !matches_filter(pr, query) {
+        if only_sla && !pr.sla.is_some_and(|s| s.is_at_risk()) {
+            continue;
+        }
+        if only_mentioned && !pr.pr.is_mentioned {
+            continue;
+        }
+        if only_bots && !is_bot(&pr.pr.author) {
+            continue;
+        }
+        if !matches_filter(pr, query, groups, now) {
             continue;
         }
         out.push(idx);
@@ -311,45 +1720,42 @@ fn filtered_indices(
     out
 }
 
-fn build_list_lines(
-    prs: &[UiPr],
-    inner_width: u16,
-    inner_height: u16,
-    selected_visible_idx: usize,
-    filtered: &[usize],
-    filter_banner: Option<&str>,
-    ui: UiPrefs,
-) -> (Vec<Line<'static>>, Vec<usize>) {
-    // We build rendered lines (headers/dividers/rows/blanks) up to inner_height.
-    // Also track which `prs` indices are visible, in order, so selection works.
-    let mut lines: Vec<Line<'static>> = Vec::new();
-    let mut visible_pr_indices: Vec<usize> = Vec::new();
-
-    fn push_line(lines: &mut Vec<Line<'static>>, inner_height: u16, line: Line<'static>) {
-        if (lines.len() as u16) < inner_height {
-            lines.push(line);
-        }
+/// Status column text, with the force-push marker when the head moved since the last view,
+/// or 👀 when the PR was opened since its last update.
+fn row_status(pr: &UiPr) -> String {
+    if pr.force_pushed_since_view {
+        format!("⚠️ force-pushed · {}", pr.display_status)
+    } else if pr.opened_since_update {
+        format!("👀 {}", pr.display_status)
+    } else {
+        pr.display_status.clone()
     }
+}
 
-    // Optional filter banner at the top.
-    if let Some(banner) = filter_banner {
-        push_line(
-            &mut lines,
-            inner_height,
-            Line::from(Span::styled(
-                truncate_ellipsis(banner, inner_width as usize),
-                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
-            )),
-        );
-        push_line(&mut lines, inner_height, Line::from(Span::raw("")));
-    }
+/// Drafts go to the DRAFT section unless the draft policy ranks them with regular PRs.
+pub(crate) fn in_draft_section(pr: &UiPr) -> bool {
+    pr.pr.is_draft && !pr.draft_ranked && !pr.snoozed && !in_bot_section(pr)
+}
 
-    // Table-ish column sizing (dynamic; only truncates when the terminal width forces it).
-    // Columns: prefix(2) [repo] [author] [#num] title status
-    let iw = inner_width as usize;
-    let prefix_w = 2usize;
-    let sep_w = 2usize; // two spaces between columns
+/// Bot PRs go to the BOTS section with `bots = "separate_section"`, drafts included.
+pub(crate) fn in_bot_section(pr: &UiPr) -> bool {
+    pr.bot_section && !pr.snoozed
+}
+
+/// Render the sectioned PR list. With `groups`, rows inside each section are
+/// sub-grouped under their repo group.
+/// Widest cell per column over the filtered rows, before clamping to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ColumnMaxes {
+    repo: usize,
+    author: usize,
+    num: usize,
+    status: usize,
+}
 
+/// Measure every filtered row. This is the part of list layout that grows with the
+/// number of PRs, so the event loop caches it (see [`ListCache`]).
+fn measure_columns(prs: &[UiPr], filtered: &[usize], ui: UiPrefs) -> ColumnMaxes {
     let max_repo_len = if ui.hide_repo {
         0
     } else {
@@ -389,10 +1795,331 @@ fn build_list_lines(
     let max_status_len = filtered
         .iter()
         .filter_map(|&i| prs.get(i))
-        .map(|p| UnicodeWidthStr::width(p.display_status.as_str()))
+        .map(|p| UnicodeWidthStr::width(row_status(p).as_str()))
         .max()
         .unwrap_or(10);
 
+    ColumnMaxes {
+        repo: max_repo_len,
+        author: max_author_len,
+        num: max_num_len,
+        status: max_status_len,
+    }
+}
+
+/// Cells of the configured `[[columns]]` for the filtered rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CustomCells {
+    /// Header and width of each column.
+    heads: Vec<(String, usize)>,
+    /// Cell texts by index into `prs`, in column order.
+    rows: HashMap<usize, Vec<String>>,
+}
+
+impl CustomCells {
+    /// Width the columns take up, separators included.
+    fn width(&self) -> usize {
+        self.heads.iter().map(|(_, w)| w + 2).sum()
+    }
+}
+
+/// Evaluate `columns` for every filtered row as of `now`. Cells that cannot be computed
+/// (e.g. no review request for `now - review_requested_at`) show `-`.
+fn custom_cells(
+    prs: &[UiPr],
+    filtered: &[usize],
+    columns: &[CustomColumn],
+    now: i64,
+) -> CustomCells {
+    if columns.is_empty() {
+        return CustomCells::default();
+    }
+    let rows: HashMap<usize, Vec<String>> = filtered
+        .iter()
+        .filter_map(|&i| Some((i, prs.get(i)?)))
+        .map(|(i, p)| {
+            let cells = columns
+                .iter()
+                .map(|c| c.value(p, now).map_or("-".to_string(), |v| v.to_string()))
+                .collect();
+            (i, cells)
+        })
+        .collect();
+    let heads = columns
+        .iter()
+        .enumerate()
+        .map(|(n, c)| {
+            let widest = rows
+                .values()
+                .map(|cells| UnicodeWidthStr::width(cells[n].as_str()))
+                .max()
+                .unwrap_or(0);
+            (c.name.to_uppercase(), widest.max(c.name.len()))
+        })
+        .collect();
+    CustomCells { heads, rows }
+}
+
+/// Named blocks of list rows (the repo and topic layouts).
+type Blocks = Vec<(String, Vec<usize>)>;
+
+/// Filtered rows by repository (`owner/repo`, alphabetical), in display order within
+/// each. Snoozed PRs are left out unless the snoozed section is open.
+fn repo_blocks(prs: &[UiPr], filtered: &[usize], show_snoozed: bool) -> Blocks {
+    let mut blocks: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for &idx in filtered {
+        let Some(p) = prs.get(idx) else { continue };
+        if p.snoozed && !show_snoozed {
+            continue;
+        }
+        blocks
+            .entry(format!("{}/{}", p.pr.owner, p.pr.repo))
+            .or_default()
+            .push(idx);
+    }
+    blocks.into_iter().collect()
+}
+
+/// Filtered rows by topic (see [`topics`]), biggest topic first and PRs without one last,
+/// in display order within each. Snoozed PRs are left out unless the snoozed section is open.
+fn topic_blocks(prs: &[UiPr], filtered: &[usize], show_snoozed: bool) -> Blocks {
+    let topic_of = topics(prs);
+    let mut blocks: Blocks = Vec::new();
+    for &idx in filtered {
+        let Some(p) = prs.get(idx) else { continue };
+        if p.snoozed && !show_snoozed {
+            continue;
+        }
+        let name = topic_of[idx].as_deref().unwrap_or(OTHER_TOPIC);
+        match blocks.iter_mut().find(|(n, _)| n == name) {
+            Some((_, rows)) => rows.push(idx),
+            None => blocks.push((name.to_string(), vec![idx])),
+        }
+    }
+    blocks.sort_by(|(a, a_rows), (b, b_rows)| {
+        (a == OTHER_TOPIC)
+            .cmp(&(b == OTHER_TOPIC))
+            .then(b_rows.len().cmp(&a_rows.len()))
+            .then(a.cmp(b))
+    });
+    blocks
+}
+
+/// Everything the filtered row set and column widths depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListDataKey {
+    list_version: u64,
+    query: String,
+    only: [bool; 6],
+    ui: UiPrefs,
+}
+
+/// Everything else the rendered list lines depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListViewKey {
+    width: u16,
+    height: u16,
+    selected: usize,
+    banner: Option<String>,
+    group_rows: bool,
+    show_snoozed: bool,
+    layout: ListLayout,
+    split_focus: SplitPane,
+    collapsed_blocks: HashSet<String>,
+    marked: HashSet<String>,
+}
+
+/// List layout carried between frames.
+///
+/// Filtering and column measurement walk every PR, so they are redone only when the PRs,
+/// filters or column prefs change. The rendered lines (at most a screenful) are rebuilt
+/// when the size, selection or banner changes as well.
+#[derive(Default)]
+struct ListCache {
+    data: Option<(ListDataKey, Vec<usize>, ColumnMaxes, CustomCells)>,
+    view: Option<ListViewKey>,
+    lines: Vec<Line<'static>>,
+    visible: Vec<usize>,
+}
+
+impl ListCache {
+    fn layout(
+        &mut self,
+        state: &AppState,
+        width: u16,
+        height: u16,
+        banner: Option<&str>,
+    ) -> (Vec<Line<'static>>, Vec<usize>) {
+        let data_key = ListDataKey {
+            list_version: state.list_version,
+            query: state.filter_query.clone(),
+            only: [
+                state.only_needs_you,
+                state.only_failing_ci,
+                state.only_review_requested,
+                state.only_sla,
+                state.only_mentioned,
+                state.only_bots,
+            ],
+            ui: state.ui,
+        };
+        let (filtered, cols, custom) = match &self.data {
+            Some((key, filtered, cols, custom)) if *key == data_key => (filtered, cols, custom),
+            _ => {
+                let filtered = state.filtered();
+                let cols = measure_columns(&state.prs, &filtered, state.ui);
+                let custom = custom_cells(&state.prs, &filtered, &state.custom_columns, now_unix());
+                self.view = None;
+                let (_, filtered, cols, custom) =
+                    self.data.insert((data_key, filtered, cols, custom));
+                (&*filtered, &*cols, &*custom)
+            }
+        };
+
+        let view_key = ListViewKey {
+            width,
+            height,
+            selected: state.selected_idx,
+            banner: banner.map(str::to_string),
+            group_rows: state.group_rows,
+            show_snoozed: state.snoozed_open,
+            layout: state.layout,
+            split_focus: state.split_focus,
+            collapsed_blocks: state.collapsed_blocks.clone(),
+            marked: state.marked.clone(),
+        };
+        // Two panes do not fit next to a narrow terminal's single column.
+        let split = state.layout == ListLayout::Split && width >= NARROW_LIST_WIDTH;
+        if self.view.as_ref() != Some(&view_key) && split {
+            (self.lines, self.visible) =
+                build_split_lines(state, width, height, filtered, banner, cols, custom);
+            self.view = Some(view_key);
+        } else if self.view.as_ref() != Some(&view_key) {
+            let by_repo = state.layout == ListLayout::Repos;
+            let blocks = match state.layout {
+                ListLayout::Repos => Some(repo_blocks(&state.prs, filtered, state.snoozed_open)),
+                ListLayout::Topics => Some(topic_blocks(&state.prs, filtered, state.snoozed_open)),
+                ListLayout::Categories | ListLayout::Split => None,
+            };
+            (self.lines, self.visible) = build_list_lines_measured(
+                &state.prs,
+                width,
+                height,
+                state.selected_idx,
+                filtered,
+                banner,
+                // Repo headers make the repo column redundant.
+                UiPrefs {
+                    hide_repo: state.ui.hide_repo || by_repo,
+                    ..state.ui
+                },
+                state.group_rows.then_some(&state.groups),
+                state.snoozed_open,
+                &state.marked,
+                cols,
+                custom,
+                blocks.map(|b| (b, &state.collapsed_blocks)),
+            );
+            self.view = Some(view_key);
+        }
+        (self.lines.clone(), self.visible.clone())
+    }
+}
+
+/// Below this inner width the list drops its columns for one line per PR: category icon,
+/// title and status glyph.
+const NARROW_LIST_WIDTH: u16 = 50;
+/// Selection marker, category icon, status glyph and the spaces between them.
+const NARROW_ROW_CHROME: usize = 8;
+
+/// Smaller terminals get a "too small" notice instead of a mangled frame.
+const MIN_TERMINAL_WIDTH: u16 = 24;
+const MIN_TERMINAL_HEIGHT: u16 = 8;
+
+/// One-shot list layout without the cache.
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+fn build_list_lines(
+    prs: &[UiPr],
+    inner_width: u16,
+    inner_height: u16,
+    selected_visible_idx: usize,
+    filtered: &[usize],
+    filter_banner: Option<&str>,
+    ui: UiPrefs,
+    groups: Option<&RepoGroups>,
+) -> (Vec<Line<'static>>, Vec<usize>) {
+    let cols = measure_columns(prs, filtered, ui);
+    build_list_lines_measured(
+        prs,
+        inner_width,
+        inner_height,
+        selected_visible_idx,
+        filtered,
+        filter_banner,
+        ui,
+        groups,
+        false,
+        &HashSet::new(),
+        &cols,
+        &CustomCells::default(),
+        None,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_list_lines_measured(
+    prs: &[UiPr],
+    inner_width: u16,
+    inner_height: u16,
+    selected_visible_idx: usize,
+    filtered: &[usize],
+    filter_banner: Option<&str>,
+    ui: UiPrefs,
+    groups: Option<&RepoGroups>,
+    show_snoozed: bool,
+    marked: &HashSet<String>,
+    cols: &ColumnMaxes,
+    custom: &CustomCells,
+    by_block: Option<(Blocks, &HashSet<String>)>,
+) -> (Vec<Line<'static>>, Vec<usize>) {
+    // We build rendered lines (headers/dividers/rows/blanks) up to inner_height.
+    // Also track which `prs` indices are visible, in order, so selection works.
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut visible_pr_indices: Vec<usize> = Vec::new();
+
+    fn push_line(lines: &mut Vec<Line<'static>>, inner_height: u16, line: Line<'static>) {
+        if (lines.len() as u16) < inner_height {
+            lines.push(line);
+        }
+    }
+
+    // Optional filter banner at the top.
+    if let Some(banner) = filter_banner {
+        push_line(
+            &mut lines,
+            inner_height,
+            Line::from(Span::styled(
+                truncate_ellipsis(banner, inner_width as usize),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            )),
+        );
+        push_line(&mut lines, inner_height, Line::from(Span::raw("")));
+    }
+
+    // Table-ish column sizing (dynamic; only truncates when the terminal width forces it).
+    // Columns: prefix(2) [repo] [author] [#num] title [custom...] status
+    let iw = inner_width as usize;
+    let prefix_w = 2usize;
+    let sep_w = 2usize; // two spaces between columns
+
+    let ColumnMaxes {
+        repo: max_repo_len,
+        author: max_author_len,
+        num: max_num_len,
+        status: max_status_len,
+    } = *cols;
+
     // Reasonable upper bounds so title keeps most of the width,
     // but allow longer statuses like "CI running (123m)" without truncation.
     let status_w = max_status_len.clamp(12, 34);
@@ -421,19 +2148,26 @@ fn build_list_lines(
     let num_block = if ui.hide_pr_numbers { 0 } else { num_w + sep_w };
 
     // Compute remaining for title and shrink repo if needed.
-    let fixed = prefix_w + repo_block + author_block + num_block + status_w + sep_w;
+    let fixed =
+        prefix_w + repo_block + author_block + num_block + custom.width() + status_w + sep_w;
     let mut title_w = iw.saturating_sub(fixed);
     if title_w < min_title_w {
         let missing = min_title_w - title_w;
         repo_w = repo_w.saturating_sub(missing);
         let repo_block2 = if ui.hide_repo { 0 } else { repo_w + sep_w };
-        let fixed2 = prefix_w + repo_block2 + author_block + num_block + status_w + sep_w;
+        let fixed2 =
+            prefix_w + repo_block2 + author_block + num_block + custom.width() + status_w + sep_w;
         title_w = iw.saturating_sub(fixed2);
     }
     if title_w < 8 {
         // Extremely narrow terminal; keep things from going negative.
         title_w = 8;
     }
+    // Too narrow for columns (tmux side panes): one line per PR instead.
+    let narrow = inner_width < NARROW_LIST_WIDTH;
+    if narrow {
+        title_w = iw.saturating_sub(NARROW_ROW_CHROME);
+    }
 
     fn render_row(
         lines: &mut Vec<Line<'static>>,
@@ -446,6 +2180,9 @@ fn build_list_lines(
         num_w: usize,
         title_w: usize,
         status_w: usize,
+        narrow: bool,
+        custom: &CustomCells,
+        marked: &HashSet<String>,
         idx: usize,
         pr: &UiPr,
     ) {
@@ -456,19 +2193,25 @@ fn build_list_lines(
         visible_pr_indices.push(idx);
 
         let is_selected = visible_idx == selected_visible_idx;
-        // Show pin indicator for pinned PRs
-        let prefix = match (is_selected, pr.is_pinned) {
-            (true, true) => ">📌",
-            (true, false) => "> ",
-            (false, true) => " 📌",
-            (false, false) => "  ",
+        // Show the select-mode mark, else the pin indicator for pinned PRs
+        let prefix = match (is_selected, marked.contains(&pr.pr.pr_key), pr.is_pinned) {
+            (true, true, _) => ">●",
+            (false, true, _) => " ●",
+            (true, false, true) => ">📌",
+            (true, false, false) => "> ",
+            (false, false, true) => " 📌",
+            (false, false, false) => "  ",
         };
 
         let repo = if ui.hide_repo {
             String::new()
         } else {
             pad_right(
-                &truncate_ellipsis(&format!("{}/{}", pr.pr.owner, pr.pr.repo), repo_w),
+                &truncate_cell(
+                    &format!("{}/{}", pr.pr.owner, pr.pr.repo),
+                    repo_w,
+                    ui.keep_tail.repo,
+                ),
                 repo_w,
             )
         };
@@ -476,8 +2219,11 @@ fn build_list_lines(
         let author = if ui.hide_author {
             String::new()
         } else {
-            pad_right(&truncate_ellipsis(&pr.pr.author, author_w), author_w)
-        };
+            pad_right(
+                &truncate_cell(&pr.pr.author, author_w, ui.keep_tail.author),
+                author_w,
+            )
+        };
 
         let num = if ui.hide_pr_numbers {
             String::new()
@@ -488,55 +2234,199 @@ fn build_list_lines(
             )
         };
 
-        let title = pad_right(&truncate_ellipsis(&pr.pr.title, title_w), title_w);
-        let status = pad_right(&truncate_ellipsis(&pr.display_status, status_w), status_w);
+        let title = pad_right(
+            &truncate_cell(&pr.pr.title, title_w, ui.keep_tail.title),
+            title_w,
+        );
+        let status = pad_right(
+            &truncate_cell(&row_status(pr), status_w, ui.keep_tail.status),
+            status_w,
+        );
 
         let base = if is_selected {
             Style::default().add_modifier(Modifier::REVERSED)
-        } else if pr.pr.is_draft {
+        } else if pr.pr.is_draft || pr.opened_since_update || pr.inaccessible_since.is_some() {
+            // Already looked at since the last update, or nothing to act on.
             Style::default().add_modifier(Modifier::DIM)
         } else {
             Style::default()
         };
 
         let status_color = match pr.pr.ci_state {
-            crate::model::CiState::Success => Color::Green,
-            crate::model::CiState::Failure => Color::Red,
-            crate::model::CiState::Running => Color::Yellow,
-            crate::model::CiState::None => Color::Gray,
+            needle_core::model::CiState::Success => Color::Green,
+            needle_core::model::CiState::Failure => Color::Red,
+            needle_core::model::CiState::Running => Color::Yellow,
+            needle_core::model::CiState::None => Color::Gray,
+        };
+
+        // PRs the code host no longer shows are greyed out until their tombstone expires.
+        let fg = |color: Color| {
+            if pr.inaccessible_since.is_some() {
+                base.fg(Color::DarkGray)
+            } else {
+                base.fg(color)
+            }
         };
 
         let mut spans: Vec<Span<'static>> = Vec::new();
-        spans.push(Span::styled(prefix.to_string(), base.fg(Color::White)));
+        spans.push(Span::styled(prefix.to_string(), fg(Color::White)));
+        if narrow {
+            let icon = category_title(pr.category)
+                .split(' ')
+                .next()
+                .unwrap_or_default();
+            let status = row_status(pr);
+            let glyph = status.split(' ').next().unwrap_or_default();
+            spans.push(Span::styled(
+                format!("{} ", pad_right(icon, 2)),
+                fg(Color::White),
+            ));
+            spans.push(Span::styled(title, fg(Color::White)));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                glyph.to_string(),
+                fg(status_color).add_modifier(Modifier::BOLD),
+            ));
+            push_line(lines, inner_height, Line::from(spans));
+            return;
+        }
         if !ui.hide_repo {
-            spans.push(Span::styled(repo, base.fg(Color::Cyan)));
+            spans.push(Span::styled(repo, fg(Color::Cyan)));
             spans.push(Span::raw("  "));
         }
         if !ui.hide_author {
-            spans.push(Span::styled(author, base.fg(Color::Magenta)));
+            spans.push(Span::styled(author, fg(Color::Magenta)));
             spans.push(Span::raw("  "));
         }
         if !ui.hide_pr_numbers {
             spans.push(Span::styled(
                 num,
-                base.fg(Color::Blue).add_modifier(Modifier::BOLD),
+                fg(Color::Blue).add_modifier(Modifier::BOLD),
             ));
             spans.push(Span::raw("  "));
         }
-        spans.push(Span::styled(title, base.fg(Color::White)));
+        spans.push(Span::styled(title, fg(Color::White)));
         spans.push(Span::raw("  "));
+        let cells = custom.rows.get(&idx);
+        for (n, (_, w)) in custom.heads.iter().enumerate() {
+            let cell = cells.and_then(|c| c.get(n)).map_or("", String::as_str);
+            spans.push(Span::styled(
+                pad_right(&truncate_ellipsis(cell, *w), *w),
+                fg(Color::White),
+            ));
+            spans.push(Span::raw("  "));
+        }
         spans.push(Span::styled(
             status,
-            base.fg(status_color).add_modifier(Modifier::BOLD),
+            fg(status_color).add_modifier(Modifier::BOLD),
         ));
         push_line(lines, inner_height, Line::from(spans));
     }
 
+    // Repo and topic layouts: one block per repository or topic instead of the score
+    // sections.
+    if let Some((blocks, collapsed)) = by_block {
+        let mut heads = String::from("  ");
+        if !ui.hide_author {
+            heads.push_str(&pad_right("AUTHOR", author_w));
+            heads.push_str("  ");
+        }
+        if !ui.hide_pr_numbers {
+            heads.push_str(&pad_right("PR", num_w));
+            heads.push_str("  ");
+        }
+        heads.push_str(&pad_right("TITLE", title_w));
+        heads.push_str("  ");
+        for (head, w) in &custom.heads {
+            heads.push_str(&pad_right(head, *w));
+            heads.push_str("  ");
+        }
+        heads.push_str(&pad_right("STATUS", status_w));
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    heads,
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
+
+        for (block, rows) in blocks {
+            if (lines.len() as u16) >= inner_height {
+                break;
+            }
+            let needs = rows
+                .iter()
+                .filter(|&&i| prs[i].category == Category::NeedsYou)
+                .count();
+            let count = if needs > 0 {
+                format!("{} · {needs} need you", rows.len())
+            } else {
+                rows.len().to_string()
+            };
+            let head_style = Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD);
+            if collapsed.contains(&block) {
+                // The folded block is one selectable line standing in for its top PR.
+                let is_selected = visible_pr_indices.len() == selected_visible_idx;
+                visible_pr_indices.push(rows[0]);
+                let style = if is_selected {
+                    head_style.add_modifier(Modifier::REVERSED)
+                } else {
+                    head_style
+                };
+                push_line(
+                    &mut lines,
+                    inner_height,
+                    Line::from(Span::styled(
+                        truncate_ellipsis(
+                            &format!("{}▸ {block} ({count})", if is_selected { ">" } else { " " }),
+                            iw,
+                        ),
+                        style,
+                    )),
+                );
+                continue;
+            }
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    truncate_ellipsis(&format!(" ▾ {block} ({count})"), iw),
+                    head_style,
+                )),
+            );
+            for idx in rows {
+                render_row(
+                    &mut lines,
+                    &mut visible_pr_indices,
+                    inner_height,
+                    selected_visible_idx,
+                    ui,
+                    repo_w,
+                    author_w,
+                    num_w,
+                    title_w,
+                    status_w,
+                    narrow,
+                    custom,
+                    marked,
+                    idx,
+                    &prs[idx],
+                );
+            }
+        }
+        return (lines, visible_pr_indices);
+    }
+
     // PINNED section is rendered first.
     let has_pinned = filtered
         .iter()
         .filter_map(|&i| prs.get(i))
-        .any(|p| p.is_pinned && !p.pr.is_draft);
+        .any(|p| p.is_pinned && !p.snoozed && !in_draft_section(p) && !in_bot_section(p));
 
     if has_pinned && (lines.len() as u16) < inner_height {
         let start_len = lines.len();
@@ -558,37 +2448,43 @@ fn build_list_lines(
                 std::iter::repeat('─').take(iw).collect::<String>(),
             )),
         );
-        push_line(
-            &mut lines,
-            inner_height,
-            Line::from(Span::styled(
-                {
-                    let mut s = String::new();
-                    s.push_str("  ");
-                    if !ui.hide_repo {
-                        s.push_str(&pad_right("REPO", repo_w));
-                        s.push_str("  ");
-                    }
-                    if !ui.hide_author {
-                        s.push_str(&pad_right("AUTHOR", author_w));
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    {
+                        let mut s = String::new();
                         s.push_str("  ");
-                    }
-                    if !ui.hide_pr_numbers {
-                        s.push_str(&pad_right("PR", num_w));
+                        if !ui.hide_repo {
+                            s.push_str(&pad_right("REPO", repo_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_author {
+                            s.push_str(&pad_right("AUTHOR", author_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_pr_numbers {
+                            s.push_str(&pad_right("PR", num_w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("TITLE", title_w));
                         s.push_str("  ");
-                    }
-                    s.push_str(&pad_right("TITLE", title_w));
-                    s.push_str("  ");
-                    s.push_str(&pad_right("STATUS", status_w));
-                    s
-                },
-                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
-            )),
-        );
+                        for (head, w) in &custom.heads {
+                            s.push_str(&pad_right(head, *w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("STATUS", status_w));
+                        s
+                    },
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
 
         for &idx in filtered {
             let Some(pr) = prs.get(idx) else { continue };
-            if !pr.is_pinned || pr.pr.is_draft {
+            if !pr.is_pinned || pr.snoozed || in_draft_section(pr) || in_bot_section(pr) {
                 continue;
             }
             render_row(
@@ -602,6 +2498,9 @@ fn build_list_lines(
                 num_w,
                 title_w,
                 status_w,
+                narrow,
+                custom,
+                marked,
                 idx,
                 pr,
             );
@@ -619,20 +2518,18 @@ fn build_list_lines(
     let has_drafts = filtered
         .iter()
         .filter_map(|&i| prs.get(i))
-        .any(|p| p.pr.is_draft);
+        .any(in_draft_section);
+    let has_bots = filtered
+        .iter()
+        .filter_map(|&i| prs.get(i))
+        .any(in_bot_section);
 
-    let cats = [
-        Category::ReadyToMerge,
-        Category::NeedsYou,
-        Category::Waiting,
-        Category::Stale,
-    ];
-    for cat in cats {
+    for section in SECTIONS {
         // Skip empty sections entirely. Exclude pinned PRs (shown in their own section).
         if !filtered
             .iter()
             .filter_map(|&i| prs.get(i))
-            .any(|p| !p.pr.is_draft && !p.is_pinned && p.category == cat)
+            .any(|p| section.contains(p))
         {
             continue;
         }
@@ -643,10 +2540,7 @@ fn build_list_lines(
         push_line(
             &mut lines,
             inner_height,
-            Line::from(Span::styled(
-                category_title(cat).to_string(),
-                category_style(cat),
-            )),
+            Line::from(Span::styled(section.title().to_string(), section.style())),
         );
         push_line(
             &mut lines,
@@ -655,46 +2549,74 @@ fn build_list_lines(
                 std::iter::repeat('─').take(iw).collect::<String>(),
             )),
         );
-        push_line(
-            &mut lines,
-            inner_height,
-            Line::from(Span::styled(
-                {
-                    let mut s = String::new();
-                    s.push_str("  ");
-                    if !ui.hide_repo {
-                        s.push_str(&pad_right("REPO", repo_w));
-                        s.push_str("  ");
-                    }
-                    if !ui.hide_author {
-                        s.push_str(&pad_right("AUTHOR", author_w));
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    {
+                        let mut s = String::new();
                         s.push_str("  ");
-                    }
-                    if !ui.hide_pr_numbers {
-                        s.push_str(&pad_right("PR", num_w));
+                        if !ui.hide_repo {
+                            s.push_str(&pad_right("REPO", repo_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_author {
+                            s.push_str(&pad_right("AUTHOR", author_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_pr_numbers {
+                            s.push_str(&pad_right("PR", num_w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("TITLE", title_w));
                         s.push_str("  ");
-                    }
-                    s.push_str(&pad_right("TITLE", title_w));
-                    s.push_str("  ");
-                    s.push_str(&pad_right("STATUS", status_w));
-                    s
-                },
-                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
-            )),
-        );
+                        for (head, w) in &custom.heads {
+                            s.push_str(&pad_right(head, *w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("STATUS", status_w));
+                        s
+                    },
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
 
-        // Rows in this category (exclude pinned PRs, shown in their own section)
-        for &idx in filtered {
-            let Some(pr) = prs.get(idx) else { continue };
-            if pr.pr.is_draft || pr.is_pinned {
-                continue;
-            }
-            if pr.category != cat {
-                continue;
-            }
+        // Rows in this section (exclude pinned PRs, shown in their own section)
+        let mut rows: Vec<usize> = filtered
+            .iter()
+            .copied()
+            .filter(|&idx| prs.get(idx).is_some_and(|pr| section.contains(pr)))
+            .collect();
+        let rank_of = |idx: usize| {
+            let pr = &prs[idx].pr;
+            groups
+                .filter(|g| !g.is_empty())
+                .map(|g| g.rank_of(&format!("{}/{}", pr.owner, pr.repo)))
+        };
+        // Stable sort keeps score order within each group.
+        rows.sort_by_key(|&idx| rank_of(idx));
+        let mut current_group: Option<usize> = None;
+        for idx in rows {
+            let pr = &prs[idx];
             if (lines.len() as u16) >= inner_height {
                 break;
             }
+            let rank = rank_of(idx);
+            if let (Some(g), Some(r)) = (groups, rank.filter(|_| rank != current_group)) {
+                current_group = rank;
+                push_line(
+                    &mut lines,
+                    inner_height,
+                    Line::from(Span::styled(
+                        format!("  ▸ {}", g.label(r)),
+                        Style::default()
+                            .fg(Color::LightCyan)
+                            .add_modifier(Modifier::ITALIC),
+                    )),
+                );
+            }
             render_row(
                 &mut lines,
                 &mut visible_pr_indices,
@@ -706,6 +2628,9 @@ fn build_list_lines(
                 num_w,
                 title_w,
                 status_w,
+                narrow,
+                custom,
+                marked,
                 idx,
                 pr,
             );
@@ -743,37 +2668,128 @@ fn build_list_lines(
                 std::iter::repeat('─').take(iw).collect::<String>(),
             )),
         );
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    {
+                        let mut s = String::new();
+                        s.push_str("  ");
+                        if !ui.hide_repo {
+                            s.push_str(&pad_right("REPO", repo_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_author {
+                            s.push_str(&pad_right("AUTHOR", author_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_pr_numbers {
+                            s.push_str(&pad_right("PR", num_w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("TITLE", title_w));
+                        s.push_str("  ");
+                        for (head, w) in &custom.heads {
+                            s.push_str(&pad_right(head, *w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("STATUS", status_w));
+                        s
+                    },
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
+
+        for &idx in filtered {
+            let Some(pr) = prs.get(idx) else { continue };
+            if !in_draft_section(pr) {
+                continue;
+            }
+            render_row(
+                &mut lines,
+                &mut visible_pr_indices,
+                inner_height,
+                selected_visible_idx,
+                ui,
+                repo_w,
+                author_w,
+                num_w,
+                title_w,
+                status_w,
+                narrow,
+                custom,
+                marked,
+                idx,
+                pr,
+            );
+            if (lines.len() as u16) >= inner_height {
+                break;
+            }
+        }
+
+        if lines.len() != start_len && (lines.len() as u16) < inner_height {
+            push_line(&mut lines, inner_height, Line::from(Span::raw("")));
+        }
+    }
+
+    // BOTS section (`bots = "separate_section"`), after the drafts.
+    if has_bots && (lines.len() as u16) < inner_height {
+        let start_len = lines.len();
+
         push_line(
             &mut lines,
             inner_height,
             Line::from(Span::styled(
-                {
-                    let mut s = String::new();
-                    s.push_str("  ");
-                    if !ui.hide_repo {
-                        s.push_str(&pad_right("REPO", repo_w));
-                        s.push_str("  ");
-                    }
-                    if !ui.hide_author {
-                        s.push_str(&pad_right("AUTHOR", author_w));
-                        s.push_str("  ");
-                    }
-                    if !ui.hide_pr_numbers {
-                        s.push_str(&pad_right("PR", num_w));
-                        s.push_str("  ");
-                    }
-                    s.push_str(&pad_right("TITLE", title_w));
-                    s.push_str("  ");
-                    s.push_str(&pad_right("STATUS", status_w));
-                    s
-                },
-                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                "🤖 BOTS".to_string(),
+                Style::default()
+                    .fg(Color::Gray)
+                    .add_modifier(Modifier::BOLD),
             )),
         );
+        push_line(
+            &mut lines,
+            inner_height,
+            Line::from(Span::raw("─".repeat(iw))),
+        );
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    {
+                        let mut s = String::new();
+                        s.push_str("  ");
+                        if !ui.hide_repo {
+                            s.push_str(&pad_right("REPO", repo_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_author {
+                            s.push_str(&pad_right("AUTHOR", author_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_pr_numbers {
+                            s.push_str(&pad_right("PR", num_w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("TITLE", title_w));
+                        s.push_str("  ");
+                        for (head, w) in &custom.heads {
+                            s.push_str(&pad_right(head, *w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("STATUS", status_w));
+                        s
+                    },
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
 
         for &idx in filtered {
             let Some(pr) = prs.get(idx) else { continue };
-            if !pr.pr.is_draft {
+            if !in_bot_section(pr) {
                 continue;
             }
             render_row(
@@ -787,6 +2803,9 @@ fn build_list_lines(
                 num_w,
                 title_w,
                 status_w,
+                narrow,
+                custom,
+                marked,
                 idx,
                 pr,
             );
@@ -800,26 +2819,216 @@ fn build_list_lines(
         }
     }
 
+    // SNOOZED section, last and collapsed to its header unless `show_snoozed`.
+    let snoozed_count = filtered
+        .iter()
+        .filter_map(|&i| prs.get(i))
+        .filter(|p| p.snoozed)
+        .count();
+    if snoozed_count > 0 && (lines.len() as u16) < inner_height {
+        let hint = if show_snoozed {
+            "Z to hide"
+        } else {
+            "Z to show"
+        };
+        push_line(
+            &mut lines,
+            inner_height,
+            Line::from(vec![
+                Span::styled(
+                    format!("💤 SNOOZED ({snoozed_count})"),
+                    Style::default()
+                        .fg(Color::Gray)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {hint}"),
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                ),
+            ]),
+        );
+    }
+    if snoozed_count > 0 && show_snoozed && (lines.len() as u16) < inner_height {
+        push_line(
+            &mut lines,
+            inner_height,
+            Line::from(Span::raw("─".repeat(iw))),
+        );
+        if !narrow {
+            push_line(
+                &mut lines,
+                inner_height,
+                Line::from(Span::styled(
+                    {
+                        let mut s = String::new();
+                        s.push_str("  ");
+                        if !ui.hide_repo {
+                            s.push_str(&pad_right("REPO", repo_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_author {
+                            s.push_str(&pad_right("AUTHOR", author_w));
+                            s.push_str("  ");
+                        }
+                        if !ui.hide_pr_numbers {
+                            s.push_str(&pad_right("PR", num_w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("TITLE", title_w));
+                        s.push_str("  ");
+                        for (head, w) in &custom.heads {
+                            s.push_str(&pad_right(head, *w));
+                            s.push_str("  ");
+                        }
+                        s.push_str(&pad_right("STATUS", status_w));
+                        s
+                    },
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )),
+            );
+        }
+
+        for &idx in filtered {
+            let Some(pr) = prs.get(idx) else { continue };
+            if !pr.snoozed {
+                continue;
+            }
+            render_row(
+                &mut lines,
+                &mut visible_pr_indices,
+                inner_height,
+                selected_visible_idx,
+                ui,
+                repo_w,
+                author_w,
+                num_w,
+                title_w,
+                status_w,
+                narrow,
+                custom,
+                marked,
+                idx,
+                pr,
+            );
+            if (lines.len() as u16) >= inner_height {
+                break;
+            }
+        }
+    }
+
     (lines, visible_pr_indices)
 }
 
-fn build_footer(
+/// The split layout: "YOUR PRS" on top and "NEEDS YOUR REVIEW" below, each half the
+/// height with the usual sections inside. Only the focused pane has a selection; the
+/// returned visible rows are that pane's.
+fn build_split_lines(
+    state: &AppState,
     inner_width: u16,
-    mode: ViewMode,
-    refreshing: bool,
-    shimmer_phase: u8,
-    filter_mode: bool,
-) -> (Line<'static>, usize) {
-    #[derive(Clone)]
-    struct Seg {
-        text: String,
-        style: Style,
+    inner_height: u16,
+    filtered: &[usize],
+    filter_banner: Option<&str>,
+    cols: &ColumnMaxes,
+    custom: &CustomCells,
+) -> (Vec<Line<'static>>, Vec<usize>) {
+    let prs = &state.prs;
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    if let Some(banner) = filter_banner {
+        lines.push(Line::from(Span::styled(
+            truncate_ellipsis(banner, inner_width as usize),
+            Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+        )));
     }
-    fn keycap(k: &str) -> Seg {
-        Seg {
-            text: format!("[{k}]"),
-            style: Style::default()
-                .fg(Color::Cyan)
+    let room = inner_height.saturating_sub(lines.len() as u16);
+    let top = room / 2;
+    let mut visible = Vec::new();
+    for (pane, height) in [(SplitPane::Mine, top), (SplitPane::Review, room - top)] {
+        if height == 0 {
+            continue;
+        }
+        let rows: Vec<usize> = filtered
+            .iter()
+            .copied()
+            .filter(|&i| prs.get(i).is_some_and(|p| pane.holds(p)))
+            .collect();
+        let focused = pane == state.split_focus;
+        let style = if focused {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD)
+        };
+        let marker = if focused { "▶" } else { " " };
+        lines.push(Line::from(Span::styled(
+            truncate_ellipsis(
+                &format!("{marker} {} ({})", pane.title(), rows.len()),
+                inner_width as usize,
+            ),
+            style,
+        )));
+        let body_height = height - 1;
+        let start = lines.len();
+        if rows.is_empty() {
+            if body_height > 0 {
+                lines.push(Line::from(Span::styled(
+                    "  Nothing here".to_string(),
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+                )));
+            }
+        } else {
+            let (pane_lines, pane_visible) = build_list_lines_measured(
+                prs,
+                inner_width,
+                body_height,
+                if focused {
+                    state.selected_idx
+                } else {
+                    usize::MAX
+                },
+                &rows,
+                None,
+                state.ui,
+                state.group_rows.then_some(&state.groups),
+                state.snoozed_open,
+                &state.marked,
+                cols,
+                custom,
+                None,
+            );
+            lines.extend(pane_lines);
+            if focused {
+                visible = pane_visible;
+            }
+        }
+        // Pad the top pane so the bottom one stays put as rows come and go.
+        while lines.len() < start + body_height as usize {
+            lines.push(Line::from(""));
+        }
+    }
+    (lines, visible)
+}
+
+fn build_footer(
+    inner_width: u16,
+    mode: ViewMode,
+    refreshing: bool,
+    shimmer_phase: u8,
+    color_mode: ColorMode,
+    filter_mode: bool,
+) -> (Line<'static>, usize) {
+    #[derive(Clone)]
+    struct Seg {
+        text: String,
+        style: Style,
+    }
+    fn keycap(k: &str) -> Seg {
+        Seg {
+            text: format!("[{k}]"),
+            style: Style::default()
+                .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         }
     }
@@ -836,13 +3045,14 @@ fn build_footer(
         }
     }
 
-    fn shimmer(phase: u8) -> String {
+    fn shimmer(phase: u8, color_mode: ColorMode) -> String {
         // 10-column shimmer bar with a moving bright block.
         let w = 10usize;
         let pos = (phase as usize) % w;
+        let (block, track) = color_mode.shimmer_glyphs();
         let mut s = String::with_capacity(w);
         for i in 0..w {
-            s.push(if i == pos { '▓' } else { '░' });
+            s.push(if i == pos { block } else { track });
         }
         s
     }
@@ -870,6 +3080,15 @@ fn build_footer(
                     keycap("Ctrl+v"),
                     label("review"),
                     sep(),
+                    keycap("Ctrl+s"),
+                    label("sla"),
+                    sep(),
+                    keycap("Ctrl+m"),
+                    label("mentions"),
+                    sep(),
+                    keycap("Ctrl+b"),
+                    label("bots"),
+                    sep(),
                     keycap("Ctrl+x"),
                     label("clear"),
                     sep(),
@@ -884,7 +3103,7 @@ fn build_footer(
                     keycap("r"),
                     if refreshing {
                         Seg {
-                            text: format!("refreshing {}", shimmer(shimmer_phase)),
+                            text: format!("refreshing {}", shimmer(shimmer_phase, color_mode)),
                             style: Style::default()
                                 .fg(Color::Yellow)
                                 .add_modifier(Modifier::BOLD),
@@ -924,10 +3143,16 @@ fn build_footer(
                 keycap("f"),
                 label("open failing"),
                 sep(),
+                keycap("m"),
+                label("merge"),
+                sep(),
+                keycap("c"),
+                label("comment"),
+                sep(),
                 keycap("r"),
                 if refreshing {
                     Seg {
-                        text: format!("refreshing {}", shimmer(shimmer_phase)),
+                        text: format!("refreshing {}", shimmer(shimmer_phase, color_mode)),
                         style: Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD),
@@ -965,7 +3190,7 @@ fn build_footer(
                 keycap("r"),
                 if refreshing {
                     Seg {
-                        text: format!("refreshing {}", shimmer(shimmer_phase)),
+                        text: format!("refreshing {}", shimmer(shimmer_phase, color_mode)),
                         style: Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD),
@@ -1002,7 +3227,7 @@ fn build_footer(
                 keycap("r"),
                 if refreshing {
                     Seg {
-                        text: format!("refreshing {}", shimmer(shimmer_phase)),
+                        text: format!("refreshing {}", shimmer(shimmer_phase, color_mode)),
                         style: Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD),
@@ -1061,15 +3286,169 @@ fn build_footer(
     (Line::from(spans), final_width)
 }
 
+/// Short reviewer verdict marker for the preview popup.
+fn reviewer_marker(state: &str) -> String {
+    match state {
+        "APPROVED" => "✅".to_string(),
+        "CHANGES_REQUESTED" => "❌".to_string(),
+        "COMMENTED" => "💬".to_string(),
+        "REQUESTED" => "⏳".to_string(),
+        other => other.to_lowercase().replace('_', " "),
+    }
+}
+
+/// Reviewers in the order the details view lists them: pending requests (who the PR is
+/// waiting on) first, then requested changes, approvals and the rest.
+fn reviewers_by_verdict(pr: &Pr) -> Vec<&Reviewer> {
+    let rank = |state: &str| match state {
+        "REQUESTED" => 0,
+        "CHANGES_REQUESTED" => 1,
+        "APPROVED" => 2,
+        _ => 3,
+    };
+    let mut out: Vec<&Reviewer> = pr.reviewers.iter().collect();
+    out.sort_by_key(|r| rank(&r.state));
+    out
+}
+
+/// What a reviewer's state means, for the details view.
+fn reviewer_verdict(state: &str) -> String {
+    match state {
+        "REQUESTED" => "waiting for their review".to_string(),
+        "CHANGES_REQUESTED" => "requested changes".to_string(),
+        "APPROVED" => "approved".to_string(),
+        "COMMENTED" => "commented".to_string(),
+        other => other.to_lowercase().replace('_', " "),
+    }
+}
+
+/// Body of the quick preview popup (`Space` on a list row): who, labels, reviewers,
+/// failing checks, and the start of the description.
+fn build_preview_lines(pr: &UiPr, inner_width: u16) -> Vec<Line<'static>> {
+    let iw = inner_width as usize;
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let field = |k: &str, v: String, color: Color| {
+        let key = format!("{k}: ");
+        let val_w = iw.saturating_sub(UnicodeWidthStr::width(key.as_str()));
+        Line::from(vec![
+            Span::styled(key, key_style),
+            Span::styled(truncate_ellipsis(&v, val_w), Style::default().fg(color)),
+        ])
+    };
+
+    let mut out: Vec<Line<'static>> = vec![
+        Line::from(Span::styled(
+            truncate_ellipsis(&pr.pr.title, iw),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            truncate_ellipsis(
+                &format!(
+                    "{}/{}#{} by {} · {}",
+                    pr.pr.owner,
+                    pr.pr.repo,
+                    pr.pr.number,
+                    pr.pr.author,
+                    row_status(pr)
+                ),
+                iw,
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(""),
+    ];
+
+    if !pr.pr.labels.is_empty() {
+        out.push(field("Labels", pr.pr.labels.join(", "), Color::Magenta));
+    }
+    let reviewers = if pr.pr.reviewers.is_empty() {
+        "none".to_string()
+    } else {
+        pr.pr
+            .reviewers
+            .iter()
+            .map(|r| format!("{} {}", r.login, reviewer_marker(&r.state)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    out.push(field("Reviewers", reviewers, Color::White));
+    let failing: Vec<&str> = pr
+        .pr
+        .ci_checks
+        .iter()
+        .filter(|c| c.state.is_failure())
+        .map(|c| c.name.as_str())
+        .collect();
+    if !failing.is_empty() {
+        out.push(field("Failing", failing.join(", "), Color::Red));
+    }
+
+    out.push(Line::from(""));
+    match pr.pr.body_excerpt.as_deref() {
+        Some(body) => out.extend(body.lines().map(|l| {
+            Line::from(Span::styled(
+                truncate_ellipsis(l, iw),
+                Style::default().fg(Color::White),
+            ))
+        })),
+        None => out.push(Line::from(Span::styled(
+            "(no description)",
+            Style::default().fg(Color::Gray),
+        ))),
+    }
+    out
+}
+
+/// The requested-changes checklist shown in details view for the viewer's own PRs.
+#[derive(Debug, Clone, Copy, Default)]
+struct Checklist<'a> {
+    /// Thread keys checked off for this PR.
+    addressed: Option<&'a HashSet<String>>,
+    selected: usize,
+}
+
+impl Checklist<'_> {
+    /// Resolved on GitHub, or checked off locally.
+    fn is_addressed(&self, thread: &ReviewThread) -> bool {
+        thread.is_resolved || self.addressed.is_some_and(|a| a.contains(&thread.key()))
+    }
+}
+
+/// The details view checks list: rows from [`ci_rows`] and the selected one.
+#[derive(Debug, Clone, Copy)]
+struct CiList<'a> {
+    rows: &'a [CiRow],
+    selected: usize,
+    /// Failures per check name on the head commit, across refreshes.
+    failures: Option<&'a HashMap<String, CheckFailure>>,
+}
+
+/// Threads the checklist lists: change requests on the viewer's own PR.
+fn checklist_threads(pr: &UiPr) -> Vec<&ReviewThread> {
+    if pr.pr.is_viewer_author {
+        pr.pr.change_request_threads()
+    } else {
+        Vec::new()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn build_details_lines(
     pr: &UiPr,
     inner_width: u16,
     inner_height: u16,
-    ci_selected: usize,
+    ci: CiList<'_>,
+    conversation_scroll: &mut usize,
+    checklist: Checklist,
+    quick_links: &[(String, String)],
+    now: i64,
 ) -> Vec<Line<'static>> {
     let iw = inner_width as usize;
     let mut out: Vec<Line<'static>> = Vec::new();
-    let now = now_unix();
 
     // Title line
     out.push(Line::from(Span::styled(
@@ -1083,7 +3462,7 @@ fn build_details_lines(
         Style::default().fg(Color::Gray),
     )));
 
-    let rows = [
+    let mut rows = vec![
         ("Repo", format!("{}/{}", pr.pr.owner, pr.pr.repo)),
         ("PR", format!("#{}", pr.pr.number)),
         ("Author", pr.pr.author.clone()),
@@ -1121,6 +3500,18 @@ fn build_details_lines(
                 .unwrap_or_else(|| "unknown".to_string()),
         ),
     ];
+    if let Some(milestone) = &pr.pr.milestone {
+        rows.push(("Milestone", milestone.clone()));
+    }
+    if let Some(status) = &pr.pr.project_status {
+        rows.push(("Project", status.clone()));
+    }
+    if let Some(delta) = pr.author_priority {
+        rows.push((
+            "Priority",
+            format!("{delta:+} ([scoring.authors] rule for {})", pr.pr.author),
+        ));
+    }
 
     for (k, v) in rows {
         if (out.len() as u16) >= inner_height {
@@ -1144,6 +3535,117 @@ fn build_details_lines(
         ]));
     }
 
+    if pr.force_pushed_since_view && (out.len() as u16) < inner_height {
+        out.push(Line::from(Span::styled(
+            truncate_ellipsis("⚠️ force-pushed since your last view", iw),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    // Threads the viewer opened as a reviewer and that are still open.
+    let mine = pr.pr.my_unresolved_threads().len();
+    if mine > 0 && (out.len() as u16) < inner_height {
+        let waiting = pr.pr.my_threads_awaiting_reply();
+        let mut text = format!(
+            "🧵 {mine} unresolved thread{} you started",
+            if mine == 1 { "" } else { "s" }
+        );
+        if waiting > 0 {
+            text.push_str(&format!(" · {waiting} waiting on you"));
+        }
+        out.push(Line::from(Span::styled(
+            truncate_ellipsis(&text, iw),
+            Style::default().fg(if waiting > 0 {
+                Color::Magenta
+            } else {
+                Color::Yellow
+            }),
+        )));
+    }
+
+    // Reviewers: who the PR is waiting on, and who approved or requested changes
+    let reviewers = reviewers_by_verdict(&pr.pr);
+    if !reviewers.is_empty() && (out.len() as u16) + 3 < inner_height {
+        let count = |state: &str| reviewers.iter().filter(|r| r.state == state).count();
+        let summary: Vec<String> = [
+            (count("REQUESTED"), "waiting"),
+            (count("APPROVED"), "approved"),
+            (count("CHANGES_REQUESTED"), "changes requested"),
+        ]
+        .into_iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, what)| format!("{n} {what}"))
+        .collect();
+        out.push(Line::from(Span::raw("")));
+        out.push(Line::from(vec![
+            Span::styled(
+                "REVIEWERS".to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                truncate_ellipsis(&format!("  {}", summary.join(" · ")), iw.saturating_sub(9)),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+        out.push(Line::from(Span::styled(
+            "─".repeat(iw),
+            Style::default().fg(Color::Gray),
+        )));
+        for r in reviewers {
+            if (out.len() as u16) >= inner_height {
+                break;
+            }
+            let color = match r.state.as_str() {
+                "REQUESTED" => Color::Yellow,
+                "CHANGES_REQUESTED" => Color::Red,
+                "APPROVED" => Color::Green,
+                _ => Color::Gray,
+            };
+            let head = format!("  {} {}  ", reviewer_marker(&r.state), r.login);
+            let head_w = UnicodeWidthStr::width(head.as_str());
+            out.push(Line::from(vec![
+                Span::styled(head, Style::default().fg(Color::White)),
+                Span::styled(
+                    truncate_ellipsis(&reviewer_verdict(&r.state), iw.saturating_sub(head_w)),
+                    Style::default().fg(color),
+                ),
+            ]));
+        }
+    }
+
+    // Quick links, opened with their digit
+    if !quick_links.is_empty() && (out.len() as u16) + 3 < inner_height {
+        out.push(Line::from(Span::raw("")));
+        out.push(Line::from(Span::styled(
+            "LINKS".to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        out.push(Line::from(Span::styled(
+            "─".repeat(iw),
+            Style::default().fg(Color::Gray),
+        )));
+        for (idx, (name, url)) in quick_links.iter().enumerate() {
+            if (out.len() as u16) >= inner_height {
+                break;
+            }
+            let head = format!("  {} {name}  ", idx + 1);
+            let head_w = UnicodeWidthStr::width(head.as_str());
+            out.push(Line::from(vec![
+                Span::styled(head, Style::default().fg(Color::White)),
+                Span::styled(
+                    truncate_ellipsis(url, iw.saturating_sub(head_w)),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+        }
+    }
+
     // Merge blockers section
     if let Some(blockers) = &pr.pr.merge_blockers {
         if (out.len() as u16) < inner_height {
@@ -1173,11 +3675,88 @@ fn build_details_lines(
             } else {
                 ("•", Color::Gray)
             };
-            out.push(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("  {} ", icon), Style::default().fg(color)),
-                Span::styled(desc, Style::default().fg(color)),
+                Span::styled(desc.clone(), Style::default().fg(color)),
+            ];
+            if desc.starts_with("Branch behind") && update_branch_readiness(&pr.pr).is_ok() {
+                spans.push(Span::styled(
+                    " · u update branch",
+                    Style::default().fg(Color::Gray),
+                ));
+            }
+            out.push(Line::from(spans));
+        }
+    }
+
+    // Requested changes: one checkbox per thread, so the author knows when to re-request review
+    let threads = checklist_threads(pr);
+    if !threads.is_empty() && (out.len() as u16) + 3 < inner_height {
+        let done = threads.iter().filter(|t| checklist.is_addressed(t)).count();
+        let (summary, color) = if done == threads.len() {
+            (
+                format!("{done}/{} addressed · re-request review", threads.len()),
+                Color::Green,
+            )
+        } else {
+            (format!("{done}/{} addressed", threads.len()), Color::Yellow)
+        };
+        out.push(Line::from(Span::raw("")));
+        out.push(Line::from(vec![
+            Span::styled(
+                "CHANGES REQUESTED".to_string(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                truncate_ellipsis(&format!("  {summary}"), iw.saturating_sub(17)),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        out.push(Line::from(Span::styled(
+            "─".repeat(iw),
+            Style::default().fg(Color::Gray),
+        )));
+        let selected = checklist.selected.min(threads.len() - 1);
+        for (idx, t) in threads.iter().enumerate() {
+            if (out.len() as u16) >= inner_height {
+                break;
+            }
+            let is_sel = idx == selected;
+            let base = if is_sel {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            let (mark, col) = if checklist.is_addressed(t) {
+                ("☑", Color::Green)
+            } else {
+                ("☐", Color::White)
+            };
+            let first = t.comments.first();
+            let mut text = format!(
+                "{} · @{}: {}",
+                t.path.as_deref().unwrap_or("(no file)"),
+                first.map_or("", |c| c.author.as_str()),
+                first.and_then(|c| c.body.lines().next()).unwrap_or(""),
+            );
+            if t.is_resolved {
+                text.push_str(" (resolved)");
+            }
+            out.push(Line::from(vec![
+                Span::styled(if is_sel { "> " } else { "  " }, base.fg(Color::White)),
+                Span::styled(
+                    format!("{mark} "),
+                    base.fg(col).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(truncate_ellipsis(&text, iw.saturating_sub(4)), base.fg(col)),
             ]));
         }
+        if (out.len() as u16) < inner_height {
+            out.push(Line::from(Span::styled(
+                "[/]: select   x: mark addressed".to_string(),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            )));
+        }
     }
 
     // CI checks list
@@ -1185,8 +3764,13 @@ fn build_details_lines(
         out.push(Line::from(Span::raw("")));
     }
     if (out.len() as u16) < inner_height {
+        let heading = if pr.pr.ci_from_previous_commit {
+            "CI CHECKS (previous commit; the new one has none yet)"
+        } else {
+            "CI CHECKS"
+        };
         out.push(Line::from(Span::styled(
-            "CI CHECKS".to_string(),
+            heading.to_string(),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -1213,9 +3797,9 @@ fn build_details_lines(
         let mut n_other = 0usize;
         for c in &pr.pr.ci_checks {
             match c.state {
-                crate::model::CiCheckState::Failure => n_fail += 1,
-                crate::model::CiCheckState::Running => n_run += 1,
-                crate::model::CiCheckState::Success => n_ok += 1,
+                needle_core::model::CiCheckState::Failure => n_fail += 1,
+                needle_core::model::CiCheckState::Running => n_run += 1,
+                needle_core::model::CiCheckState::Success => n_ok += 1,
                 _ => n_other += 1,
             }
         }
@@ -1241,33 +3825,73 @@ fn build_details_lines(
             )));
         }
 
-        for (idx, c) in pr.pr.ci_checks.iter().enumerate() {
+        for (row_idx, row) in ci.rows.iter().enumerate() {
             if (out.len() as u16) >= inner_height {
                 break;
             }
-            let is_sel = idx == ci_selected;
+            let is_sel = row_idx == ci.selected;
             let prefix = if is_sel { "> " } else { "  " };
-            let (icon, col) = match c.state {
-                crate::model::CiCheckState::Success => ("✅", Color::Green),
-                crate::model::CiCheckState::Failure => ("❌", Color::Red),
-                crate::model::CiCheckState::Running => ("🟡", Color::Yellow),
-                crate::model::CiCheckState::Neutral => ("➖", Color::Gray),
-                crate::model::CiCheckState::None => ("⏺", Color::Gray),
+            let mut history = None;
+            let (state, started_at, label, indent) = match row {
+                CiRow::Check { idx, nested } => {
+                    let Some(c) = pr.pr.ci_checks.get(*idx) else {
+                        continue;
+                    };
+                    let indent = if *nested { "   " } else { "" };
+                    history = ci.failures.and_then(|f| f.get(&c.name));
+                    (c.state.clone(), c.started_at_unix, c.name.clone(), indent)
+                }
+                CiRow::Group {
+                    label,
+                    members,
+                    expanded,
+                } => {
+                    let checks = || members.iter().filter_map(|&i| pr.pr.ci_checks.get(i));
+                    let started_at = checks()
+                        .filter(|c| matches!(c.state, CiCheckState::Running))
+                        .filter_map(|c| c.started_at_unix)
+                        .min();
+                    let arrow = if *expanded { "▾" } else { "▸" };
+                    (
+                        aggregate_check_state(checks()),
+                        started_at,
+                        format!("{arrow} {label} ({})", members.len()),
+                        "",
+                    )
+                }
+            };
+            let (icon, col) = match state {
+                CiCheckState::Success => ("✅", Color::Green),
+                CiCheckState::Failure => ("❌", Color::Red),
+                CiCheckState::Running => ("🟡", Color::Yellow),
+                CiCheckState::Neutral => ("➖", Color::Gray),
+                CiCheckState::None => ("⏺", Color::Gray),
             };
             let mut suffix = String::new();
-            if matches!(c.state, crate::model::CiCheckState::Running) {
-                if let Some(start) = c.started_at_unix {
+            if matches!(state, CiCheckState::Running) {
+                if let Some(start) = started_at {
                     suffix = format!(" ({})", human_duration(now.saturating_sub(start)));
                 }
             }
-            let name = truncate_ellipsis(&format!("{}{}", c.name, suffix), iw.saturating_sub(6));
+            // Tells a persistent breakage from a one-off flake.
+            if let Some(h) = history.filter(|_| state.is_failure()) {
+                suffix = format!(
+                    " (failed ×{}, first {})",
+                    h.failures,
+                    human_age(now, h.first_failed_at)
+                );
+            }
+            let name = truncate_ellipsis(
+                &format!("{label}{suffix}"),
+                iw.saturating_sub(6 + indent.len()),
+            );
             let base = if is_sel {
                 Style::default().add_modifier(Modifier::REVERSED)
             } else {
                 Style::default()
             };
             out.push(Line::from(vec![
-                Span::styled(prefix.to_string(), base.fg(Color::White)),
+                Span::styled(format!("{prefix}{indent}"), base.fg(Color::White)),
                 Span::styled(
                     format!("{icon} "),
                     base.fg(col).add_modifier(Modifier::BOLD),
@@ -1275,6 +3899,13 @@ fn build_details_lines(
                 Span::styled(name, base.fg(Color::White)),
             ]));
         }
+        let has_groups = ci.rows.iter().any(|r| matches!(r, CiRow::Group { .. }));
+        if has_groups && (out.len() as u16) < inner_height {
+            out.push(Line::from(Span::styled(
+                "Enter on a group: expand/collapse its checks".to_string(),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            )));
+        }
         if (out.len() as u16) < inner_height {
             out.push(Line::from(Span::styled(
                 "Enter: open selected check   f: open first failing check".to_string(),
@@ -1283,26 +3914,203 @@ fn build_details_lines(
         }
     }
 
+    // Conversation: scrollable, takes whatever height is left
+    if (out.len() as u16) + 3 < inner_height {
+        out.push(Line::from(Span::raw("")));
+        out.push(Line::from(Span::styled(
+            "CONVERSATION".to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        out.push(Line::from(Span::styled(
+            "─".repeat(iw),
+            Style::default().fg(Color::Gray),
+        )));
+        let convo = conversation_lines(pr, iw, now);
+        let room = (inner_height as usize).saturating_sub(out.len());
+        if convo.len() <= room {
+            *conversation_scroll = 0;
+            out.extend(convo);
+        } else {
+            // Keep the last row for the scroll hint.
+            let window = room.saturating_sub(1).max(1);
+            *conversation_scroll = (*conversation_scroll).min(convo.len() - window);
+            let start = *conversation_scroll;
+            let total = convo.len();
+            out.extend(convo.into_iter().skip(start).take(window));
+            out.push(Line::from(Span::styled(
+                truncate_ellipsis(
+                    &format!(
+                        "PgUp/PgDn: scroll conversation ({}-{} of {total})",
+                        start + 1,
+                        start + window
+                    ),
+                    iw,
+                ),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            )));
+        }
+    }
+
     out
 }
 
-fn clamp_selection(selected: &mut usize, visible_len: usize) {
-    if visible_len == 0 {
-        *selected = 0;
-    } else if *selected >= visible_len {
-        *selected = visible_len - 1;
+/// Lines scrolled per PgUp/PgDn in the details conversation.
+const CONVERSATION_SCROLL_STEP: usize = 5;
+
+/// Conversation comments and review threads, oldest first, wrapped to `width`.
+fn conversation_lines(pr: &UiPr, width: usize, now: i64) -> Vec<Line<'static>> {
+    let comments = &pr.pr.comments;
+    let threads = &pr.pr.review_threads;
+    let mut out: Vec<Line<'static>> = Vec::new();
+    if comments.is_empty() && threads.is_empty() {
+        out.push(Line::from(Span::styled(
+            "No comments".to_string(),
+            Style::default().fg(Color::Gray),
+        )));
+        return out;
     }
-}
 
-fn help_lines() -> Vec<Line<'static>> {
-    let mins = |s: i64| s / 60;
-    let hours = |s: i64| s / 3600;
-    vec![
+    let unresolved = pr.pr.unresolved_threads();
+    let mut summary = format!("{} comments · {} threads", comments.len(), threads.len());
+    if unresolved > 0 {
+        summary.push_str(&format!(" ({unresolved} unresolved)"));
+    }
+    out.push(Line::from(Span::styled(
+        truncate_ellipsis(&summary, width),
+        Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+    )));
+
+    enum Entry<'a> {
+        Comment(&'a Comment),
+        Thread(&'a ReviewThread),
+    }
+    let started = |e: &Entry| match e {
+        Entry::Comment(c) => c.created_at_unix,
+        Entry::Thread(t) => t.comments.first().map_or(0, |c| c.created_at_unix),
+    };
+    let mut entries: Vec<Entry> = comments
+        .iter()
+        .map(Entry::Comment)
+        .chain(threads.iter().map(Entry::Thread))
+        .collect();
+    entries.sort_by_key(|e| started(e));
+
+    let author_line = |c: &Comment, indent: usize| {
         Line::from(vec![
+            Span::raw(" ".repeat(indent)),
             Span::styled(
-                "needle",
+                format!("@{}", c.author),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" · {}", human_age(now, c.created_at_unix)),
+                Style::default().fg(Color::Gray),
+            ),
+        ])
+    };
+    let body_lines = |c: &Comment, indent: usize| {
+        wrap_words(&c.body, width.saturating_sub(indent).max(1))
+            .into_iter()
+            .map(move |l| {
+                Line::from(Span::styled(
+                    format!("{}{l}", " ".repeat(indent)),
+                    Style::default().fg(Color::White),
+                ))
+            })
+    };
+
+    for entry in entries {
+        out.push(Line::from(""));
+        match entry {
+            Entry::Comment(c) => {
+                out.push(author_line(c, 0));
+                out.extend(body_lines(c, 2));
+            }
+            Entry::Thread(t) => {
+                let path = t.path.as_deref().unwrap_or("(no file)");
+                let (head, style) = if t.is_resolved {
+                    (
+                        format!("✓ {path} · resolved"),
+                        Style::default().fg(Color::Gray),
+                    )
+                } else {
+                    (
+                        format!("🧵 {path} · unresolved"),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                };
+                out.push(Line::from(Span::styled(
+                    truncate_ellipsis(&head, width),
+                    style,
+                )));
+                for c in &t.comments {
+                    out.push(author_line(c, 2));
+                    out.extend(body_lines(c, 4));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Greedy word wrap by display width; overlong words are hard-split.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    for para in text.lines() {
+        let mut line = String::new();
+        let mut line_w = 0usize;
+        for word in para.split_whitespace() {
+            let word_w = UnicodeWidthStr::width(word);
+            if line_w > 0 && line_w + 1 + word_w > width {
+                out.push(std::mem::take(&mut line));
+                line_w = 0;
+            }
+            if line_w > 0 {
+                line.push(' ');
+                line_w += 1;
+            }
+            for ch in word.chars() {
+                let cw = UnicodeWidthChar::width(ch).unwrap_or(0);
+                if line_w + cw > width && line_w > 0 {
+                    out.push(std::mem::take(&mut line));
+                    line_w = 0;
+                }
+                line.push(ch);
+                line_w += cw;
+            }
+        }
+        out.push(line);
+    }
+    // Drop trailing blank paragraphs.
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out
+}
+
+fn clamp_selection(selected: &mut usize, visible_len: usize) {
+    if visible_len == 0 {
+        *selected = 0;
+    } else if *selected >= visible_len {
+        *selected = visible_len - 1;
+    }
+}
+
+fn help_lines(usage_mode: UsageMode) -> Vec<Line<'static>> {
+    let mins = |s: i64| s / 60;
+    let hours = |s: i64| s / 3600;
+    vec![
+        Line::from(vec![
+            Span::styled(
+                "needle",
+                Style::default()
+                    .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled("  help", Style::default().fg(Color::Gray)),
@@ -1318,14 +4126,27 @@ fn help_lines() -> Vec<Line<'static>> {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )),
-        Line::from(format!("  🔥 NEEDS YOU: score >= {CATEGORY_NEEDS_YOU_MIN}")),
-        Line::from("  🚢 READY TO MERGE: your PR, CI green, no blockers"),
+        Line::from(if usage_mode == UsageMode::Author {
+            format!(
+                "  🔥 NEEDS YOU: score >= {CATEGORY_NEEDS_YOU_MIN}, failing CI or merge conflicts"
+            )
+        } else {
+            format!("  🔥 NEEDS YOU: score >= {CATEGORY_NEEDS_YOU_MIN}")
+        }),
+        Line::from(if usage_mode == UsageMode::Reviewer {
+            "  🚢 READY TO MERGE: hidden, your own PRs are not fetched (mode = \"reviewer\")"
+        } else {
+            "  🚢 READY TO MERGE: your PR, CI green, no blockers"
+        }),
         Line::from("  ✅ NO ACTION NEEDED: score 0..39 (not ready-to-merge)"),
         Line::from(format!(
             "  ⏳ WAITING ON OTHERS: score < {CATEGORY_NO_ACTION_MIN} (currently: green + no review)"
         )),
         Line::from(
-            "  📝 DRAFT: drafts are dimmed and shown in their own section (regardless of score)",
+            "  👥 TEAM REVIEW: review requested from one of your teams (include_team_requests)",
+        ),
+        Line::from(
+            "  📝 DRAFT: drafts are dimmed and shown in their own section (see [scoring.drafts])",
         ),
         Line::from(""),
         Line::from(Span::styled(
@@ -1337,6 +4158,9 @@ fn help_lines() -> Vec<Line<'static>> {
         Line::from(format!(
             "  +{SCORE_REVIEW_REQUESTED:<2}  review requested from you"
         )),
+        Line::from(format!(
+            "  +{SCORE_TEAM_REVIEW_REQUESTED:<2}  review requested from your team"
+        )),
         Line::from(format!("  +{SCORE_CI_FAILED_NEW:<2}  CI failed (new)")),
         Line::from("       (state changed since last_seen or new commit)"),
         Line::from(format!(
@@ -1347,12 +4171,29 @@ fn help_lines() -> Vec<Line<'static>> {
             "  +{SCORE_APPROVED_UNMERGED_OLD:<2}  approved but unmerged for >{}h",
             hours(APPROVED_UNMERGED_OLD_SECS)
         )),
+        Line::from(format!(
+            "  +{SCORE_CHANGES_REQUESTED:<2}  🛑 changes requested on your PR"
+        )),
         Line::from(format!(
             "  {SCORE_WAITING_ON_OTHERS_GREEN:<3} waiting on others (green + no review, not approved)"
         )),
         Line::from(format!(
             "  {SCORE_CI_FAILED_UNCHANGED:<3} CI failed (unchanged)"
         )),
+        Line::from(format!(
+            "  +{SCORE_SLA_APPROACHING:<2}  ⏰ review SLA approaching (last quarter of the window)"
+        )),
+        Line::from(format!("  +{SCORE_SLA_OVERDUE:<2}  🚨 review SLA overdue")),
+        Line::from(format!(
+            "  +{SCORE_REPLY_TO_YOU:<2}  💬 reply to your comment since you last opened the PR"
+        )),
+        Line::from(format!(
+            "  +{SCORE_THREAD_AWAITING_YOU:<2}  🧵 author answered a review thread you opened"
+        )),
+        Line::from(format!(
+            "  +{SCORE_ASSIGNED:<2}  assigned to you (someone else's PR)"
+        )),
+        Line::from("  ±N   the author's [scoring.authors] rule"),
         Line::from(""),
         Line::from(Span::styled(
             "Keys",
@@ -1363,10 +4204,49 @@ fn help_lines() -> Vec<Line<'static>> {
         Line::from(
             "  List    : ↑/↓ move  Enter open  Tab details  p pin  r refresh  / filter  ? help  q quit",
         ),
+        Line::from("            Space preview (description, reviewers, labels, failing checks)"),
         Line::from("            Esc clears active filter (when not typing)"),
-        Line::from("  Filter  : type to filter  ↑/↓ move  Enter open  Esc clear+exit"),
-        Line::from("            Ctrl+n needs  Ctrl+c failing  Ctrl+v review  Ctrl+x clear"),
+        Line::from(
+            "            g group by repo group, then by repository, then by topic (←/→ fold)",
+        ),
+        Line::from(
+            "            n needs  c failing  v review  s SLA at risk  m mentions  b bots  x clear",
+        ),
+        Line::from("            z snooze (again to wake)  Z show/hide the SNOOZED section"),
+        Line::from("            O open every NEEDS YOU PR in the browser (asks when over 5)"),
+        Line::from("            V select mode: Space mark, * mark all filtered, then o open,"),
+        Line::from("              O mark opened, z snooze or A approve them all; Esc leaves"),
+        Line::from("            i ignore for good  I review and restore ignored PRs"),
+        Line::from("            S review requests safe to shed (d decline, h hand off)"),
+        Line::from("            T today: overdue reviews, your red PRs, approved PRs to merge"),
+        Line::from("            w switch the day window: 7, 14, 30, 90 days (older PRs fetched)"),
+        Line::from("            L split into your PRs / needs your review  ←/→ switch pane"),
+        Line::from("            e refresh error log  Esc dismisses the refresh error banner"),
+        Line::from(
+            "  Filter  : type to filter (group:<name> for a repo group)  ↑/↓ move  Enter open",
+        ),
+        Line::from(
+            "            repo:api author:alice is:draft ci:failing review:requested age:>3d",
+        ),
+        Line::from("            Esc clear+exit  Ctrl+x clear"),
+        Line::from(
+            "            Ctrl+n needs  Ctrl+c failing  Ctrl+v review  Ctrl+s sla  Ctrl+m mentions  Ctrl+b bots",
+        ),
         Line::from("  Details : ↑/↓ select  Enter open check  f open failing  Tab back"),
+        Line::from("            l last lines of the selected (or first) failing check's log"),
+        Line::from("            m merge (mergeable + green CI; pick merge, squash or rebase)"),
+        Line::from("            u update branch (merge the base in; only when it is behind)"),
+        Line::from("            d mark your draft ready for review, or turn your PR into a draft"),
+        Line::from("            c comment (Enter new line, Ctrl+s post, Esc discard)"),
+        Line::from("            h hand off: pick a recent collaborator, then edit and post"),
+        Line::from("            PgUp/PgDn scroll the conversation (comments and review threads)"),
+        Line::from("            [/] select a requested change  x mark it addressed (your PRs)"),
+        Line::from("            1-9 open one of the repo's quick links ([[links]] in the config)"),
+        Line::from("  Any     : o open a PR link from the clipboard (fetched if not listed)"),
+        Line::from("            a track/untrack this PR (keeps refreshing it outside your queue)"),
+        Line::from(
+            "            Q<a-z> record keys into a macro (Q stops)  @<a-z> replay  @@ again",
+        ),
         Line::from(""),
         Line::from(Span::styled(
             "Press ? or Esc to close.",
@@ -1375,13 +4255,462 @@ fn help_lines() -> Vec<Line<'static>> {
     ]
 }
 
+/// The composer text hard-wrapped to `width` columns, with a cursor after the last
+/// character. Only the last `height` rows are returned so the cursor stays in view.
+fn composer_lines(text: &str, width: u16, height: u16) -> Vec<Line<'static>> {
+    let width = (width as usize).max(1);
+    let mut rows: Vec<String> = Vec::new();
+    for line in format!("{text}▏").split('\n') {
+        let mut row = String::new();
+        let mut row_w = 0;
+        for ch in line.chars() {
+            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+            if row_w + w > width {
+                rows.push(std::mem::take(&mut row));
+                row_w = 0;
+            }
+            row.push(ch);
+            row_w += w;
+        }
+        rows.push(row);
+    }
+    let skip = rows.len().saturating_sub(height as usize);
+    rows.into_iter().skip(skip).map(Line::from).collect()
+}
+
+/// Shown at the top of the list when needle started without a GitHub token.
+/// Refresh failures kept for the error log (`e`).
+const ERROR_LOG_LEN: usize = 20;
+
+/// Red banner over the list for the last refresh failure. Offline, the offline banner
+/// already explains why refreshes fail.
+/// The whole frame when the terminal is below `MIN_TERMINAL_WIDTH` × `MIN_TERMINAL_HEIGHT`,
+/// vertically centred.
+fn too_small_lines(width: u16, height: u16) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(""); (height as usize).saturating_sub(3) / 2];
+    lines.push(Line::from(Span::styled(
+        "Terminal too small",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(format!(
+        "{width}×{height}, needs {MIN_TERMINAL_WIDTH}×{MIN_TERMINAL_HEIGHT}"
+    )));
+    lines.push(Line::from(Span::styled(
+        "q quits",
+        Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+    )));
+    lines
+}
+
+fn error_banner(state: &AppState) -> Option<String> {
+    if state.mode != ViewMode::List || state.offline {
+        return None;
+    }
+    let error = state.last_refresh_error.as_deref()?;
+    let first_line = error.lines().next().unwrap_or_default();
+    Some(format!(
+        "⚠ Refresh failed: {first_line}  (e: error log, Esc: dismiss)"
+    ))
+}
+
+/// Body of the error log overlay (`e`): recent refresh failures, newest first.
+fn error_log_lines(state: &AppState, now: i64) -> Vec<Line<'static>> {
+    if state.error_log.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No refresh errors this session.",
+            Style::default().fg(Color::Gray),
+        ))];
+    }
+    let mut out = Vec::new();
+    for (at, error) in state.error_log.iter().rev() {
+        out.push(Line::from(Span::styled(
+            human_age(now, *at),
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD),
+        )));
+        out.extend(error.lines().map(|l| {
+            Line::from(Span::styled(
+                format!("  {l}"),
+                Style::default().fg(Color::Red),
+            ))
+        }));
+    }
+    out.push(Line::from(""));
+    out.push(Line::from(Span::styled(
+        "e / Esc: close",
+        Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+    )));
+    out
+}
+
+const OFFLINE_BANNER: &str = "Offline: cached snapshot, read-only. Set NEEDLE_GITHUB_TOKEN or GITHUB_TOKEN and restart to refresh.";
+
+/// Local wall-clock time of `ts`, e.g. `14:05`.
+fn clock_time(ts: i64) -> String {
+    chrono::Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Header strip: signed-in login, PRs per section, last refresh, API budget, profile,
+/// scope filters and days window, so it is obvious whose queue is on screen and how much
+/// is in it. Later segments are cut first when space runs out.
+fn header_line(state: &AppState, width: u16) -> Line<'static> {
+    let login = match (&state.viewer_login, state.offline) {
+        (Some(login), _) => format!("@{login}"),
+        (None, true) => "not signed in".to_string(),
+        (None, false) => "@unknown".to_string(),
+    };
+    let profile = if state.oncall { "on-call" } else { "default" };
+    let scope = if state.scope_summary.is_empty() {
+        "all repos".to_string()
+    } else {
+        state.scope_summary.join(" ")
+    };
+    let mut segs: Vec<(String, Style)> = vec![(
+        login,
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+    let dim = Style::default().fg(Color::Gray);
+    for cat in [
+        Category::NeedsYou,
+        Category::ReadyToMerge,
+        Category::Waiting,
+        Category::Stale,
+    ] {
+        let n = state
+            .prs
+            .iter()
+            .filter(|p| Section::Category(cat).contains(p))
+            .count();
+        let icon = category_title(cat).split(' ').next().unwrap_or_default();
+        segs.push((format!(" · {icon} {n}"), category_style(cat)));
+    }
+    if let Some(at) = state.last_refreshed_at {
+        segs.push((format!(" · refreshed {}", clock_time(at)), dim));
+    }
+    if let Some(rl) = state.rate_limit {
+        segs.push((format!(" · API {}/{}", rl.remaining, rl.limit), dim));
+    }
+    segs.push((format!(" · profile: {profile}"), dim));
+    segs.push((format!(" · scope: {scope}"), dim));
+    if let Some(days) = state.days {
+        segs.push((format!(" · last {days}d"), dim));
+    }
+
+    let mut left = width as usize;
+    let mut spans = Vec::new();
+    for (text, style) in segs {
+        if left == 0 {
+            break;
+        }
+        let text = truncate_ellipsis(&text, left);
+        left -= UnicodeWidthStr::width(text.as_str());
+        spans.push(Span::styled(text, style));
+    }
+    Line::from(spans)
+}
+
+/// Banner above the list: the active filters, or the offline notice when none are set.
+fn list_banner(state: &AppState) -> Option<String> {
+    if state.filter_editing {
+        return Some(format!("Filter: {} (Esc back)", state.filter_edit));
+    }
+    let mut parts: Vec<String> = Vec::new();
+    if !state.filter_query.is_empty() {
+        parts.push(format!("q=\"{}\"", state.filter_query));
+    }
+    if state.only_needs_you {
+        parts.push("needs".to_string());
+    }
+    if state.only_failing_ci {
+        parts.push("failing".to_string());
+    }
+    if state.only_review_requested {
+        parts.push("review".to_string());
+    }
+    if state.only_sla {
+        parts.push("sla".to_string());
+    }
+    if state.only_mentioned {
+        parts.push("mentions".to_string());
+    }
+    if state.only_bots {
+        parts.push("bots".to_string());
+    }
+    let filter = (!parts.is_empty()).then(|| format!("Filter: {}", parts.join("  ")));
+    if state.select_mode {
+        let select = format!(
+            "Select: {} marked  Space mark  * all  o open  O mark opened  z snooze  A approve  Esc done",
+            state.marked_prs().len()
+        );
+        Some(match filter {
+            Some(filter) => format!("{filter} · {select}"),
+            None => select,
+        })
+    } else if let Some(filter) = filter {
+        Some(filter)
+    } else if state.offline {
+        Some(OFFLINE_BANNER.to_string())
+    } else {
+        None
+    }
+}
+
+/// Body of the merge method picker (`m` in details view).
+fn merge_picker_lines(selected: usize) -> Vec<Line<'static>> {
+    let mut out: Vec<Line<'static>> = MergeMethod::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let style = if i == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" {} ", m.label()), style))
+        })
+        .collect();
+    out.push(Line::from(Span::styled(
+        "Enter merge  Esc cancel",
+        Style::default().fg(Color::Gray),
+    )));
+    out
+}
+
+/// Body of the handoff picker (`h` in details view).
+fn handoff_picker_lines(picker: &HandoffPicker) -> Vec<Line<'static>> {
+    let mut out: Vec<Line<'static>> = picker
+        .logins
+        .iter()
+        .enumerate()
+        .map(|(i, login)| {
+            let style = if i == picker.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" @{login} "), style))
+        })
+        .collect();
+    out.push(Line::from(Span::styled(
+        "Enter draft comment  Esc cancel",
+        Style::default().fg(Color::Gray),
+    )));
+    out
+}
+
+/// Body of the ignored-PR list, scrolled so the selected row stays within `height`.
+fn ignored_view_lines(view: &IgnoredView, width: u16, height: u16) -> Vec<Line<'static>> {
+    let hint = Line::from(Span::styled(
+        "Enter restore  Esc close",
+        Style::default().fg(Color::Gray),
+    ));
+    if view.rows.is_empty() {
+        return vec![Line::from(" No ignored PRs "), hint];
+    }
+    let rows_h = (height as usize).saturating_sub(1).max(1);
+    let skip = (view.selected + 1).saturating_sub(rows_h);
+    let mut out: Vec<Line<'static>> = view
+        .rows
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(rows_h)
+        .map(|(i, (key, title))| {
+            let style = if i == view.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            let text = truncate_ellipsis(&format!(" {key}  {title} "), width as usize);
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    out.push(hint);
+    out
+}
+
+/// Body of the shedding list: one row per suggestion, scrolled so the selected row stays
+/// within `height`.
+fn shed_view_lines(view: &ShedView, width: u16, height: u16) -> Vec<Line<'static>> {
+    let hint = Line::from(Span::styled(
+        "Enter suggested action  d decline  h hand off  Esc close",
+        Style::default().fg(Color::Gray),
+    ));
+    if view.rows.is_empty() {
+        return vec![
+            Line::from(" Nothing to shed: no small or bot review requests "),
+            hint,
+        ];
+    }
+    let rows_h = (height as usize).saturating_sub(1).max(1);
+    let skip = (view.selected + 1).saturating_sub(rows_h);
+    let mut out: Vec<Line<'static>> = view
+        .rows
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(rows_h)
+        .map(|(i, s)| {
+            let style = if i == view.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            let text = format!(
+                " {:<8}  {}  {} · {} ",
+                s.action.label(),
+                s.pr_key,
+                s.reason,
+                s.title
+            );
+            Line::from(Span::styled(
+                truncate_ellipsis(&text, width as usize),
+                style,
+            ))
+        })
+        .collect();
+    out.push(hint);
+    out
+}
+
+/// Body of the Today view: numbered rows, skipped ones dimmed, scrolled so the selected
+/// row stays visible.
+fn today_view_lines(view: &TodayView, width: u16, height: u16, now: i64) -> Vec<Line<'static>> {
+    let hint = Line::from(Span::styled(
+        "Enter open  d done  s skip  Esc close",
+        Style::default().fg(Color::Gray),
+    ));
+    if view.items.is_empty() {
+        let text = if view.done > 0 {
+            " All done for today ✓ "
+        } else {
+            " Nothing overdue, red or waiting to be merged "
+        };
+        return vec![Line::from(text), hint];
+    }
+    let rows_h = (height as usize).saturating_sub(1).max(1);
+    let skip = (view.selected + 1).saturating_sub(rows_h);
+    let mut out: Vec<Line<'static>> = view
+        .items
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(rows_h)
+        .map(|(i, item)| {
+            let mut style = Style::default();
+            if item.skipped {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if i == view.selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            let text = format!(
+                " {:>2}. {:<14}  {}  {}{} · {} ",
+                i + 1,
+                item.reason.label(),
+                item.pr_key,
+                human_age(now, item.since_unix),
+                if item.skipped { " · skipped" } else { "" },
+                item.title
+            );
+            Line::from(Span::styled(
+                truncate_ellipsis(&text, width as usize),
+                style,
+            ))
+        })
+        .collect();
+    out.push(hint);
+    out
+}
+
+/// The visible part of a job log: `height` rows ending `from_bottom` lines above the end.
+/// Error annotations are shown in red.
+fn log_view_lines(view: &LogView, height: u16) -> Vec<Line<'static>> {
+    let height = height as usize;
+    let end = view
+        .lines
+        .len()
+        .saturating_sub(view.from_bottom)
+        .max(height.min(view.lines.len()));
+    view.lines[end.saturating_sub(height)..end]
+        .iter()
+        .map(|line| match line.strip_prefix("##[error]") {
+            Some(msg) => Line::from(Span::styled(
+                format!("error: {msg}"),
+                Style::default().fg(Color::Red),
+            )),
+            None => Line::from(line.clone()),
+        })
+        .collect()
+}
+
+/// Body of the snooze picker (`z` on a list row).
+fn snooze_picker_lines(selected: usize) -> Vec<Line<'static>> {
+    let mut out: Vec<Line<'static>> = SnoozeChoice::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let style = if i == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" {} ", c.label()), style))
+        })
+        .collect();
+    out.push(Line::from(Span::styled(
+        "Enter snooze  Esc cancel",
+        Style::default().fg(Color::Gray),
+    )));
+    out
+}
+
+/// One step of input for the event loop.
+pub(crate) enum Input {
+    Key(KeyEvent),
+    /// Nothing happened within the poll timeout.
+    Idle,
+    /// The input source is exhausted; leave the loop.
+    End,
+}
+
+/// Where the event loop gets keys from (the real terminal, or a replayed script).
+pub(crate) trait EventSource {
+    fn next(&mut self, timeout: Duration) -> Result<Input, String>;
+}
+
+struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn next(&mut self, timeout: Duration) -> Result<Input, String> {
+        if !event::poll(timeout).map_err(|e| format!("Event poll failed: {e}"))? {
+            return Ok(Input::Idle);
+        }
+        match event::read().map_err(|e| format!("Event read failed: {e}"))? {
+            Event::Key(k) => Ok(Input::Key(k)),
+            _ => Ok(Input::Idle),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run_tui(
     conn: &Connection,
-    mut state: AppState,
-    refresh_fn: Arc<dyn Fn() -> Result<Vec<UiPr>, String> + Send + Sync>,
+    state: AppState,
+    callbacks: Callbacks,
     start_refresh_immediately: bool,
-    bell_enabled: bool,
+    bell_events: Vec<String>,
     refresh_intervals: RefreshIntervals,
+    config_reload: Option<mpsc::Receiver<ConfigReload>>,
 ) -> Result<(), String> {
     if !io::stdin().is_tty() || !io::stdout().is_tty() {
         return Err("Not a TTY: run `needle` in an interactive terminal.".to_string());
@@ -1395,29 +4724,204 @@ pub fn run_tui(
     let mut terminal: Terminal<CrosstermBackend<Stdout>> =
         Terminal::new(backend).map_err(|e| format!("Failed to init terminal: {e}"))?;
 
-    let mut refresh_rx: Option<mpsc::Receiver<Result<Vec<UiPr>, String>>> = None;
-    let mut update_rx = spawn_update_check();
+    let Callbacks {
+        refresh_fn,
+        refresh_pr_fn,
+        window_fn,
+        lookup_fn,
+        check_log_fn,
+        merge_fn,
+        comment_fn,
+        update_branch_fn,
+        set_draft_fn,
+        decline_fn,
+        approve_fn,
+        mark_read_fn,
+    } = callbacks;
+    let result = run_loop(
+        &mut terminal,
+        conn,
+        state,
+        refresh_fn,
+        LoopOptions {
+            refresh_pr_fn,
+            window_fn: Some(window_fn),
+            lookup_fn,
+            check_log_fn,
+            merge_fn,
+            comment_fn,
+            update_branch_fn,
+            set_draft_fn,
+            decline_fn,
+            approve_fn,
+            mark_read_fn,
+            start_refresh_immediately,
+            bell_events,
+            check_updates: true,
+            remember_ui_state: true,
+            refresh_intervals,
+            config_reload,
+        },
+        &mut CrosstermEvents,
+        &mut |_| {},
+    );
 
-    if start_refresh_immediately && !state.refreshing {
-        state.refreshing = true;
-        state.shimmer_phase = 0;
-        state.last_refresh_started = Some(Instant::now());
-        let (tx, rx) = mpsc::channel();
-        refresh_rx = Some(rx);
-        let rf = Arc::clone(&refresh_fn);
-        std::thread::spawn(move || {
-            let res = rf();
-            let _ = tx.send(res);
-        });
-    }
+    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {e}"))?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)
+        .map_err(|e| format!("Failed to leave alt screen: {e}"))?;
+    terminal
+        .show_cursor()
+        .map_err(|e| format!("Failed to show cursor: {e}"))?;
+    result
+}
 
-    loop {
-        // Non-blocking update check result.
-        if let Some(rx) = &update_rx {
-            match rx.try_recv() {
-                Ok(msg) => {
-                    state.update_notice = Some(msg);
+/// Day windows the `w` key cycles through.
+const DAY_WINDOWS: [i64; 4] = [7, 14, 30, 90];
+
+/// The window after `days` in [`DAY_WINDOWS`], back to the smallest after the largest.
+fn next_day_window(days: i64) -> i64 {
+    DAY_WINDOWS
+        .into_iter()
+        .find(|&d| d > days)
+        .unwrap_or(DAY_WINDOWS[0])
+}
+
+pub(crate) struct LoopOptions {
+    /// Re-fetches the PR shown in details view (its auto-refresh).
+    pub(crate) refresh_pr_fn: RefreshPrFn,
+    /// Switches the `--days` window (the `w` key); `None` leaves the key unbound.
+    pub(crate) window_fn: Option<WindowFn>,
+    /// Fetches a single PR by URL or `owner/repo#123` (the `o` key).
+    pub(crate) lookup_fn: LookupFn,
+    /// Fetches the tail of a CI job log (the `l` key in details view).
+    pub(crate) check_log_fn: CheckLogFn,
+    pub(crate) merge_fn: MergeFn,
+    /// Posts a comment on a PR (the `c` key in details view).
+    pub(crate) comment_fn: CommentFn,
+    /// Merges the base branch into a PR that is behind it (the `u` key in details view).
+    pub(crate) update_branch_fn: UpdateBranchFn,
+    /// Converts the viewer's PR to a draft or marks it ready (the `d` key in details view).
+    pub(crate) set_draft_fn: SetDraftFn,
+    /// Removes the viewer from a PR's requested reviewers (`d` in the shedding list).
+    pub(crate) decline_fn: DeclineFn,
+    /// Approves a PR (`A` on the rows marked in select mode).
+    pub(crate) approve_fn: ApproveFn,
+    /// Marks a PR read in the GitHub inbox when it is opened (`mark_read_on_open`).
+    pub(crate) mark_read_fn: Option<MarkReadFn>,
+    pub(crate) start_refresh_immediately: bool,
+    pub(crate) bell_events: Vec<String>,
+    pub(crate) check_updates: bool,
+    /// Save the filter, toggles, layout and selection to the cache on quit.
+    pub(crate) remember_ui_state: bool,
+    pub(crate) refresh_intervals: RefreshIntervals,
+    /// Settings re-read whenever config.toml changes.
+    pub(crate) config_reload: Option<mpsc::Receiver<ConfigReload>>,
+}
+
+/// A finished draft toggle: the PR key, the draft state asked for, and the result.
+type DraftOutcome = (String, bool, Result<(), String>);
+
+/// A fetched job log: the check name and its last lines.
+type LogOutcome = (String, Result<Vec<String>, String>);
+
+/// A finished bulk approval: the result for each PR key.
+type ApproveOutcome = Vec<(String, Result<(), String>)>;
+
+/// Redraw at least this often while idle, so relative ages ("5m ago") stay current.
+const IDLE_REDRAW: Duration = Duration::from_secs(10);
+
+/// Event poll timeout while the shimmer animates or a background result is pending.
+const ACTIVE_POLL: Duration = Duration::from_millis(50);
+/// Event poll timeout otherwise. Key presses still wake the loop immediately.
+const IDLE_POLL: Duration = Duration::from_millis(500);
+
+/// The event loop shared by the interactive TUI and scripted replays.
+///
+/// `on_frame` sees every rendered buffer (used to record asciicasts).
+pub(crate) fn run_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    conn: &Connection,
+    mut state: AppState,
+    refresh_fn: Arc<dyn Fn() -> Result<Vec<UiPr>, String> + Send + Sync>,
+    opts: LoopOptions,
+    events: &mut dyn EventSource,
+    on_frame: &mut dyn FnMut(&Buffer),
+) -> Result<(), String> {
+    let LoopOptions {
+        refresh_pr_fn,
+        window_fn,
+        lookup_fn,
+        check_log_fn,
+        merge_fn,
+        comment_fn,
+        update_branch_fn,
+        set_draft_fn,
+        decline_fn,
+        approve_fn,
+        mark_read_fn,
+        start_refresh_immediately,
+        mut bell_events,
+        check_updates,
+        remember_ui_state,
+        mut refresh_intervals,
+        config_reload,
+    } = opts;
+
+    let mut refresh_rx: Option<mpsc::Receiver<Result<Vec<UiPr>, String>>> = None;
+    let mut refresh_pr_rx: Option<mpsc::Receiver<Result<UiPr, String>>> = None;
+    let mut window_rx: Option<mpsc::Receiver<Result<Vec<UiPr>, String>>> = None;
+    let mut lookup_rx: Option<mpsc::Receiver<Result<UiPr, String>>> = None;
+    let mut merge_rx: Option<mpsc::Receiver<(String, Result<(), String>)>> = None;
+    let mut comment_rx: Option<mpsc::Receiver<(Composer, Result<(), String>)>> = None;
+    let mut branch_update_rx: Option<mpsc::Receiver<(String, Result<(), String>)>> = None;
+    let mut draft_rx: Option<mpsc::Receiver<DraftOutcome>> = None;
+    let mut decline_rx: Option<mpsc::Receiver<(String, Result<(), String>)>> = None;
+    let mut approve_rx: Option<mpsc::Receiver<ApproveOutcome>> = None;
+    let (mark_read_tx, mark_read_rx) = mpsc::channel::<String>();
+    let mut log_rx: Option<mpsc::Receiver<LogOutcome>> = None;
+    let mut update_rx = if check_updates {
+        spawn_update_check()
+    } else {
+        None
+    };
+
+    // Checklist state is local-only; a read failure just starts with nothing checked.
+    state.addressed_threads = load_addressed_threads(conn).unwrap_or_default();
+    state.check_failures = load_check_failures(conn).unwrap_or_default();
+
+    if start_refresh_immediately && !state.refreshing {
+        state.refreshing = true;
+        state.shimmer_phase = 0;
+        state.last_refresh_started = Some(Instant::now());
+        let (tx, rx) = mpsc::channel();
+        refresh_rx = Some(rx);
+        let rf = Arc::clone(&refresh_fn);
+        std::thread::spawn(move || {
+            let res = rf();
+            let _ = tx.send(res);
+        });
+    }
+
+    let mut visible_for_events: Vec<usize> = Vec::new();
+    let mut list_cache = ListCache::default();
+    let mut dirty = true;
+    let mut last_area = Default::default();
+    let mut last_draw = Instant::now();
+    let mut title = TitlePublisher::default();
+    loop {
+        title.update(state.title_status, state.needs_you_count());
+        if let Ok(e) = mark_read_rx.try_recv() {
+            state.status_message = Some(e);
+            dirty = true;
+        }
+
+        // Non-blocking update check result.
+        if let Some(rx) = &update_rx {
+            match rx.try_recv() {
+                Ok(msg) => {
+                    state.update_notice = Some(msg);
                     update_rx = None;
+                    dirty = true;
                 }
                 Err(TryRecvError::Disconnected) => {
                     update_rx = None;
@@ -1426,11 +4930,239 @@ pub fn run_tui(
             }
         }
 
+        // Config file changed: apply what can change without a restart.
+        if let Some(reload) = config_reload.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            state.status_message = Some(match reload {
+                Ok((settings, warning)) => {
+                    bell_events = settings.bell_events.clone();
+                    refresh_intervals = settings.refresh_intervals;
+                    state.apply_live_settings(settings);
+                    match warning {
+                        Some(w) => format!("Config reloaded ({w})"),
+                        None => "Config reloaded".to_string(),
+                    }
+                }
+                Err(e) => {
+                    let first_line = e.lines().next().unwrap_or_default();
+                    format!("Config not reloaded: {first_line}")
+                }
+            });
+            dirty = true;
+        }
+
+        // Clipboard PR lookup result.
+        if let Some(rx) = &lookup_rx {
+            match rx.try_recv() {
+                Ok(Ok(pr)) => {
+                    let key = pr.pr.pr_key.clone();
+                    state.lookups.retain(|p| p.pr.pr_key != key);
+                    state.lookups.push(pr);
+                    state.status_message = None;
+                    state.open_details(key);
+                    lookup_rx = None;
+                    dirty = true;
+                }
+                Ok(Err(e)) => {
+                    state.status_message = Some(e);
+                    lookup_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    lookup_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Merge result: drop the merged PR and go back to the list.
+        if let Some(rx) = &merge_rx {
+            match rx.try_recv() {
+                Ok((key, Ok(()))) => {
+                    state.prs_mut().retain(|p| p.pr.pr_key != key);
+                    state.lookups.retain(|p| p.pr.pr_key != key);
+                    if state.details_pr_key.as_deref() == Some(key.as_str()) {
+                        state.mode = ViewMode::List;
+                        state.details_last_auto_refresh = None;
+                    }
+                    state.status_message = Some(format!("Merged {key}"));
+                    merge_rx = None;
+                    dirty = true;
+                }
+                Ok((_, Err(e))) => {
+                    state.status_message = Some(e);
+                    merge_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    merge_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Comment result: on failure reopen the composer so the draft is not lost.
+        if let Some(rx) = &comment_rx {
+            match rx.try_recv() {
+                Ok((draft, res)) => {
+                    state.status_message = Some(match res {
+                        Ok(()) => format!("Commented on {}", draft.pr_key),
+                        Err(e) => {
+                            state.composer = Some(draft);
+                            e
+                        }
+                    });
+                    comment_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    comment_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Branch update result: re-fetch the shown PR so the new head and checks show up.
+        if let Some(rx) = &branch_update_rx {
+            match rx.try_recv() {
+                Ok((key, res)) => {
+                    state.status_message = Some(match res {
+                        Ok(()) => {
+                            if state.details_pr_key.as_deref() == Some(key.as_str()) {
+                                state.details_last_auto_refresh = None;
+                            }
+                            format!("Updated {key} with its base branch")
+                        }
+                        Err(e) => e,
+                    });
+                    branch_update_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    branch_update_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Draft toggle result: move the PR in or out of the DRAFT section right away, then
+        // re-fetch it for the rest.
+        if let Some(rx) = &draft_rx {
+            match rx.try_recv() {
+                Ok((key, draft, res)) => {
+                    state.status_message = Some(match res {
+                        Ok(()) => {
+                            for p in state.prs_mut().iter_mut().filter(|p| p.pr.pr_key == key) {
+                                p.pr.is_draft = draft;
+                            }
+                            for p in state.lookups.iter_mut().filter(|p| p.pr.pr_key == key) {
+                                p.pr.is_draft = draft;
+                            }
+                            if state.details_pr_key.as_deref() == Some(key.as_str()) {
+                                state.details_last_auto_refresh = None;
+                            }
+                            if draft {
+                                format!("Converted {key} to a draft")
+                            } else {
+                                format!("Marked {key} ready for review")
+                            }
+                        }
+                        Err(e) => e,
+                    });
+                    draft_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    draft_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Job log fetched: open it over the details view.
+        if let Some(rx) = &log_rx {
+            match rx.try_recv() {
+                Ok((check, res)) => {
+                    match res {
+                        Ok(lines) if lines.is_empty() => {
+                            state.status_message = Some(format!("The log of {check} is empty"));
+                        }
+                        Ok(lines) => {
+                            state.status_message = None;
+                            state.log_view = Some(LogView {
+                                check,
+                                lines,
+                                ..LogView::default()
+                            });
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    }
+                    log_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    log_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Decline result: drop the suggestion and refresh so the PR leaves the queue.
+        if let Some(rx) = &decline_rx {
+            match rx.try_recv() {
+                Ok((key, res)) => {
+                    state.status_message = Some(match res {
+                        Ok(()) => {
+                            if let Some(view) = state.shed_view.as_mut() {
+                                view.rows.retain(|s| s.pr_key != key);
+                                clamp_selection(&mut view.selected, view.rows.len());
+                            }
+                            state.last_refresh_started = None;
+                            format!("Declined the review of {key}")
+                        }
+                        Err(e) => e,
+                    });
+                    decline_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    decline_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Bulk approval result: unmark what went through and refresh so it moves on.
+        if let Some(rx) = &approve_rx {
+            match rx.try_recv() {
+                Ok(results) => {
+                    let approved = results.iter().filter(|(_, r)| r.is_ok()).count();
+                    for (key, _) in results.iter().filter(|(_, r)| r.is_ok()) {
+                        state.marked.remove(key);
+                    }
+                    state.status_message =
+                        Some(match results.iter().find_map(|(_, r)| r.as_ref().err()) {
+                            None => format!("Approved {approved} PRs"),
+                            Some(e) => format!("Approved {approved} of {} PRs; {e}", results.len()),
+                        });
+                    state.last_refresh_started = None;
+                    approve_rx = None;
+                    dirty = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    approve_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        // Auto refresh, slowed down or paused while the API budget is low.
+        let intervals = refresh_intervals.for_budget(state.rate_limit, now_unix());
+
         // Auto refresh in list view (non-blocking).
         if state.mode == ViewMode::List && !state.refreshing {
             let should = state
                 .last_refresh_started
-                .map(|t| t.elapsed() >= Duration::from_secs(refresh_intervals.list_secs))
+                .map(|t| t.elapsed() >= Duration::from_secs(intervals.list_secs))
                 .unwrap_or(true);
             if should {
                 state.last_refresh_started = Some(Instant::now());
@@ -1450,19 +5182,28 @@ pub fn run_tui(
         if state.mode == ViewMode::Details && !state.refreshing {
             let should = state
                 .details_last_auto_refresh
-                .map(|t| t.elapsed() >= Duration::from_secs(refresh_intervals.details_secs))
+                .map(|t| t.elapsed() >= Duration::from_secs(intervals.details_secs))
                 .unwrap_or(true);
-            if should {
+            // Only the shown PR is re-fetched; `r` still refreshes everything. PRs the host
+            // no longer shows are left to the list refresh.
+            let key = should
+                .then(|| state.details_pr_key.clone())
+                .flatten()
+                .filter(|key| {
+                    !state
+                        .prs
+                        .iter()
+                        .any(|p| &p.pr.pr_key == key && p.inaccessible_since.is_some())
+                });
+            if let Some(key) = key {
                 state.details_last_auto_refresh = Some(Instant::now());
-                state.last_refresh_started = Some(Instant::now());
                 state.refreshing = true;
                 state.shimmer_phase = 0;
                 let (tx, rx) = mpsc::channel();
-                refresh_rx = Some(rx);
-                let rf = Arc::clone(&refresh_fn);
+                refresh_pr_rx = Some(rx);
+                let rf = Arc::clone(&refresh_pr_fn);
                 std::thread::spawn(move || {
-                    let res = rf();
-                    let _ = tx.send(res);
+                    let _ = tx.send(rf(&key));
                 });
             }
         }
@@ -1470,39 +5211,70 @@ pub fn run_tui(
         // If a refresh is in-flight, animate shimmer and apply results when ready.
         if state.refreshing {
             state.shimmer_phase = state.shimmer_phase.wrapping_add(1);
+            dirty = true;
+            if let Some(rx) = &refresh_pr_rx {
+                match rx.try_recv() {
+                    Ok(res) => {
+                        // A failed single refresh keeps the last known state; the next
+                        // tick retries.
+                        match res {
+                            Ok(pr) => {
+                                state.replace_pr(pr);
+                                state.check_failures =
+                                    load_check_failures(conn).unwrap_or_default();
+                            }
+                            Err(e) => state.record_refresh_error(e, now_unix()),
+                        }
+                        state.refreshing = false;
+                        refresh_pr_rx = None;
+                    }
+                    Err(TryRecvError::Empty) => {}
+                    Err(TryRecvError::Disconnected) => {
+                        state.refreshing = false;
+                        refresh_pr_rx = None;
+                    }
+                }
+            }
             if let Some(rx) = &refresh_rx {
                 match rx.try_recv() {
                     Ok(Ok(new_prs)) => {
-                        // Compute changes for bell alerts.
-                        let old_needs: HashSet<String> = state
-                            .prs
-                            .iter()
-                            .filter(|p| p.category == Category::NeedsYou)
-                            .map(|p| p.pr.pr_key.clone())
-                            .collect();
-                        let prs_entered_needs_you: Vec<&UiPr> = new_prs
+                        // Bell alert (terminal bell).
+                        let mut raised = alerts(&state.prs, &new_prs);
+                        raised.extend(state.reminders.check(
+                            &raised,
+                            &new_prs,
+                            now_unix(),
+                            |key| opened_at(conn, key).ok().flatten(),
+                        ));
+                        // Ring once per occurrence, even across restarts or with `watch` running.
+                        let ringing: Vec<Alert> = raised
                             .iter()
-                            .filter(|p| {
-                                p.category == Category::NeedsYou
-                                    && !old_needs.contains(&p.pr.pr_key)
-                            })
+                            .filter(|a| bell_events.iter().any(|e| a.is(e)))
+                            .cloned()
                             .collect();
-                        let prs_new_ci_failure: Vec<&UiPr> =
-                            new_prs.iter().filter(|p| p.is_new_ci_failure).collect();
-
-                        // Bell alert (terminal bell).
-                        if bell_enabled
-                            && (!prs_entered_needs_you.is_empty() || !prs_new_ci_failure.is_empty())
-                        {
-                            let _ = execute!(terminal.backend_mut(), Print("\x07"));
+                        if !unannounced(conn, &ringing, &new_prs, now_unix()).is_empty() {
+                            let _ = execute!(io::stdout(), Print("\x07"));
+                        }
+                        // An approval is the cue to go merge, so say so in the footer.
+                        let approved = raised.iter().find_map(|a| {
+                            let p = new_prs.iter().find(|p| p.pr.pr_key == a.pr_key())?;
+                            matches!(a, Alert::Approved(_))
+                                .then(|| format!("✅ {} {}", p.pr.pr_key, a.headline(p)))
+                        });
+                        if let Some(msg) = approved {
+                            state.status_message = Some(msg);
                         }
 
-                        state.prs = new_prs;
+                        *state.prs_mut() = new_prs;
+                        state.check_failures = load_check_failures(conn).unwrap_or_default();
+                        state.last_refresh_error = None;
+                        state.last_refreshed_at = Some(now_unix());
                         state.refreshing = false;
                         refresh_rx = None;
                     }
-                    Ok(Err(_e)) => {
-                        // Keep V1 minimal: stop refreshing; errors surface on next startup/log.
+                    Ok(Err(e)) => {
+                        // Keep the last good list; say why it is stale.
+                        state.record_refresh_error(e, now_unix());
                         state.refreshing = false;
                         refresh_rx = None;
                     }
@@ -1513,481 +5285,3173 @@ pub fn run_tui(
                     }
                 }
             }
+            // Older PRs of a wider window join quietly; they are not news.
+            if let Some(rx) = &window_rx {
+                match rx.try_recv() {
+                    Ok(res) => {
+                        match res {
+                            Ok(new_prs) => *state.prs_mut() = new_prs,
+                            Err(e) => state.record_refresh_error(e, now_unix()),
+                        }
+                        state.refreshing = false;
+                        window_rx = None;
+                    }
+                    Err(TryRecvError::Empty) => {}
+                    Err(TryRecvError::Disconnected) => {
+                        state.refreshing = false;
+                        window_rx = None;
+                    }
+                }
+            }
+            if !state.refreshing {
+                state.rate_limit = last_rate_limit();
+            }
+        }
+        // Refreshes skip what GitHub could not resolve (e.g. one forbidden repository).
+        for skipped in take_partial_errors() {
+            state.log_error(skipped, now_unix());
         }
 
+        // Only rebuild and draw when something changed; idle terminals stay quiet.
         let area = terminal
             .size()
             .map_err(|e| format!("Failed to read terminal size: {e}"))?;
-        let inner_height = area.height.saturating_sub(2); // borders
-        let inner_width = area.width.saturating_sub(2); // borders
-        let content_height = inner_height.saturating_sub(1); // footer rendered separately at bottom
+        if dirty || area != last_area || last_draw.elapsed() >= IDLE_REDRAW {
+            dirty = false;
+            last_area = area;
+            last_draw = Instant::now();
+            let inner_height = area.height.saturating_sub(2); // borders
+            let inner_width = area.width.saturating_sub(2); // borders
+            let error_banner = error_banner(&state);
+            // Header, footer and the error banner are rendered separately.
+            let content_height = inner_height.saturating_sub(2 + error_banner.is_some() as u16);
 
-        let (lines, visible) = if state.mode == ViewMode::List {
-            let filtered = filtered_indices(
-                &state.prs,
-                &state.filter_query,
-                state.only_needs_you,
-                state.only_failing_ci,
-                state.only_review_requested,
-            );
-            let mut banner = String::new();
-            if state.filter_editing {
-                banner = format!("Filter: {} (Esc back)", state.filter_edit);
-            } else if !state.filter_query.is_empty()
-                || state.only_needs_you
-                || state.only_failing_ci
-                || state.only_review_requested
-            {
-                let mut parts: Vec<String> = Vec::new();
-                if !state.filter_query.is_empty() {
-                    parts.push(format!("q=\"{}\"", state.filter_query));
-                }
-                if state.only_needs_you {
-                    parts.push("needs".to_string());
-                }
-                if state.only_failing_ci {
-                    parts.push("failing".to_string());
-                }
-                if state.only_review_requested {
-                    parts.push("review".to_string());
-                }
-                banner = format!("Filter: {}", parts.join("  "));
-            }
-            let banner_opt = if banner.is_empty() {
-                None
+            let (lines, visible) = if state.mode == ViewMode::List {
+                let banner = list_banner(&state);
+                list_cache.layout(&state, inner_width, content_height, banner.as_deref())
             } else {
-                Some(banner.as_str())
+                if let Some(pr) = state.details_pr().cloned() {
+                    let ci_rows = state.details_ci_rows();
+                    (
+                        build_details_lines(
+                            &pr,
+                            inner_width,
+                            content_height,
+                            CiList {
+                                rows: &ci_rows,
+                                selected: state.details_ci_selected,
+                                failures: state.check_failures.get(&pr.pr.pr_key),
+                            },
+                            &mut state.details_conversation_scroll,
+                            Checklist {
+                                addressed: state.addressed_threads.get(&pr.pr.pr_key),
+                                selected: state.details_thread_selected,
+                            },
+                            &state.quick_links.for_pr(&pr),
+                            now_unix(),
+                        ),
+                        Vec::new(),
+                    )
+                } else {
+                    state.mode = ViewMode::List;
+                    list_cache.layout(&state, inner_width, content_height, None)
+                }
             };
-            let (l, v) = build_list_lines(
-                &state.prs,
+            let (footer_line, footer_line_width) = build_footer(
                 inner_width,
-                content_height,
-                state.selected_idx,
-                &filtered,
-                banner_opt,
-                state.ui,
+                state.mode,
+                state.refreshing,
+                state.shimmer_phase,
+                state.color_mode,
+                state.mode == ViewMode::List && state.filter_editing,
             );
-            (l, v)
-        } else {
-            let key = state.details_pr_key.clone();
-            let maybe = key.and_then(|k| state.prs.iter().find(|p| p.pr.pr_key == k).cloned());
-            if let Some(pr) = maybe {
-                (
-                    build_details_lines(
-                        &pr,
-                        inner_width,
-                        content_height,
-                        state.details_ci_selected,
-                    ),
-                    Vec::new(),
-                )
-            } else {
-                state.mode = ViewMode::List;
-                let filtered = filtered_indices(
-                    &state.prs,
-                    &state.filter_query,
-                    state.only_needs_you,
-                    state.only_failing_ci,
-                    state.only_review_requested,
-                );
-                let (l, v) = build_list_lines(
-                    &state.prs,
-                    inner_width,
-                    content_height,
-                    state.selected_idx,
-                    &filtered,
-                    None,
-                    state.ui,
-                );
-                (l, v)
-            }
-        };
-        let (footer_line, footer_line_width) = build_footer(
-            inner_width,
-            state.mode,
-            state.refreshing,
-            state.shimmer_phase,
-            state.mode == ViewMode::List && state.filter_editing,
-        );
-        let visible_for_events = visible;
-        if state.mode == ViewMode::List {
-            clamp_selection(&mut state.selected_idx, visible_for_events.len());
-        }
-
-        terminal
-            .draw(|f| {
-                let area = f.area();
-                let block = Block::default().borders(Borders::ALL);
-                let inner = block.inner(area);
-                f.render_widget(block, area);
-                let parts = Layout::default()
-                    .constraints([Constraint::Min(0), Constraint::Length(1)])
-                    .split(inner);
-
-                // Content (top)
-                let text = Text::from(lines.clone());
-                let content = Paragraph::new(text);
-                f.render_widget(content, parts[0]);
-
-                // Footer (bottom): update notice on the left, controls on the right.
-                // Calculate the width needed for shortcuts to ensure they fit
-                let shortcuts_width = footer_line_width.min(parts[1].width as usize) as u16;
-                let footer_width = parts[1].width;
-
-                // Allocate space: shortcuts get what they need (or available space), update notice gets the rest
-                let shortcuts_allocated = shortcuts_width.min(footer_width);
-                let update_notice_allocated = footer_width.saturating_sub(shortcuts_allocated);
-
-                let footer_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Length(update_notice_allocated.max(1)),
-                        Constraint::Length(shortcuts_allocated.max(1)),
-                    ])
-                    .split(parts[1]);
-
-                let left_line = if let Some(msg) = state.update_notice.as_deref() {
-                    let w = footer_chunks[0].width.max(1) as usize;
-                    let txt = truncate_ellipsis(msg, w.saturating_sub(1));
-                    Line::from(Span::styled(
-                        txt,
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD),
-                    ))
-                } else {
-                    Line::from("")
-                };
-                let footer_left = Paragraph::new(left_line).alignment(Alignment::Left);
-                f.render_widget(footer_left, footer_chunks[0]);
-
-                let footer_right = Paragraph::new(footer_line.clone()).alignment(Alignment::Right);
-                f.render_widget(footer_right, footer_chunks[1]);
-
-                if state.help_open {
-                    // Centered modal overlay.
-                    let w = (inner.width as f32 * 0.85) as u16;
-                    let h = (inner.height as f32 * 0.70) as u16;
-                    let popup = ratatui::layout::Rect {
-                        x: inner.x + (inner.width.saturating_sub(w)) / 2,
-                        y: inner.y + (inner.height.saturating_sub(h)) / 2,
-                        width: w.max(20),
-                        height: h.max(8),
-                    };
-                    f.render_widget(Clear, popup);
-                    let b = Block::default().title("Help").borders(Borders::ALL);
-                    let t = Text::from(help_lines());
-                    let p = Paragraph::new(t).block(b).wrap(Wrap { trim: false });
-                    f.render_widget(p, popup);
+            visible_for_events = visible;
+            if state.mode == ViewMode::List {
+                if let Some(key) = &state.pending_selection {
+                    let listed = visible_for_events
+                        .iter()
+                        .position(|&i| state.prs.get(i).is_some_and(|p| &p.pr.pr_key == key));
+                    if let Some(pos) = listed {
+                        state.selected_idx = pos;
+                        state.pending_selection = None;
+                    }
                 }
-            })
-            .map_err(|e| format!("Draw failed: {e}"))?;
+                clamp_selection(&mut state.selected_idx, visible_for_events.len());
+            }
 
-        // Keep the UI responsive on quit/navigation.
-        if event::poll(Duration::from_millis(50)).map_err(|e| format!("Event poll failed: {e}"))? {
-            if let Event::Key(k) = event::read().map_err(|e| format!("Event read failed: {e}"))? {
-                if k.kind != KeyEventKind::Press {
-                    continue;
-                }
-                if state.help_open {
-                    match k.code {
-                        KeyCode::Char('?') | KeyCode::Esc => state.help_open = false,
-                        KeyCode::Char('q') => state.help_open = false,
-                        _ => {}
+            let frame = terminal
+                .draw(|f| {
+                    let area = f.area();
+                    if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+                        let notice = too_small_lines(area.width, area.height);
+                        f.render_widget(
+                            Paragraph::new(Text::from(notice))
+                                .alignment(Alignment::Center)
+                                .wrap(Wrap { trim: true }),
+                            area,
+                        );
+                        return;
                     }
-                    continue;
-                }
+                    let mut block = Block::default().borders(Borders::ALL);
+                    if state.oncall {
+                        block = block.title(Span::styled(
+                            " 🚒 ON-CALL ",
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        ));
+                    }
+                    if state.offline {
+                        block = block.title(Span::styled(
+                            " 📴 OFFLINE ",
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ));
+                    }
+                    let inner = block.inner(area);
+                    f.render_widget(block, area);
+                    let parts = Layout::default()
+                        .constraints([
+                            Constraint::Length(1),
+                            Constraint::Min(0),
+                            Constraint::Length(1),
+                        ])
+                        .split(inner);
 
-                if state.filter_editing {
-                    match (k.code, k.modifiers) {
-                        (KeyCode::Up, _) => {
-                            if state.mode == ViewMode::List {
-                                let n = visible_for_events.len();
-                                if n == 0 {
-                                    // nothing to select
-                                } else if state.selected_idx == 0 {
-                                    state.selected_idx = n - 1;
-                                } else {
-                                    state.selected_idx -= 1;
-                                }
-                            }
-                        }
-                        (KeyCode::Down, _) => {
-                            if state.mode == ViewMode::List {
-                                let n = visible_for_events.len();
-                                if n == 0 {
-                                    // nothing to select
-                                } else if state.selected_idx + 1 >= n {
-                                    state.selected_idx = 0;
-                                } else {
-                                    state.selected_idx += 1;
-                                }
-                            }
-                        }
-                        (KeyCode::Esc, _) => {
-                            // Exit filter mode and clear the filter text (back to unfiltered list).
-                            state.filter_prev_query.clear();
-                            state.filter_edit.clear();
-                            state.filter_query.clear();
-                            state.filter_editing = false;
-                            state.selected_idx = 0;
-                        }
-                        (KeyCode::Backspace, _) => {
-                            state.filter_edit.pop();
-                            state.filter_query = state.filter_edit.clone();
+                    // Header (top): whose queue this is and how it was fetched.
+                    f.render_widget(Paragraph::new(header_line(&state, inner.width)), parts[0]);
+
+                    // Content, under the refresh error banner when there is one.
+                    let content_area = match &error_banner {
+                        Some(msg) => {
+                            let rows = Layout::default()
+                                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                                .split(parts[1]);
+                            let banner = Span::styled(
+                                truncate_ellipsis(msg, rows[0].width as usize),
+                                Style::default()
+                                    .fg(Color::White)
+                                    .bg(Color::Red)
+                                    .add_modifier(Modifier::BOLD),
+                            );
+                            f.render_widget(Paragraph::new(Line::from(banner)), rows[0]);
+                            rows[1]
                         }
-                        (KeyCode::Enter, _) => {
-                            // Live filtering already applied; Enter opens the selected PR (same as list mode).
-                            if state.mode == ViewMode::List {
-                                if let Some(pr_idx) =
-                                    visible_for_events.get(state.selected_idx).copied()
-                                {
-                                    if let Some(pr) = state.prs.get_mut(pr_idx) {
-                                        open_in_browser(&pr.pr.url);
-                                    }
-                                }
+                        None => parts[1],
+                    };
+                    let text = Text::from(lines.clone());
+                    let content = Paragraph::new(text);
+                    f.render_widget(content, content_area);
+
+                    // Footer (bottom): update notice on the left, controls on the right.
+                    // Calculate the width needed for shortcuts to ensure they fit
+                    let shortcuts_width = footer_line_width.min(parts[2].width as usize) as u16;
+                    let footer_width = parts[2].width;
+
+                    // Allocate space: shortcuts get what they need (or available space), update notice gets the rest
+                    let shortcuts_allocated = shortcuts_width.min(footer_width);
+                    let update_notice_allocated = footer_width.saturating_sub(shortcuts_allocated);
+
+                    let footer_chunks = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([
+                            Constraint::Length(update_notice_allocated.max(1)),
+                            Constraint::Length(shortcuts_allocated.max(1)),
+                        ])
+                        .split(parts[2]);
+
+                    let left_line = if let Some((msg, tone)) =
+                        footer_note(&state, refresh_intervals.list_secs, now_unix())
+                    {
+                        let w = footer_chunks[0].width.max(1) as usize;
+                        let txt = truncate_ellipsis(&msg, w.saturating_sub(1));
+                        let style = match tone {
+                            Tone::Quiet => {
+                                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM)
                             }
-                            state.filter_editing = false;
-                            state.filter_edit.clear();
-                        }
-                        (KeyCode::Char('x'), m) if m.contains(KeyModifiers::CONTROL) => {
-                            state.filter_prev_query.clear();
-                            state.filter_edit.clear();
-                            state.filter_query.clear();
-                            state.only_needs_you = false;
-                            state.only_failing_ci = false;
-                            state.only_review_requested = false;
-                            state.selected_idx = 0;
-                        }
-                        (KeyCode::Char('n'), m) if m.contains(KeyModifiers::CONTROL) => {
-                            state.only_needs_you = !state.only_needs_you;
-                            state.selected_idx = 0;
-                        }
-                        (KeyCode::Char('c'), m) if m.contains(KeyModifiers::CONTROL) => {
-                            state.only_failing_ci = !state.only_failing_ci;
-                            state.selected_idx = 0;
-                        }
-                        (KeyCode::Char('v'), m) if m.contains(KeyModifiers::CONTROL) => {
-                            state.only_review_requested = !state.only_review_requested;
-                            state.selected_idx = 0;
-                        }
-                        (KeyCode::Char(ch), _) => {
-                            if !ch.is_control() {
-                                state.filter_edit.push(ch);
-                                state.filter_query = state.filter_edit.clone();
+                            Tone::Loud => Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                            Tone::Alarm => {
+                                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
                             }
-                        }
-                        _ => {}
+                        };
+                        Line::from(Span::styled(txt, style))
+                    } else {
+                        Line::from("")
+                    };
+                    let footer_left = Paragraph::new(left_line).alignment(Alignment::Left);
+                    f.render_widget(footer_left, footer_chunks[0]);
+
+                    let footer_right =
+                        Paragraph::new(footer_line.clone()).alignment(Alignment::Right);
+                    f.render_widget(footer_right, footer_chunks[1]);
+
+                    if state.error_log_open {
+                        let w = (inner.width as f32 * 0.85) as u16;
+                        let h = (inner.height as f32 * 0.70) as u16;
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w.max(20),
+                            height: h.max(8),
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default()
+                            .title("Refresh errors")
+                            .borders(Borders::ALL);
+                        let t = Text::from(error_log_lines(&state, now_unix()));
+                        let p = Paragraph::new(t).block(b).wrap(Wrap { trim: false });
+                        f.render_widget(p, popup);
                     }
-                    continue;
-                }
 
-                match k.code {
-                    KeyCode::Char('?') => {
-                        state.help_open = !state.help_open;
-                    }
-                    KeyCode::Esc => {
-                        // In list mode, Esc clears any active filter/toggles even when not currently typing.
-                        if state.mode == ViewMode::List {
-                            if !state.filter_query.is_empty()
-                                || state.only_needs_you
-                                || state.only_failing_ci
-                                || state.only_review_requested
-                            {
-                                state.filter_query.clear();
-                                state.only_needs_you = false;
-                                state.only_failing_ci = false;
-                                state.only_review_requested = false;
-                                state.selected_idx = 0;
-                            }
-                        }
+                    if state.help_open {
+                        // Centered modal overlay.
+                        let w = (inner.width as f32 * 0.85) as u16;
+                        let h = (inner.height as f32 * 0.70) as u16;
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w.max(20),
+                            height: h.max(8),
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Help").borders(Borders::ALL);
+                        let t = Text::from(help_lines(state.usage_mode));
+                        let p = Paragraph::new(t).block(b).wrap(Wrap { trim: false });
+                        f.render_widget(p, popup);
                     }
-                    KeyCode::Char('q') => break,
-                    KeyCode::Char('r') => {
-                        if !state.refreshing {
-                            state.refreshing = true;
-                            state.shimmer_phase = 0;
-                            state.last_refresh_started = Some(Instant::now());
-                            let (tx, rx) = mpsc::channel();
-                            refresh_rx = Some(rx);
-                            // Run the refresh off-thread so we can animate shimmer + keep quit responsive.
-                            // Note: closure may block on network.
-                            let rf = Arc::clone(&refresh_fn);
-                            std::thread::spawn(move || {
-                                let res = rf();
-                                let _ = tx.send(res);
-                            });
-                        }
+
+                    let preview_pr = state
+                        .preview_open
+                        .then(|| visible_for_events.get(state.selected_idx))
+                        .flatten()
+                        .and_then(|&i| state.prs.get(i));
+                    if let Some(pr) = preview_pr {
+                        let w = (inner.width as f32 * 0.8) as u16;
+                        let lines = build_preview_lines(pr, w.saturating_sub(2));
+                        let h = (lines.len() as u16 + 2).min(inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Preview").borders(Borders::ALL);
+                        f.render_widget(Paragraph::new(Text::from(lines)).block(b), popup);
                     }
-                    KeyCode::Char('f') => {
-                        if state.mode == ViewMode::Details {
-                            let pr_opt = state
-                                .details_pr_key
-                                .as_ref()
-                                .and_then(|k| state.prs.iter_mut().find(|p| &p.pr.pr_key == k));
-                            if let Some(pr) = pr_opt {
-                                let url = pr
-                                    .pr
-                                    .ci_checks
-                                    .iter()
-                                    .find(|c| c.state.is_failure())
-                                    .and_then(|c| c.url.as_deref())
-                                    .unwrap_or(pr.pr.url.as_str());
-                                open_in_browser(url);
-                            }
-                        }
+
+                    if let Some(selected) = state.merge_picker {
+                        let w = 32u16.min(inner.width);
+                        let h = (MergeMethod::ALL.len() as u16 + 3).min(inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Merge").borders(Borders::ALL);
+                        let p = Paragraph::new(Text::from(merge_picker_lines(selected))).block(b);
+                        f.render_widget(p, popup);
                     }
-                    KeyCode::Char('/') => {
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            state.filter_editing = true;
-                            state.filter_prev_query = state.filter_query.clone();
-                            state.filter_edit = state.filter_query.clone();
-                            state.selected_idx = 0;
-                        }
+
+                    if let Some(picker) = &state.handoff_picker {
+                        let w = 36u16.min(inner.width);
+                        let h = (picker.logins.len() as u16 + 3).min(inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Hand off to").borders(Borders::ALL);
+                        let p = Paragraph::new(Text::from(handoff_picker_lines(picker))).block(b);
+                        f.render_widget(p, popup);
                     }
-                    KeyCode::Char('x') => {
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            state.filter_query.clear();
-                            state.only_needs_you = false;
-                            state.only_failing_ci = false;
-                            state.only_review_requested = false;
-                            state.selected_idx = 0;
+
+                    if let Some((_, selected)) = state.snooze_picker {
+                        let w = 32u16.min(inner.width);
+                        let h = (SnoozeChoice::ALL.len() as u16 + 3).min(inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Snooze").borders(Borders::ALL);
+                        let p = Paragraph::new(Text::from(snooze_picker_lines(selected))).block(b);
+                        f.render_widget(p, popup);
+                    }
+
+                    if let Some(view) = &state.ignored_view {
+                        let w = ((inner.width as f32 * 0.7) as u16).min(inner.width);
+                        let h = (view.rows.len().max(1) as u16 + 3)
+                            .min((inner.height as f32 * 0.7) as u16)
+                            .max(4.min(inner.height));
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default().title("Ignored").borders(Borders::ALL);
+                        let body =
+                            ignored_view_lines(view, w.saturating_sub(2), h.saturating_sub(2));
+                        f.render_widget(Paragraph::new(Text::from(body)).block(b), popup);
+                    }
+
+                    if let Some(view) = &state.log_view {
+                        let w = ((inner.width as f32 * 0.9) as u16).min(inner.width);
+                        let h = ((inner.height as f32 * 0.8) as u16)
+                            .clamp(5.min(inner.height), inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default()
+                            .title(format!(
+                                "Log: {} (last {} lines)",
+                                view.check,
+                                view.lines.len()
+                            ))
+                            .title_bottom(" ↑/↓ PgUp/PgDn Home/End scroll  Esc close ")
+                            .borders(Borders::ALL);
+                        let body_h = h.saturating_sub(2);
+                        view.page.set(body_h as usize);
+                        let body = log_view_lines(view, body_h);
+                        f.render_widget(Paragraph::new(Text::from(body)).block(b), popup);
+                    }
+
+                    if let Some(view) = &state.shed_view {
+                        let w = ((inner.width as f32 * 0.8) as u16).min(inner.width);
+                        let h = (view.rows.len().max(1) as u16 + 3)
+                            .min((inner.height as f32 * 0.7) as u16)
+                            .max(4.min(inner.height));
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default()
+                            .title("Lighten your review load")
+                            .borders(Borders::ALL);
+                        let body = shed_view_lines(view, w.saturating_sub(2), h.saturating_sub(2));
+                        f.render_widget(Paragraph::new(Text::from(body)).block(b), popup);
+                    }
+
+                    if let Some(view) = &state.today_view {
+                        let w = ((inner.width as f32 * 0.8) as u16).min(inner.width);
+                        let h = (view.items.len().max(1) as u16 + 3)
+                            .min((inner.height as f32 * 0.7) as u16)
+                            .max(4.min(inner.height));
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default()
+                            .title(format!("Today · {} done", view.done))
+                            .borders(Borders::ALL);
+                        let body = today_view_lines(
+                            view,
+                            w.saturating_sub(2),
+                            h.saturating_sub(2),
+                            now_unix(),
+                        );
+                        f.render_widget(Paragraph::new(Text::from(body)).block(b), popup);
+                    }
+
+                    if let Some(composer) = &state.composer {
+                        let w = ((inner.width as f32 * 0.8) as u16).min(inner.width);
+                        let h = ((inner.height as f32 * 0.5) as u16)
+                            .clamp(5.min(inner.height), inner.height);
+                        let popup = ratatui::layout::Rect {
+                            x: inner.x + (inner.width.saturating_sub(w)) / 2,
+                            y: inner.y + (inner.height.saturating_sub(h)) / 2,
+                            width: w,
+                            height: h,
+                        };
+                        f.render_widget(Clear, popup);
+                        let b = Block::default()
+                            .title(format!("Comment on {}", composer.pr_key))
+                            .title_bottom(" Enter new line  Ctrl+s post  Esc discard ")
+                            .borders(Borders::ALL);
+                        let body = composer_lines(
+                            &composer.text,
+                            w.saturating_sub(2),
+                            h.saturating_sub(2),
+                        );
+                        f.render_widget(Paragraph::new(Text::from(body)).block(b), popup);
+                    }
+                    state.color_mode.degrade(f.buffer_mut());
+                })
+                .map_err(|e| format!("Draw failed: {e}"))?;
+            on_frame(frame.buffer);
+        }
+
+        // Poll fast only while something is animating or about to arrive.
+        let busy = state.refreshing
+            || lookup_rx.is_some()
+            || merge_rx.is_some()
+            || comment_rx.is_some()
+            || branch_update_rx.is_some()
+            || draft_rx.is_some()
+            || decline_rx.is_some()
+            || approve_rx.is_some()
+            || log_rx.is_some();
+        let poll = if busy { ACTIVE_POLL } else { IDLE_POLL };
+        // Keys of a running macro come before anything typed.
+        let (k, replayed) = match state.macros.next_replayed() {
+            Some(k) => (k, true),
+            None => match events.next(poll)? {
+                Input::Key(k) => (k, false),
+                Input::Idle => continue,
+                Input::End => break,
+            },
+        };
+        if k.kind != KeyEventKind::Press {
+            continue;
+        }
+        dirty = true;
+        state.status_message = None;
+        state.pending_selection = None;
+        let typing = state.composer.is_some() || state.filter_editing;
+        if let MacroKey::Consumed(msg) = state.macros.feed(&k, typing, replayed) {
+            state.status_message = msg;
+            continue;
+        }
+        if state.help_open {
+            match k.code {
+                KeyCode::Char('?') | KeyCode::Esc => state.help_open = false,
+                KeyCode::Char('q') => state.help_open = false,
+                _ => {}
+            }
+            continue;
+        }
+        if state.error_log_open {
+            if matches!(
+                k.code,
+                KeyCode::Char('e') | KeyCode::Esc | KeyCode::Char('q')
+            ) {
+                state.error_log_open = false;
+            }
+            continue;
+        }
+        if let Some(selected) = state.merge_picker {
+            let n = MergeMethod::ALL.len();
+            match k.code {
+                KeyCode::Up => state.merge_picker = Some((selected + n - 1) % n),
+                KeyCode::Down => state.merge_picker = Some((selected + 1) % n),
+                KeyCode::Enter => {
+                    state.merge_picker = None;
+                    let method = MergeMethod::ALL[selected];
+                    if let Some(pr) = state.details_pr().map(|p| p.pr.clone()) {
+                        state.status_message =
+                            Some(format!("Merging {} ({})…", pr.pr_key, method.label()));
+                        let (tx, rx) = mpsc::channel();
+                        merge_rx = Some(rx);
+                        let mf = Arc::clone(&merge_fn);
+                        std::thread::spawn(move || {
+                            let _ = tx.send((pr.pr_key.clone(), mf(&pr, method)));
+                        });
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') => {
+                    state.merge_picker = None;
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if let Some(mut picker) = state.handoff_picker.take() {
+            let n = picker.logins.len();
+            match k.code {
+                KeyCode::Up => picker.selected = (picker.selected + n - 1) % n,
+                KeyCode::Down => picker.selected = (picker.selected + 1) % n,
+                KeyCode::Enter => {
+                    // Draft the comment in the composer so it can be edited before posting.
+                    let login = &picker.logins[picker.selected];
+                    state.composer = Some(Composer {
+                        text: state.handoff_message.replace("{login}", login),
+                        pr_key: picker.pr_key,
+                    });
+                    continue;
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') => continue,
+                _ => {}
+            }
+            state.handoff_picker = Some(picker);
+            continue;
+        }
+        if let Some((pr_key, selected)) = state.snooze_picker.take() {
+            let n = SnoozeChoice::ALL.len();
+            match k.code {
+                KeyCode::Up => state.snooze_picker = Some((pr_key, (selected + n - 1) % n)),
+                KeyCode::Down => state.snooze_picker = Some((pr_key, (selected + 1) % n)),
+                KeyCode::Enter if state.select_mode => {
+                    // Bulk snooze: every marked PR, reporting the first failure.
+                    let choice = SnoozeChoice::ALL[selected];
+                    let keys: Vec<String> = state
+                        .marked_prs()
+                        .iter()
+                        .map(|p| p.pr.pr_key.clone())
+                        .collect();
+                    let mut snoozed = 0;
+                    let mut failure = None;
+                    for key in &keys {
+                        let Some(i) = state.prs.iter().position(|p| &p.pr.pr_key == key) else {
+                            continue;
+                        };
+                        match snooze(conn, &state.prs[i].pr, choice, now_unix()) {
+                            Ok(()) => {
+                                state.prs_mut()[i].snoozed = true;
+                                state.marked.remove(key);
+                                snoozed += 1;
+                            }
+                            Err(e) => failure = failure.or(Some(e)),
                         }
                     }
-                    KeyCode::Char('n') => {
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            state.only_needs_you = !state.only_needs_you;
-                            state.selected_idx = 0;
+                    state.status_message = Some(match failure {
+                        None => format!("Snoozed {snoozed} PRs ({})", choice.label()),
+                        Some(e) => format!("Snoozed {snoozed} of {} PRs; {e}", keys.len()),
+                    });
+                }
+                KeyCode::Enter => {
+                    let choice = SnoozeChoice::ALL[selected];
+                    if let Some(i) = state.prs.iter().position(|p| p.pr.pr_key == pr_key) {
+                        match snooze(conn, &state.prs[i].pr, choice, now_unix()) {
+                            Ok(()) => {
+                                state.prs_mut()[i].snoozed = true;
+                                state.status_message =
+                                    Some(format!("Snoozed {pr_key} ({})", choice.label()));
+                            }
+                            Err(e) => state.status_message = Some(e),
                         }
                     }
-                    KeyCode::Char('c') => {
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            state.only_failing_ci = !state.only_failing_ci;
-                            state.selected_idx = 0;
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('z') => {}
+                _ => state.snooze_picker = Some((pr_key, selected)),
+            }
+            continue;
+        }
+        if let Some(mut view) = state.ignored_view.take() {
+            let n = view.rows.len();
+            match k.code {
+                KeyCode::Up if n > 0 => view.selected = (view.selected + n - 1) % n,
+                KeyCode::Down if n > 0 => view.selected = (view.selected + 1) % n,
+                KeyCode::Enter | KeyCode::Char('i') if n > 0 => {
+                    let key = view.rows[view.selected].0.clone();
+                    match set_ignored(conn, &key, false) {
+                        Ok(()) => {
+                            view.rows.remove(view.selected);
+                            clamp_selection(&mut view.selected, view.rows.len());
+                            // Refresh right away so the restored PR shows up again.
+                            state.last_refresh_started = None;
+                            state.status_message = Some(format!("Restored {key}"));
                         }
+                        Err(e) => state.status_message = Some(e),
                     }
-                    KeyCode::Char('v') => {
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            state.only_review_requested = !state.only_review_requested;
-                            state.selected_idx = 0;
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('I') => continue,
+                _ => {}
+            }
+            state.ignored_view = Some(view);
+            continue;
+        }
+        if let Some(view) = state.log_view.as_mut() {
+            let top = view.lines.len().saturating_sub(view.page.get());
+            match k.code {
+                KeyCode::Up => view.from_bottom = (view.from_bottom + 1).min(top),
+                KeyCode::Down => view.from_bottom = view.from_bottom.saturating_sub(1),
+                KeyCode::PageUp => view.from_bottom = (view.from_bottom + LOG_SCROLL_PAGE).min(top),
+                KeyCode::PageDown => {
+                    view.from_bottom = view.from_bottom.saturating_sub(LOG_SCROLL_PAGE)
+                }
+                KeyCode::Home => view.from_bottom = top,
+                KeyCode::End => view.from_bottom = 0,
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('l') => state.log_view = None,
+                _ => {}
+            }
+            continue;
+        }
+        if let Some(mut view) = state.shed_view.take() {
+            let n = view.rows.len();
+            let picked = view.rows.get(view.selected).cloned();
+            let action = match k.code {
+                KeyCode::Up if n > 0 => {
+                    view.selected = (view.selected + n - 1) % n;
+                    None
+                }
+                KeyCode::Down if n > 0 => {
+                    view.selected = (view.selected + 1) % n;
+                    None
+                }
+                KeyCode::Enter => picked.as_ref().map(|s| s.action),
+                KeyCode::Char('d') => Some(ShedAction::Decline),
+                KeyCode::Char('h') => Some(ShedAction::HandOff),
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => continue,
+                _ => None,
+            };
+            match (action, picked) {
+                (Some(ShedAction::Decline), Some(s)) => {
+                    let pr = state
+                        .prs
+                        .iter()
+                        .find(|p| p.pr.pr_key == s.pr_key)
+                        .map(|p| p.pr.clone());
+                    match (pr, state.viewer_login.clone()) {
+                        _ if decline_rx.is_some() => {
+                            state.status_message =
+                                Some("A review is still being declined".to_string());
+                        }
+                        (Some(pr), Some(login)) => {
+                            state.status_message =
+                                Some(format!("Declining the review of {}…", pr.pr_key));
+                            let (tx, rx) = mpsc::channel();
+                            decline_rx = Some(rx);
+                            let df = Arc::clone(&decline_fn);
+                            std::thread::spawn(move || {
+                                let _ = tx.send((pr.pr_key.clone(), df(&pr, &login)));
+                            });
+                        }
+                        (_, None) => {
+                            state.status_message =
+                                Some("Signed-in login unknown; cannot decline".to_string());
                         }
+                        (None, _) => {}
                     }
-                    KeyCode::Char('p') => {
-                        // Toggle pin on selected PR
-                        if state.mode == ViewMode::List && !state.filter_editing {
-                            if let Some(pr_idx) =
-                                visible_for_events.get(state.selected_idx).copied()
-                            {
-                                if let Some(pr) = state.prs.get_mut(pr_idx) {
-                                    if let Ok(new_state) = toggle_pin(conn, &pr.pr.pr_key) {
-                                        pr.is_pinned = new_state;
-                                        // Re-sort PRs to reflect new pin state
-                                        state.prs.sort_by(|a, b| {
-                                            b.is_pinned
-                                                .cmp(&a.is_pinned)
-                                                .then_with(|| b.score.cmp(&a.score))
-                                                .then_with(|| {
-                                                    b.pr.updated_at_unix.cmp(&a.pr.updated_at_unix)
-                                                })
-                                        });
-                                    }
-                                }
-                            }
+                }
+                (Some(ShedAction::HandOff), Some(s)) => {
+                    if comment_rx.is_some() {
+                        state.status_message = Some("A comment is still being posted".to_string());
+                    } else {
+                        open_handoff_picker(&mut state, &s.pr_key);
+                        if state.handoff_picker.is_some() {
+                            continue;
                         }
                     }
-                    KeyCode::Tab => {
-                        if state.mode == ViewMode::List {
-                            if let Some(pr_idx) =
-                                visible_for_events.get(state.selected_idx).copied()
-                            {
-                                if let Some(pr) = state.prs.get(pr_idx) {
-                                    state.details_pr_key = Some(pr.pr.pr_key.clone());
-                                    state.mode = ViewMode::Details;
-                                    state.details_ci_selected = 0;
-                                    state.details_last_auto_refresh = Some(Instant::now());
-                                }
-                            }
-                        } else {
-                            state.mode = ViewMode::List;
-                            state.details_last_auto_refresh = None;
+                }
+                _ => {}
+            }
+            state.shed_view = Some(view);
+            continue;
+        }
+
+        if let Some(mut view) = state.today_view.take() {
+            let n = view.items.len();
+            let picked = view.items.get(view.selected).map(|i| i.pr_key.clone());
+            let mark = match k.code {
+                KeyCode::Up if n > 0 => {
+                    view.selected = (view.selected + n - 1) % n;
+                    None
+                }
+                KeyCode::Down if n > 0 => {
+                    view.selected = (view.selected + 1) % n;
+                    None
+                }
+                KeyCode::Enter => {
+                    if let Some(key) = picked {
+                        if let Some(pr) = state.prs_mut().iter_mut().find(|p| p.pr.pr_key == key) {
+                            mark_viewed(conn, pr);
                         }
+                        state.open_details(key);
+                        continue;
                     }
-                    KeyCode::Up => {
-                        if state.mode == ViewMode::List {
-                            let n = visible_for_events.len();
-                            if n == 0 {
-                                // nothing to select
-                            } else if state.selected_idx == 0 {
-                                state.selected_idx = n - 1;
-                            } else {
-                                state.selected_idx -= 1;
-                            }
+                    None
+                }
+                KeyCode::Char('d') => Some(TodayMark::Done),
+                KeyCode::Char('s') => Some(TodayMark::Skipped),
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('T') => continue,
+                _ => None,
+            };
+            if let (Some(mark), Some(key)) = (mark, picked) {
+                let now = now_unix();
+                match set_today_mark(conn, &today_key(now), &key, mark.as_str()) {
+                    Ok(()) => {
+                        let selected = view.selected;
+                        view = TodayView::load(&state, conn, now);
+                        view.selected = selected.min(view.items.len().saturating_sub(1));
+                    }
+                    Err(e) => state.status_message = Some(e),
+                }
+            }
+            state.today_view = Some(view);
+            continue;
+        }
+
+        if let Some(composer) = state.composer.as_mut() {
+            match (k.code, k.modifiers) {
+                (KeyCode::Esc, _) => state.composer = None,
+                (KeyCode::Char('s'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    let pr = state
+                        .prs
+                        .iter()
+                        .chain(state.lookups.iter())
+                        .find(|p| p.pr.pr_key == composer.pr_key)
+                        .map(|p| p.pr.clone());
+                    if composer.text.trim().is_empty() {
+                        state.status_message = Some("Comment is empty".to_string());
+                    } else if let Some(pr) = pr {
+                        let draft = std::mem::take(composer);
+                        state.composer = None;
+                        state.status_message = Some(format!("Posting comment on {}…", pr.pr_key));
+                        let (tx, rx) = mpsc::channel();
+                        comment_rx = Some(rx);
+                        let cf = Arc::clone(&comment_fn);
+                        std::thread::spawn(move || {
+                            let res = cf(&pr, &draft.text);
+                            let _ = tx.send((draft, res));
+                        });
+                    } else {
+                        state.status_message =
+                            Some(format!("{} is no longer listed", composer.pr_key));
+                    }
+                }
+                _ => {
+                    composer.edit(&k);
+                }
+            }
+            continue;
+        }
+
+        if state.preview_open {
+            // Any key dismisses the preview; Space and Esc only do that.
+            state.preview_open = false;
+            if matches!(k.code, KeyCode::Char(' ') | KeyCode::Esc) {
+                continue;
+            }
+        }
+
+        if state.filter_editing {
+            match (k.code, k.modifiers) {
+                (KeyCode::Up, _) => {
+                    if state.mode == ViewMode::List {
+                        let n = visible_for_events.len();
+                        if n == 0 {
+                            // nothing to select
+                        } else if state.selected_idx == 0 {
+                            state.selected_idx = n - 1;
                         } else {
-                            if state.details_ci_selected > 0 {
-                                state.details_ci_selected -= 1;
-                            }
+                            state.selected_idx -= 1;
                         }
                     }
-                    KeyCode::Down => {
-                        if state.mode == ViewMode::List {
-                            let n = visible_for_events.len();
-                            if n == 0 {
-                                // nothing to select
-                            } else if state.selected_idx + 1 >= n {
-                                state.selected_idx = 0;
-                            } else {
-                                state.selected_idx += 1;
-                            }
+                }
+                (KeyCode::Down, _) => {
+                    if state.mode == ViewMode::List {
+                        let n = visible_for_events.len();
+                        if n == 0 {
+                            // nothing to select
+                        } else if state.selected_idx + 1 >= n {
+                            state.selected_idx = 0;
                         } else {
-                            // Clamp based on selected PR's available CI checks.
-                            let ci_len = state
-                                .details_pr_key
-                                .as_ref()
-                                .and_then(|k| state.prs.iter().find(|p| &p.pr.pr_key == k))
-                                .map(|p| p.pr.ci_checks.len())
-                                .unwrap_or(0);
-                            if ci_len > 0 && state.details_ci_selected + 1 < ci_len {
-                                state.details_ci_selected += 1;
-                            }
+                            state.selected_idx += 1;
                         }
                     }
-                    KeyCode::Enter => {
-                        if state.mode == ViewMode::List {
-                            if let Some(pr_idx) =
-                                visible_for_events.get(state.selected_idx).copied()
-                            {
-                                if let Some(pr) = state.prs.get_mut(pr_idx) {
-                                    open_in_browser(&pr.pr.url);
-                                }
-                            }
-                        } else {
-                            // In details view, Enter opens the selected CI check URL if present, else PR URL.
-                            let pr_opt = state
-                                .details_pr_key
-                                .as_ref()
-                                .and_then(|k| state.prs.iter_mut().find(|p| &p.pr.pr_key == k));
-                            if let Some(pr) = pr_opt {
-                                let url = pr
-                                    .pr
-                                    .ci_checks
-                                    .get(state.details_ci_selected)
-                                    .and_then(|c| c.url.as_deref())
-                                    .unwrap_or(pr.pr.url.as_str());
-                                open_in_browser(url);
+                }
+                (KeyCode::Esc, _) => {
+                    // Exit filter mode and clear the filter text (back to unfiltered list).
+                    state.filter_prev_query.clear();
+                    state.filter_edit.clear();
+                    state.filter_query.clear();
+                    state.filter_editing = false;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Backspace, _) => {
+                    state.filter_edit.pop();
+                    state.filter_query = state.filter_edit.clone();
+                }
+                (KeyCode::Enter, _) => {
+                    // Live filtering already applied; Enter opens the selected PR (same as list mode).
+                    if state.mode == ViewMode::List {
+                        if let Some(pr_idx) = visible_for_events.get(state.selected_idx).copied() {
+                            if let Some(pr) = state.prs_mut().get_mut(pr_idx) {
+                                mark_viewed(conn, pr);
+                                open_in_browser(&pr.pr.url);
+                                spawn_mark_read(mark_read_fn.as_ref(), &pr.pr, &mark_read_tx);
                             }
                         }
                     }
-                    _ => {}
+                    state.filter_editing = false;
+                    state.filter_edit.clear();
+                }
+                (KeyCode::Char('x'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.filter_prev_query.clear();
+                    state.filter_edit.clear();
+                    state.filter_query.clear();
+                    state.only_needs_you = false;
+                    state.only_failing_ci = false;
+                    state.only_review_requested = false;
+                    state.only_sla = false;
+                    state.only_mentioned = false;
+                    state.only_bots = false;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('n'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_needs_you = !state.only_needs_you;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('c'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_failing_ci = !state.only_failing_ci;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('v'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_review_requested = !state.only_review_requested;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('s'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_sla = !state.only_sla;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('m'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_mentioned = !state.only_mentioned;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char('b'), m) if m.contains(KeyModifiers::CONTROL) => {
+                    state.only_bots = !state.only_bots;
+                    state.selected_idx = 0;
+                }
+                (KeyCode::Char(ch), _) => {
+                    if !ch.is_control() {
+                        state.filter_edit.push(ch);
+                        state.filter_query = state.filter_edit.clone();
+                    }
                 }
+                _ => {}
             }
+            continue;
         }
-    }
 
-    disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {e}"))?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)
-        .map_err(|e| format!("Failed to leave alt screen: {e}"))?;
-    terminal
-        .show_cursor()
-        .map_err(|e| format!("Failed to show cursor: {e}"))?;
-    Ok(())
+        if let Some(keys) = state.confirm_open_all.take() {
+            if k.code == KeyCode::Char('y') {
+                let n = open_prs(
+                    conn,
+                    &mut state,
+                    &keys,
+                    mark_read_fn.as_ref(),
+                    &mark_read_tx,
+                );
+                state.status_message = Some(format!("Opened {n} NEEDS YOU PRs"));
+            } else {
+                state.status_message = Some("Opening cancelled".to_string());
+            }
+            continue;
+        }
+
+        if state.confirm_approve {
+            state.confirm_approve = false;
+            if k.code != KeyCode::Char('y') {
+                state.status_message = Some("Approval cancelled".to_string());
+            } else if approve_rx.is_some() {
+                state.status_message = Some("PRs are still being approved".to_string());
+            } else {
+                let prs: Vec<Pr> = state
+                    .marked_prs()
+                    .into_iter()
+                    .filter(|p| approve_readiness(&p.pr).is_ok())
+                    .map(|p| p.pr.clone())
+                    .collect();
+                state.status_message = Some(format!("Approving {} PRs…", prs.len()));
+                let (tx, rx) = mpsc::channel();
+                approve_rx = Some(rx);
+                let af = Arc::clone(&approve_fn);
+                // One worker for the batch, one request per PR.
+                std::thread::spawn(move || {
+                    let results = prs.iter().map(|pr| (pr.pr_key.clone(), af(pr))).collect();
+                    let _ = tx.send(results);
+                });
+            }
+            continue;
+        }
+
+        if state.select_mode && state.mode == ViewMode::List {
+            let handled = match k.code {
+                KeyCode::Char(' ') => {
+                    let key = visible_for_events
+                        .get(state.selected_idx)
+                        .and_then(|&i| state.prs.get(i))
+                        .map(|p| p.pr.pr_key.clone());
+                    if let Some(key) = key {
+                        if !state.marked.remove(&key) {
+                            state.marked.insert(key);
+                        }
+                        let last = visible_for_events.len().saturating_sub(1);
+                        state.selected_idx = (state.selected_idx + 1).min(last);
+                    }
+                    true
+                }
+                KeyCode::Char('*') => {
+                    // Everything the filter shows (snoozed rows only while listed), or
+                    // nothing when all of it is marked already.
+                    let keys: Vec<String> = state
+                        .filtered()
+                        .into_iter()
+                        .filter_map(|i| state.prs.get(i))
+                        .filter(|p| !p.snoozed || state.snoozed_open)
+                        .map(|p| p.pr.pr_key.clone())
+                        .collect();
+                    if keys.iter().all(|k| state.marked.contains(k)) {
+                        state.marked.clear();
+                    } else {
+                        state.marked.extend(keys);
+                    }
+                    true
+                }
+                KeyCode::Char('o') => {
+                    let keys: Vec<String> = state
+                        .marked_prs()
+                        .iter()
+                        .map(|p| p.pr.pr_key.clone())
+                        .collect();
+                    let n = open_prs(
+                        conn,
+                        &mut state,
+                        &keys,
+                        mark_read_fn.as_ref(),
+                        &mark_read_tx,
+                    );
+                    state.status_message = Some(format!("Opened {n} PRs"));
+                    true
+                }
+                KeyCode::Char('O') => {
+                    let keys: Vec<String> = state
+                        .marked_prs()
+                        .iter()
+                        .map(|p| p.pr.pr_key.clone())
+                        .collect();
+                    for key in &keys {
+                        if let Some(pr) = state.prs_mut().iter_mut().find(|p| &p.pr.pr_key == key) {
+                            mark_viewed(conn, pr);
+                        }
+                    }
+                    state.status_message = Some(format!("Marked {} PRs opened", keys.len()));
+                    true
+                }
+                KeyCode::Char('z') => {
+                    match state.marked_prs().first() {
+                        Some(p) => state.snooze_picker = Some((p.pr.pr_key.clone(), 0)),
+                        None => state.status_message = Some("No PRs marked".to_string()),
+                    }
+                    true
+                }
+                KeyCode::Char('A') => {
+                    let marked = state.marked_prs();
+                    let ready = marked
+                        .iter()
+                        .filter(|p| approve_readiness(&p.pr).is_ok())
+                        .count();
+                    let own = marked.len() - ready;
+                    let skipped = if own > 0 {
+                        format!(" ({own} of your own skipped)")
+                    } else {
+                        String::new()
+                    };
+                    if ready == 0 {
+                        state.status_message = Some(format!("Nothing to approve{skipped}"));
+                    } else {
+                        state.status_message = Some(format!(
+                            "Approve {ready} PRs{skipped}? y to confirm, any other key cancels"
+                        ));
+                        state.confirm_approve = true;
+                    }
+                    true
+                }
+                KeyCode::Char('V') | KeyCode::Esc => {
+                    state.end_select_mode();
+                    true
+                }
+                _ => false,
+            };
+            if handled {
+                continue;
+            }
+        }
+
+        match k.code {
+            KeyCode::Char('?') => {
+                state.help_open = !state.help_open;
+            }
+            KeyCode::Esc if error_banner(&state).is_some() => {
+                state.last_refresh_error = None;
+            }
+            KeyCode::Char('e') => {
+                state.error_log_open = true;
+            }
+            KeyCode::Esc => {
+                // In list mode, Esc clears any active filter/toggles even when not currently typing.
+                if state.mode == ViewMode::List {
+                    if !state.filter_query.is_empty()
+                        || state.only_needs_you
+                        || state.only_failing_ci
+                        || state.only_review_requested
+                        || state.only_sla
+                        || state.only_mentioned
+                        || state.only_bots
+                    {
+                        state.filter_query.clear();
+                        state.only_needs_you = false;
+                        state.only_failing_ci = false;
+                        state.only_review_requested = false;
+                        state.only_sla = false;
+                        state.only_mentioned = false;
+                        state.only_bots = false;
+                        state.selected_idx = 0;
+                    }
+                }
+            }
+            KeyCode::Char('q') => break,
+            KeyCode::Char('r') => {
+                if !state.refreshing {
+                    state.refreshing = true;
+                    state.shimmer_phase = 0;
+                    state.last_refresh_started = Some(Instant::now());
+                    let (tx, rx) = mpsc::channel();
+                    refresh_rx = Some(rx);
+                    // Run the refresh off-thread so we can animate shimmer + keep quit responsive.
+                    // Note: closure may block on network.
+                    let rf = Arc::clone(&refresh_fn);
+                    std::thread::spawn(move || {
+                        let res = rf();
+                        let _ = tx.send(res);
+                    });
+                }
+            }
+            KeyCode::Char('f') => {
+                if state.mode == ViewMode::Details {
+                    if let Some(pr) = state.details_pr() {
+                        let url = pr
+                            .pr
+                            .ci_checks
+                            .iter()
+                            .find(|c| c.state.is_failure())
+                            .and_then(|c| c.url.as_deref())
+                            .unwrap_or(pr.pr.url.as_str());
+                        open_in_browser(url);
+                    }
+                }
+            }
+            KeyCode::Char('l') if state.mode == ViewMode::Details => {
+                if log_rx.is_some() {
+                    state.status_message = Some("A log is still being fetched".to_string());
+                } else if let Some(pr) = state.details_pr().map(|p| p.pr.clone()) {
+                    // The selected check when it failed, else the first failing one.
+                    let selected = match state.details_ci_rows().get(state.details_ci_selected) {
+                        Some(CiRow::Check { idx, .. }) => pr.ci_checks.get(*idx).cloned(),
+                        _ => None,
+                    };
+                    let check = selected
+                        .filter(|c| c.state.is_failure())
+                        .or_else(|| pr.ci_checks.iter().find(|c| c.state.is_failure()).cloned());
+                    match check {
+                        Some(check) => {
+                            state.status_message =
+                                Some(format!("Fetching the log of {}…", check.name));
+                            let (tx, rx) = mpsc::channel();
+                            log_rx = Some(rx);
+                            let lf = Arc::clone(&check_log_fn);
+                            std::thread::spawn(move || {
+                                let _ = tx.send((check.name.clone(), lf(&pr, &check)));
+                            });
+                        }
+                        None => {
+                            state.status_message = Some("No failing check on this PR".to_string());
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('V') if state.mode == ViewMode::List => {
+                state.select_mode = true;
+            }
+            KeyCode::Char('O') if state.mode == ViewMode::List => {
+                let keys = state.needs_you_keys();
+                if keys.is_empty() {
+                    state.status_message = Some("Nothing in NEEDS YOU".to_string());
+                } else if keys.len() > OPEN_ALL_CONFIRM_OVER {
+                    state.status_message = Some(format!(
+                        "Open {} NEEDS YOU PRs in the browser? y to confirm, any other key cancels",
+                        keys.len()
+                    ));
+                    state.confirm_open_all = Some(keys);
+                } else {
+                    let n = open_prs(
+                        conn,
+                        &mut state,
+                        &keys,
+                        mark_read_fn.as_ref(),
+                        &mark_read_tx,
+                    );
+                    state.status_message = Some(format!("Opened {n} NEEDS YOU PRs"));
+                }
+            }
+            KeyCode::Char(' ') if state.mode == ViewMode::List => {
+                state.preview_open = visible_for_events.get(state.selected_idx).is_some();
+            }
+            KeyCode::Char('m') if state.mode == ViewMode::Details => {
+                // Merging is offered only for mergeable PRs with green CI.
+                if merge_rx.is_some() {
+                    state.status_message = Some("A merge is already in progress".to_string());
+                } else if let Some(pr) = state.details_pr() {
+                    match merge_readiness(&pr.pr) {
+                        Ok(()) => state.merge_picker = Some(0),
+                        Err(e) => state.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Char('u') if state.mode == ViewMode::Details => {
+                // Offered only while GitHub reports the branch behind its base.
+                if branch_update_rx.is_some() {
+                    state.status_message =
+                        Some("A branch update is already in progress".to_string());
+                } else if let Some(pr) = state.details_pr().map(|p| p.pr.clone()) {
+                    match update_branch_readiness(&pr) {
+                        Ok(()) => {
+                            state.status_message =
+                                Some(format!("Updating {} with its base branch…", pr.pr_key));
+                            let (tx, rx) = mpsc::channel();
+                            branch_update_rx = Some(rx);
+                            let uf = Arc::clone(&update_branch_fn);
+                            std::thread::spawn(move || {
+                                let _ = tx.send((pr.pr_key.clone(), uf(&pr)));
+                            });
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Char('d') if state.mode == ViewMode::Details => {
+                if draft_rx.is_some() {
+                    state.status_message =
+                        Some("A draft change is already in progress".to_string());
+                } else if let Some(pr) = state.details_pr().map(|p| p.pr.clone()) {
+                    match draft_toggle(&pr) {
+                        Ok(draft) => {
+                            state.status_message = Some(if draft {
+                                format!("Converting {} to a draft…", pr.pr_key)
+                            } else {
+                                format!("Marking {} ready for review…", pr.pr_key)
+                            });
+                            let (tx, rx) = mpsc::channel();
+                            draft_rx = Some(rx);
+                            let df = Arc::clone(&set_draft_fn);
+                            std::thread::spawn(move || {
+                                let _ = tx.send((pr.pr_key.clone(), draft, df(&pr, draft)));
+                            });
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Char('c') if state.mode == ViewMode::Details => {
+                if comment_rx.is_some() {
+                    state.status_message = Some("A comment is still being posted".to_string());
+                } else if let Some(pr) = state.details_pr() {
+                    state.composer = Some(Composer {
+                        pr_key: pr.pr.pr_key.clone(),
+                        text: String::new(),
+                    });
+                }
+            }
+            KeyCode::Char('h') if state.mode == ViewMode::Details => {
+                if comment_rx.is_some() {
+                    state.status_message = Some("A comment is still being posted".to_string());
+                } else if let Some(key) = state.details_pr().map(|p| p.pr.pr_key.clone()) {
+                    open_handoff_picker(&mut state, &key);
+                }
+            }
+            KeyCode::Char('o') => {
+                // Open a PR link from the clipboard (fetched on demand if not already listed).
+                match read_clipboard().as_deref().and_then(parse_ref) {
+                    None => {
+                        state.status_message = Some("Clipboard has no PR link".to_string());
+                    }
+                    Some((owner, repo, number)) => {
+                        let key = format!("{owner}/{repo}#{number}");
+                        let known = state
+                            .prs
+                            .iter()
+                            .chain(state.lookups.iter())
+                            .any(|p| p.pr.pr_key == key);
+                        if known {
+                            state.open_details(key);
+                        } else if lookup_rx.is_none() {
+                            state.status_message = Some(format!("Fetching {key}…"));
+                            let (tx, rx) = mpsc::channel();
+                            lookup_rx = Some(rx);
+                            let lf = Arc::clone(&lookup_fn);
+                            std::thread::spawn(move || {
+                                let _ = tx.send(lf(&key));
+                            });
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('a') => {
+                // Toggle tracking of the shown PR so it refreshes even outside the attention set.
+                let key = if state.mode == ViewMode::Details {
+                    state.details_pr().map(|p| p.pr.pr_key.clone())
+                } else {
+                    visible_for_events
+                        .get(state.selected_idx)
+                        .and_then(|&i| state.prs.get(i))
+                        .map(|p| p.pr.pr_key.clone())
+                };
+                if let Some(key) = key {
+                    let res = match untrack_pr(conn, &key) {
+                        Ok(true) => Ok(format!("Stopped tracking {key}")),
+                        Ok(false) => {
+                            track_pr(conn, &key, now_unix()).map(|_| format!("Tracking {key}"))
+                        }
+                        Err(e) => Err(e),
+                    };
+                    state.status_message = Some(res.unwrap_or_else(|e| e));
+                }
+            }
+            KeyCode::Char('/') => {
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    state.filter_editing = true;
+                    state.filter_prev_query = state.filter_query.clone();
+                    state.filter_edit = state.filter_query.clone();
+                    state.selected_idx = 0;
+                }
+            }
+            KeyCode::Char(c @ '1'..='9') if state.mode == ViewMode::Details => {
+                let links = state
+                    .details_pr()
+                    .map(|p| state.quick_links.for_pr(p))
+                    .unwrap_or_default();
+                let idx = c as usize - '1' as usize;
+                match links.get(idx) {
+                    Some((name, url)) => {
+                        open_in_browser(url);
+                        state.status_message = Some(format!("Opened {name}"));
+                    }
+                    None => {
+                        state.status_message = Some(format!("No quick link {c} for this repo"));
+                    }
+                }
+            }
+            KeyCode::Char('[') if state.mode == ViewMode::Details => {
+                state.details_thread_selected = state.details_thread_selected.saturating_sub(1);
+            }
+            KeyCode::Char(']') if state.mode == ViewMode::Details => {
+                let n = state.details_pr().map_or(0, |p| checklist_threads(p).len());
+                if state.details_thread_selected + 1 < n {
+                    state.details_thread_selected += 1;
+                }
+            }
+            KeyCode::Char('x') if state.mode == ViewMode::Details => {
+                // Check the selected requested change off (or back on); stored locally only.
+                let target = state.details_pr().and_then(|p| {
+                    let threads = checklist_threads(p);
+                    let t = threads.get(state.details_thread_selected)?;
+                    Some((p.pr.pr_key.clone(), t.key(), threads.len()))
+                });
+                if let Some((pr_key, thread_key, total)) = target {
+                    let set = state.addressed_threads.entry(pr_key.clone()).or_default();
+                    let addressed = !set.contains(&thread_key);
+                    match set_thread_addressed(conn, &pr_key, &thread_key, addressed, now_unix()) {
+                        Ok(()) => {
+                            if addressed {
+                                set.insert(thread_key);
+                            } else {
+                                set.remove(&thread_key);
+                            }
+                            let done = state
+                                .details_pr()
+                                .map(|p| {
+                                    let checklist = Checklist {
+                                        addressed: state.addressed_threads.get(&pr_key),
+                                        selected: 0,
+                                    };
+                                    checklist_threads(p)
+                                        .iter()
+                                        .filter(|t| checklist.is_addressed(t))
+                                        .count()
+                                })
+                                .unwrap_or(0);
+                            state.status_message = Some(if done == total {
+                                format!(
+                                    "All {total} requested changes addressed: re-request review"
+                                )
+                            } else {
+                                format!("{done}/{total} requested changes addressed")
+                            });
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Char('x') => {
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    state.filter_query.clear();
+                    state.only_needs_you = false;
+                    state.only_failing_ci = false;
+                    state.only_review_requested = false;
+                    state.only_sla = false;
+                    state.only_mentioned = false;
+                    state.only_bots = false;
+                    state.selected_idx = 0;
+                }
+            }
+            KeyCode::Char('L')
+                if state.mode == ViewMode::List
+                    && !state.filter_editing
+                    && state.usage_mode != UsageMode::Full =>
+            {
+                state.status_message = Some(format!(
+                    "Split layout needs your PRs and review requests (mode = \"{}\")",
+                    state.usage_mode.as_str()
+                ));
+            }
+            KeyCode::Char('L') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.layout = match state.layout {
+                    ListLayout::Split => ListLayout::Categories,
+                    ListLayout::Categories | ListLayout::Repos | ListLayout::Topics => {
+                        ListLayout::Split
+                    }
+                };
+                state.split_focus = SplitPane::default();
+                state.split_parked = 0;
+                state.selected_idx = 0;
+            }
+            KeyCode::Left | KeyCode::Right
+                if state.mode == ViewMode::List && state.layout == ListLayout::Split =>
+            {
+                // Each pane keeps its own selection.
+                state.split_focus = state.split_focus.other();
+                std::mem::swap(&mut state.selected_idx, &mut state.split_parked);
+            }
+            KeyCode::Left | KeyCode::Right
+                if state.mode == ViewMode::List
+                    && matches!(state.layout, ListLayout::Repos | ListLayout::Topics) =>
+            {
+                let topic_of = if state.layout == ListLayout::Topics {
+                    topics(&state.prs)
+                } else {
+                    Vec::new()
+                };
+                let block_of = |i: usize| {
+                    let p = state.prs.get(i)?;
+                    Some(if state.layout == ListLayout::Topics {
+                        topic_of[i].as_deref().unwrap_or(OTHER_TOPIC).to_string()
+                    } else {
+                        format!("{}/{}", p.pr.owner, p.pr.repo)
+                    })
+                };
+                let selected = visible_for_events
+                    .get(state.selected_idx)
+                    .and_then(|&i| block_of(i));
+                if let Some(block) = selected {
+                    // Blocks are contiguous, so the block's first row is where the folded
+                    // line (or the first PR, once unfolded) ends up.
+                    if let Some(start) = visible_for_events
+                        .iter()
+                        .position(|&i| block_of(i).as_ref() == Some(&block))
+                    {
+                        state.selected_idx = start;
+                    }
+                    if k.code == KeyCode::Left {
+                        state.collapsed_blocks.insert(block);
+                    } else {
+                        state.collapsed_blocks.remove(&block);
+                    }
+                }
+            }
+            KeyCode::Char('g') if state.mode == ViewMode::List && !state.filter_editing => {
+                // Cycle: sections → sections by repo group (if configured) → by repository
+                // → by topic.
+                if state.layout == ListLayout::Repos {
+                    state.layout = ListLayout::Topics;
+                } else if state.layout == ListLayout::Topics {
+                    state.layout = ListLayout::Categories;
+                } else if !state.group_rows && !state.groups.is_empty() {
+                    state.group_rows = true;
+                } else {
+                    state.group_rows = false;
+                    state.layout = ListLayout::Repos;
+                }
+                state.selected_idx = 0;
+            }
+            KeyCode::Char('n') => {
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    state.only_needs_you = !state.only_needs_you;
+                    state.selected_idx = 0;
+                }
+            }
+            KeyCode::Char('c') => {
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    state.only_failing_ci = !state.only_failing_ci;
+                    state.selected_idx = 0;
+                }
+            }
+            KeyCode::Char('v') => {
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    state.only_review_requested = !state.only_review_requested;
+                    state.selected_idx = 0;
+                }
+            }
+            KeyCode::Char('s') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.only_sla = !state.only_sla;
+                state.selected_idx = 0;
+            }
+            KeyCode::Char('m') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.only_mentioned = !state.only_mentioned;
+                state.selected_idx = 0;
+            }
+            KeyCode::Char('b') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.only_bots = !state.only_bots;
+                state.selected_idx = 0;
+            }
+            KeyCode::Char('z') if state.mode == ViewMode::List && !state.filter_editing => {
+                let selected = visible_for_events.get(state.selected_idx).and_then(|&i| {
+                    state
+                        .prs
+                        .get(i)
+                        .map(|p| (i, p.pr.pr_key.clone(), p.snoozed))
+                });
+                match selected {
+                    Some((_, key, false)) => state.snooze_picker = Some((key, 0)),
+                    Some((pr_idx, key, true)) => match unsnooze_pr(conn, &key) {
+                        Ok(()) => {
+                            state.prs_mut()[pr_idx].snoozed = false;
+                            state.status_message = Some(format!("Woke up {key}"));
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    },
+                    None => {}
+                }
+            }
+            KeyCode::Char('Z') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.snoozed_open = !state.snoozed_open;
+            }
+            KeyCode::Char('i') if state.mode == ViewMode::List && !state.filter_editing => {
+                let selected = visible_for_events
+                    .get(state.selected_idx)
+                    .and_then(|&i| state.prs.get(i))
+                    .map(|p| p.pr.pr_key.clone());
+                if let Some(key) = selected {
+                    match set_ignored(conn, &key, true) {
+                        Ok(()) => {
+                            state.prs_mut().retain(|p| p.pr.pr_key != key);
+                            state.status_message =
+                                Some(format!("Ignored {key} (I lists ignored PRs)"));
+                        }
+                        Err(e) => state.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Char('T') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.today_view = Some(TodayView::load(&state, conn, now_unix()));
+            }
+            KeyCode::Char('w') if state.mode == ViewMode::List && !state.filter_editing => {
+                if let (Some(window_fn), Some(days)) = (&window_fn, state.days) {
+                    let next = next_day_window(days);
+                    state.days = Some(next);
+                    // A refresh or backfill still running was for the old window.
+                    refresh_rx = None;
+                    window_rx = None;
+                    state.refreshing = false;
+                    // The cache answers at once; only a wider window needs a fetch.
+                    match window_fn(next, false) {
+                        Ok(prs) => *state.prs_mut() = prs,
+                        Err(e) => state.status_message = Some(e),
+                    }
+                    if next > days {
+                        state.refreshing = true;
+                        state.shimmer_phase = 0;
+                        let (tx, rx) = mpsc::channel();
+                        window_rx = Some(rx);
+                        let wf = Arc::clone(window_fn);
+                        std::thread::spawn(move || {
+                            let _ = tx.send(wf(next, true));
+                        });
+                    }
+                }
+            }
+            KeyCode::Char('S') if state.mode == ViewMode::List && !state.filter_editing => {
+                state.shed_view = Some(ShedView {
+                    rows: suggest_shedding(&state.prs, state.viewer_login.as_deref()),
+                    selected: 0,
+                });
+            }
+            KeyCode::Char('I') if state.mode == ViewMode::List && !state.filter_editing => {
+                match load_ignored_prs(conn) {
+                    Ok(rows) => {
+                        state.ignored_view = Some(IgnoredView {
+                            rows: rows
+                                .iter()
+                                .map(|r| (r.pr_key.clone(), cached_title(r)))
+                                .collect(),
+                            selected: 0,
+                        });
+                    }
+                    Err(e) => state.status_message = Some(e),
+                }
+            }
+            KeyCode::Char('p') => {
+                // Toggle pin on selected PR
+                if state.mode == ViewMode::List && !state.filter_editing {
+                    if let Some(pr_idx) = visible_for_events.get(state.selected_idx).copied() {
+                        if let Some(pr) = state.prs_mut().get_mut(pr_idx) {
+                            if let Ok(new_state) = toggle_pin(conn, &pr.pr.pr_key) {
+                                pr.is_pinned = new_state;
+                                // Re-sort PRs to reflect new pin state
+                                sort_for_display(&mut state.prs);
+                            }
+                        }
+                    }
+                }
+            }
+            KeyCode::Tab => {
+                if state.mode == ViewMode::List {
+                    if let Some(pr_idx) = visible_for_events.get(state.selected_idx).copied() {
+                        if let Some(pr) = state.prs_mut().get_mut(pr_idx) {
+                            mark_viewed(conn, pr);
+                            let key = pr.pr.pr_key.clone();
+                            state.open_details(key);
+                        }
+                    }
+                } else {
+                    state.mode = ViewMode::List;
+                    state.details_last_auto_refresh = None;
+                }
+            }
+            KeyCode::Up => {
+                if state.mode == ViewMode::List {
+                    let n = visible_for_events.len();
+                    if n == 0 {
+                        // nothing to select
+                    } else if state.selected_idx == 0 {
+                        state.selected_idx = n - 1;
+                    } else {
+                        state.selected_idx -= 1;
+                    }
+                } else {
+                    if state.details_ci_selected > 0 {
+                        state.details_ci_selected -= 1;
+                    }
+                }
+            }
+            KeyCode::Down => {
+                if state.mode == ViewMode::List {
+                    let n = visible_for_events.len();
+                    if n == 0 {
+                        // nothing to select
+                    } else if state.selected_idx + 1 >= n {
+                        state.selected_idx = 0;
+                    } else {
+                        state.selected_idx += 1;
+                    }
+                } else {
+                    // Clamp based on the selected PR's check rows.
+                    let ci_len = state.details_ci_rows().len();
+                    if ci_len > 0 && state.details_ci_selected + 1 < ci_len {
+                        state.details_ci_selected += 1;
+                    }
+                }
+            }
+            KeyCode::PageUp if state.mode == ViewMode::Details => {
+                state.details_conversation_scroll = state
+                    .details_conversation_scroll
+                    .saturating_sub(CONVERSATION_SCROLL_STEP);
+            }
+            KeyCode::PageDown if state.mode == ViewMode::Details => {
+                // Clamped to the conversation length on the next render.
+                state.details_conversation_scroll += CONVERSATION_SCROLL_STEP;
+            }
+            KeyCode::Enter => {
+                if state.mode == ViewMode::List {
+                    if let Some(pr_idx) = visible_for_events.get(state.selected_idx).copied() {
+                        if let Some(pr) = state.prs_mut().get_mut(pr_idx) {
+                            mark_viewed(conn, pr);
+                            open_in_browser(&pr.pr.url);
+                            spawn_mark_read(mark_read_fn.as_ref(), &pr.pr, &mark_read_tx);
+                        }
+                    }
+                } else {
+                    // In details view, Enter expands/collapses a check group, or opens the
+                    // selected CI check URL if present, else PR URL.
+                    match state
+                        .details_ci_rows()
+                        .get(state.details_ci_selected)
+                        .cloned()
+                    {
+                        Some(CiRow::Group { label, .. }) => {
+                            if !state.details_ci_expanded.remove(&label) {
+                                state.details_ci_expanded.insert(label);
+                            }
+                        }
+                        row => {
+                            if let Some(pr) = state.details_pr() {
+                                let url = match row {
+                                    Some(CiRow::Check { idx, .. }) => pr.pr.ci_checks.get(idx),
+                                    _ => None,
+                                }
+                                .and_then(|c| c.url.as_deref())
+                                .unwrap_or(pr.pr.url.as_str());
+                                open_in_browser(url);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    title.clear();
+    if remember_ui_state {
+        let selected = match state.mode {
+            ViewMode::List => visible_for_events
+                .get(state.selected_idx)
+                .and_then(|&i| state.prs.get(i))
+                .map(|p| p.pr.pr_key.clone()),
+            _ => state.details_pr_key.clone(),
+        };
+        // Losing the saved spot is not worth an error on the way out.
+        let _ = save_ui_state(conn, &state.ui_state_entries(selected.as_deref()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::model::{
+        CiCheck, CiCheckState, CiState, Comment, MergeBlockers, Pr, ReviewState, Reviewer,
+    };
+    use needle_core::sla::SlaStatus;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn lines_to_text(lines: &[Line<'_>]) -> String {
+        lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn check(name: &str, state: CiCheckState, started_at_unix: Option<i64>) -> CiCheck {
+        CiCheck {
+            name: name.to_string(),
+            state,
+            url: Some(format!("https://example.com/checks/{name}")),
+            started_at_unix,
+        }
+    }
+
+    fn fixture_pr(
+        owner: &str,
+        repo: &str,
+        number: i64,
+        author: &str,
+        title: &str,
+        category: Category,
+        display_status: &str,
+    ) -> UiPr {
+        UiPr {
+            pr: Pr {
+                pr_key: format!("{owner}/{repo}#{number}"),
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                number,
+                author: author.to_string(),
+                title: title.to_string(),
+                url: format!("https://github.com/{owner}/{repo}/pull/{number}"),
+                updated_at_unix: NOW - 3600,
+                created_at_unix: None,
+                last_commit_sha: Some("abc1234".to_string()),
+                ci_state: CiState::Success,
+                ci_checks: Vec::new(),
+                review_state: ReviewState::None,
+                is_draft: false,
+                mergeable: Some("MERGEABLE".to_string()),
+                merge_state_status: Some("CLEAN".to_string()),
+                is_viewer_author: false,
+                is_assignee: false,
+                is_mentioned: false,
+                is_unsubscribed: false,
+                merge_blockers: None,
+                labels: Vec::new(),
+                review_requested_at_unix: None,
+                head_ref_name: None,
+                base_ref_name: None,
+                milestone: None,
+                project_status: None,
+                ci_from_previous_commit: false,
+                lines_changed: None,
+                last_force_push_at_unix: None,
+                last_reply_at_unix: None,
+                body_excerpt: None,
+                reviewers: Vec::new(),
+                comments: Vec::new(),
+                review_threads: Vec::new(),
+            },
+            score: 0,
+            category,
+            display_status: display_status.to_string(),
+            is_new_ci_failure: false,
+            is_pinned: false,
+            sla: None,
+            draft_ranked: false,
+            bot_section: false,
+            force_pushed_since_view: false,
+            reply_to_you: false,
+            snoozed: false,
+            opened_since_update: false,
+            inaccessible_since: None,
+            author_priority: None,
+        }
+    }
+
+    /// Deterministic PR set covering every list section.
+    fn fixture_prs() -> Vec<UiPr> {
+        let mut pinned = fixture_pr(
+            "acme-inc",
+            "billing-api",
+            842,
+            "anika",
+            "Fix idempotency for retries on charge capture",
+            Category::Waiting,
+            "✅ green (1h ago)",
+        );
+        pinned.is_pinned = true;
+
+        let mut needs = fixture_pr(
+            "acme-inc",
+            "payments-worker",
+            219,
+            "santiago",
+            "Handle duplicate webhook deliveries and add metrics",
+            Category::NeedsYou,
+            "❌ CI failed (new)",
+        );
+        needs.pr.ci_state = CiState::Failure;
+        needs.pr.review_state = ReviewState::Requested;
+        needs.score = 90;
+        needs.is_new_ci_failure = true;
+
+        let mut ready = fixture_pr(
+            "you-inc",
+            "product",
+            12,
+            "you",
+            "Ready: merge cleanup for onboarding flow",
+            Category::ReadyToMerge,
+            "✅ ready to merge (45m ago)",
+        );
+        ready.pr.is_viewer_author = true;
+        ready.pr.review_state = ReviewState::Approved;
+
+        let mut stale = fixture_pr(
+            "windmill-labs",
+            "sdk-rust",
+            98,
+            "chen",
+            "Add retry policy for 429/503 responses",
+            Category::Stale,
+            "✅ green (12d ago)",
+        );
+        stale.score = -20;
+
+        let mut draft = fixture_pr(
+            "orbit",
+            "web",
+            1932,
+            "santiago",
+            "Add keyboard navigation to project switcher",
+            Category::Waiting,
+            "🟡 CI running (4m)",
+        );
+        draft.pr.is_draft = true;
+        draft.pr.ci_state = CiState::Running;
+
+        vec![pinned, needs, ready, stale, draft]
+    }
+
+    fn fixture_details_pr() -> UiPr {
+        let mut pr = fixture_pr(
+            "windmill-labs",
+            "infra",
+            317,
+            "chen",
+            "Bump Postgres to 16.2 and tune autovacuum thresholds",
+            Category::NeedsYou,
+            "❌ CI failed",
+        );
+        pr.pr.ci_state = CiState::Failure;
+        pr.pr.ci_checks = vec![
+            check("test / unit", CiCheckState::Failure, None),
+            check(
+                "deploy / preview",
+                CiCheckState::Running,
+                Some(NOW - 41 * 60),
+            ),
+            check("build / linux", CiCheckState::Success, None),
+            check("e2e / chrome", CiCheckState::Neutral, None),
+        ];
+        pr.pr.merge_blockers = Some(MergeBlockers {
+            has_conflicts: true,
+            required_approvals: Some(2),
+            current_approvals: 0,
+            required_checks: vec!["test".to_string()],
+            failing_required_checks: vec!["test".to_string()],
+            is_behind_base: false,
+        });
+        pr
+    }
+
+    fn render_list(width: u16, height: u16, ui: UiPrefs) -> String {
+        let prs = fixture_prs();
+        let filtered: Vec<usize> = (0..prs.len()).collect();
+        let (lines, _) = build_list_lines(&prs, width, height, 0, &filtered, None, ui, None);
+        lines_to_text(&lines)
+    }
+
+    #[test]
+    fn list_snapshot_narrow() {
+        insta::assert_snapshot!(render_list(60, 40, UiPrefs::default()));
+    }
+
+    #[test]
+    fn list_snapshot_side_pane() {
+        let text = render_list(40, 40, UiPrefs::default());
+        assert!(!text.contains("TITLE"));
+        insta::assert_snapshot!(text);
+    }
+
+    #[test]
+    fn tiny_terminals_get_a_too_small_notice() {
+        let lines = too_small_lines(20, 6);
+        let text = lines_to_text(&lines);
+        assert!(text.contains("Terminal too small"));
+        assert!(text.contains("20×6, needs 24×8"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn list_snapshot_medium() {
+        insta::assert_snapshot!(render_list(100, 40, UiPrefs::default()));
+    }
+
+    #[test]
+    fn list_snapshot_wide() {
+        insta::assert_snapshot!(render_list(140, 40, UiPrefs::default()));
+    }
+
+    #[test]
+    fn list_snapshot_hidden_columns() {
+        let ui = UiPrefs {
+            hide_pr_numbers: true,
+            hide_repo: true,
+            hide_author: true,
+            keep_tail: TailColumns::default(),
+        };
+        insta::assert_snapshot!(render_list(80, 40, ui));
+    }
+
+    #[test]
+    fn truncation_keeps_trailing_issue_keys_and_tags() {
+        let title = "Fix login redirect for SSO users [PROJ-123]";
+        assert_eq!(
+            truncate_keep_tail(title, 30),
+            "Fix login redirect… [PROJ-123]"
+        );
+        assert_eq!(truncate_keep_tail(title, 60), title);
+        assert_eq!(
+            truncate_keep_tail("Bump the parser to handle nested lists PROJ-77", 24),
+            "Bump the parser… PROJ-77"
+        );
+        assert_eq!(
+            truncate_keep_tail("Retry webhooks on timeout (#42)", 20),
+            "Retry webhook… (#42)"
+        );
+        // No keyword, or too little room left for the text: cut at the end.
+        assert_eq!(
+            truncate_keep_tail("Refactor the session store", 12),
+            truncate_ellipsis("Refactor the session store", 12)
+        );
+        assert_eq!(truncate_keep_tail(title, 14), truncate_ellipsis(title, 14));
+        // Whitespace wider than a byte (no-break space) is still a word boundary.
+        assert_eq!(
+            truncate_keep_tail("Bump the parser to handle nested lists\u{a0}PROJ-77", 24),
+            "Bump the parser… PROJ-77"
+        );
+        assert!(TailColumns::parse(&["status".to_string()]).unwrap().status);
+        assert_eq!(
+            TailColumns::parse(&["titel".to_string()]).unwrap_err(),
+            "Unknown column `titel` (known: title, repo, author, status)"
+        );
+    }
+
+    #[test]
+    fn list_snapshot_truncated_height() {
+        insta::assert_snapshot!(render_list(100, 8, UiPrefs::default()));
+    }
+
+    #[test]
+    fn list_tracks_visible_rows_in_render_order() {
+        let prs = fixture_prs();
+        let filtered: Vec<usize> = (0..prs.len()).collect();
+        let (_, visible) =
+            build_list_lines(&prs, 100, 40, 0, &filtered, None, UiPrefs::default(), None);
+        // Pinned, then ready-to-merge, needs-you, waiting, stale; drafts last.
+        assert_eq!(visible, vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn list_cache_rebuilds_only_when_inputs_change() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        let mut cache = ListCache::default();
+        let (first, _) = cache.layout(&state, 100, 40, None);
+
+        // Writing through the field skips the version bump, so the cached layout stays.
+        state.prs[0].pr.title = "Renamed".to_string();
+        let (cached, _) = cache.layout(&state, 100, 40, None);
+        assert_eq!(lines_to_text(&cached), lines_to_text(&first));
+
+        // A different width re-renders rows but keeps the measured columns.
+        let (narrow, _) = cache.layout(&state, 60, 40, None);
+        assert!(lines_to_text(&narrow).contains("Renamed"));
+
+        state.prs_mut()[0].pr.title = "Renamed again".to_string();
+        let (fresh, _) = cache.layout(&state, 100, 40, None);
+        assert!(lines_to_text(&fresh).contains("Renamed again"));
+        assert_eq!(
+            lines_to_text(&fresh),
+            lines_to_text(
+                &build_list_lines(
+                    &state.prs,
+                    100,
+                    40,
+                    0,
+                    &[0, 1, 2, 3, 4],
+                    None,
+                    UiPrefs::default(),
+                    None
+                )
+                .0
+            )
+        );
+    }
+
+    #[test]
+    fn ignored_view_scrolls_to_the_selection() {
+        let rows: Vec<(String, String)> = (1..=6)
+            .map(|n| (format!("acme/api#{n}"), format!("Change {n}")))
+            .collect();
+        let view = IgnoredView { rows, selected: 4 };
+        let text = lines_to_text(&ignored_view_lines(&view, 40, 4));
+        assert!(!text.contains("acme/api#2 "));
+        assert!(text.contains("acme/api#3  Change 3"));
+        assert!(text.contains("acme/api#5  Change 5"));
+        assert!(text.ends_with("Enter restore  Esc close"));
+
+        let empty = ignored_view_lines(&IgnoredView::default(), 40, 4);
+        assert!(lines_to_text(&empty).contains("No ignored PRs"));
+    }
+
+    #[test]
+    fn log_view_shows_the_tail_and_scrolls_up() {
+        let mut view = LogView {
+            check: "test / unit".to_string(),
+            lines: (1..=10).map(|n| format!("line {n}")).collect(),
+            ..LogView::default()
+        };
+        view.lines
+            .push("##[error]Process completed with exit code 101.".to_string());
+        let text = lines_to_text(&log_view_lines(&view, 3));
+        assert_eq!(
+            text,
+            "line 9\nline 10\nerror: Process completed with exit code 101."
+        );
+
+        view.from_bottom = 4;
+        assert_eq!(
+            lines_to_text(&log_view_lines(&view, 3)),
+            "line 5\nline 6\nline 7"
+        );
+        // Scrolled past the top, the first lines still fill the pane.
+        view.from_bottom = 50;
+        assert_eq!(
+            lines_to_text(&log_view_lines(&view, 3)),
+            "line 1\nline 2\nline 3"
+        );
+    }
+
+    #[test]
+    fn conversation_scrolls_below_the_ci_checks() {
+        let comment = |author: &str, body: &str, at: i64| Comment {
+            author: author.to_string(),
+            body: body.to_string(),
+            created_at_unix: at,
+        };
+        let mut pr = fixture_details_pr();
+        pr.pr.comments = (0..6)
+            .map(|n| comment("mira", &format!("note {n}"), NOW - 3600 + n))
+            .collect();
+        pr.pr.review_threads = vec![ReviewThread {
+            path: Some("src/vacuum.rs".to_string()),
+            is_resolved: false,
+            comments: vec![comment(
+                "chen",
+                "why not 0.05 here? it matches the staging value",
+                NOW - 7200,
+            )],
+            started_by_viewer: false,
+        }];
+
+        let tall = lines_to_text(&build_details_lines(
+            &pr,
+            40,
+            80,
+            CiList {
+                rows: &check_rows(&pr),
+                selected: 0,
+                failures: None,
+            },
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        ));
+        assert!(tall.contains("6 comments · 1 threads (1 unresolved)"));
+        // Threads and comments interleave by time; long bodies wrap under the author.
+        let thread = tall.find("🧵 src/vacuum.rs · unresolved").unwrap();
+        assert!(thread < tall.find("note 0").unwrap());
+        assert!(tall.contains("    why not 0.05 here? it matches the\n    staging value"));
+        assert!(!tall.contains("PgUp/PgDn"));
+
+        let mut scroll = 100;
+        let short = lines_to_text(&build_details_lines(
+            &pr,
+            40,
+            40,
+            CiList {
+                rows: &check_rows(&pr),
+                selected: 0,
+                failures: None,
+            },
+            &mut scroll,
+            Checklist::default(),
+            &[],
+            NOW,
+        ));
+        assert!(short.contains("note 5"));
+        assert!(!short.contains("🧵"));
+        assert!(
+            short
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("PgUp/PgDn: scroll conversation")
+        );
+        assert!(scroll < 100, "scroll is clamped to the conversation");
+    }
+
+    #[test]
+    fn split_layout_keeps_a_selection_per_pane() {
+        let mut state =
+            AppState::new(fixture_prs(), UiPrefs::default()).with_layout(ListLayout::Split);
+        let filtered: Vec<usize> = (0..state.prs.len()).collect();
+        let cols = measure_columns(&state.prs, &filtered, state.ui);
+
+        let (lines, visible) = build_split_lines(
+            &state,
+            100,
+            30,
+            &filtered,
+            None,
+            &cols,
+            &CustomCells::default(),
+        );
+        let text = lines_to_text(&lines);
+        assert!(text.starts_with("▶ YOUR PRS (1)"));
+        assert_eq!(visible, vec![2], "only your PR is selectable");
+        let review = text.find("  NEEDS YOUR REVIEW (4)").unwrap();
+        assert_eq!(
+            text[..review].lines().count(),
+            15,
+            "the top pane keeps half the height"
+        );
+        assert!(text[..review].contains("> you-inc/product"));
+
+        state.split_focus = SplitPane::Review;
+        state.selected_idx = 1;
+        let (lines, visible) = build_split_lines(
+            &state,
+            100,
+            30,
+            &filtered,
+            None,
+            &cols,
+            &CustomCells::default(),
+        );
+        let text = lines_to_text(&lines);
+        assert!(text.contains("▶ NEEDS YOUR REVIEW (4)"));
+        assert!(!visible.contains(&2));
+        let review = text.find("NEEDS YOUR REVIEW").unwrap();
+        assert!(!text[..review].contains("> "));
+        assert_eq!(text[review..].matches("> ").count(), 1);
+    }
+
+    #[test]
+    fn requested_changes_count_resolved_and_checked_off_threads() {
+        let thread = |path: &str, author: &str, resolved: bool, at: i64| ReviewThread {
+            path: Some(path.to_string()),
+            is_resolved: resolved,
+            comments: vec![Comment {
+                author: author.to_string(),
+                body: format!("please fix {path}"),
+                created_at_unix: at,
+            }],
+            started_by_viewer: false,
+        };
+        let mut pr = fixture_details_pr();
+        pr.pr.is_viewer_author = true;
+        pr.pr.reviewers = vec![
+            Reviewer {
+                login: "mira".to_string(),
+                state: "CHANGES_REQUESTED".to_string(),
+            },
+            Reviewer {
+                login: "chen".to_string(),
+                state: "COMMENTED".to_string(),
+            },
+        ];
+        pr.pr.review_threads = vec![
+            thread("src/a.rs", "mira", false, NOW - 300),
+            thread("src/b.rs", "mira", true, NOW - 200),
+            thread("src/c.rs", "chen", false, NOW - 100),
+            thread("src/d.rs", "mira", false, NOW - 50),
+        ];
+        let render = |pr: &UiPr, checklist: Checklist| {
+            let rows = check_rows(pr);
+            let ci = CiList {
+                rows: &rows,
+                selected: 0,
+                failures: None,
+            };
+            lines_to_text(&build_details_lines(
+                pr,
+                80,
+                80,
+                ci,
+                &mut 0,
+                checklist,
+                &[],
+                NOW,
+            ))
+        };
+
+        // chen only commented, so their thread is not a requested change.
+        let text = render(&pr, Checklist::default());
+        assert!(text.contains("CHANGES REQUESTED  1/3 addressed"));
+        assert!(text.contains("> ☐ src/a.rs · @mira: please fix src/a.rs"));
+        assert!(text.contains("☑ src/b.rs · @mira: please fix src/b.rs (resolved)"));
+        assert!(!text.contains("☐ src/c.rs"));
+
+        let addressed: HashSet<String> = [pr.pr.review_threads[0].key()].into();
+        let text = render(
+            &pr,
+            Checklist {
+                addressed: Some(&addressed),
+                selected: 2,
+            },
+        );
+        assert!(text.contains("2/3 addressed"));
+        assert!(text.contains("> ☐ src/d.rs"));
+
+        let addressed: HashSet<String> = [0, 3]
+            .iter()
+            .map(|&i| pr.pr.review_threads[i].key())
+            .collect();
+        let text = render(
+            &pr,
+            Checklist {
+                addressed: Some(&addressed),
+                selected: 0,
+            },
+        );
+        assert!(text.contains("3/3 addressed · re-request review"));
+
+        // Someone else's PR: nothing for the viewer to check off.
+        pr.pr.is_viewer_author = false;
+        assert!(!render(&pr, Checklist::default()).contains("CHANGES REQUESTED"));
+    }
+
+    #[test]
+    fn failed_checks_show_how_often_they_failed_on_this_commit() {
+        let pr = fixture_details_pr();
+        let failures = HashMap::from([(
+            "test / unit".to_string(),
+            CheckFailure {
+                failures: 3,
+                first_failed_at: NOW - 2 * 3600,
+            },
+        )]);
+        let rows = check_rows(&pr);
+        let text = lines_to_text(&build_details_lines(
+            &pr,
+            100,
+            80,
+            CiList {
+                rows: &rows,
+                selected: 0,
+                failures: Some(&failures),
+            },
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        ));
+        assert!(text.contains("test / unit (failed ×3, first 2h ago)"));
+    }
+
+    #[test]
+    fn quick_links_match_the_repo_and_fill_in_the_pr() {
+        let link = |repos: &[&str], name: &str, url: &str| QuickLink {
+            repos: repos.iter().map(|r| r.to_string()).collect(),
+            name: name.to_string(),
+            url: url.to_string(),
+        };
+        let links = QuickLinks::new(vec![
+            link(
+                &["windmill-labs/infra"],
+                "Grafana",
+                "https://grafana.example.com/d/{repo}",
+            ),
+            link(&["acme/*"], "Deploys", "https://deploy.example.com"),
+            link(&[], "Preview", "https://pr-{number}.{owner}.example.com"),
+        ]);
+        let pr = fixture_details_pr();
+        let resolved = links.for_pr(&pr);
+        assert_eq!(
+            resolved,
+            vec![
+                (
+                    "Grafana".to_string(),
+                    "https://grafana.example.com/d/infra".to_string()
+                ),
+                (
+                    "Preview".to_string(),
+                    "https://pr-317.windmill-labs.example.com".to_string()
+                ),
+            ]
+        );
+
+        let rows = check_rows(&pr);
+        let ci = CiList {
+            rows: &rows,
+            selected: 0,
+            failures: None,
+        };
+        let text = lines_to_text(&build_details_lines(
+            &pr,
+            80,
+            80,
+            ci,
+            &mut 0,
+            Checklist::default(),
+            &resolved,
+            NOW,
+        ));
+        assert!(text.contains("LINKS"));
+        assert!(text.contains("  1 Grafana  https://grafana.example.com/d/infra"));
+        assert!(text.contains("  2 Preview  https://pr-317.windmill-labs.example.com"));
+    }
+
+    #[test]
+    fn ui_state_survives_a_restart() {
+        let conn = needle_core::db::TestDb::new("ui-state-test");
+        let restored = |keep_layout: bool| {
+            AppState::new(fixture_prs(), UiPrefs::default())
+                .with_layout(ListLayout::Split)
+                .with_saved_ui_state(&conn, keep_layout)
+        };
+
+        // Nothing saved yet: the usual start.
+        let fresh = restored(false);
+        assert!(fresh.filter_query.is_empty() && !fresh.only_sla);
+        assert_eq!(fresh.layout, ListLayout::Split);
+
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        state.filter_query = "repo:api is:draft".to_string();
+        state.only_failing_ci = true;
+        state.only_mentioned = true;
+        state.layout = ListLayout::Topics;
+        save_ui_state(&conn, &state.ui_state_entries(Some("acme/api#7"))).unwrap();
+
+        let back = restored(false);
+        assert_eq!(back.filter_query, "repo:api is:draft");
+        assert!(back.only_failing_ci && back.only_mentioned);
+        assert!(!back.only_needs_you && !back.only_review_requested && !back.only_sla);
+        assert_eq!(back.layout, ListLayout::Topics);
+        assert_eq!(back.pending_selection.as_deref(), Some("acme/api#7"));
+        assert_eq!(restored(true).layout, ListLayout::Split);
+    }
+
+    #[test]
+    fn today_view_is_worked_off_with_done_and_skip() {
+        let conn = needle_core::db::TestDb::new("today-view-test");
+        let mut prs = fixture_prs();
+        prs[1].pr.review_requested_at_unix = Some(NOW - 2 * 86_400);
+        let state = AppState::new(prs, UiPrefs::default());
+        let text = |view: &TodayView| lines_to_text(&today_view_lines(view, 120, 10, NOW));
+        let mark = |key: &str, mark: TodayMark| {
+            set_today_mark(&conn, &today_key(NOW), key, mark.as_str()).unwrap();
+            TodayView::load(&state, &conn, NOW)
+        };
+
+        let view = TodayView::load(&state, &conn, NOW);
+        let listed = text(&view);
+        assert!(listed.contains(" 1. review overdue  acme-inc/payments-worker#219  2d ago"));
+        assert!(listed.contains(" 2. merge           you-inc/product#12"));
+        assert_eq!(view.items.len(), 2);
+
+        let view = mark("acme-inc/payments-worker#219", TodayMark::Skipped);
+        assert!(
+            text(&view)
+                .contains(" 2. review overdue  acme-inc/payments-worker#219  2d ago · skipped")
+        );
+        let view = mark("acme-inc/payments-worker#219", TodayMark::Done);
+        assert_eq!((view.items.len(), view.done), (1, 1));
+        let view = mark("you-inc/product#12", TodayMark::Done);
+        assert!(text(&view).contains("All done for today"));
+    }
+
+    #[test]
+    fn header_names_the_queue_owner_and_scope() {
+        let scope = ScopeFilters {
+            orgs: vec!["acme".to_string()],
+            exclude_repos: vec!["acme/legacy".to_string()],
+            discussion: true,
+            ..ScopeFilters::default()
+        };
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default())
+            .with_viewer_login(Some("chen".to_string()))
+            .with_oncall(true)
+            .with_scope(&scope, 14);
+        assert_eq!(
+            lines_to_text(&[header_line(&state, 160)]),
+            "@chen · 🔥 1 · 🚢 1 · ✅ 0 · ⏳ 1 · profile: on-call · scope: org:acme -repo:acme/legacy involves-me:discussion · last 14d"
+        );
+        assert_eq!(
+            lines_to_text(&[header_line(&state, 30)]),
+            "@chen · 🔥 1 · 🚢 1 · ✅ 0 · …"
+        );
+
+        state.last_refreshed_at = Some(NOW);
+        state.rate_limit = Some(RateLimit {
+            limit: 5000,
+            remaining: 4321,
+            reset_at_unix: NOW + 1800,
+        });
+        assert!(
+            lines_to_text(&[header_line(&state, 160)]).starts_with(&format!(
+                "@chen · 🔥 1 · 🚢 1 · ✅ 0 · ⏳ 1 · refreshed {} · API 4321/5000 · profile",
+                clock_time(NOW)
+            ))
+        );
+
+        let offline = AppState::new(Vec::new(), UiPrefs::default())
+            .with_offline(true)
+            .with_scope(&ScopeFilters::default(), 30);
+        assert_eq!(
+            lines_to_text(&[header_line(&offline, 100)]),
+            "not signed in · 🔥 0 · 🚢 0 · ✅ 0 · ⏳ 0 · profile: default · scope: all repos · last 30d"
+        );
+    }
+
+    #[test]
+    fn offline_banner_yields_to_active_filters() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        assert_eq!(list_banner(&state), None);
+
+        state = state.with_offline(true);
+        assert_eq!(list_banner(&state).as_deref(), Some(OFFLINE_BANNER));
+
+        state.only_needs_you = true;
+        assert_eq!(list_banner(&state).as_deref(), Some("Filter: needs"));
+    }
+
+    #[test]
+    fn refresh_errors_show_a_banner_and_a_bounded_log() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        assert_eq!(error_banner(&state), None);
+
+        for i in 0..ERROR_LOG_LEN + 5 {
+            state.record_refresh_error(format!("timeout {i}\nretry later"), i as i64);
+        }
+        let last = ERROR_LOG_LEN + 4;
+        assert_eq!(
+            error_banner(&state),
+            Some(format!(
+                "⚠ Refresh failed: timeout {last}  (e: error log, Esc: dismiss)"
+            ))
+        );
+        assert_eq!(state.error_log.len(), ERROR_LOG_LEN);
+        assert_eq!(state.error_log.front().unwrap().1, "timeout 5\nretry later");
+
+        // Newest first in the log overlay.
+        let lines = error_log_lines(&state, last as i64);
+        assert!(
+            lines_to_text(&lines).starts_with(&format!("now\n  timeout {last}\n  retry later\n"))
+        );
+
+        state.mode = ViewMode::Details;
+        assert_eq!(error_banner(&state), None);
+        state.mode = ViewMode::List;
+        state = state.with_offline(true);
+        assert_eq!(error_banner(&state), None);
+    }
+
+    #[test]
+    fn handoff_offers_recent_collaborators_but_not_the_author_viewer_or_bots() {
+        let mut prs = fixture_prs();
+        prs[1].pr.reviewers = vec![
+            Reviewer {
+                login: "dana".to_string(),
+                state: "APPROVED".to_string(),
+            },
+            Reviewer {
+                login: "platform-team".to_string(),
+                state: "REQUESTED".to_string(),
+            },
+        ];
+        for author in ["renovate[bot]", "You", "dana"] {
+            prs[1].pr.comments.push(Comment {
+                author: author.to_string(),
+                body: "lgtm".to_string(),
+                created_at_unix: NOW - 60,
+            });
+        }
+        prs[3].pr.updated_at_unix = NOW - 600;
+
+        assert_eq!(
+            recent_collaborators(prs.iter(), "santiago", Some("you")),
+            vec!["dana", "chen", "anika"]
+        );
+
+        let state = AppState::new(prs, UiPrefs::default())
+            .with_handoff_message(Some("{login}: over to you".to_string()));
+        assert_eq!(
+            state.handoff_message.replace("{login}", "@dana"),
+            "@dana: over to you"
+        );
+    }
+
+    #[test]
+    fn custom_columns_sit_between_title_and_status() {
+        let columns = vec![
+            CustomColumn::parse("double", "number * 2").unwrap(),
+            CustomColumn::parse("waited", "now - review_requested_at").unwrap(),
+        ];
+        let state = AppState::new(fixture_prs(), UiPrefs::default()).with_custom_columns(columns);
+
+        let (lines, _) = ListCache::default().layout(&state, 140, 40, None);
+        let text = lines_to_text(&lines);
+        let header = text.lines().find(|l| l.contains("TITLE")).unwrap();
+        let (title, double, waited, status) = (
+            header.find("TITLE").unwrap(),
+            header.find("DOUBLE").unwrap(),
+            header.find("WAITED").unwrap(),
+            header.find("STATUS").unwrap(),
+        );
+        assert!(title < double && double < waited && waited < status);
+        let row = text.lines().find(|l| l.contains("#219")).unwrap();
+        assert_eq!(row[double..].split_whitespace().next(), Some("438"));
+        // No review request, so nothing to subtract from.
+        assert_eq!(row[waited..].split_whitespace().next(), Some("-"));
+    }
+
+    #[test]
+    fn repo_layout_groups_by_repository_and_folds_repos() {
+        let mut state =
+            AppState::new(fixture_prs(), UiPrefs::default()).with_layout(ListLayout::Repos);
+        let (lines, visible) = ListCache::default().layout(&state, 100, 40, None);
+        let text = lines_to_text(&lines);
+        assert!(!text.contains("🔥 NEEDS YOU"));
+        assert!(!text.contains("REPO"));
+        let billing = text.find(" ▾ acme-inc/billing-api (1)").unwrap();
+        let payments = text
+            .find(" ▾ acme-inc/payments-worker (1 · 1 need you)")
+            .unwrap();
+        let product = text.find(" ▾ you-inc/product (1)").unwrap();
+        assert!(billing < payments && payments < product);
+        assert_eq!(visible, vec![0, 1, 4, 3, 2]);
+
+        state
+            .collapsed_blocks
+            .insert("acme-inc/payments-worker".to_string());
+        state.selected_idx = 1;
+        let (lines, visible) = ListCache::default().layout(&state, 100, 40, None);
+        let text = lines_to_text(&lines);
+        assert!(text.contains(">▸ acme-inc/payments-worker (1 · 1 need you)"));
+        assert!(!text.contains("Handle duplicate webhook deliveries"));
+        // The folded line stays selectable in place of the repo's PRs.
+        assert_eq!(visible, vec![0, 1, 4, 3, 2]);
+    }
+
+    #[test]
+    fn topic_layout_lists_related_prs_together() {
+        let mut prs = fixture_prs();
+        prs[0].pr.labels = vec!["retries".to_string()];
+        prs[3].pr.labels = vec!["Retries".to_string()];
+        let mut state = AppState::new(prs, UiPrefs::default()).with_layout(ListLayout::Topics);
+        let (lines, visible) = ListCache::default().layout(&state, 100, 40, None);
+        let text = lines_to_text(&lines);
+        let retries = text.find(" ▾ retries (2)").unwrap();
+        let other = text.find(" ▾ other (3 · 1 need you)").unwrap();
+        assert!(retries < other);
+        assert_eq!(visible, vec![0, 3, 1, 2, 4]);
+
+        state.collapsed_blocks.insert("retries".to_string());
+        let (lines, visible) = ListCache::default().layout(&state, 100, 40, None);
+        assert!(lines_to_text(&lines).contains(">▸ retries (2)"));
+        assert_eq!(visible, vec![0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn team_review_requests_get_their_own_section_after_needs_you() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        assert_eq!(state.prs[3].pr.repo, "sdk-rust");
+        state.prs_mut()[3].pr.review_state = ReviewState::TeamRequested;
+
+        let (lines, visible) = ListCache::default().layout(&state, 100, 40, None);
+        let text = lines_to_text(&lines);
+        let needs = text.find("🔥 NEEDS YOU").unwrap();
+        let team = text.find("👥 TEAM REVIEW").unwrap();
+        assert!(needs < team && team < text.find("sdk-rust").unwrap());
+        assert!(!text.contains("⏳ WAITING ON OTHERS"));
+        assert_eq!(visible, vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn snoozed_prs_collapse_into_their_own_section() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        assert_eq!(state.prs[1].pr.repo, "payments-worker");
+        state.prs_mut()[1].snoozed = true;
+        let mut cache = ListCache::default();
+
+        let (collapsed, visible) = cache.layout(&state, 100, 40, None);
+        let text = lines_to_text(&collapsed);
+        assert!(text.contains("💤 SNOOZED (1)  Z to show"));
+        assert!(!text.contains("payments-worker"));
+        assert!(!visible.contains(&1));
+
+        state.snoozed_open = true;
+        let (open, visible) = cache.layout(&state, 100, 40, None);
+        let text = lines_to_text(&open);
+        assert!(text.find("💤 SNOOZED").unwrap() < text.find("payments-worker").unwrap());
+        assert_eq!(visible.last(), Some(&1));
+    }
+
+    #[test]
+    fn bot_prs_get_their_own_section_and_filter() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        assert!(state.prs[4].pr.is_draft);
+        for i in [1, 4] {
+            state.prs_mut()[i].pr.author = "dependabot".to_string();
+            state.prs_mut()[i].bot_section = true;
+        }
+        let mut cache = ListCache::default();
+
+        let (lines, visible) = cache.layout(&state, 100, 40, None);
+        let text = lines_to_text(&lines);
+        assert!(!text.contains("📝 DRAFT"));
+        assert!(text.find("🤖 BOTS").unwrap() < text.find("payments-worker").unwrap());
+        assert_eq!(&visible[visible.len() - 2..], &[1, 4]);
+
+        // Without the section (bots = "normal") the filter still finds them.
+        state.prs_mut()[1].bot_section = false;
+        state.only_bots = true;
+        let (_, visible) = cache.layout(&state, 100, 40, None);
+        assert_eq!(visible, vec![1, 4]);
+        assert_eq!(list_banner(&state).as_deref(), Some("Filter: bots"));
+    }
+
+    #[test]
+    fn title_count_is_needs_you_whatever_the_filter() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        let n = state.needs_you_keys().len();
+        assert!(n > 0);
+        assert_eq!(state.needs_you_count(), n);
+        state.filter_query = "no-such-repo".to_string();
+        assert_eq!(state.needs_you_count(), n);
+    }
+
+    #[test]
+    fn open_all_takes_the_needs_you_rows_the_filter_shows() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        let needs_you: Vec<String> = state
+            .prs
+            .iter()
+            .filter(|p| Section::Category(Category::NeedsYou).contains(p))
+            .map(|p| p.pr.pr_key.clone())
+            .collect();
+        assert!(!needs_you.is_empty());
+        assert_eq!(state.needs_you_keys(), needs_you);
+
+        // Snoozed and filtered-out PRs stay closed.
+        let first = needs_you[0].clone();
+        state
+            .prs_mut()
+            .iter_mut()
+            .find(|p| p.pr.pr_key == first)
+            .unwrap()
+            .snoozed = true;
+        assert_eq!(state.needs_you_keys(), needs_you[1..]);
+        state.filter_query = "no-such-repo".to_string();
+        assert!(state.needs_you_keys().is_empty());
+    }
+
+    #[test]
+    fn replace_pr_swaps_in_place_and_invalidates_layout() {
+        let mut state = AppState::new(fixture_prs(), UiPrefs::default());
+        let version = state.list_version;
+        let mut fresh = state.prs[3].clone();
+        fresh.pr.title = "Updated title".to_string();
+        fresh.score = 1_000;
+        state.replace_pr(fresh);
+
+        assert_eq!(state.prs.len(), 5);
+        assert!(state.list_version > version);
+        let updated = state.prs.iter().find(|p| p.pr.title == "Updated title");
+        assert_eq!(updated.map(|p| p.score), Some(1_000));
+
+        // PRs that are shown nowhere are ignored.
+        let mut stranger = state.prs[0].clone();
+        stranger.pr.pr_key = "someone/else#1".to_string();
+        state.replace_pr(stranger);
+        assert_eq!(state.prs.len(), 5);
+    }
+
+    #[test]
+    fn ranked_drafts_render_in_their_category() {
+        let mut prs = fixture_prs();
+        prs[4].draft_ranked = true;
+        let filtered: Vec<usize> = (0..prs.len()).collect();
+        let (lines, visible) =
+            build_list_lines(&prs, 100, 40, 0, &filtered, None, UiPrefs::default(), None);
+        // The draft (category Waiting) now sits before the stale PR.
+        assert_eq!(visible, vec![0, 2, 1, 4, 3]);
+        let text: String = lines.iter().map(|l| l.to_string() + "\n").collect();
+        assert!(!text.contains("📝 DRAFT"));
+    }
+
+    fn fixture_groups() -> RepoGroups {
+        RepoGroups::new([
+            ("payments".to_string(), vec!["acme-inc/*".to_string()]),
+            (
+                "sdk".to_string(),
+                vec!["Windmill-Labs/SDK-Rust".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn group_filter_tokens_select_repo_groups() {
+        let prs = fixture_prs();
+        let groups = fixture_groups();
+        let filter = |q: &str| {
+            filtered_indices(
+                &prs, q, false, false, false, false, false, false, &groups, NOW,
+            )
+        };
+
+        assert_eq!(filter("group:payments"), vec![0, 1]);
+        assert_eq!(filter("group:payments webhook"), vec![1]);
+        assert_eq!(filter("group:sdk group:payments"), vec![0, 1, 3]);
+        assert_eq!(filter("group:nope"), Vec::<usize>::new());
+        assert_eq!(filter("retry").len(), 1);
+    }
+
+    #[test]
+    fn field_terms_narrow_the_filter() {
+        let mut prs = fixture_prs();
+        prs[3].pr.created_at_unix = Some(NOW - 5 * 86_400);
+        let groups = RepoGroups::default();
+        let filter = |q: &str| {
+            filtered_indices(
+                &prs, q, false, false, false, false, false, false, &groups, NOW,
+            )
+        };
+
+        assert_eq!(filter("repo:acme-inc/"), vec![0, 1]);
+        assert_eq!(filter("author:@Santiago"), vec![1, 4]);
+        assert_eq!(filter("author:santiago is:draft"), vec![4]);
+        assert_eq!(filter("is:mine"), vec![2]);
+        assert_eq!(filter("is:bot"), Vec::<usize>::new());
+        assert_eq!(filter("ci:failing"), vec![1]);
+        assert_eq!(filter("review:requested"), vec![1]);
+        assert_eq!(filter("age:>3d"), vec![3]);
+        assert_eq!(filter("age:<2h").len(), 4);
+        assert_eq!(filter("age:>99999999999999w"), Vec::<usize>::new());
+        assert_eq!(filter("repo:acme webhook"), vec![1]);
+        assert_eq!(filter("ci:"), vec![0, 1, 2, 3, 4]);
+        assert_eq!(filter("is:whatever"), Vec::<usize>::new());
+        // Unknown fields are plain text.
+        assert_eq!(filter("fix:"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn sla_filter_keeps_only_at_risk_prs() {
+        let mut prs = fixture_prs();
+        prs[1].sla = Some(SlaStatus::OnTrack { due_at_unix: 0 });
+        prs[2].sla = Some(SlaStatus::Approaching { due_at_unix: 0 });
+        prs[4].sla = Some(SlaStatus::Overdue { due_at_unix: 0 });
+        let groups = RepoGroups::default();
+
+        assert_eq!(
+            filtered_indices(
+                &prs, "", false, false, false, true, false, false, &groups, NOW
+            ),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn mention_filter_keeps_only_mentioned_prs() {
+        let mut prs = fixture_prs();
+        prs[3].pr.is_mentioned = true;
+        let groups = RepoGroups::default();
+
+        assert_eq!(
+            filtered_indices(
+                &prs, "", false, false, false, false, true, false, &groups, NOW
+            ),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn list_sub_groups_sections_by_repo_group() {
+        let mut prs = fixture_prs();
+        // Put a second, ungrouped PR in NEEDS YOU ahead of the grouped one.
+        let mut extra = prs[3].clone();
+        extra.pr.pr_key = "orbit/api#7".to_string();
+        extra.pr.owner = "orbit".to_string();
+        extra.pr.repo = "api".to_string();
+        extra.category = Category::NeedsYou;
+        extra.score = 90;
+        prs.insert(1, extra);
+
+        let filtered: Vec<usize> = (0..prs.len()).collect();
+        let groups = fixture_groups();
+        let (lines, visible) = build_list_lines(
+            &prs,
+            100,
+            60,
+            0,
+            &filtered,
+            None,
+            UiPrefs::default(),
+            Some(&groups),
+        );
+        let text = lines_to_text(&lines);
+
+        // Within NEEDS YOU, the payments group comes before ungrouped repos.
+        let needs = &text[text.find("NEEDS YOU").unwrap()..];
+        let payments = needs.find("▸ payments").unwrap();
+        let other = needs.find("▸ other").unwrap();
+        assert!(payments < other);
+        assert_eq!(visible, vec![0, 3, 2, 1, 4, 5]);
+    }
+
+    #[test]
+    fn details_falls_back_to_looked_up_prs() {
+        let mut prs = fixture_prs();
+        let outside = prs.pop().unwrap();
+        let key = outside.pr.pr_key.clone();
+        let mut state = AppState::new(prs, UiPrefs::default());
+        state.lookups.push(outside);
+
+        state.open_details(key.clone());
+        assert_eq!(state.mode, ViewMode::Details);
+        assert_eq!(
+            state.details_pr().map(|p| p.pr.pr_key.as_str()),
+            Some(key.as_str())
+        );
+
+        // A refresh replaces the attention set but keeps the looked-up PR reachable.
+        state.prs = Vec::new();
+        assert!(state.details_pr().is_some());
+    }
+
+    fn check_rows(pr: &UiPr) -> Vec<CiRow> {
+        ci_rows(&pr.pr.ci_checks, &CheckGroups::default(), &HashSet::new())
+    }
+
+    #[test]
+    fn footer_warns_when_the_api_budget_runs_low() {
+        let mut state = AppState::new(Vec::new(), UiPrefs::default());
+        assert_eq!(footer_note(&state, 180, NOW), None);
+
+        let rl = |remaining| RateLimit {
+            limit: 5000,
+            remaining,
+            reset_at_unix: NOW + 1800,
+        };
+        state.rate_limit = Some(rl(4321));
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("API 4321/5000".to_string(), Tone::Quiet))
+        );
+        state.rate_limit = Some(rl(300));
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some((
+                "⚠ GitHub API 300/5000 left (resets in 30m); auto-refresh every 12m".to_string(),
+                Tone::Loud
+            ))
+        );
+        state.rate_limit = Some(rl(0));
+        assert!(footer_note(&state, 180, NOW).unwrap().0.contains("paused"));
+        state.status_message = Some("Copied".to_string());
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("Copied".to_string(), Tone::Loud))
+        );
+    }
+
+    #[test]
+    fn footer_says_how_old_the_list_is() {
+        let mut state = AppState::new(Vec::new(), UiPrefs::default());
+        state.last_refreshed_at = Some(NOW - 30);
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("updated just now".to_string(), Tone::Quiet))
+        );
+        state.last_refreshed_at = Some(NOW - 7 * 60);
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("updated 7m ago".to_string(), Tone::Loud))
+        );
+        state.last_refreshed_at = Some(NOW - 13 * 60);
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("updated 13m ago".to_string(), Tone::Alarm))
+        );
+
+        // A failed refresh means the list is stale however recent the last good one was.
+        state.last_refreshed_at = Some(NOW - 60);
+        state.last_refresh_error = Some("timed out".to_string());
+        state.rate_limit = Some(RateLimit {
+            limit: 5000,
+            remaining: 4321,
+            reset_at_unix: NOW + 1800,
+        });
+        assert_eq!(
+            footer_note(&state, 180, NOW),
+            Some(("updated 1m ago · API 4321/5000".to_string(), Tone::Alarm))
+        );
+    }
+
+    #[test]
+    fn matrix_and_configured_checks_collapse_into_expandable_groups() {
+        let check = |name: &str, state: CiCheckState| CiCheck {
+            name: name.to_string(),
+            state,
+            url: None,
+            started_at_unix: None,
+        };
+        let checks = vec![
+            check("test (ubuntu, 1.75)", CiCheckState::Failure),
+            check("e2e shard 1/2", CiCheckState::Running),
+            check("test (ubuntu, 1.76)", CiCheckState::Success),
+            check("E2E shard 2/2", CiCheckState::Success),
+            check("lint (stable)", CiCheckState::Success),
+            check("build", CiCheckState::Success),
+        ];
+        let groups = CheckGroups::new(vec!["e2e shard *".to_string()]);
+        let group = |label: &str, members: Vec<usize>, expanded: bool| CiRow::Group {
+            label: label.to_string(),
+            members,
+            expanded,
+        };
+        let check_row = |idx: usize, nested: bool| CiRow::Check { idx, nested };
+
+        // A lone matrix job stays a plain row.
+        assert_eq!(
+            ci_rows(&checks, &groups, &HashSet::new()),
+            vec![
+                group("test", vec![0, 2], false),
+                group("e2e shard *", vec![1, 3], false),
+                check_row(4, false),
+                check_row(5, false),
+            ]
+        );
+        let expanded: HashSet<String> = ["test".to_string()].into();
+        assert_eq!(
+            ci_rows(&checks, &groups, &expanded)[..3],
+            [
+                group("test", vec![0, 2], true),
+                check_row(0, true),
+                check_row(2, true),
+            ]
+        );
+        assert!(matches!(
+            aggregate_check_state([&checks[1], &checks[3]].into_iter()),
+            CiCheckState::Running
+        ));
+
+        let mut pr = fixture_details_pr();
+        pr.pr.ci_checks = checks;
+        let rows = ci_rows(&pr.pr.ci_checks, &groups, &expanded);
+        let ci = CiList {
+            rows: &rows,
+            selected: 0,
+            failures: None,
+        };
+        let text = lines_to_text(&build_details_lines(
+            &pr,
+            80,
+            60,
+            ci,
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        ));
+        assert!(text.contains("> ❌ ▾ test (2)"));
+        assert!(text.contains("     ✅ test (ubuntu, 1.76)"));
+        assert!(text.contains("  🟡 ▸ e2e shard * (2)"));
+        assert!(text.contains("Enter on a group: expand/collapse its checks"));
+    }
+
+    #[test]
+    fn details_list_reviewers_waiting_first() {
+        let mut pr = fixture_details_pr();
+        pr.pr.reviewers = ["COMMENTED", "APPROVED", "REQUESTED", "CHANGES_REQUESTED"]
+            .iter()
+            .zip(["ana", "mira", "you", "bo"])
+            .map(|(state, login)| Reviewer {
+                login: login.to_string(),
+                state: state.to_string(),
+            })
+            .collect();
+        let rows = check_rows(&pr);
+        let text = lines_to_text(&build_details_lines(
+            &pr,
+            80,
+            60,
+            CiList {
+                rows: &rows,
+                selected: 0,
+                failures: None,
+            },
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        ));
+        assert!(
+            text.contains("REVIEWERS  1 waiting · 1 approved · 1 changes requested\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "  ⏳ you  waiting for their review\n  ❌ bo  requested changes\n  \
+                 ✅ mira  approved\n  💬 ana  commented\n"
+            ),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn details_snapshot_narrow() {
+        let pr = fixture_details_pr();
+        let lines = build_details_lines(
+            &pr,
+            50,
+            40,
+            CiList {
+                rows: &check_rows(&pr),
+                selected: 1,
+                failures: None,
+            },
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        );
+        insta::assert_snapshot!(lines_to_text(&lines));
+    }
+
+    #[test]
+    fn details_snapshot_wide() {
+        let pr = fixture_details_pr();
+        let lines = build_details_lines(
+            &pr,
+            100,
+            40,
+            CiList {
+                rows: &check_rows(&pr),
+                selected: 0,
+                failures: None,
+            },
+            &mut 0,
+            Checklist::default(),
+            &[],
+            NOW,
+        );
+        insta::assert_snapshot!(lines_to_text(&lines));
+    }
+
+    #[test]
+    fn behind_branches_offer_an_update() {
+        let mut pr = fixture_details_pr();
+        pr.pr.merge_blockers = Some(MergeBlockers {
+            is_behind_base: true,
+            ..MergeBlockers::default()
+        });
+        let render = |pr: &UiPr| {
+            let lines = build_details_lines(
+                pr,
+                100,
+                40,
+                CiList {
+                    rows: &check_rows(pr),
+                    selected: 0,
+                    failures: None,
+                },
+                &mut 0,
+                Checklist::default(),
+                &[],
+                NOW,
+            );
+            lines_to_text(&lines)
+        };
+
+        pr.pr.merge_state_status = Some("BEHIND".to_string());
+        assert!(render(&pr).contains("Branch behind base · u update branch"));
+        pr.pr.merge_state_status = Some("BLOCKED".to_string());
+        assert!(!render(&pr).contains("u update branch"));
+    }
+
+    #[test]
+    fn footer_snapshots_across_widths() {
+        let mut out = Vec::new();
+        for mode in [ViewMode::List, ViewMode::Details] {
+            for width in [40u16, 80, 160] {
+                for filter_mode in [false, true] {
+                    if mode == ViewMode::Details && filter_mode {
+                        continue;
+                    }
+                    let (line, w) =
+                        build_footer(width, mode, false, 0, ColorMode::TrueColor, filter_mode);
+                    out.push(format!(
+                        "{mode:?} width={width} filter={filter_mode} used={w}\n{}",
+                        lines_to_text(&[line])
+                    ));
+                }
+            }
+        }
+        insta::assert_snapshot!(out.join("\n\n"));
+    }
+
+    #[test]
+    fn merge_picker_highlights_selected_method() {
+        let lines = merge_picker_lines(1);
+        let texts: Vec<&str> = lines.iter().map(|l| l.spans[0].content.as_ref()).collect();
+        assert_eq!(
+            texts,
+            vec![" merge ", " squash ", " rebase ", "Enter merge  Esc cancel"]
+        );
+        let reversed = |i: usize| {
+            lines[i].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        };
+        assert!(reversed(1));
+        assert!(!reversed(0) && !reversed(2));
+    }
+
+    #[test]
+    fn preview_shows_reviewers_failing_checks_and_description() {
+        let mut pr = fixture_details_pr();
+        pr.pr.labels = vec!["infra".to_string()];
+        pr.pr.reviewers = vec![
+            Reviewer {
+                login: "mira".to_string(),
+                state: "APPROVED".to_string(),
+            },
+            Reviewer {
+                login: "you".to_string(),
+                state: "REQUESTED".to_string(),
+            },
+        ];
+        pr.pr.body_excerpt = Some("Bumps Postgres.\nTunes autovacuum.".to_string());
+        let text = lines_to_text(&build_preview_lines(&pr, 80));
+        assert!(
+            text.contains("windmill-labs/infra#317 by chen · ❌ CI failed"),
+            "{text}"
+        );
+        assert!(text.contains("Labels: infra"), "{text}");
+        assert!(text.contains("Reviewers: mira ✅, you ⏳"), "{text}");
+        assert!(text.contains("Failing: test / unit\n"), "{text}");
+        assert!(
+            text.ends_with("Bumps Postgres.\nTunes autovacuum."),
+            "{text}"
+        );
+
+        pr.pr.body_excerpt = None;
+        let text = lines_to_text(&build_preview_lines(&pr, 80));
+        assert!(text.contains("(no description)"), "{text}");
+    }
+
+    #[test]
+    fn composer_edits_and_wraps_with_cursor_in_view() {
+        let mut c = Composer::default();
+        for code in [
+            KeyCode::Char('l'),
+            KeyCode::Char('g'),
+            KeyCode::Char('x'),
+            KeyCode::Backspace,
+            KeyCode::Char('t'),
+            KeyCode::Char('m'),
+            KeyCode::Enter,
+            KeyCode::Char('a'),
+        ] {
+            assert!(c.edit(&KeyEvent::new(code, KeyModifiers::NONE)));
+        }
+        assert!(!c.edit(&KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert_eq!(c.text, "lgtm\na");
+
+        let text = |lines: Vec<Line<'static>>| lines_to_text(&lines);
+        assert_eq!(text(composer_lines(&c.text, 3, 10)), "lgt\nm\na▏");
+        // Only the last rows fit, so the cursor stays visible.
+        assert_eq!(text(composer_lines(&c.text, 3, 2)), "m\na▏");
+    }
+
+    #[test]
+    fn footer_shows_shimmer_while_refreshing() {
+        let (line, _) = build_footer(160, ViewMode::List, true, 3, ColorMode::TrueColor, false);
+        let text = lines_to_text(&[line]);
+        assert!(text.contains("refreshing ░░░▓░░░░░░"), "{text}");
+        let (line, _) = build_footer(160, ViewMode::List, true, 3, ColorMode::Mono, false);
+        let text = lines_to_text(&[line]);
+        assert!(text.contains("refreshing ---#------"), "{text}");
+    }
 }
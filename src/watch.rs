@@ -0,0 +1,528 @@
+//! Headless refresh loop (`needle watch`).
+//!
+//! Keeps the cache fresh with the TUI closed, e.g. under launchd or systemd, and prints one
+//! line per alert (the same events that ring the TUI bell) so the service log shows them.
+
+use crate::RefreshFn;
+use needle_core::db::{mark_alert_announced, now_unix, opened_at};
+use needle_core::github::{last_rate_limit, take_partial_errors};
+use needle_core::model::ReviewState;
+use needle_core::refresh::{Category, UiPr};
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::Duration;
+
+/// Something that changed between two refreshes and deserves the user's attention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Alert {
+    /// The PR entered NEEDS YOU (e.g. a new review request).
+    NeedsYou(String),
+    /// CI started failing on the PR.
+    CiFailed(String),
+    /// Someone replied to the viewer's comments on the PR.
+    Reply(String),
+    /// The PR has been in NEEDS YOU for the grace period without being opened or snoozed.
+    Reminder(String),
+    /// One of the viewer's PRs became approved, usually the cue to go merge it.
+    Approved(String),
+    /// The viewer's review was requested, newly or again.
+    ReviewRequested(String),
+    /// The PR entered READY TO MERGE.
+    ReadyToMerge(String),
+    /// The first PR from a repository not in the previous refresh.
+    NewRepo(String),
+}
+
+/// Alert kinds `bell_events` and `[slack] events` accept (see [`Alert::kind`]).
+pub(crate) const ALERT_KINDS: [&str; 8] = [
+    "needs_you",
+    "review_requested",
+    "ci_failed",
+    "ready_to_merge",
+    "new_repo",
+    "reply",
+    "reminder",
+    "approved_by",
+];
+
+/// Other names accepted for an alert kind, as `(alias, kind)`.
+pub(crate) const ALERT_ALIASES: [(&str, &str); 1] = [("ci_failure", "ci_failed")];
+
+impl Alert {
+    pub(crate) fn pr_key(&self) -> &str {
+        match self {
+            Alert::NeedsYou(k)
+            | Alert::CiFailed(k)
+            | Alert::Reply(k)
+            | Alert::Reminder(k)
+            | Alert::Approved(k)
+            | Alert::ReviewRequested(k)
+            | Alert::ReadyToMerge(k)
+            | Alert::NewRepo(k) => k,
+        }
+    }
+
+    /// Name used in config (`bell_events`, `[slack] events`).
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Alert::NeedsYou(_) => "needs_you",
+            Alert::CiFailed(_) => "ci_failed",
+            Alert::Reply(_) => "reply",
+            Alert::Reminder(_) => "reminder",
+            Alert::Approved(_) => "approved_by",
+            Alert::ReviewRequested(_) => "review_requested",
+            Alert::ReadyToMerge(_) => "ready_to_merge",
+            Alert::NewRepo(_) => "new_repo",
+        }
+    }
+
+    /// Whether `kind`, as written in the config, names this alert (or an alias of it).
+    pub(crate) fn is(&self, kind: &str) -> bool {
+        kind == self.kind()
+            || ALERT_ALIASES
+                .iter()
+                .any(|&(alias, k)| alias == kind && k == self.kind())
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Alert::NeedsYou(_) => "needs you",
+            Alert::CiFailed(_) => "CI failed",
+            Alert::Reply(_) => "new reply",
+            Alert::Reminder(_) => "still needs you",
+            Alert::Approved(_) => "approved",
+            Alert::ReviewRequested(_) => "review requested",
+            Alert::ReadyToMerge(_) => "ready to merge",
+            Alert::NewRepo(_) => "new repo",
+        }
+    }
+
+    /// The label with details from `p`, e.g. who approved.
+    pub(crate) fn headline(&self, p: &UiPr) -> String {
+        let approvers: Vec<&str> =
+            p.pr.reviewers
+                .iter()
+                .filter(|r| r.state == "APPROVED")
+                .map(|r| r.login.as_str())
+                .collect();
+        match self {
+            Alert::Approved(_) if !approvers.is_empty() => {
+                format!("approved by {}", approvers.join(", "))
+            }
+            _ => self.label().to_string(),
+        }
+    }
+
+    /// Identifies one occurrence of the alert: a PR re-entering NEEDS YOU for the same review
+    /// request, or failing or getting approved again on the same commit, is not announced
+    /// (or posted to Slack) twice.
+    pub(crate) fn event_key(&self, p: &UiPr) -> String {
+        let occurrence = match self {
+            Alert::NeedsYou(_) | Alert::Reminder(_) | Alert::ReviewRequested(_) => {
+                p.pr.review_requested_at_unix
+                    .unwrap_or(p.pr.updated_at_unix)
+                    .to_string()
+            }
+            Alert::CiFailed(_) | Alert::Approved(_) | Alert::ReadyToMerge(_) => {
+                p.pr.last_commit_sha.clone().unwrap_or_default()
+            }
+            Alert::Reply(_) => {
+                p.pr.last_reply_at_unix
+                    .unwrap_or(p.pr.updated_at_unix)
+                    .to_string()
+            }
+            Alert::NewRepo(_) => format!("{}/{}", p.pr.owner, p.pr.repo),
+        };
+        format!("{}:{occurrence}", self.kind())
+    }
+
+    fn describe(&self, prs: &[UiPr]) -> String {
+        let key = self.pr_key();
+        match prs.iter().find(|p| p.pr.pr_key == key) {
+            Some(p) => format!("{}: {key} {} {}", self.headline(p), p.pr.title, p.pr.url),
+            None => format!("{}: {key}", self.label()),
+        }
+    }
+}
+
+/// Alerts raised by going from `old` to `new`. Snoozed PRs never alert.
+pub(crate) fn alerts(old: &[UiPr], new: &[UiPr]) -> Vec<Alert> {
+    let old_needs: HashSet<&str> = old
+        .iter()
+        .filter(|p| p.category == Category::NeedsYou)
+        .map(|p| p.pr.pr_key.as_str())
+        .collect();
+    let old_replies: HashSet<&str> = old
+        .iter()
+        .filter(|p| p.reply_to_you)
+        .map(|p| p.pr.pr_key.as_str())
+        .collect();
+    // Only approvals seen happening: a PR that shows up already approved stays quiet.
+    let old_unapproved: HashSet<&str> = old
+        .iter()
+        .filter(|p| !matches!(p.pr.review_state, ReviewState::Approved))
+        .map(|p| p.pr.pr_key.as_str())
+        .collect();
+    let old_ready: HashSet<&str> = old
+        .iter()
+        .filter(|p| p.category == Category::ReadyToMerge)
+        .map(|p| p.pr.pr_key.as_str())
+        .collect();
+    // When each PR last asked for the viewer's review: a later request is a new one.
+    let old_requests: HashMap<&str, Option<i64>> = old
+        .iter()
+        .filter(|p| matches!(p.pr.review_state, ReviewState::Requested))
+        .map(|p| (p.pr.pr_key.as_str(), p.pr.review_requested_at_unix))
+        .collect();
+    let old_repos: HashSet<(&str, &str)> = old
+        .iter()
+        .map(|p| (p.pr.owner.as_str(), p.pr.repo.as_str()))
+        .collect();
+    let mut new_repos = HashSet::new();
+
+    let mut out = Vec::new();
+    for p in new.iter().filter(|p| !p.snoozed) {
+        let key = &p.pr.pr_key;
+        if p.category == Category::NeedsYou && !old_needs.contains(key.as_str()) {
+            out.push(Alert::NeedsYou(key.clone()));
+        }
+        if p.is_new_ci_failure {
+            out.push(Alert::CiFailed(key.clone()));
+        }
+        if p.reply_to_you && !old_replies.contains(key.as_str()) {
+            out.push(Alert::Reply(key.clone()));
+        }
+        if p.pr.is_viewer_author
+            && matches!(p.pr.review_state, ReviewState::Approved)
+            && old_unapproved.contains(key.as_str())
+        {
+            out.push(Alert::Approved(key.clone()));
+        }
+        if matches!(p.pr.review_state, ReviewState::Requested)
+            && old_requests
+                .get(key.as_str())
+                .is_none_or(|&at| p.pr.review_requested_at_unix > at)
+        {
+            out.push(Alert::ReviewRequested(key.clone()));
+        }
+        if p.category == Category::ReadyToMerge && !old_ready.contains(key.as_str()) {
+            out.push(Alert::ReadyToMerge(key.clone()));
+        }
+        // Without a previous refresh every repository would be new.
+        let repo = (p.pr.owner.as_str(), p.pr.repo.as_str());
+        if !old.is_empty() && !old_repos.contains(&repo) && new_repos.insert(repo) {
+            out.push(Alert::NewRepo(key.clone()));
+        }
+    }
+    out
+}
+
+/// The alerts in `raised` not announced before, recording them as announced. The cache
+/// remembers across restarts and frontends, so `needle watch` and the TUI bell announce
+/// each occurrence once. Alerts stay new when the cache cannot say otherwise.
+pub(crate) fn unannounced(
+    conn: &Connection,
+    raised: &[Alert],
+    prs: &[UiPr],
+    now: i64,
+) -> Vec<Alert> {
+    raised
+        .iter()
+        .filter(|a| {
+            let Some(p) = prs.iter().find(|p| p.pr.pr_key == a.pr_key()) else {
+                return true;
+            };
+            mark_alert_announced(conn, a.pr_key(), &a.event_key(p), now).unwrap_or(true)
+        })
+        .cloned()
+        .collect()
+}
+
+/// One follow-up alert for PRs that entered NEEDS YOU and were neither opened nor snoozed
+/// within the grace period (`remind_after_mins`). Disabled without a grace period.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Reminders {
+    grace_secs: Option<i64>,
+    /// PRs waiting for a reminder, with when they entered NEEDS YOU.
+    pending: HashMap<String, i64>,
+}
+
+impl Reminders {
+    pub(crate) fn new(grace_secs: Option<i64>) -> Self {
+        Self {
+            grace_secs,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Start the clock for PRs in `raised` that entered NEEDS YOU, and return reminders for
+    /// those past the grace period. `opened_at` is when the user last opened a PR.
+    pub(crate) fn check(
+        &mut self,
+        raised: &[Alert],
+        prs: &[UiPr],
+        now: i64,
+        opened_at: impl Fn(&str) -> Option<i64>,
+    ) -> Vec<Alert> {
+        let Some(grace) = self.grace_secs else {
+            return Vec::new();
+        };
+        for alert in raised {
+            if let Alert::NeedsYou(key) = alert {
+                self.pending.insert(key.clone(), now);
+            }
+        }
+        let mut due = Vec::new();
+        self.pending.retain(|key, entered| {
+            let Some(p) = prs.iter().find(|p| &p.pr.pr_key == key) else {
+                return false;
+            };
+            let acknowledged = p.snoozed || opened_at(key).is_some_and(|t| t >= *entered);
+            if p.category != Category::NeedsYou || acknowledged {
+                return false;
+            }
+            if now - *entered < grace {
+                return true;
+            }
+            due.push(Alert::Reminder(key.clone()));
+            false
+        });
+        due.sort_by(|a, b| a.pr_key().cmp(b.pr_key()));
+        due
+    }
+}
+
+/// Refresh every `refresh_secs` until the process is killed, printing each alert to stdout
+/// (ringing the bell for `bell_events`) and refresh failures to stderr. `prs` (usually the
+/// cached snapshot) is the baseline, so anything that changed while needle was not running
+/// shows up on the first refresh.
+pub fn run_watch(
+    conn: &Connection,
+    prs: Vec<UiPr>,
+    refresh_fn: RefreshFn,
+    refresh_secs: u64,
+    bell_events: Vec<String>,
+    mut reminders: Reminders,
+) -> Result<(), String> {
+    eprintln!(
+        "needle watching {} PR(s), refreshing every {refresh_secs}s",
+        prs.len()
+    );
+    let mut current = prs;
+    loop {
+        match refresh_fn() {
+            Ok(new_prs) => {
+                let mut raised = alerts(&current, &new_prs);
+                raised.extend(reminders.check(&raised, &new_prs, now_unix(), |key| {
+                    opened_at(conn, key).ok().flatten()
+                }));
+                let raised = unannounced(conn, &raised, &new_prs, now_unix());
+                for alert in &raised {
+                    println!("{}", alert.describe(&new_prs));
+                }
+                if raised.iter().any(|a| bell_events.iter().any(|e| a.is(e))) {
+                    print!("\x07");
+                }
+                let _ = std::io::stdout().flush();
+                current = new_prs;
+            }
+            Err(e) => eprintln!("Refresh failed: {e}"),
+        }
+        for skipped in take_partial_errors() {
+            eprintln!("Warning: {skipped}");
+        }
+        // Back off while the GitHub API budget is low.
+        let wait = last_rate_limit().map_or(refresh_secs, |rl| {
+            rl.refresh_interval_secs(refresh_secs, now_unix())
+        });
+        std::thread::sleep(Duration::from_secs(wait.max(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use needle_core::db::{TestDb, open_db};
+    use needle_core::refresh::{ScopeFilters, refresh_demo};
+
+    #[test]
+    fn alerts_cover_needs_you_ci_failures_and_replies_but_not_snoozed() {
+        let conn = TestDb::new("watch-test");
+        let mut old = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        for p in &mut old {
+            p.is_new_ci_failure = false;
+        }
+        assert!(alerts(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        let needs = new
+            .iter()
+            .position(|p| p.category == Category::NeedsYou)
+            .unwrap();
+        let key = new[needs].pr.pr_key.clone();
+        old.retain(|p| p.pr.pr_key != key);
+        new[needs].is_new_ci_failure = true;
+        new[needs].reply_to_you = true;
+        assert_eq!(
+            alerts(&old, &new),
+            vec![
+                Alert::NeedsYou(key.clone()),
+                Alert::CiFailed(key.clone()),
+                Alert::Reply(key.clone()),
+            ]
+        );
+
+        new[needs].snoozed = true;
+        assert!(alerts(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn approving_one_of_my_prs_alerts_once_with_the_approvers() {
+        let conn = TestDb::new("watch-approved-test");
+        let mut old = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        for p in &mut old {
+            p.is_new_ci_failure = false;
+        }
+        let mine = 0;
+        old[mine].pr.is_viewer_author = true;
+        old[mine].pr.review_state = ReviewState::None;
+        let key = old[mine].pr.pr_key.clone();
+
+        let mut new = old.clone();
+        new[mine].pr.review_state = ReviewState::Approved;
+        new[mine].pr.reviewers = vec![needle_core::model::Reviewer {
+            login: "alice".to_string(),
+            state: "APPROVED".to_string(),
+        }];
+        let raised: Vec<Alert> = alerts(&old, &new)
+            .into_iter()
+            .filter(|a| a.kind() == "approved_by")
+            .collect();
+        assert_eq!(raised, vec![Alert::Approved(key.clone())]);
+        assert_eq!(raised[0].headline(&new[mine]), "approved by alice");
+        assert!(alerts(&new, &new).is_empty());
+
+        // Someone else's PR, or one first seen already approved, stays quiet.
+        new[mine].pr.is_viewer_author = false;
+        assert!(!alerts(&old, &new).contains(&Alert::Approved(key.clone())));
+        new[mine].pr.is_viewer_author = true;
+        old.remove(mine);
+        assert!(!alerts(&old, &new).contains(&Alert::Approved(key)));
+    }
+
+    #[test]
+    fn review_requests_ready_to_merge_and_new_repos_alert() {
+        let conn = TestDb::new("watch-kinds-test");
+        let mut old = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        for p in &mut old {
+            p.is_new_ci_failure = false;
+        }
+        let of_kind = |old: &[UiPr], new: &[UiPr], kind: &str| -> Vec<Alert> {
+            alerts(old, new)
+                .into_iter()
+                .filter(|a| a.is(kind))
+                .collect()
+        };
+
+        let ready = old
+            .iter()
+            .position(|p| p.category == Category::ReadyToMerge)
+            .unwrap();
+        let key = old[ready].pr.pr_key.clone();
+        let mut before = old.clone();
+        before[ready].category = Category::Waiting;
+        assert_eq!(
+            of_kind(&before, &old, "ready_to_merge"),
+            vec![Alert::ReadyToMerge(key)]
+        );
+        assert!(of_kind(&old, &old, "ready_to_merge").is_empty());
+
+        // A first request, and a later one, but not the same one again.
+        let mut new = old.clone();
+        new[0].pr.review_state = ReviewState::Requested;
+        new[0].pr.review_requested_at_unix = Some(100);
+        let key = new[0].pr.pr_key.clone();
+        old[0].pr.review_state = ReviewState::None;
+        let requested = vec![Alert::ReviewRequested(key)];
+        assert_eq!(of_kind(&old, &new, "review_requested"), requested);
+        assert!(of_kind(&new, &new, "review_requested").is_empty());
+        let mut again = new.clone();
+        again[0].pr.review_requested_at_unix = Some(200);
+        assert_eq!(of_kind(&new, &again, "review_requested"), requested);
+
+        // One alert per new repository, and none without a previous refresh.
+        new[1].pr.repo = "brand-new".to_string();
+        new[2].pr.repo = "brand-new".to_string();
+        assert_eq!(
+            of_kind(&old, &new, "new_repo"),
+            vec![Alert::NewRepo(new[1].pr.pr_key.clone())]
+        );
+        assert!(of_kind(&[], &new, "new_repo").is_empty());
+
+        let failed = Alert::CiFailed(new[0].pr.pr_key.clone());
+        assert!(failed.is("ci_failed") && failed.is("ci_failure") && !failed.is("reply"));
+    }
+
+    #[test]
+    fn alerts_are_announced_once_across_restarts() {
+        let db = TestDb::new("watch-announced-test");
+        let mut prs = refresh_demo(&db, 30, &ScopeFilters::default()).unwrap();
+        let i = prs
+            .iter()
+            .position(|p| p.category == Category::NeedsYou)
+            .unwrap();
+        prs[i].pr.last_commit_sha = Some("abc123".to_string());
+        let key = prs[i].pr.pr_key.clone();
+        let raised = [Alert::NeedsYou(key.clone()), Alert::CiFailed(key.clone())];
+
+        assert_eq!(unannounced(&db, &raised, &prs, 100), raised);
+        assert!(unannounced(&db, &raised, &prs, 200).is_empty());
+
+        // A restart opens the cache afresh; a new commit failing is a new occurrence.
+        let conn = open_db(db.path()).unwrap();
+        assert!(unannounced(&conn, &raised, &prs, 300).is_empty());
+        prs[i].pr.last_commit_sha = Some("def456".to_string());
+        assert_eq!(
+            unannounced(&conn, &raised, &prs, 400),
+            vec![Alert::CiFailed(key)]
+        );
+    }
+
+    #[test]
+    fn reminders_fire_once_unless_the_pr_was_opened_or_snoozed() {
+        let conn = TestDb::new("watch-reminder-test");
+        let mut prs = refresh_demo(&conn, 30, &ScopeFilters::default()).unwrap();
+        let mut needs = prs
+            .iter()
+            .filter(|p| p.category == Category::NeedsYou && !p.snoozed)
+            .map(|p| p.pr.pr_key.clone());
+        let (a, b) = (needs.next().unwrap(), needs.next().unwrap());
+        let raised = [Alert::NeedsYou(a.clone()), Alert::NeedsYou(b.clone())];
+        let never_opened = |_: &str| None;
+
+        assert!(
+            Reminders::new(None)
+                .check(&raised, &prs, 0, never_opened)
+                .is_empty()
+        );
+
+        let mut reminders = Reminders::new(Some(1800));
+        assert!(reminders.check(&raised, &prs, 0, never_opened).is_empty());
+        assert!(reminders.check(&[], &prs, 1799, never_opened).is_empty());
+        // `b` was opened after it entered NEEDS YOU, so only `a` gets a reminder, once.
+        let opened = |key: &str| (key == b).then_some(60);
+        assert_eq!(
+            reminders.check(&[], &prs, 1800, opened),
+            vec![Alert::Reminder(a.clone())]
+        );
+        assert!(reminders.check(&[], &prs, 3600, never_opened).is_empty());
+
+        let mut reminders = Reminders::new(Some(1800));
+        reminders.check(&raised[..1], &prs, 0, never_opened);
+        prs.iter_mut()
+            .filter(|p| p.pr.pr_key == a)
+            .for_each(|p| p.snoozed = true);
+        assert!(reminders.check(&[], &prs, 1800, never_opened).is_empty());
+    }
+}